@@ -5,6 +5,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use kwite::audio::process::process_audio;
+use kwite::audio::parallel::ChannelWorkers;
 use kwite::ai_metrics::{AiMetrics, create_shared_metrics};
 use nnnoiseless::DenoiseState;
 use std::time::Duration;
@@ -248,6 +249,34 @@ fn benchmark_vad_accuracy(c: &mut Criterion) {
     group.finish();
 }
 
+fn denoise_channel(samples: &mut [f32], denoiser: &mut DenoiseState<'static>) {
+    let input = samples.to_vec();
+    process_audio(&input, samples, denoiser, None);
+}
+
+fn benchmark_parallel_channels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_channels");
+    
+    // Stereo frame with one independent denoiser per channel, serial vs a persistent worker
+    for &threads in &[1usize, 2] {
+        group.bench_with_input(
+            BenchmarkId::new("stereo_denoise", threads),
+            &threads,
+            |b, &threads| {
+                let denoisers = vec![create_test_denoiser(), create_test_denoiser()];
+                let mut workers = ChannelWorkers::new(denoisers, threads, denoise_channel);
+                let mut channels = vec![vec![0.1; 480], vec![0.1; 480]];
+                
+                b.iter(|| {
+                    workers.process(black_box(&mut channels));
+                });
+            },
+        );
+    }
+    
+    group.finish();
+}
+
 criterion_group!(
     ai_benchmarks,
     benchmark_ai_processing_latency,
@@ -255,7 +284,8 @@ criterion_group!(
     benchmark_ai_metrics_performance,
     benchmark_real_time_performance,
     benchmark_competitive_performance,
-    benchmark_vad_accuracy
+    benchmark_vad_accuracy,
+    benchmark_parallel_channels
 );
 
 criterion_main!(ai_benchmarks);
\ No newline at end of file
@@ -139,6 +139,7 @@ fn benchmark_memory_usage(c: &mut Criterion) {
                     remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
                     analytics: kwite::config::AnalyticsConfig::default(),
                     auto_update: kwite::config::AutoUpdateConfig::default(),
+                    ..KwiteConfig::default()
                 });
             }
         })
@@ -69,6 +69,60 @@ pub struct AiMetrics {
     
     /// Environmental adaptation confidence
     pub adaptation_confidence: f32,
+    
+    /// Whether the denoiser changed each recent non-silent frame
+    pub frames_modified: VecDeque<bool>,
+    
+    /// Latest smoothed score from the analyzer VAD (see `VadSource`)
+    pub analyzer_vad_score: Option<f32>,
+    
+    /// Summed input energy of frames the gate treated as noise
+    pub noise_input_energy: f64,
+    
+    /// Summed output energy of the same noise frames
+    pub noise_output_energy: f64,
+    
+    /// RMS of the latest input frame (linear, before denoising)
+    pub input_rms: f32,
+    
+    /// Peak magnitude of the latest input frame (linear, before denoising)
+    pub input_peak: f32,
+    
+    /// Latest input spectrum in log-spaced bands (dBFS), while the spectrum display is open
+    pub spectrum: Option<Vec<f32>>,
+    
+    /// Capture-to-playback latency of recent frames in microseconds, measured at the output
+    pub end_to_end_latencies: VecDeque<u64>,
+    
+    /// Pipeline rebuilds by the stream watchdog in this process (kept across `reset`)
+    pub watchdog_restarts: u64,
+}
+
+/// Input peak below which a frame is too quiet to judge whether RNNoise changed it
+const MODIFIED_FRAME_MIN_INPUT: f32 = 1e-3;
+
+/// Largest per-sample difference still treated as "output equals input"
+const MODIFIED_FRAME_TOLERANCE: f32 = 1e-6;
+
+/// Whether the denoiser meaningfully changed a frame
+/// 
+/// Returns `None` for near-silent input, where identical input and output says
+/// nothing about the denoiser's health.
+pub fn frame_modified(input: &[f32], output: &[f32]) -> Option<bool> {
+    if input.iter().all(|s| s.abs() < MODIFIED_FRAME_MIN_INPUT) {
+        return None;
+    }
+    Some(output.iter().zip(input.iter()).any(|(o, i)| (o - i).abs() > MODIFIED_FRAME_TOLERANCE))
+}
+
+/// Noise reduction measured from input and output energy (0 - 100)
+/// 
+/// `None` until some noise energy has been seen.
+pub fn energy_reduction_percent(input_energy: f64, output_energy: f64) -> Option<f32> {
+    if input_energy <= 0.0 {
+        return None;
+    }
+    Some(((1.0 - output_energy / input_energy) * 100.0).clamp(0.0, 100.0) as f32)
 }
 
 impl Default for AiMetrics {
@@ -86,6 +140,15 @@ impl Default for AiMetrics {
             noise_reduction_percent: 0.0,
             current_noise_type: NoiseType::Unknown,
             adaptation_confidence: 0.0,
+            frames_modified: VecDeque::with_capacity(100),
+            analyzer_vad_score: None,
+            noise_input_energy: 0.0,
+            noise_output_energy: 0.0,
+            input_rms: 0.0,
+            input_peak: 0.0,
+            spectrum: None,
+            end_to_end_latencies: VecDeque::with_capacity(100),
+            watchdog_restarts: 0,
         }
     }
 }
@@ -185,6 +248,51 @@ impl AiMetrics {
         variance.sqrt() // Return standard deviation
     }
     
+    /// Record whether the denoiser changed a non-silent frame (see `frame_modified`)
+    pub fn record_frame_modified(&mut self, modified: bool) {
+        self.frames_modified.push_back(modified);
+        if self.frames_modified.len() > 100 {
+            self.frames_modified.pop_front();
+        }
+    }
+    
+    /// Share of recent non-silent frames the denoiser actually changed (0 - 100)
+    /// 
+    /// `None` until a non-silent frame has been seen. A value stuck near zero
+    /// means audio is flowing but RNNoise isn't doing anything to it.
+    pub fn processing_active_percent(&self) -> Option<f32> {
+        if self.frames_modified.is_empty() {
+            return None;
+        }
+        let modified = self.frames_modified.iter().filter(|&&m| m).count();
+        Some(modified as f32 / self.frames_modified.len() as f32 * 100.0)
+    }
+    
+    /// Accumulate the energy of a frame the gate treated as noise
+    /// 
+    /// Comparing raw input with final output over noise frames gives a
+    /// measured reduction, unlike the VAD-based `noise_reduction_percent`.
+    pub fn record_noise_frame(&mut self, input: &[f32], output: &[f32]) {
+        self.noise_input_energy += input.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>();
+        self.noise_output_energy += output.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>();
+    }
+    
+    /// Noise reduction measured over this run's noise frames (0 - 100)
+    pub fn measured_noise_reduction_percent(&self) -> Option<f32> {
+        energy_reduction_percent(self.noise_input_energy, self.noise_output_energy)
+    }
+    
+    /// Processing latency at `percentile` (0.0 - 1.0) over the recent window, in microseconds
+    pub fn latency_percentile_us(&self, percentile: f32) -> u64 {
+        if self.processing_latencies.is_empty() {
+            return 0;
+        }
+        let mut sorted: Vec<u64> = self.processing_latencies.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (percentile.clamp(0.0, 1.0) * (sorted.len() - 1) as f32).round() as usize;
+        sorted[rank]
+    }
+    
     /// Get current frames per second estimate
     pub fn calculate_fps(&self) -> u32 {
         // Estimate based on 48kHz sample rate and 480 sample frames
@@ -249,6 +357,35 @@ impl AiMetrics {
     
     /// Update confidence score
     /// 
+    /// Record the measured time from capture until a frame starts playing
+    /// 
+    /// Unlike `record_frame`'s processing time, this covers the whole path through
+    /// Kwite: channel queues, framing, processing and the output buffer.
+    pub fn record_end_to_end_latency(&mut self, latency: Duration) {
+        self.end_to_end_latencies.push_back(latency.as_micros() as u64);
+        if self.end_to_end_latencies.len() > 100 {
+            self.end_to_end_latencies.pop_front();
+        }
+    }
+    
+    /// Average end-to-end latency over recent frames in milliseconds (`None` before any output)
+    pub fn avg_end_to_end_latency_ms(&self) -> Option<f32> {
+        if self.end_to_end_latencies.is_empty() {
+            return None;
+        }
+        let sum: u64 = self.end_to_end_latencies.iter().sum();
+        Some(sum as f32 / self.end_to_end_latencies.len() as f32 / 1000.0)
+    }
+    
+    /// Record the level of the input frame the denoiser is about to process
+    pub fn record_input_level(&mut self, frame: &[f32]) {
+        if frame.is_empty() {
+            return;
+        }
+        self.input_rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        self.input_peak = frame.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    }
+    
     /// Allows external updating of model confidence for environmental adaptation
     pub fn update_confidence(&mut self, confidence: f32) {
         self.model_confidence = confidence.clamp(0.0, 1.0);
@@ -264,6 +401,19 @@ impl AiMetrics {
             noise_reduction_percent: self.noise_reduction_percent,
             frames_processed: self.total_frames,
             estimated_fps: self.calculate_fps(),
+            processing_active_percent: self.processing_active_percent(),
+            denoiser_vad_score: self.vad_scores.back().copied(),
+            analyzer_vad_score: self.analyzer_vad_score,
+            measured_noise_reduction_percent: self.measured_noise_reduction_percent(),
+            noise_input_energy: self.noise_input_energy,
+            noise_output_energy: self.noise_output_energy,
+            input_rms: self.input_rms,
+            input_peak: self.input_peak,
+            spectrum: self.spectrum.clone(),
+            end_to_end_latency_ms: self.avg_end_to_end_latency_ms(),
+            peak_end_to_end_latency_ms: self.end_to_end_latencies.iter().max().map_or(0.0, |&us| us as f32 / 1000.0),
+            noise_type: self.current_noise_type,
+            watchdog_restarts: self.watchdog_restarts,
             ai_status: if self.model_confidence > 0.8 {
                 AiStatus::Excellent
             } else if self.model_confidence > 0.6 {
@@ -289,6 +439,14 @@ impl AiMetrics {
         self.noise_reduction_percent = 0.0;
         self.current_noise_type = NoiseType::Unknown;
         self.adaptation_confidence = 0.0;
+        self.frames_modified.clear();
+        self.analyzer_vad_score = None;
+        self.noise_input_energy = 0.0;
+        self.noise_output_energy = 0.0;
+        self.input_rms = 0.0;
+        self.input_peak = 0.0;
+        self.spectrum = None;
+        self.end_to_end_latencies.clear();
         self.last_update = Instant::now();
     }
 }
@@ -303,6 +461,32 @@ pub struct PerformanceSummary {
     pub noise_reduction_percent: f32,
     pub frames_processed: u64,
     pub estimated_fps: u32,
+    /// Share of recent non-silent frames RNNoise changed (`None` before any input)
+    pub processing_active_percent: Option<f32>,
+    /// Latest per-frame RNNoise VAD score
+    pub denoiser_vad_score: Option<f32>,
+    /// Latest smoothed analyzer VAD score
+    pub analyzer_vad_score: Option<f32>,
+    /// Reduction measured from input vs. output energy over noise frames
+    pub measured_noise_reduction_percent: Option<f32>,
+    /// Summed input energy of noise frames (for session totals)
+    pub noise_input_energy: f64,
+    /// Summed output energy of noise frames (for session totals)
+    pub noise_output_energy: f64,
+    /// RMS of the latest input frame (linear)
+    pub input_rms: f32,
+    /// Peak magnitude of the latest input frame (linear)
+    pub input_peak: f32,
+    /// Latest input spectrum bands (dBFS), `None` while the display is closed
+    pub spectrum: Option<Vec<f32>>,
+    /// Average capture-to-playback latency over recent frames (`None` before any output)
+    pub end_to_end_latency_ms: Option<f32>,
+    /// Highest capture-to-playback latency over recent frames
+    pub peak_end_to_end_latency_ms: f32,
+    /// Latest (debounced) noise-type classification of the input
+    pub noise_type: NoiseType,
+    /// Pipeline rebuilds by the stream watchdog in this process
+    pub watchdog_restarts: u64,
     pub ai_status: AiStatus,
 }
 
@@ -335,6 +519,78 @@ impl AiStatus {
     }
 }
 
+/// Performance totals that survive enable/disable cycles within one app run
+///
+/// `AiMetrics` is recreated every time processing starts, so the GUI folds each
+/// finished run into this summary to keep a "this session" view. Averages are
+/// weighted by the number of frames each run processed.
+#[derive(Debug, Clone, Default)]
+pub struct CumulativePerformance {
+    /// Number of completed processing runs
+    pub runs: u32,
+    /// Frames processed across all runs
+    pub frames_processed: u64,
+    /// Highest per-frame latency seen in any run
+    pub peak_latency_ms: f32,
+    vad_sum: f64,
+    latency_sum_ms: f64,
+    noise_reduction_sum: f64,
+    noise_input_energy: f64,
+    noise_output_energy: f64,
+}
+
+impl CumulativePerformance {
+    /// Fold a finished run into the session totals
+    pub fn absorb(&mut self, run: &PerformanceSummary) {
+        let frames = run.frames_processed as f64;
+        self.runs += 1;
+        self.frames_processed += run.frames_processed;
+        self.peak_latency_ms = self.peak_latency_ms.max(run.peak_latency_ms);
+        self.vad_sum += run.avg_vad_score as f64 * frames;
+        self.latency_sum_ms += run.avg_latency_ms as f64 * frames;
+        self.noise_reduction_sum += run.noise_reduction_percent as f64 * frames;
+        self.noise_input_energy += run.noise_input_energy;
+        self.noise_output_energy += run.noise_output_energy;
+    }
+
+    /// Session totals including the in-progress run (without absorbing it)
+    pub fn including(&self, current: Option<&PerformanceSummary>) -> Self {
+        let mut totals = self.clone();
+        if let Some(run) = current {
+            totals.absorb(run);
+        }
+        totals
+    }
+
+    fn weighted_average(&self, sum: f64) -> f32 {
+        if self.frames_processed == 0 {
+            0.0
+        } else {
+            (sum / self.frames_processed as f64) as f32
+        }
+    }
+
+    /// Frame-weighted average VAD score
+    pub fn avg_vad_score(&self) -> f32 {
+        self.weighted_average(self.vad_sum)
+    }
+
+    /// Frame-weighted average processing latency
+    pub fn avg_latency_ms(&self) -> f32 {
+        self.weighted_average(self.latency_sum_ms)
+    }
+
+    /// Frame-weighted average noise reduction
+    pub fn noise_reduction_percent(&self) -> f32 {
+        self.weighted_average(self.noise_reduction_sum)
+    }
+
+    /// Energy-weighted average noise reduction measured over every noise frame
+    pub fn measured_noise_reduction_percent(&self) -> Option<f32> {
+        energy_reduction_percent(self.noise_input_energy, self.noise_output_energy)
+    }
+}
+
 /// Thread-safe AI metrics container for sharing between threads
 pub type SharedAiMetrics = Arc<Mutex<AiMetrics>>;
 
@@ -385,6 +641,37 @@ mod tests {
         assert_eq!(summary.avg_vad_score, 0.9);
         assert_eq!(summary.avg_latency_ms, 5.0);
         assert_eq!(summary.frames_processed, 1);
+        assert_eq!(summary.noise_type, NoiseType::Unknown);
+
+        metrics.update_noise_type(NoiseType::Keyboard);
+        assert_eq!(metrics.get_performance_summary().noise_type, NoiseType::Keyboard);
+    }
+    
+    #[test]
+    fn test_processing_active_percent() {
+        let input = vec![0.2, -0.1, 0.05];
+        assert_eq!(frame_modified(&[0.0; 3], &[0.0; 3]), None, "Silence is not judged");
+        assert_eq!(frame_modified(&input, &input), Some(false));
+        assert_eq!(frame_modified(&input, &[0.1, -0.05, 0.0]), Some(true));
+        
+        let mut metrics = AiMetrics::new();
+        assert_eq!(metrics.processing_active_percent(), None);
+        for i in 0..10 {
+            metrics.record_frame_modified(i < 9);
+        }
+        assert_eq!(metrics.processing_active_percent(), Some(90.0));
+        assert_eq!(metrics.get_performance_summary().processing_active_percent, Some(90.0));
+    }
+    
+    #[test]
+    fn test_latency_percentile() {
+        let mut metrics = AiMetrics::new();
+        assert_eq!(metrics.latency_percentile_us(0.95), 0);
+        for us in 1..=100 {
+            metrics.record_frame(0.5, Duration::from_micros(us));
+        }
+        assert_eq!(metrics.latency_percentile_us(0.95), 95);
+        assert_eq!(metrics.latency_percentile_us(1.0), 100);
     }
     
     #[test]
@@ -399,4 +686,83 @@ mod tests {
         let summary = metrics.get_performance_summary();
         assert!(matches!(summary.ai_status, AiStatus::Excellent | AiStatus::Good));
     }
+    
+    #[test]
+    fn test_cumulative_performance_survives_runs() {
+        let mut first = AiMetrics::new();
+        for _ in 0..30 {
+            first.record_frame(0.9, Duration::from_micros(2000));
+        }
+        let mut second = AiMetrics::new();
+        for _ in 0..10 {
+            second.record_frame(0.5, Duration::from_micros(6000));
+        }
+        
+        let mut session = CumulativePerformance::default();
+        session.absorb(&first.get_performance_summary());
+        
+        let totals = session.including(Some(&second.get_performance_summary()));
+        assert_eq!(session.runs, 1, "including() must not modify the session");
+        assert_eq!(totals.runs, 2);
+        assert_eq!(totals.frames_processed, 40);
+        assert!((totals.avg_vad_score() - 0.8).abs() < 0.01);
+        assert!((totals.avg_latency_ms() - 3.0).abs() < 0.01);
+        assert!((totals.peak_latency_ms - 6.0).abs() < 0.01);
+    }
+    
+    #[test]
+    fn test_measured_noise_reduction_accumulates_across_runs() {
+        let mut first = AiMetrics::new();
+        assert_eq!(first.measured_noise_reduction_percent(), None);
+        // Noise gated to 10% amplitude keeps 1% of its energy
+        first.record_noise_frame(&[0.5; 480], &[0.05; 480]);
+        assert!((first.measured_noise_reduction_percent().unwrap() - 99.0).abs() < 0.01);
+        
+        // An equally loud run that removed nothing
+        let mut second = AiMetrics::new();
+        second.record_noise_frame(&[0.5; 480], &[0.5; 480]);
+        
+        let mut session = CumulativePerformance::default();
+        session.absorb(&first.get_performance_summary());
+        let totals = session.including(Some(&second.get_performance_summary()));
+        assert!((totals.measured_noise_reduction_percent().unwrap() - 49.5).abs() < 0.01);
+        
+        first.reset();
+        assert_eq!(first.measured_noise_reduction_percent(), None);
+    }
+    
+    #[test]
+    fn test_input_level_is_published_in_summary() {
+        let mut metrics = AiMetrics::new();
+        // Square wave at half scale: RMS and peak are both 0.5
+        let frame: Vec<f32> = (0..480).map(|i| if i % 2 == 0 { 0.5 } else { -0.5 }).collect();
+        metrics.record_input_level(&frame);
+        
+        let summary = metrics.get_performance_summary();
+        assert!((summary.input_rms - 0.5).abs() < 1e-6);
+        assert!((summary.input_peak - 0.5).abs() < 1e-6);
+        
+        metrics.record_input_level(&[0.0, 0.0, 0.0, 1.0]);
+        assert!((metrics.input_rms - 0.5).abs() < 1e-6);
+        assert_eq!(metrics.input_peak, 1.0);
+    }
+    
+    #[test]
+    fn test_end_to_end_latency_is_kept_apart_from_processing_time() {
+        let mut metrics = AiMetrics::new();
+        assert_eq!(metrics.get_performance_summary().end_to_end_latency_ms, None);
+        
+        metrics.record_frame(0.5, Duration::from_micros(300));
+        for ms in [18, 22, 20] {
+            metrics.record_end_to_end_latency(Duration::from_millis(ms));
+        }
+        
+        let summary = metrics.get_performance_summary();
+        assert_eq!(summary.end_to_end_latency_ms, Some(20.0));
+        assert_eq!(summary.peak_end_to_end_latency_ms, 22.0);
+        assert_eq!(summary.avg_latency_ms, 0.3);
+        
+        metrics.reset();
+        assert_eq!(metrics.avg_end_to_end_latency_ms(), None);
+    }
 }
\ No newline at end of file
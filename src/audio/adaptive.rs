@@ -0,0 +1,163 @@
+//! # Adaptive Sensitivity Module
+//!
+//! With auto sensitivity enabled, the analyzer sensitivity follows the room
+//! instead of the slider. The processing thread feeds every background (non-
+//! speech) frame into a noise floor estimate and maps the floor to a
+//! sensitivity: a quiet room gets a conservative detector that keeps soft
+//! speech, a loud one an aggressive detector that rejects more of the noise.
+//!
+//! The floor estimate starts from the `SpectralGate` update rule: frames far
+//! above the current floor are ignored, so a door slam or a cough never moves
+//! it. Unlike the gate's, it also follows a room that gets louder: noise that
+//! stays loud for `SUSTAINED_NOISE_FRAMES` starts raising it. That is only safe
+//! because it is fed background frames alone; continuous speech would drag it
+//! up to the talker's level. The resulting sensitivity glides towards its
+//! target over about a second, so the detector never jumps between frames.
+//!
+//! Auto sensitivity runs in the mono pipeline only.
+
+/// Noise floor assumed before any background has been measured (-60 dBFS)
+pub const INITIAL_NOISE_FLOOR: f32 = 0.001;
+
+/// Frames louder than this multiple of the floor don't update it directly
+const TRANSIENT_RATIO: f32 = 2.0;
+
+/// Consecutive loud background frames after which the floor starts rising (1s)
+pub const SUSTAINED_NOISE_FRAMES: u32 = 100;
+
+/// Per-frame rise of the floor while noise stays loud (~30 dB in under 2s)
+const SUSTAINED_RISE: f32 = 1.02;
+
+/// Floor at or below which the quiet-room sensitivity applies (dBFS)
+pub const QUIET_FLOOR_DB: f32 = -65.0;
+
+/// Floor at or above which the noisy-room sensitivity applies (dBFS)
+pub const NOISY_FLOOR_DB: f32 = -35.0;
+
+/// Sensitivity used in a quiet room (conservative)
+pub const QUIET_SENSITIVITY: f32 = 0.25;
+
+/// Sensitivity used in a noisy room (aggressive)
+pub const NOISY_SENSITIVITY: f32 = 0.02;
+
+/// Share of the remaining distance to the target covered per frame (~1s glide)
+const SENSITIVITY_SMOOTHING: f32 = 0.01;
+
+/// Smallest drift worth reconfiguring the voice detectors for
+pub const MIN_APPLIED_CHANGE: f32 = 0.005;
+
+/// Running estimate of the background level, robust against short loud transients
+///
+/// Feed it background frames only (see the module docs).
+#[derive(Debug, Clone)]
+pub struct NoiseFloorTracker {
+    floor: f32,
+    loud_frames: u32,
+}
+
+impl Default for NoiseFloorTracker {
+    fn default() -> Self {
+        Self { floor: INITIAL_NOISE_FLOOR, loud_frames: 0 }
+    }
+}
+
+impl NoiseFloorTracker {
+    /// Fold one frame's RMS into the estimate and return the new floor
+    pub fn update(&mut self, rms: f32) -> f32 {
+        if rms < self.floor * TRANSIENT_RATIO {
+            self.floor = self.floor * 0.99 + rms * 0.01;
+            self.loud_frames = 0;
+        } else {
+            self.loud_frames = self.loud_frames.saturating_add(1);
+            if self.loud_frames >= SUSTAINED_NOISE_FRAMES {
+                self.floor = (self.floor * SUSTAINED_RISE).min(rms);
+            }
+        }
+        self.floor
+    }
+
+    /// Current floor estimate (linear RMS)
+    pub fn floor(&self) -> f32 {
+        self.floor
+    }
+}
+
+/// Sensitivity for a given noise floor, interpolated between the quiet and noisy settings
+pub fn sensitivity_for_floor(floor_db: f32) -> f32 {
+    let t = ((floor_db - QUIET_FLOOR_DB) / (NOISY_FLOOR_DB - QUIET_FLOOR_DB)).clamp(0.0, 1.0);
+    QUIET_SENSITIVITY + (NOISY_SENSITIVITY - QUIET_SENSITIVITY) * t
+}
+
+/// Analyzer sensitivity derived from the measured noise floor
+#[derive(Debug, Clone)]
+pub struct AutoSensitivity {
+    tracker: NoiseFloorTracker,
+    sensitivity: f32,
+}
+
+impl AutoSensitivity {
+    /// Start from `initial` (the manual sensitivity) and glide from there
+    pub fn new(initial: f32) -> Self {
+        Self { tracker: NoiseFloorTracker::default(), sensitivity: initial.clamp(crate::audio::MIN_SENSITIVITY, crate::audio::MAX_SENSITIVITY) }
+    }
+
+    /// Feed one background frame; returns the updated sensitivity
+    ///
+    /// Call only for frames judged to be noise, so speech never raises the floor.
+    pub fn update(&mut self, frame: &[f32]) -> f32 {
+        let rms = (frame.iter().map(|&s| s * s).sum::<f32>() / frame.len().max(1) as f32).sqrt();
+        let floor = self.tracker.update(rms);
+        let floor_db = 20.0 * floor.max(1e-10).log10();
+        self.sensitivity += (sensitivity_for_floor(floor_db) - self.sensitivity) * SENSITIVITY_SMOOTHING;
+        self.sensitivity
+    }
+
+    /// Current sensitivity
+    pub fn sensitivity(&self) -> f32 {
+        self.sensitivity
+    }
+
+    /// Current noise floor estimate in dBFS
+    pub fn noise_floor_db(&self) -> f32 {
+        20.0 * self.tracker.floor().max(1e-10).log10()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_sensitivity_follows_floor_but_ignores_transients() {
+        let quiet = vec![0.0005; 480]; // about -66 dBFS
+        let mut auto = AutoSensitivity::new(0.1);
+        for _ in 0..1000 {
+            auto.update(&quiet);
+        }
+        assert!((auto.sensitivity() - QUIET_SENSITIVITY).abs() < 0.01);
+
+        // Half a second of door slam doesn't move the floor
+        let floor_before = auto.noise_floor_db();
+        for _ in 0..50 {
+            auto.update(&[0.5; 480]);
+        }
+        assert_eq!(auto.noise_floor_db(), floor_before);
+        assert!((auto.sensitivity() - QUIET_SENSITIVITY).abs() < 0.01);
+
+        // A fan that keeps running does, after a while
+        let fan = vec![0.03; 480]; // about -30 dBFS
+        for _ in 0..1000 {
+            auto.update(&fan);
+        }
+        assert!(auto.noise_floor_db() > -31.0);
+        assert!((auto.sensitivity() - NOISY_SENSITIVITY).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sensitivity_mapping_is_clamped() {
+        assert!((sensitivity_for_floor(-90.0) - QUIET_SENSITIVITY).abs() < 1e-6);
+        assert!((sensitivity_for_floor(-10.0) - NOISY_SENSITIVITY).abs() < 1e-6);
+        let middle = sensitivity_for_floor((QUIET_FLOOR_DB + NOISY_FLOOR_DB) / 2.0);
+        assert!((middle - (QUIET_SENSITIVITY + NOISY_SENSITIVITY) / 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,99 @@
+//! # Processing Thread Affinity Module
+//!
+//! On hybrid CPUs (Intel Alder Lake and later, ARM big.LITTLE) the scheduler may
+//! move the audio processing thread onto an efficiency core, where it can miss its
+//! 10ms deadline and cause dropouts. This module optionally pins the processing
+//! thread to a single core using `core_affinity`.
+//!
+//! Pinning is off by default. It complements the thread-priority tweaks applied on
+//! Apple Silicon; note that macOS does not support hard affinity, so pinning is a
+//! logged no-op there.
+
+use crate::logger::log;
+use serde::{Deserialize, Serialize};
+
+/// Where to pin the audio processing thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThreadAffinity {
+    /// Let the OS scheduler place the thread (default)
+    #[default]
+    Disabled,
+    /// Pick a performance core automatically
+    ///
+    /// Hybrid CPUs enumerate performance cores first, so the lowest-numbered cores
+    /// are P-cores. Core 0 and its SMT sibling are skipped when possible because
+    /// they service most interrupts.
+    PerformanceCore,
+    /// Pin to a specific logical core index
+    Core(usize),
+}
+
+impl ThreadAffinity {
+    /// Human readable description for the settings UI
+    pub fn label(&self) -> String {
+        match self {
+            ThreadAffinity::Disabled => "Disabled (OS scheduler)".to_string(),
+            ThreadAffinity::PerformanceCore => "Performance core (auto)".to_string(),
+            ThreadAffinity::Core(index) => format!("Core {}", index),
+        }
+    }
+}
+
+/// Choose the logical core index for an affinity setting
+///
+/// Returns `None` when pinning is disabled or the requested core doesn't exist.
+pub fn select_core_index(affinity: ThreadAffinity, core_count: usize) -> Option<usize> {
+    if core_count == 0 {
+        return None;
+    }
+
+    match affinity {
+        ThreadAffinity::Disabled => None,
+        ThreadAffinity::PerformanceCore => Some(if core_count > 2 { 2 } else { 0 }),
+        ThreadAffinity::Core(index) => (index < core_count).then_some(index),
+    }
+}
+
+/// Number of logical cores available for pinning
+pub fn available_core_count() -> usize {
+    core_affinity::get_core_ids().map(|ids| ids.len()).unwrap_or(0)
+}
+
+/// Pin the calling thread according to `affinity`, logging the outcome
+///
+/// Failures are logged and otherwise ignored; an unpinned thread still works.
+pub fn pin_current_thread(affinity: ThreadAffinity) {
+    if affinity == ThreadAffinity::Disabled {
+        return;
+    }
+
+    let core_ids = core_affinity::get_core_ids().unwrap_or_default();
+    let core = match select_core_index(affinity, core_ids.len()) {
+        Some(index) => core_ids[index],
+        None => {
+            log::warn!("⚠️ Cannot apply thread affinity {:?}: only {} cores available", affinity, core_ids.len());
+            return;
+        }
+    };
+
+    if core_affinity::set_for_current(core) {
+        log::info!("📌 Audio processing thread pinned to core {}", core.id);
+    } else {
+        log::warn!("⚠️ Failed to pin audio processing thread to core {} (not supported on this OS?)", core.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_core_index() {
+        assert_eq!(select_core_index(ThreadAffinity::Disabled, 8), None);
+        assert_eq!(select_core_index(ThreadAffinity::PerformanceCore, 8), Some(2));
+        assert_eq!(select_core_index(ThreadAffinity::PerformanceCore, 2), Some(0));
+        assert_eq!(select_core_index(ThreadAffinity::Core(3), 8), Some(3));
+        assert_eq!(select_core_index(ThreadAffinity::Core(8), 8), None);
+        assert_eq!(select_core_index(ThreadAffinity::PerformanceCore, 0), None);
+    }
+}
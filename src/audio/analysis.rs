@@ -23,10 +23,162 @@
 #![allow(dead_code)]
 
 #[cfg(feature = "ai-enhanced")]
-use webrtc_vad::{Vad, SampleRate};
+use webrtc_vad::{Vad, SampleRate, VadMode};
 #[cfg(feature = "ai-enhanced")]
 use rustfft::{FftPlanner, num_complex::Complex};
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Default length of the voice probability smoothing window (frames)
+pub const DEFAULT_VAD_HISTORY_LEN: usize = 10;
+/// Shortest allowed smoothing window (no smoothing)
+pub const MIN_VAD_HISTORY_LEN: usize = 1;
+/// Longest allowed smoothing window (500ms at 10ms frames)
+pub const MAX_VAD_HISTORY_LEN: usize = 50;
+
+/// Which voice activity score drives the speech/noise gain decision
+///
+/// The two detectors measure different things and can disagree:
+///
+/// - `Denoiser`: RNNoise's own per-frame speech probability. It is continuous,
+///   reacts within a frame and is what the gate has always used.
+/// - `Analyzer`: the analyzer's `VoiceActivityDetector` (WebRTC VAD with the
+///   `ai-enhanced` feature, an energy detector otherwise), smoothed over
+///   `vad_history_len` frames. Steadier, but slower to react and effectively
+///   two-valued, so it suits environments where RNNoise flickers on noise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VadSource {
+    /// RNNoise's per-frame VAD (default)
+    #[default]
+    Denoiser,
+    /// The analyzer's smoothed voice activity detector
+    Analyzer,
+}
+
+impl VadSource {
+    /// Human readable name for the settings UI
+    pub fn label(&self) -> &'static str {
+        match self {
+            VadSource::Denoiser => "RNNoise",
+            VadSource::Analyzer => "Analyzer",
+        }
+    }
+}
+
+/// WebRTC VAD aggressiveness level (0 = quality .. 3 = very aggressive) for an analyzer sensitivity
+///
+/// Analyzer sensitivity follows the same convention as the sensitivity slider:
+/// lower values reject noise more aggressively. The 0.1 default keeps WebRTC's
+/// quality mode. It is independent of the denoise gain threshold (`vad_threshold`).
+pub fn vad_aggressiveness(sensitivity: f32) -> u8 {
+    match sensitivity {
+        s if s >= 0.1 => 0,
+        s if s >= 0.05 => 1,
+        s if s >= 0.02 => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(feature = "ai-enhanced")]
+fn vad_mode(sensitivity: f32) -> VadMode {
+    match vad_aggressiveness(sensitivity) {
+        0 => VadMode::Quality,
+        1 => VadMode::LowBitrate,
+        2 => VadMode::Aggressive,
+        _ => VadMode::VeryAggressive,
+    }
+}
+
+/// Default spectral analysis window (one RNNoise frame)
+pub const DEFAULT_ANALYSIS_WINDOW: usize = 480;
+/// Smallest spectral analysis window (samples)
+pub const MIN_ANALYSIS_WINDOW: usize = 480;
+/// Largest spectral analysis window (samples, 80ms at 48kHz)
+pub const MAX_ANALYSIS_WINDOW: usize = 3840;
+
+/// Bands shown by the Geek Mode spectrum display
+pub const SPECTRUM_DISPLAY_BANDS: usize = 32;
+/// Lowest frequency of the spectrum display (Hz)
+pub const SPECTRUM_MIN_HZ: f32 = 50.0;
+/// Level reported for empty spectrum bands (dBFS)
+pub const SPECTRUM_FLOOR_DB: f32 = -90.0;
+
+/// Center frequency of each FFT bin for a window of `window_size` samples
+///
+/// Bin spacing is `sample_rate / window_size`, so larger windows resolve
+/// neighbouring frequencies more finely.
+pub fn frequency_bins(window_size: usize, sample_rate: f32) -> Vec<f32> {
+    (0..window_size / 2)
+        .map(|i| i as f32 * sample_rate / window_size as f32)
+        .collect()
+}
+
+/// Reduce a Hann-windowed magnitude spectrum to `band_count` log-spaced bands (dBFS)
+///
+/// Bands run from `min_hz` up to the highest bin. Each band shows its loudest
+/// bin, scaled so a full-scale sine reads 0 dB; bands too narrow to contain a
+/// bin (at the low end) take the bin nearest their center.
+pub fn log_spaced_bands(magnitudes: &[f32], bins: &[f32], band_count: usize, min_hz: f32) -> Vec<f32> {
+    let max_hz = bins.last().copied().unwrap_or(0.0);
+    if magnitudes.is_empty() || max_hz <= min_hz {
+        return vec![SPECTRUM_FLOOR_DB; band_count];
+    }
+    // A Hann window halves the coherent gain, so a sine of amplitude A peaks at A * N / 4
+    let scale = 2.0 / magnitudes.len() as f32;
+    let ratio = (max_hz / min_hz).powf(1.0 / band_count as f32);
+    
+    (0..band_count)
+        .map(|band| {
+            let low = min_hz * ratio.powi(band as i32);
+            let high = low * ratio;
+            let in_band = bins.iter().zip(magnitudes)
+                .filter(|(&frequency, _)| frequency >= low && frequency < high)
+                .map(|(_, &magnitude)| magnitude)
+                .reduce(f32::max);
+            let magnitude = in_band.unwrap_or_else(|| {
+                let center = (low * high).sqrt();
+                bins.iter().zip(magnitudes)
+                    .min_by(|a, b| (a.0 - center).abs().total_cmp(&(b.0 - center).abs()))
+                    .map_or(0.0, |(_, &magnitude)| magnitude)
+            });
+            let level = magnitude * scale;
+            if level > 0.0 { (20.0 * level.log10()).max(SPECTRUM_FLOOR_DB) } else { SPECTRUM_FLOOR_DB }
+        })
+        .collect()
+}
+
+/// Moving-average smoother for per-frame voice probabilities
+/// 
+/// Shorter windows react faster to speech onset; longer windows give a more
+/// stable probability at the cost of responsiveness.
+#[derive(Debug, Clone)]
+struct ProbabilitySmoother {
+    /// Recent voice probability scores
+    history: VecDeque<f32>,
+    /// Maximum number of scores kept in the window
+    history_len: usize,
+}
+
+impl ProbabilitySmoother {
+    fn new(history_len: usize) -> Self {
+        let history_len = history_len.clamp(MIN_VAD_HISTORY_LEN, MAX_VAD_HISTORY_LEN);
+        Self {
+            history: VecDeque::with_capacity(history_len),
+            history_len,
+        }
+    }
+    
+    /// Add a new score and return the smoothed probability
+    fn push(&mut self, probability: f32) -> f32 {
+        self.history.push_back(probability);
+        while self.history.len() > self.history_len {
+            self.history.pop_front();
+        }
+        
+        self.history.iter().sum::<f32>() / self.history.len() as f32
+    }
+}
 
 /// Enhanced Voice Activity Detection using professional WebRTC algorithms
 /// 
@@ -38,10 +190,7 @@ pub struct VoiceActivityDetector {
     vad: Vad,
     
     /// History of voice probability scores for smoothing
-    voice_probability_history: VecDeque<f32>,
-    
-    /// Sample rate for VAD processing
-    _sample_rate: SampleRate,
+    voice_probability_history: ProbabilitySmoother,
     
     /// Confidence threshold for voice detection
     confidence_threshold: f32,
@@ -51,8 +200,11 @@ pub struct VoiceActivityDetector {
 impl VoiceActivityDetector {
     /// Create a new Voice Activity Detector
     /// 
-    /// Uses WebRTC's proven VAD algorithms with configurable sensitivity
-    pub fn new(sample_rate: u32, sensitivity: f32) -> Result<Self, Box<dyn std::error::Error>> {
+    /// Uses WebRTC's proven VAD algorithms with configurable sensitivity.
+    /// `history_len` sets the smoothing window in frames and is clamped to
+    /// `MIN_VAD_HISTORY_LEN..=MAX_VAD_HISTORY_LEN`. `sample_rate` is the rate
+    /// of the frames passed to `detect`, which must be 10, 20 or 30ms long.
+    pub fn new(sample_rate: u32, sensitivity: f32, history_len: usize) -> Result<Self, Box<dyn std::error::Error>> {
         let vad_sample_rate = match sample_rate {
             8000 => SampleRate::Rate8kHz,
             16000 => SampleRate::Rate16kHz,
@@ -61,12 +213,11 @@ impl VoiceActivityDetector {
             _ => return Err("Unsupported sample rate for VAD".into()),
         };
         
-        let vad = Vad::new();
+        let vad = Vad::new_with_rate_and_mode(vad_sample_rate, vad_mode(sensitivity));
         
         Ok(Self {
             vad,
-            voice_probability_history: VecDeque::with_capacity(10),
-            _sample_rate: vad_sample_rate,
+            voice_probability_history: ProbabilitySmoother::new(history_len),
             confidence_threshold: sensitivity,
         })
     }
@@ -87,19 +238,14 @@ impl VoiceActivityDetector {
         // Convert binary result to probability with smoothing
         let current_probability = if is_speech { 0.9 } else { 0.1 };
         
-        // Add to history for smoothing
-        self.voice_probability_history.push_back(current_probability);
-        if self.voice_probability_history.len() > 10 {
-            self.voice_probability_history.pop_front();
-        }
-        
-        // Return smoothed probability
-        self.voice_probability_history.iter().sum::<f32>() / self.voice_probability_history.len() as f32
+        // Add to history and return smoothed probability
+        self.voice_probability_history.push(current_probability)
     }
     
-    /// Update detection sensitivity
+    /// Update detection sensitivity (maps to the WebRTC aggressiveness mode)
     pub fn set_sensitivity(&mut self, sensitivity: f32) {
         self.confidence_threshold = sensitivity;
+        self.vad.set_mode(vad_mode(sensitivity));
     }
 }
 
@@ -107,7 +253,7 @@ impl VoiceActivityDetector {
 #[cfg(not(feature = "ai-enhanced"))]
 pub struct VoiceActivityDetector {
     /// History of voice probability scores for smoothing
-    voice_probability_history: VecDeque<f32>,
+    voice_probability_history: ProbabilitySmoother,
     /// Confidence threshold for voice detection
     confidence_threshold: f32,
     /// Energy threshold for basic voice detection
@@ -117,11 +263,11 @@ pub struct VoiceActivityDetector {
 #[cfg(not(feature = "ai-enhanced"))]
 impl VoiceActivityDetector {
     /// Create a new basic Voice Activity Detector
-    pub fn new(_sample_rate: u32, sensitivity: f32) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(_sample_rate: u32, sensitivity: f32, history_len: usize) -> Result<Self, Box<dyn std::error::Error>> {
         Ok(Self {
-            voice_probability_history: VecDeque::with_capacity(10),
+            voice_probability_history: ProbabilitySmoother::new(history_len),
             confidence_threshold: sensitivity,
-            energy_threshold: 0.01,
+            energy_threshold: sensitivity * 0.1,
         })
     }
     
@@ -134,14 +280,8 @@ impl VoiceActivityDetector {
         // Simple threshold-based detection
         let current_probability = if rms > self.energy_threshold { 0.8 } else { 0.2 };
         
-        // Add to history for smoothing
-        self.voice_probability_history.push_back(current_probability);
-        if self.voice_probability_history.len() > 10 {
-            self.voice_probability_history.pop_front();
-        }
-        
-        // Return smoothed probability
-        self.voice_probability_history.iter().sum::<f32>() / self.voice_probability_history.len() as f32
+        // Add to history and return smoothed probability
+        self.voice_probability_history.push(current_probability)
     }
     
     /// Update detection sensitivity
@@ -185,9 +325,7 @@ impl SpectralAnalyzer {
             .collect();
         
         // Calculate frequency bins
-        let frequency_bins: Vec<f32> = (0..frame_size/2)
-            .map(|i| i as f32 * sample_rate / frame_size as f32)
-            .collect();
+        let frequency_bins = frequency_bins(frame_size, sample_rate);
         
         Self {
             fft_planner,
@@ -197,6 +335,22 @@ impl SpectralAnalyzer {
         }
     }
     
+    /// Number of samples analyzed per FFT
+    pub fn window_size(&self) -> usize {
+        self.window.len()
+    }
+    
+    /// Center frequency of each analyzed bin
+    pub fn frequency_bins(&self) -> &[f32] {
+        &self.frequency_bins
+    }
+    
+    /// Last analyzed spectrum in `SPECTRUM_DISPLAY_BANDS` log-spaced bands (dBFS)
+    pub fn display_bands(&self) -> Option<Vec<f32>> {
+        self.spectral_history.back()
+            .map(|magnitudes| log_spaced_bands(magnitudes, &self.frequency_bins, SPECTRUM_DISPLAY_BANDS, SPECTRUM_MIN_HZ))
+    }
+    
     /// Analyze frequency content of audio frame
     pub fn analyze(&mut self, samples: &[f32]) -> FrequencyProfile {
         if samples.len() != self.window.len() {
@@ -309,6 +463,16 @@ impl SpectralAnalyzer {
         }
     }
     
+    /// Number of samples analyzed per call
+    pub fn window_size(&self) -> usize {
+        self.frame_size
+    }
+    
+    /// No FFT runs without `ai-enhanced`, so there is no spectrum to display
+    pub fn display_bands(&self) -> Option<Vec<f32>> {
+        None
+    }
+    
     /// Basic energy-based analysis
     pub fn analyze(&mut self, samples: &[f32]) -> FrequencyProfile {
         if samples.len() != self.frame_size {
@@ -334,7 +498,7 @@ impl SpectralAnalyzer {
 }
 
 /// Frequency domain characteristics of audio
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct FrequencyProfile {
     /// Total energy in the frame
     pub total_energy: f32,
@@ -350,6 +514,93 @@ pub struct FrequencyProfile {
     pub spectral_rolloff: f32,
 }
 
+impl FrequencyProfile {
+    /// How alike two profiles' band distributions are (0.0 - 1.0)
+    ///
+    /// Compares the low/mid/high energy ratios only, so the same noise at a
+    /// different level still matches.
+    pub fn similarity(&self, other: &FrequencyProfile) -> f32 {
+        let distance = (self.low_freq_ratio - other.low_freq_ratio).abs()
+            + (self.mid_freq_ratio - other.mid_freq_ratio).abs()
+            + (self.high_freq_ratio - other.high_freq_ratio).abs();
+        (1.0 - distance / 2.0).clamp(0.0, 1.0)
+    }
+}
+
+/// Frames after which the noise profile average starts forgetting old audio (~30s)
+pub const NOISE_PROFILE_MAX_FRAMES: u64 = 3000;
+
+/// Similarity to an imported noise profile above which a frame counts as that background
+pub const ROOM_PROFILE_MATCH: f32 = 0.9;
+
+/// Averaged spectrum of the background noise, exportable as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoiseProfile {
+    /// When the profile was captured (RFC 3339)
+    pub captured_at: String,
+    /// Number of noise frames averaged into the profile
+    pub frames: u64,
+    /// Averaged frequency characteristics of the noise frames
+    pub profile: FrequencyProfile,
+}
+
+impl NoiseProfile {
+    /// Load a profile previously written by `save_to`
+    pub fn load_from(path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the profile as pretty-printed JSON
+    pub fn save_to(&self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Running average of the spectra of frames classified as noise
+///
+/// A plain mean up to `NOISE_PROFILE_MAX_FRAMES`, then an exponential average
+/// with the same weight, so the profile follows a changing room.
+#[derive(Debug, Clone, Default)]
+pub struct NoiseProfileAccumulator {
+    profile: FrequencyProfile,
+    frames: u64,
+}
+
+impl NoiseProfileAccumulator {
+    /// Fold one noise frame's spectrum into the average
+    pub fn add(&mut self, frame: &FrequencyProfile) {
+        self.frames += 1;
+        let weight = 1.0 / self.frames.min(NOISE_PROFILE_MAX_FRAMES) as f32;
+        let blend = |avg: &mut f32, value: f32| *avg += (value - *avg) * weight;
+        blend(&mut self.profile.total_energy, frame.total_energy);
+        blend(&mut self.profile.low_freq_ratio, frame.low_freq_ratio);
+        blend(&mut self.profile.mid_freq_ratio, frame.mid_freq_ratio);
+        blend(&mut self.profile.high_freq_ratio, frame.high_freq_ratio);
+        blend(&mut self.profile.spectral_centroid, frame.spectral_centroid);
+        blend(&mut self.profile.spectral_rolloff, frame.spectral_rolloff);
+    }
+
+    /// Start from an imported profile; new noise frames refine it
+    pub fn seed(&mut self, profile: &NoiseProfile) {
+        self.profile = profile.profile.clone();
+        self.frames = profile.frames.min(NOISE_PROFILE_MAX_FRAMES);
+    }
+
+    /// Current averaged profile, or `None` before any noise was heard
+    pub fn snapshot(&self) -> Option<NoiseProfile> {
+        (self.frames > 0).then(|| NoiseProfile {
+            captured_at: chrono::Local::now().to_rfc3339(),
+            frames: self.frames,
+            profile: self.profile.clone(),
+        })
+    }
+}
+
 /// Intelligent noise type classification
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum NoiseType {
@@ -368,6 +619,16 @@ pub enum NoiseType {
 }
 
 impl NoiseType {
+    /// Every classification, in display order
+    pub const ALL: [NoiseType; 6] = [
+        NoiseType::Silence,
+        NoiseType::Speech,
+        NoiseType::Keyboard,
+        NoiseType::HVAC,
+        NoiseType::Music,
+        NoiseType::Unknown,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             NoiseType::Silence => "Silence",
@@ -393,36 +654,220 @@ pub struct AudioContext {
     pub recommended_gain: f32,
 }
 
+/// Trend-aware noise type gate
+/// 
+/// The per-frame classifier can flap between noise types when the signal sits
+/// near a decision boundary. This gate inspects the recent context history and
+/// only switches to a new noise type once it has been observed for a number of
+/// consecutive frames, so downstream gain decisions stay stable.
+#[derive(Debug, Clone)]
+pub struct NoiseTypeGate {
+    /// Noise type currently considered stable
+    stable_type: NoiseType,
+    /// Consecutive frames required before switching to a new noise type
+    required_frames: usize,
+}
+
+impl NoiseTypeGate {
+    /// Create a new gate requiring `required_frames` consecutive frames to switch
+    pub fn new(required_frames: usize) -> Self {
+        Self {
+            stable_type: NoiseType::Unknown,
+            required_frames: required_frames.max(1),
+        }
+    }
+    
+    /// Update the smoothed decision from the analyzer's context history
+    /// 
+    /// Returns the stable noise type after taking the newest frame into account.
+    pub fn update(&mut self, history: &VecDeque<AudioContext>) -> NoiseType {
+        let latest = match history.back() {
+            Some(context) => context.noise_type,
+            None => return self.stable_type,
+        };
+        
+        if latest != self.stable_type {
+            let consecutive = history.iter()
+                .rev()
+                .take_while(|context| context.noise_type == latest)
+                .count();
+            
+            if consecutive >= self.required_frames {
+                self.stable_type = latest;
+            }
+        }
+        
+        self.stable_type
+    }
+    
+    /// Current smoothed noise type
+    pub fn stable_type(&self) -> NoiseType {
+        self.stable_type
+    }
+    
+    /// Number of consecutive frames required to switch
+    pub fn required_frames(&self) -> usize {
+        self.required_frames
+    }
+}
+
+/// Default minimum spacing between noise type change notifications
+pub const DEFAULT_NOISE_EVENT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Notification emitted when the smoothed noise type changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseTypeEvent {
+    /// Noise type reported by the previous event (or `Unknown` at start)
+    pub previous: NoiseType,
+    /// Newly stable noise type
+    pub current: NoiseType,
+    /// Wall-clock time of the transition
+    pub timestamp: SystemTime,
+}
+
+/// Callback invoked with each noise type change notification
+pub type NoiseTypeCallback = Box<dyn Fn(NoiseTypeEvent) + Send>;
+
+/// Debounced emitter for smoothed noise type transitions
+/// 
+/// `NoiseTypeGate` already suppresses per-frame flapping, but a real environment
+/// change (music starting and stopping) can still produce bursts of transitions.
+/// The notifier reports at most one event per `min_interval`; a change that
+/// happens inside the window is held back and reported once the window expires,
+/// and only if the type is still different from the last reported one.
+#[derive(Debug, Clone)]
+pub struct NoiseTypeNotifier {
+    /// Noise type carried by the last emitted event
+    reported_type: NoiseType,
+    /// When the last event was emitted
+    last_emit: Option<Instant>,
+    /// Minimum spacing between events
+    min_interval: Duration,
+}
+
+impl NoiseTypeNotifier {
+    /// Create a notifier emitting at most one event per `min_interval`
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            reported_type: NoiseType::Unknown,
+            last_emit: None,
+            min_interval,
+        }
+    }
+    
+    /// Observe the current smoothed noise type
+    /// 
+    /// Returns an event if the type differs from the last reported one and the
+    /// debounce interval has elapsed.
+    pub fn observe(&mut self, smoothed: NoiseType, now: Instant) -> Option<NoiseTypeEvent> {
+        if smoothed == self.reported_type {
+            return None;
+        }
+        
+        if let Some(last) = self.last_emit {
+            if now.saturating_duration_since(last) < self.min_interval {
+                return None;
+            }
+        }
+        
+        let event = NoiseTypeEvent {
+            previous: self.reported_type,
+            current: smoothed,
+            timestamp: SystemTime::now(),
+        };
+        self.reported_type = smoothed;
+        self.last_emit = Some(now);
+        Some(event)
+    }
+}
+
 /// Professional audio analyzer combining multiple analysis techniques
 pub struct AudioAnalyzer {
     /// Voice activity detector
     vad: VoiceActivityDetector,
     /// Spectral analyzer
     spectral_analyzer: SpectralAnalyzer,
+    /// Most recent samples, sized to the spectral analysis window
+    ///
+    /// Each processing frame slides the window forward, so consecutive FFTs
+    /// overlap by `window - frame_size` samples.
+    analysis_buffer: VecDeque<f32>,
+    /// Spectral profile of the last full window, reused while the buffer fills
+    last_profile: FrequencyProfile,
     /// Analysis history for context
     context_history: VecDeque<AudioContext>,
+    /// Imported background noise profile and the type its matching frames get
+    room_profile: Option<(FrequencyProfile, NoiseType)>,
 }
 
 impl AudioAnalyzer {
     /// Create a new audio analyzer
     pub fn new(sample_rate: u32, frame_size: usize, sensitivity: f32) -> Result<Self, Box<dyn std::error::Error>> {
-        let vad = VoiceActivityDetector::new(sample_rate, sensitivity)?;
-        let spectral_analyzer = SpectralAnalyzer::new(frame_size, sample_rate as f32);
+        Self::with_vad_history(sample_rate, frame_size, sensitivity, DEFAULT_VAD_HISTORY_LEN)
+    }
+    
+    /// Create a new audio analyzer with a custom VAD smoothing window
+    pub fn with_vad_history(
+        sample_rate: u32,
+        frame_size: usize,
+        sensitivity: f32,
+        vad_history_len: usize
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_analysis_window(sample_rate, frame_size, frame_size, sensitivity, vad_history_len)
+    }
+    
+    /// Create a new audio analyzer whose spectral window spans several frames
+    ///
+    /// Frames are still fed one at a time (the VAD runs per frame), but the FFT
+    /// runs over the last `analysis_window` samples for finer frequency
+    /// resolution. The window is clamped to at least one frame and to
+    /// `MAX_ANALYSIS_WINDOW`.
+    pub fn with_analysis_window(
+        sample_rate: u32,
+        frame_size: usize,
+        analysis_window: usize,
+        sensitivity: f32,
+        vad_history_len: usize
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let analysis_window = analysis_window.clamp(frame_size, MAX_ANALYSIS_WINDOW.max(frame_size));
+        let vad = VoiceActivityDetector::new(sample_rate, sensitivity, vad_history_len)?;
+        let spectral_analyzer = SpectralAnalyzer::new(analysis_window, sample_rate as f32);
         
         Ok(Self {
             vad,
             spectral_analyzer,
+            analysis_buffer: VecDeque::with_capacity(analysis_window),
+            last_profile: FrequencyProfile::default(),
             context_history: VecDeque::with_capacity(50),
+            room_profile: None,
         })
     }
     
+    /// Recognise frames matching an imported noise profile as the room's background
+    ///
+    /// Matching frames take the profile's own classification, so the room's
+    /// keyboard or fan noise is recognised even where it misses the generic
+    /// thresholds, and is never mistaken for music. Profiles that classify as
+    /// silence or music can't calibrate anything and are ignored.
+    pub fn set_room_profile(&mut self, profile: Option<FrequencyProfile>) {
+        self.room_profile = profile.and_then(|profile| match Self::classify_spectrum(&profile) {
+            NoiseType::Silence | NoiseType::Music => None,
+            noise_type => Some((profile, noise_type)),
+        });
+    }
+    
+    /// Number of samples covered by each spectral analysis
+    pub fn analysis_window(&self) -> usize {
+        self.spectral_analyzer.window_size()
+    }
+    
     /// Perform comprehensive audio analysis
     pub fn analyze_audio_context(&mut self, samples: &[f32]) -> AudioContext {
         // Voice activity detection
         let voice_probability = self.vad.detect(samples);
         
-        // Spectral analysis
-        let frequency_profile = self.spectral_analyzer.analyze(samples);
+        // Spectral analysis over the sliding window
+        let frequency_profile = self.analyze_window(samples);
         
         // Noise type classification
         let noise_type = self.classify_noise_type(voice_probability, &frequency_profile);
@@ -446,6 +891,24 @@ impl AudioAnalyzer {
         context
     }
     
+    /// Slide the analysis window forward by `samples` and analyze it once full
+    fn analyze_window(&mut self, samples: &[f32]) -> FrequencyProfile {
+        let window_size = self.spectral_analyzer.window_size();
+        if window_size == samples.len() {
+            return self.spectral_analyzer.analyze(samples);
+        }
+        
+        self.analysis_buffer.extend(samples.iter().copied());
+        let excess = self.analysis_buffer.len().saturating_sub(window_size);
+        self.analysis_buffer.drain(..excess);
+        
+        if self.analysis_buffer.len() == window_size {
+            let window: Vec<f32> = self.analysis_buffer.iter().copied().collect();
+            self.last_profile = self.spectral_analyzer.analyze(&window);
+        }
+        self.last_profile.clone()
+    }
+    
     /// Classify noise type based on analysis
     fn classify_noise_type(&self, voice_prob: f32, freq_profile: &FrequencyProfile) -> NoiseType {
         // Very low energy -> silence
@@ -458,6 +921,22 @@ impl AudioAnalyzer {
             return NoiseType::Speech;
         }
         
+        // Sounds like the imported room background -> whatever that background is
+        if let Some((room, noise_type)) = &self.room_profile {
+            if freq_profile.similarity(room) >= ROOM_PROFILE_MATCH {
+                return *noise_type;
+            }
+        }
+        
+        Self::classify_spectrum(freq_profile)
+    }
+    
+    /// Classify a non-speech frame from its spectrum alone
+    fn classify_spectrum(freq_profile: &FrequencyProfile) -> NoiseType {
+        if freq_profile.total_energy < 0.001 {
+            return NoiseType::Silence;
+        }
+        
         // High frequency content with sharp attacks -> keyboard
         if freq_profile.high_freq_ratio > 0.3 && freq_profile.spectral_centroid > 2000.0 {
             return NoiseType::Keyboard;
@@ -506,10 +985,67 @@ impl AudioAnalyzer {
 mod tests {
     use super::*;
     
+    #[test]
+    fn test_log_spaced_bands_locate_a_tone() {
+        // Hann-windowed full-scale 1kHz sine over one 480-sample frame
+        let window_size = 480;
+        let bins = frequency_bins(window_size, 48000.0);
+        let mut magnitudes = vec![0.0; window_size / 2];
+        magnitudes[10] = window_size as f32 / 4.0; // bin 10 = 1000 Hz
+        
+        let bands = log_spaced_bands(&magnitudes, &bins, SPECTRUM_DISPLAY_BANDS, SPECTRUM_MIN_HZ);
+        assert_eq!(bands.len(), SPECTRUM_DISPLAY_BANDS);
+        let loudest = bands.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).unwrap().0;
+        assert!(bands[loudest].abs() < 0.01, "Full-scale tone read {} dB", bands[loudest]);
+        assert_eq!(bands.iter().filter(|&&level| level > SPECTRUM_FLOOR_DB).count(), 1);
+        // Band edges are log-spaced from SPECTRUM_MIN_HZ to the top bin
+        let ratio = (bins[bins.len() - 1] / SPECTRUM_MIN_HZ).powf(1.0 / SPECTRUM_DISPLAY_BANDS as f32);
+        let low = SPECTRUM_MIN_HZ * ratio.powi(loudest as i32);
+        assert!((low..low * ratio).contains(&1000.0));
+        
+        assert!(log_spaced_bands(&[], &[], 8, SPECTRUM_MIN_HZ).iter().all(|&level| level == SPECTRUM_FLOOR_DB));
+    }
+    
     #[test]
     fn test_vad_creation() {
-        let vad = VoiceActivityDetector::new(48000, 0.5);
+        let vad = VoiceActivityDetector::new(48000, 0.5, DEFAULT_VAD_HISTORY_LEN);
         assert!(vad.is_ok());
+        assert!(VoiceActivityDetector::new(16000, 0.5, DEFAULT_VAD_HISTORY_LEN).is_ok());
+        assert!(VoiceActivityDetector::new(44100, 0.5, DEFAULT_VAD_HISTORY_LEN).is_err());
+    }
+    
+    #[test]
+    fn test_vad_history_len_smoothing() {
+        // Alternating speech/noise decisions, as produced by a flapping VAD
+        let input: Vec<f32> = (0..100).map(|i| if i % 3 == 0 { 0.9 } else { 0.1 }).collect();
+        
+        let trajectory = |history_len: usize| -> Vec<f32> {
+            let mut smoother = ProbabilitySmoother::new(history_len);
+            input.iter().map(|&p| smoother.push(p)).collect()
+        };
+        let total_variation = |values: &[f32]| -> f32 {
+            values.windows(2).map(|w| (w[1] - w[0]).abs()).sum()
+        };
+        
+        let short = trajectory(2);
+        let long = trajectory(20);
+        assert!(total_variation(&long) < total_variation(&short),
+                "Longer window should produce a smoother trajectory");
+        
+        // Window length is clamped to the supported range
+        assert_eq!(ProbabilitySmoother::new(0).history_len, MIN_VAD_HISTORY_LEN);
+        assert_eq!(ProbabilitySmoother::new(10_000).history_len, MAX_VAD_HISTORY_LEN);
+    }
+    
+    #[test]
+    fn test_vad_aggressiveness_follows_sensitivity() {
+        // The default sensitivity keeps the least aggressive mode
+        assert_eq!(vad_aggressiveness(0.1), 0);
+        assert_eq!(vad_aggressiveness(0.5), 0);
+        assert_eq!(vad_aggressiveness(0.01), 3);
+        
+        let levels: Vec<u8> = [0.5, 0.1, 0.07, 0.03, 0.01].iter().map(|&s| vad_aggressiveness(s)).collect();
+        assert!(levels.windows(2).all(|w| w[1] >= w[0]), "Lower sensitivity must never be less aggressive");
     }
     
     #[test]
@@ -528,6 +1064,24 @@ mod tests {
         assert!(analyzer.is_ok());
     }
     
+    #[test]
+    fn test_larger_analysis_window_has_finer_bins() {
+        let short = frequency_bins(480, 48000.0);
+        let long = frequency_bins(1920, 48000.0);
+        
+        assert_eq!(short[1] - short[0], 100.0);
+        assert_eq!(long[1] - long[0], 25.0);
+        assert_eq!(long.len(), 4 * short.len());
+        
+        // The analyzer still accepts 480-sample frames with a larger window
+        let mut analyzer = AudioAnalyzer::with_analysis_window(48000, 480, 1920, 0.5, DEFAULT_VAD_HISTORY_LEN).unwrap();
+        assert_eq!(analyzer.analysis_window(), 1920);
+        for _ in 0..5 {
+            analyzer.analyze_audio_context(&vec![0.0; 480]);
+        }
+        assert_eq!(analyzer.analysis_buffer.len(), 1920);
+    }
+    
     #[test]
     fn test_noise_type_classification() {
         let freq_profile = FrequencyProfile {
@@ -543,4 +1097,134 @@ mod tests {
         let noise_type = analyzer.classify_noise_type(0.1, &freq_profile);
         assert_eq!(noise_type, NoiseType::HVAC);
     }
+    
+    #[test]
+    fn test_imported_room_profile_calibrates_classification() {
+        // Keystrokes in this room: bright, but the individual frames vary
+        let room = FrequencyProfile {
+            total_energy: 0.05,
+            low_freq_ratio: 0.2,
+            mid_freq_ratio: 0.45,
+            high_freq_ratio: 0.35,
+            spectral_centroid: 2500.0,
+            spectral_rolloff: 6000.0,
+        };
+        let duller_keystroke = FrequencyProfile {
+            low_freq_ratio: 0.22,
+            high_freq_ratio: 0.33,
+            spectral_centroid: 1800.0,
+            ..room.clone()
+        };
+        
+        let mut analyzer = AudioAnalyzer::new(48000, 480, 0.5).unwrap();
+        assert_eq!(analyzer.classify_noise_type(0.1, &duller_keystroke), NoiseType::Music);
+        
+        analyzer.set_room_profile(Some(room));
+        assert_eq!(analyzer.classify_noise_type(0.1, &duller_keystroke), NoiseType::Keyboard);
+        // Speech still wins over the room match
+        assert_eq!(analyzer.classify_noise_type(0.9, &duller_keystroke), NoiseType::Speech);
+        
+        analyzer.set_room_profile(None);
+        assert_eq!(analyzer.classify_noise_type(0.1, &duller_keystroke), NoiseType::Music);
+    }
+    
+    #[test]
+    fn test_noise_type_notifier_debounces() {
+        let start = Instant::now();
+        let mut notifier = NoiseTypeNotifier::new(Duration::from_secs(2));
+        
+        let event = notifier.observe(NoiseType::HVAC, start).expect("First change is reported");
+        assert_eq!((event.previous, event.current), (NoiseType::Unknown, NoiseType::HVAC));
+        assert!(notifier.observe(NoiseType::HVAC, start + Duration::from_secs(5)).is_none());
+        
+        // Music comes and goes inside the debounce window: nothing is reported
+        let at = |ms| start + Duration::from_millis(ms);
+        assert!(notifier.observe(NoiseType::Music, at(500)).is_none());
+        assert!(notifier.observe(NoiseType::HVAC, at(1000)).is_none());
+        assert!(notifier.observe(NoiseType::HVAC, at(2500)).is_none());
+        
+        // A held-back change is reported once the window expires
+        assert!(notifier.observe(NoiseType::Music, at(1500)).is_none());
+        let event = notifier.observe(NoiseType::Music, at(2100)).expect("Change reported after window");
+        assert_eq!((event.previous, event.current), (NoiseType::HVAC, NoiseType::Music));
+    }
+    
+    #[test]
+    fn test_noise_type_gate_does_not_oscillate() {
+        let context = |noise_type| AudioContext {
+            voice_probability: 0.1,
+            noise_type,
+            frequency_profile: FrequencyProfile::default(),
+            recommended_gain: 0.2,
+        };
+        
+        let mut gate = NoiseTypeGate::new(4);
+        let mut history = VecDeque::new();
+        
+        // Establish a stable HVAC baseline
+        for _ in 0..4 {
+            history.push_back(context(NoiseType::HVAC));
+            gate.update(&history);
+        }
+        assert_eq!(gate.stable_type(), NoiseType::HVAC);
+        
+        // Classifier flaps between Keyboard and HVAC every frame or two
+        let noisy = [
+            NoiseType::Keyboard, NoiseType::HVAC, NoiseType::Keyboard, NoiseType::Keyboard,
+            NoiseType::HVAC, NoiseType::Keyboard, NoiseType::HVAC, NoiseType::Keyboard,
+            NoiseType::Keyboard, NoiseType::Keyboard, NoiseType::HVAC, NoiseType::Keyboard,
+        ];
+        let mut outputs = Vec::new();
+        for noise_type in noisy {
+            history.push_back(context(noise_type));
+            outputs.push(gate.update(&history));
+        }
+        assert!(outputs.iter().all(|&t| t == NoiseType::HVAC),
+                "Gate should hold the stable type while the classifier flaps");
+        
+        // A sustained run of Keyboard switches exactly once
+        for _ in 0..4 {
+            history.push_back(context(NoiseType::Keyboard));
+            outputs.push(gate.update(&history));
+        }
+        let switches = outputs.windows(2).filter(|w| w[0] != w[1]).count();
+        assert_eq!(switches, 1);
+        assert_eq!(gate.stable_type(), NoiseType::Keyboard);
+    }
+    
+    #[test]
+    fn test_noise_profile_export_import() {
+        let hum = FrequencyProfile {
+            total_energy: 0.01,
+            low_freq_ratio: 0.8,
+            mid_freq_ratio: 0.15,
+            high_freq_ratio: 0.05,
+            spectral_centroid: 300.0,
+            spectral_rolloff: 900.0,
+        };
+        let hiss = FrequencyProfile { low_freq_ratio: 0.1, mid_freq_ratio: 0.3, high_freq_ratio: 0.6, ..hum.clone() };
+        
+        let mut accumulator = NoiseProfileAccumulator::default();
+        assert!(accumulator.snapshot().is_none());
+        for _ in 0..10 {
+            accumulator.add(&hum);
+        }
+        let exported = accumulator.snapshot().unwrap();
+        assert_eq!(exported.frames, 10);
+        assert!(exported.profile.similarity(&hum) > 0.99);
+        assert!(exported.profile.similarity(&hiss) < 0.4);
+        
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("noise_profile.json");
+        exported.save_to(&path).unwrap();
+        let imported = NoiseProfile::load_from(&path).unwrap();
+        assert_eq!(imported, exported);
+        
+        // Seeding carries the imported average forward
+        let mut seeded = NoiseProfileAccumulator::default();
+        seeded.seed(&imported);
+        seeded.add(&hum);
+        assert_eq!(seeded.snapshot().unwrap().frames, 11);
+        assert!(seeded.snapshot().unwrap().profile.similarity(&hum) > 0.99);
+    }
 }
\ No newline at end of file
@@ -10,6 +10,9 @@
 //! - **Automatic format conversion**: Converts stereo to mono when needed
 //! - **Low-latency capture**: Optimized for real-time processing
 //! - **Robust error handling**: Graceful handling of device disconnections
+//! - **Permission detection**: Recognizes OS-level microphone access denials
+//! - **Disconnect detection**: Flags a stream whose device was unplugged so the GUI can stop
+//! - **Test signals**: Synthesized pseudo-devices for exercising the pipeline without a mic
 //! 
 //! ## Audio Pipeline
 //! 
@@ -21,11 +24,182 @@
 use cpal::traits::{DeviceTrait, StreamTrait};
 use cpal::{StreamConfig, BufferSize};
 use crossbeam_channel::Sender;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use crate::logger::log;
-use crate::audio::devices::get_device_by_id;
-use crate::audio::resampling::{SimpleResampler, get_configuration_advice};
+use crate::audio::devices::{get_device_by_id, config_for_sample_rate};
+use crate::audio::resampling::{SimpleResampler, get_configuration_advice, RNNOISE_SAMPLE_RATE};
+use crate::audio::test_signal::{TestSignalGenerator, TestSignalKind, TEST_SIGNAL_SAMPLE_RATE};
+use crate::audio::meter::LevelTap;
+use crate::audio::latency_probe::{self, LatencyProbe};
+use crate::audio::filters::DcBlocker;
+use crate::audio::sidechain::SidechainLevel;
+use crate::audio::stereo;
+use crate::audio::latency::AudioFrame;
+
+/// Sample magnitude at or above which an input sample is considered clipped
+pub const CLIP_THRESHOLD: f32 = 0.999;
+
+/// Peak level above which input counts as a real signal rather than a muted mic (-50 dBFS)
+pub const SIGNAL_THRESHOLD: f32 = 0.003;
+
+/// Number of recent mono input samples kept for the dev-mode oscilloscope
+pub const SCOPE_SAMPLES: usize = 960;
+
+/// Lowercase fragments of CPAL/OS error messages that mean microphone access was denied
+/// 
+/// - Windows (WASAPI): `E_ACCESSDENIED` / `0x80070005`
+/// - macOS (CoreAudio): `kAudioDevicePermissionsError` (`'!hog'`, 560492391)
+/// - Linux and generic backends: "permission denied" / "not permitted"
+const PERMISSION_DENIED_PATTERNS: &[&str] = &[
+    "permission denied",
+    "access denied",
+    "access is denied",
+    "not permitted",
+    "e_accessdenied",
+    "0x80070005",
+    "-2147024891",
+    "!hog",
+    "560492391",
+];
+
+/// Check whether an audio backend error message indicates denied microphone access
+/// 
+/// CPAL reports these as generic backend-specific errors, so the message text is
+/// the only reliable signal across platforms.
+pub fn is_permission_denied_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    PERMISSION_DENIED_PATTERNS.iter().any(|pattern| message.contains(pattern))
+}
+
+/// Lowercase fragments of CPAL/OS error messages that mean the capture device went away
+/// 
+/// - CPAL `DeviceNotAvailable`: "the requested device is no longer available"
+/// - Windows (WASAPI): `AUDCLNT_E_DEVICE_INVALIDATED` / `0x88890004`
+/// - Linux (ALSA): `ENODEV` / "no such device"
+/// - macOS and generic backends: "disconnected"
+const DEVICE_LOST_PATTERNS: &[&str] = &[
+    "no longer available",
+    "device_invalidated",
+    "0x88890004",
+    "no such device",
+    "enodev",
+    "disconnected",
+];
+
+/// Check whether an audio backend error message means the device was disconnected
+pub fn is_device_lost_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    DEVICE_LOST_PATTERNS.iter().any(|pattern| message.contains(pattern))
+}
+
+/// Input statistics shared between the capture callback and the GUI
+/// 
+/// All counters are atomics so the real-time callback never blocks.
+#[derive(Debug, Default)]
+pub struct InputStats {
+    /// Total number of clipped input samples since the stream started
+    pub clipped_samples: AtomicU64,
+    /// Number of capture windows (callback buffers) that contained clipping
+    pub clipped_windows: AtomicU64,
+    /// Set when the OS denied access to the microphone
+    pub permission_denied: AtomicBool,
+    /// Set when the input stream died (device unplugged or failed to start)
+    pub stream_failed: AtomicBool,
+    /// Latched once any capture window peaks above `SIGNAL_THRESHOLD`
+    pub signal_detected: AtomicBool,
+    /// Most recent mono input at the device rate, for the oscilloscope
+    scope: Mutex<VecDeque<f32>>,
+    /// Input level meter (before the DC filter and resampling)
+    pub level: LevelTap,
+    /// Native sample rate of the open input stream (0 until it opens)
+    pub sample_rate: AtomicU32,
+}
+
+impl InputStats {
+    /// Record clipping for one capture window
+    fn record_window(&self, data: &[f32]) {
+        if !self.signal_detected.load(Ordering::Relaxed) && data.iter().any(|&s| s.abs() >= SIGNAL_THRESHOLD) {
+            self.signal_detected.store(true, Ordering::Relaxed);
+        }
+        
+        let clipped = data.iter().filter(|&&s| s.abs() >= CLIP_THRESHOLD).count() as u64;
+        if clipped > 0 {
+            self.clipped_samples.fetch_add(clipped, Ordering::Relaxed);
+            self.clipped_windows.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    
+    /// Keep the latest mono samples for the oscilloscope
+    /// 
+    /// Uses `try_lock` so the callback skips an update rather than wait on the GUI.
+    fn record_scope(&self, mono: &[f32]) {
+        if let Ok(mut scope) = self.scope.try_lock() {
+            let new = &mono[mono.len().saturating_sub(SCOPE_SAMPLES)..];
+            let excess = (scope.len() + new.len()).saturating_sub(SCOPE_SAMPLES);
+            scope.drain(..excess);
+            scope.extend(new.iter().copied());
+        }
+    }
+    
+    /// Copy of the most recent mono input samples, oldest first
+    pub fn scope_snapshot(&self) -> Vec<f32> {
+        self.scope.lock().map(|scope| scope.iter().copied().collect()).unwrap_or_default()
+    }
+    
+    /// Zero the clip counters (the signal and permission flags are kept)
+    pub fn reset_counters(&self) {
+        self.clipped_samples.store(0, Ordering::Relaxed);
+        self.clipped_windows.store(0, Ordering::Relaxed);
+    }
+    
+    /// Total clipped samples since the stream started
+    pub fn clip_count(&self) -> u64 {
+        self.clipped_samples.load(Ordering::Relaxed)
+    }
+    
+    /// Whether the input has carried a meaningful signal since the stream started
+    pub fn signal_detected(&self) -> bool {
+        self.signal_detected.load(Ordering::Relaxed)
+    }
+    
+    /// Whether the OS denied access to the microphone
+    pub fn permission_denied(&self) -> bool {
+        self.permission_denied.load(Ordering::Relaxed)
+    }
+    
+    /// Native sample rate of the input stream, once it has opened
+    pub fn sample_rate(&self) -> Option<u32> {
+        match self.sample_rate.load(Ordering::Relaxed) {
+            0 => None,
+            rate => Some(rate),
+        }
+    }
+    
+    /// Whether the input stream died and no more audio will arrive
+    pub fn stream_failed(&self) -> bool {
+        self.stream_failed.load(Ordering::Relaxed)
+    }
+    
+    /// Latch the failure flag once the input stream has stopped for good
+    pub fn mark_stream_failed(&self) {
+        self.stream_failed.store(true, Ordering::Relaxed);
+    }
+    
+    /// Inspect a stream error and latch the permission or disconnect flag
+    pub(crate) fn record_error(&self, error: &dyn std::fmt::Display) {
+        let message = error.to_string();
+        if is_permission_denied_error(&message) {
+            self.permission_denied.store(true, Ordering::Relaxed);
+            log::error!("🔒 Microphone access was denied by the operating system");
+        }
+        if is_device_lost_error(&message) {
+            self.mark_stream_failed();
+            log::error!("🔌 Input device was disconnected");
+        }
+    }
+}
 
 /// Start audio input capture from the specified device
 /// 
@@ -37,6 +211,11 @@ use crate::audio::resampling::{SimpleResampler, get_configuration_advice};
 /// - `sender`: Channel for sending captured audio to the processor
 /// - `running`: Atomic flag for graceful shutdown coordination
 /// - `device_id`: Identifier of the input device to use
+/// - `stats`: Shared input statistics (clip counts, permission errors) updated from the callback
+/// - `probe`: Round-trip latency probe; the callback searches for its impulse while one is in flight
+/// - `target_sample_rate`: Pipeline sample rate; non-48kHz targets open the device at that rate when possible
+/// - `dc_filter`: Remove any DC offset from the mono signal before it is resampled and sent on
+/// - `stereo`: Send interleaved left/right audio instead of mono (a mono device feeds both sides)
 /// 
 /// ## Audio Format Handling
 /// 
@@ -44,31 +223,46 @@ use crate::audio::resampling::{SimpleResampler, get_configuration_advice};
 /// optimal audio quality and compatibility. Key considerations:
 /// 
 /// - **Sample Rate**: Uses device's default rate (typically 44.1kHz or 48kHz)
-/// - **Channels**: Accepts mono or stereo, converts stereo to mono for processing
+/// - **Channels**: Accepts any channel count and keeps only `input_channel`
+///   (plus the next channel when stereo processing is enabled)
 /// - **Buffer Size**: Lets the device choose optimal buffer size for latency/stability
 /// 
-/// ## Stereo to Mono Conversion
+/// ## Multichannel to Mono Conversion
 /// 
-/// When the input device provides stereo audio, we extract only the left channel.
-/// This approach is chosen because:
+/// When the input device provides several channels, we extract only one of
+/// them: the left channel by default, or the channel chosen in the settings
+/// for interfaces (e.g. 8-channel aggregate devices) whose microphone isn't on
+/// channel 1. This approach is chosen because:
 /// 1. Most microphones provide identical data on both channels
 /// 2. The AI noise cancellation model expects mono input
-/// 3. Left channel extraction is computationally efficient
+/// 3. Single channel extraction is computationally efficient
 /// 
 /// ## Error Recovery
 /// 
 /// The stream includes error callbacks that log issues without crashing the application.
+/// A disconnected device latches `InputStats::stream_failed` for the GUI to act on.
 /// Common scenarios handled:
 /// - Device disconnection during capture
 /// - Audio driver issues or conflicts
 /// - Buffer underruns or overruns
 pub fn start_input_stream(
-    sender: Sender<Vec<f32>>,
+    sender: Sender<AudioFrame>,
     running: Arc<AtomicBool>,
     device_id: &str,
+    stats: Arc<InputStats>,
+    probe: Arc<LatencyProbe>,
+    target_sample_rate: u32,
+    dc_filter: bool,
+    stereo: bool,
+    input_channel: usize,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     log::info!("Starting input stream with device ID: {}", device_id);
     
+    // Test signal pseudo-devices are generated here instead of opened through CPAL
+    if let Some(kind) = TestSignalKind::from_device_id(device_id) {
+        return run_test_signal_input(kind, sender, running, stats, stereo);
+    }
+    
     // Resolve the device ID to an actual audio device
     // This handles both default device selection and specific device targeting
     let device = get_device_by_id(device_id, true)
@@ -79,8 +273,16 @@ pub fn start_input_stream(
 
     // Query the device's optimal input configuration
     // This ensures we work with the device's preferred settings
-    let supported_config = device.default_input_config().map_err(|e| {
+    // Narrowband targets capture at the target rate when the device supports it
+    let target_config = (target_sample_rate != RNNOISE_SAMPLE_RATE)
+        .then(|| config_for_sample_rate(&device, true, target_sample_rate))
+        .flatten();
+    if target_sample_rate != RNNOISE_SAMPLE_RATE && target_config.is_none() {
+        log::warn!("Input device doesn't support {}Hz - capturing at its default rate", target_sample_rate);
+    }
+    let supported_config = target_config.map(Ok).unwrap_or_else(|| device.default_input_config()).map_err(|e| {
         log::error!("Failed to get input device configuration: {}", e);
+        stats.record_error(&e);
         e
     })?;
 
@@ -117,12 +319,10 @@ pub fn start_input_stream(
             // Still allow it to work but with warnings
             log::info!("Detected {} on macOS as input - this is likely misconfigured", device_type);
             
-            // Warn if sample rate is not optimal for noise cancellation
+            // Other rates are resampled, so this is only a hint
             if config.sample_rate.0 != 48000 {
-                log::warn!("{} sample rate is {} Hz, expected 48000 Hz for optimal noise cancellation", 
+                log::info!("{} sample rate is {} Hz - resampling to 48kHz for noise cancellation", 
                     device_type, config.sample_rate.0);
-                log::warn!("Consider setting {} to 48kHz in Audio MIDI Setup for best performance", device_type);
-                log::warn!("Current configuration may result in degraded noise cancellation quality");
             } else {
                 log::info!("{} configured optimally at 48kHz for AI processing", device_type);
             }
@@ -140,6 +340,7 @@ pub fn start_input_stream(
 
     let running_clone = running.clone();
     let sample_rate = config.sample_rate.0;
+    stats.sample_rate.store(sample_rate, Ordering::Relaxed);
     
     // Log resampling information
     let needs_resampling = sample_rate != 48000;
@@ -149,6 +350,37 @@ pub fn start_input_stream(
         "Not needed (48kHz)".to_string()
     });
     
+    // The data callback takes ownership of `stats`; keep handles for error reporting
+    let error_stats = stats.clone();
+    let build_stats = stats.clone();
+    
+    // Carry the end of the previous buffer so an impulse split across callbacks is still found
+    let impulse = latency_probe::impulse_template();
+    let mut probe_window: Vec<f32> = Vec::new();
+    
+    // Any rate other than 48kHz (44.1kHz devices, narrowband targets) is resampled for RNNoise
+    let mut upsampler = SimpleResampler::new(sample_rate, RNNOISE_SAMPLE_RATE);
+    
+    // Filter state lives across callbacks so block boundaries don't click
+    let mut dc_blocker = dc_filter.then(|| DcBlocker::new(sample_rate));
+    log::info!("Input DC-offset filter: {}", if dc_filter { "on" } else { "off" });
+    
+    // Stereo processing carries the right channel alongside, with its own filter state
+    let mut right_dc_blocker = (stereo && dc_filter).then(|| DcBlocker::new(sample_rate));
+    let mut right_upsampler = SimpleResampler::new(sample_rate, RNNOISE_SAMPLE_RATE);
+    
+    // Interfaces with many channels don't always carry the microphone on channel 1
+    let channels = config.channels as usize;
+    let channel = input_channel.min(channels.saturating_sub(1));
+    if channel != input_channel {
+        log::warn!("Input channel {} doesn't exist on a {}-channel device - using channel {}", input_channel + 1, channels, channel + 1);
+    }
+    log::info!("Capturing input channel {} of {}", channel + 1, channels);
+    let right_channel = (channel + 1 < channels).then_some(channel + 1);
+    if stereo && right_channel.is_none() {
+        log::info!("Stereo processing without a channel after {} - it feeds both sides", channel + 1);
+    }
+    
     // Create the input stream with real-time audio callback
     // The callback runs on a high-priority audio thread and must be efficient
     let stream = device.build_input_stream(
@@ -157,39 +389,56 @@ pub fn start_input_stream(
             // Only process audio while the system is running
             // This prevents unnecessary work during shutdown
             if running_clone.load(Ordering::Relaxed) {
-                // Convert stereo input to mono for noise cancellation processing
+                // Stamped on arrival; the output measures end-to-end latency from here
+                let captured_at = std::time::Instant::now();
+                
+                // Track clipping on the raw device samples (any channel)
+                stats.record_window(data);
+                
+                // Convert multichannel input to mono for noise cancellation processing
                 // Many microphones report as stereo but provide identical left/right channels
-                let mono_data: Vec<f32> = if config.channels == 2 {
-                    // Extract left channel only (every other sample starting from index 0)
-                    // Stereo audio is interleaved: [L, R, L, R, ...]
-                    data.iter().step_by(2).copied().collect()
-                } else {
-                    // Already mono, use as-is
-                    data.to_vec()
-                };
+                let mut mono_data = extract_channel(data, channels, channel);
+                let mut right_data: Option<Vec<f32>> = stereo.then(|| match right_channel {
+                    Some(right) => extract_channel(data, channels, right),
+                    None => mono_data.clone(),
+                });
+                // The oscilloscope shows the raw signal, DC offset included
+                stats.record_scope(&mono_data);
+                stats.level.record(&mono_data, sample_rate);
+                if let Some(blocker) = dc_blocker.as_mut() {
+                    blocker.process(&mut mono_data);
+                }
+                if let (Some(blocker), Some(right)) = (right_dc_blocker.as_mut(), right_data.as_mut()) {
+                    blocker.process(right);
+                }
                 
-                // Apply basic resampling if needed (e.g., 44.1kHz virtual audio devices -> 48kHz for AI processing)
-                let processed_data = if sample_rate != 48000 && sample_rate == 44100 {
-                    // Handle the common 44.1kHz -> 48kHz case with simple interpolation
-                    let target_length = (mono_data.len() as f64 * 48000.0 / 44100.0) as usize;
-                    let mut resampled = Vec::with_capacity(target_length);
-                    
-                    for i in 0..target_length {
-                        let src_index = (i as f64 * 44100.0 / 48000.0) as usize;
-                        if src_index < mono_data.len() {
-                            resampled.push(mono_data[src_index]);
-                        } else {
-                            resampled.push(0.0);
+                // Round-trip latency measurement: look for the output impulse at the device rate
+                if probe.awaiting_detection() {
+                    probe_window.extend_from_slice(&mono_data);
+                    match latency_probe::detect_impulse(&probe_window, &impulse, latency_probe::DETECTION_THRESHOLD) {
+                        Some(offset) => {
+                            probe.record_detection(std::time::Instant::now(), offset, probe_window.len(), sample_rate);
+                            probe_window.clear();
+                        }
+                        None => {
+                            let keep = latency_probe::IMPULSE_LEN.min(probe_window.len());
+                            probe_window.drain(..probe_window.len() - keep);
                         }
                     }
-                    resampled
-                } else {
-                    mono_data
+                } else if !probe_window.is_empty() {
+                    probe_window.clear();
+                }
+                
+                // Resample to 48kHz if needed (e.g., 44.1kHz virtual audio devices)
+                let processed_data = to_processing_rate(mono_data, sample_rate, &mut upsampler);
+                let processed_data = match right_data {
+                    Some(right) => stereo::interleave(&processed_data, &to_processing_rate(right, sample_rate, &mut right_upsampler)),
+                    None => processed_data,
                 };
                 
                 // Send to processor using try_send to avoid blocking the audio thread
                 // If the processing pipeline is behind, we drop frames to prevent audio glitches
-                if let Err(_) = sender.try_send(processed_data) {
+                if let Err(_) = sender.try_send(AudioFrame::new(processed_data, captured_at)) {
                     // Channel is full - this is normal if processing can't keep up
                     // We don't log this as it would spam the logs in normal operation
                 }
@@ -199,16 +448,19 @@ pub fn start_input_stream(
             // Log audio stream errors without panicking
             // These can occur due to device disconnection, driver issues, etc.
             log::error!("Input stream error: {}", err);
+            error_stats.record_error(&err);
         },
         None, // No timeout for the stream
     ).map_err(|e| {
         log::error!("Failed to build input stream: {}", e);
+        build_stats.record_error(&e);
         e
     })?;
 
     // Start the audio capture stream
     stream.play().map_err(|e| {
         log::error!("Failed to start input stream: {}", e);
+        build_stats.record_error(&e);
         e
     })?;
     
@@ -222,4 +474,166 @@ pub fn start_input_stream(
 
     log::info!("Input stream stopping");
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Samples of one channel from an interleaved buffer
+/// 
+/// Audio is interleaved frame by frame (`[c0, c1, ..., c0, c1, ...]`); mono
+/// input is returned as-is. An out-of-range `channel` uses the last channel.
+pub fn extract_channel(data: &[f32], channels: usize, channel: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return data.to_vec();
+    }
+    let channel = channel.min(channels - 1);
+    data.chunks_exact(channels).map(|frame| frame[channel]).collect()
+}
+
+/// Bring one channel of captured audio to RNNoise's 48kHz
+/// 
+/// The resampler keeps its position across callbacks, so the output length
+/// averages exactly 48000 samples per second whatever the callback size.
+pub(crate) fn to_processing_rate(data: Vec<f32>, sample_rate: u32, upsampler: &mut SimpleResampler) -> Vec<f32> {
+    if sample_rate != RNNOISE_SAMPLE_RATE {
+        let mut resampled = Vec::with_capacity(data.len() * RNNOISE_SAMPLE_RATE as usize / sample_rate.max(1) as usize + 1);
+        upsampler.process(&data, &mut resampled);
+        resampled
+    } else {
+        data
+    }
+}
+
+/// Capture a sidechain device and publish its level for ducking
+/// 
+/// Only the block peak is needed, so the device runs at its default
+/// configuration without resampling and the peak is taken across all channels.
+/// Blocks until `running` is cleared, like `start_input_stream`.
+pub fn start_sidechain_stream(
+    device_id: &str,
+    level: Arc<SidechainLevel>,
+    running: Arc<AtomicBool>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let device = get_device_by_id(device_id, true)
+        .ok_or_else(|| {
+            log::error!("Sidechain device '{}' not found", device_id);
+            "Sidechain device not found"
+        })?;
+    let config: StreamConfig = device.default_input_config()?.into();
+    log::info!("🦆 Sidechain device: {} ({:?})", device.name().unwrap_or_else(|_| "Unknown".to_string()), config);
+
+    let running_clone = running.clone();
+    let stream = device.build_input_stream(
+        &config,
+        move |data: &[f32], _| {
+            if running_clone.load(Ordering::Relaxed) {
+                level.record_block(data);
+            }
+        },
+        move |err| {
+            log::error!("Sidechain stream error: {}", err);
+        },
+        None,
+    )?;
+    stream.play()?;
+
+    while running.load(Ordering::Relaxed) {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    log::info!("Sidechain stream stopping");
+    Ok(())
+}
+
+/// Feed a synthesized test signal into the pipeline in place of a capture device
+/// 
+/// Produces one 10ms frame (480 samples at 48kHz) per tick, paced against a fixed
+/// schedule so the processing thread sees the same cadence as a real device.
+fn run_test_signal_input(
+    kind: TestSignalKind,
+    sender: Sender<AudioFrame>,
+    running: Arc<AtomicBool>,
+    stats: Arc<InputStats>,
+    stereo: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut generator = TestSignalGenerator::new(kind.clone()).map_err(|e| {
+        log::error!("Failed to start test signal {:?}: {}", kind, e);
+        e
+    })?;
+    
+    log::info!("🧪 Using synthesized input: {}", kind.name());
+    
+    let frame_duration = std::time::Duration::from_millis(10);
+    let mut next_frame = std::time::Instant::now();
+    
+    while running.load(Ordering::Relaxed) {
+        let mut frame = vec![0.0f32; 480];
+        generator.fill(&mut frame);
+        stats.record_window(&frame);
+        stats.record_scope(&frame);
+        stats.level.record(&frame, TEST_SIGNAL_SAMPLE_RATE);
+        
+        // The same signal on both sides for stereo processing
+        if stereo {
+            frame = stereo::interleave(&frame, &frame);
+        }
+        
+        // Drop the frame if processing is behind, like the device callback does
+        let _ = sender.try_send(AudioFrame::new(frame, std::time::Instant::now()));
+        
+        next_frame += frame_duration;
+        let now = std::time::Instant::now();
+        if next_frame > now {
+            std::thread::sleep(next_frame - now);
+        } else {
+            // Fell behind (e.g. system suspend) - resynchronize instead of bursting
+            next_frame = now;
+        }
+    }
+    
+    log::info!("Test signal input stopping");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::output::DeviceRateConverter;
+    use crate::audio::process::process_audio;
+    use nnnoiseless::DenoiseState;
+
+    #[test]
+    fn test_44100hz_capture_matches_48k_frame_count() {
+        // One second of a 440Hz sine from a 44.1kHz device, delivered in 512-sample callbacks
+        let input: Vec<f32> = (0..44100)
+            .map(|i| 0.3 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 44100.0).sin())
+            .collect();
+
+        let mut upsampler = SimpleResampler::new(44100, RNNOISE_SAMPLE_RATE);
+        let mut converter = DeviceRateConverter::new(44100, false);
+        let mut denoiser = DenoiseState::new();
+        let mut pending = Vec::new();
+        let mut played = VecDeque::new();
+        let mut frames = 0;
+        for callback in input.chunks(512) {
+            pending.extend(to_processing_rate(callback.to_vec(), 44100, &mut upsampler));
+            while pending.len() >= 480 {
+                let frame: Vec<f32> = pending.drain(..480).collect();
+                let mut output = vec![0.0; 480];
+                process_audio(&frame, &mut output, &mut denoiser, None);
+                frames += 1;
+                converter.queue(output, &mut played);
+            }
+        }
+
+        // The 48kHz pipeline expects 48000 samples (100 frames) per second; the resampler
+        // holds back its last input sample until the next callback
+        let produced = frames * 480 + pending.len();
+        assert!((47998..=48000).contains(&produced), "Resampled to {} samples", produced);
+        assert!((99..=100).contains(&frames), "Got {} frames", frames);
+        // Played back at the device rate, the audio lasts as long as it was captured for
+        assert!((44100 - played.len() as i64).abs() <= 441 + 1, "Played {} samples", played.len());
+        assert!(played.iter().all(|s| s.is_finite()));
+
+        // At 48kHz capture passes through untouched
+        let mut passthrough = SimpleResampler::new(RNNOISE_SAMPLE_RATE, RNNOISE_SAMPLE_RATE);
+        assert_eq!(to_processing_rate(vec![0.1, 0.2], RNNOISE_SAMPLE_RATE, &mut passthrough), vec![0.1, 0.2]);
+    }
+}
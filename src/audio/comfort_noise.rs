@@ -0,0 +1,140 @@
+//! # Comfort Noise Module
+//!
+//! With heavy gating, the output between words drops to near silence. Listeners
+//! hear that as a dropped call, so comfort noise fills the closed gate with a
+//! faint, pink-ish hiss instead, like the background of a phone line.
+//!
+//! The noise follows the gate: its level scales with how far the smoothed gain
+//! has closed, and changes are ramped across each frame, so it fades in as the
+//! gate closes and out as speech opens it again without any discontinuity.
+//!
+//! The generator is a xorshift PRNG through a three-pole pink filter, cheap
+//! enough to run on every frame. Comfort noise runs in the mono pipeline only.
+
+/// Level used when comfort noise is first turned on (dBFS RMS)
+pub const DEFAULT_COMFORT_NOISE_DB: f32 = -65.0;
+
+/// Quietest selectable comfort noise level (dBFS RMS)
+pub const MIN_COMFORT_NOISE_DB: f32 = -80.0;
+
+/// Loudest selectable comfort noise level (dBFS RMS)
+pub const MAX_COMFORT_NOISE_DB: f32 = -40.0;
+
+/// Scales the pink filter output to unit RMS
+const PINK_NORMALIZATION: f32 = 0.58;
+
+/// Linear RMS amplitude for a comfort noise level in dBFS, clamped to the selectable range
+pub fn level_from_db(level_db: f32) -> f32 {
+    10f32.powf(level_db.clamp(MIN_COMFORT_NOISE_DB, MAX_COMFORT_NOISE_DB) / 20.0)
+}
+
+/// How far the gate is closed: 0.0 at `open_gain`, 1.0 at `closed_gain` or below
+///
+/// Returns 0.0 when the gate has no range to close over (e.g. suppression off).
+pub fn gate_closure(gain: f32, open_gain: f32, closed_gain: f32) -> f32 {
+    let range = open_gain - closed_gain;
+    if range <= f32::EPSILON {
+        return 0.0;
+    }
+    ((open_gain - gain) / range).clamp(0.0, 1.0)
+}
+
+/// Pink-ish noise source that fades with the gate
+#[derive(Debug, Clone)]
+pub struct ComfortNoise {
+    seed: u32,
+    /// Paul Kellet economy pink filter state
+    pink_state: [f32; 3],
+    /// Amplitude reached at the end of the previous frame
+    amplitude: f32,
+}
+
+impl Default for ComfortNoise {
+    fn default() -> Self {
+        Self { seed: 0x2545_F491, pink_state: [0.0; 3], amplitude: 0.0 }
+    }
+}
+
+impl ComfortNoise {
+    /// Add comfort noise at `level` (linear RMS) scaled by `closure` (see `gate_closure`)
+    ///
+    /// The amplitude ramps linearly from the previous frame's across this one.
+    /// A `level` of 0.0 turns comfort noise off, fading out what is still playing.
+    pub fn mix_into(&mut self, frame: &mut [f32], level: f32, closure: f32) {
+        let target = level.max(0.0) * closure.clamp(0.0, 1.0);
+        if target == 0.0 && self.amplitude == 0.0 {
+            return;
+        }
+
+        let start = self.amplitude;
+        let step = (target - start) / frame.len().max(1) as f32;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            *sample += self.pink() * (start + step * (i + 1) as f32);
+        }
+        self.amplitude = target;
+    }
+
+    /// Amplitude reached at the end of the last frame (linear RMS)
+    pub fn amplitude(&self) -> f32 {
+        self.amplitude
+    }
+
+    /// Next pink noise sample with roughly unit RMS
+    fn pink(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        let white = self.seed as f32 / u32::MAX as f32 * 2.0 - 1.0;
+
+        let b = &mut self.pink_state;
+        b[0] = 0.99765 * b[0] + white * 0.0990460;
+        b[1] = 0.96300 * b[1] + white * 0.2965164;
+        b[2] = 0.57000 * b[2] + white * 1.0526913;
+        (b[0] + b[1] + b[2] + white * 0.1848) * PINK_NORMALIZATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rms(frame: &[f32]) -> f32 {
+        (frame.iter().map(|&s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_comfort_noise_follows_the_gate() {
+        let level = level_from_db(DEFAULT_COMFORT_NOISE_DB);
+        let mut noise = ComfortNoise::default();
+
+        // Open gate: nothing is added
+        let mut frame = vec![0.0; 480];
+        noise.mix_into(&mut frame, level, gate_closure(1.0, 1.0, 0.1));
+        assert!(frame.iter().all(|&s| s == 0.0));
+
+        // Closed gate: a steady hiss at about the configured level
+        let mut closed = Vec::new();
+        for _ in 0..100 {
+            let mut frame = vec![0.0; 480];
+            noise.mix_into(&mut frame, level, gate_closure(0.1, 1.0, 0.1));
+            closed.extend(frame);
+        }
+        let measured_db = 20.0 * rms(&closed[4800..]).log10();
+        assert!((measured_db - DEFAULT_COMFORT_NOISE_DB).abs() < 3.0, "measured {} dBFS", measured_db);
+
+        // Reopening fades out within the frame instead of cutting off
+        let mut frame = vec![0.0; 480];
+        noise.mix_into(&mut frame, level, 0.0);
+        assert!(rms(&frame[..48]) > rms(&frame[432..]));
+        assert_eq!(noise.amplitude(), 0.0);
+    }
+
+    #[test]
+    fn test_gate_closure_range() {
+        assert_eq!(gate_closure(1.0, 1.0, 0.1), 0.0);
+        assert_eq!(gate_closure(0.1, 1.0, 0.1), 1.0);
+        assert!((gate_closure(0.55, 1.0, 0.1) - 0.5).abs() < 1e-6);
+        // No suppression means no gate to fill
+        assert_eq!(gate_closure(1.0, 1.0, 1.0), 0.0);
+    }
+}
@@ -0,0 +1,98 @@
+//! # Frame Deadline Module
+//!
+//! Every 480-sample frame carries 10ms of audio, so the processing thread has
+//! at most 10ms of work per frame before it starts falling behind. On marginal
+//! hardware RNNoise occasionally needs longer, and each overrun grows the queue
+//! between capture and output until frames are dropped.
+//!
+//! With a deadline configured, the processing thread predicts the cost of the
+//! next frame from recent timings and passes the frame through unprocessed when
+//! the prediction exceeds the budget. A frame of unprocessed audio is far less
+//! noticeable than a dropout. Off by default.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Largest configurable deadline (one full frame, in microseconds)
+pub const MAX_FRAME_DEADLINE_US: u32 = 10_000;
+
+/// Number of recent denoiser timings averaged for the prediction
+const TIMING_WINDOW: usize = 16;
+
+/// Consecutive skips after which a frame is processed anyway to refresh the timings
+const MAX_CONSECUTIVE_SKIPS: u32 = 4;
+
+/// Decides per frame whether the denoiser fits in the processing budget
+#[derive(Debug)]
+pub struct FrameDeadline {
+    budget: Duration,
+    recent: VecDeque<Duration>,
+    consecutive_skips: u32,
+}
+
+impl FrameDeadline {
+    /// Create a deadline of `budget_us` microseconds, or `None` when disabled (0)
+    pub fn new(budget_us: u32) -> Option<Self> {
+        (budget_us > 0).then(|| Self {
+            budget: Duration::from_micros(budget_us.min(MAX_FRAME_DEADLINE_US) as u64),
+            recent: VecDeque::with_capacity(TIMING_WINDOW),
+            consecutive_skips: 0,
+        })
+    }
+
+    /// Expected denoiser time for the next frame (average of recent timings)
+    pub fn predicted(&self) -> Duration {
+        if self.recent.is_empty() {
+            return Duration::ZERO;
+        }
+        self.recent.iter().sum::<Duration>() / self.recent.len() as u32
+    }
+
+    /// Whether the next frame should bypass the denoiser
+    ///
+    /// A frame is still processed after a few consecutive skips so the timings
+    /// can recover once the machine is less loaded.
+    pub fn should_skip(&mut self) -> bool {
+        if self.predicted() <= self.budget || self.consecutive_skips >= MAX_CONSECUTIVE_SKIPS {
+            self.consecutive_skips = 0;
+            return false;
+        }
+        self.consecutive_skips += 1;
+        true
+    }
+
+    /// Record how long the denoiser took on a processed frame
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.recent.len() == TIMING_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_slow_frames_and_recovers() {
+        assert!(FrameDeadline::new(0).is_none());
+
+        let mut deadline = FrameDeadline::new(5_000).unwrap();
+        assert!(!deadline.should_skip(), "No timings yet - process");
+
+        for _ in 0..TIMING_WINDOW {
+            deadline.record(Duration::from_millis(8));
+        }
+        let skipped = (0..MAX_CONSECUTIVE_SKIPS).filter(|_| deadline.should_skip()).count();
+        assert_eq!(skipped, MAX_CONSECUTIVE_SKIPS as usize);
+        // A probe frame is processed after the run of skips
+        assert!(!deadline.should_skip());
+
+        // Fast frames bring the prediction back under budget
+        for _ in 0..TIMING_WINDOW {
+            deadline.record(Duration::from_millis(1));
+        }
+        assert!(!deadline.should_skip());
+    }
+}
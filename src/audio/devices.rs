@@ -1,6 +1,19 @@
+use crate::audio::test_signal::is_test_signal_device;
+use crate::logger::log;
 use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// What to do when the configured input device can't be opened at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DeviceFallback {
+    /// Fail to start rather than use a different microphone
+    Strict,
+    /// Try the system default, then the first available input (default)
+    #[default]
+    BestEffort,
+}
+
 #[derive(Debug, Clone)]
 pub struct AudioDeviceInfo {
     pub id: String,
@@ -9,6 +22,25 @@ pub struct AudioDeviceInfo {
     pub is_virtual: bool,
 }
 
+/// ID of the placeholder input listed when enumeration finds no devices
+pub const PLACEHOLDER_INPUT_ID: &str = "input_default";
+/// ID of the placeholder output listed when enumeration finds no devices
+pub const PLACEHOLDER_OUTPUT_ID: &str = "output_default";
+
+impl AudioDeviceInfo {
+    /// Whether this entry is the stand-in added when no real device was found
+    pub fn is_placeholder(&self) -> bool {
+        self.id == PLACEHOLDER_INPUT_ID || self.id == PLACEHOLDER_OUTPUT_ID
+    }
+}
+
+/// Whether a device list contains anything besides the placeholder entry
+/// 
+/// Test-signal pseudo devices count as usable inputs.
+pub fn has_usable_devices(devices: &[AudioDeviceInfo]) -> bool {
+    devices.iter().any(|device| !device.is_placeholder())
+}
+
 impl fmt::Display for AudioDeviceInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_default {
@@ -49,10 +81,10 @@ pub fn list_input_devices() -> Vec<AudioDeviceInfo> {
         }
     }
 
-    // If no devices found, add a fallback
+    // If no devices found, add a placeholder (see `has_usable_devices`)
     if devices.is_empty() {
         devices.push(AudioDeviceInfo {
-            id: "input_default".to_string(),
+            id: PLACEHOLDER_INPUT_ID.to_string(),
             name: "Default Microphone".to_string(),
             is_default: true,
             is_virtual: false,
@@ -92,10 +124,10 @@ pub fn list_output_devices() -> Vec<AudioDeviceInfo> {
         }
     }
 
-    // If no devices found, add fallback
+    // If no devices found, add a placeholder (see `has_usable_devices`)
     if devices.is_empty() {
         devices.push(AudioDeviceInfo {
-            id: "output_default".to_string(),
+            id: PLACEHOLDER_OUTPUT_ID.to_string(),
             name: "Default Speakers".to_string(),
             is_default: true,
             is_virtual: false,
@@ -109,7 +141,7 @@ pub fn get_device_by_id(device_id: &str, is_input: bool) -> Option<cpal::Device>
     let host = cpal::default_host();
     
     if is_input {
-        if device_id == "input_default" {
+        if device_id == PLACEHOLDER_INPUT_ID {
             return host.default_input_device();
         }
         
@@ -121,7 +153,7 @@ pub fn get_device_by_id(device_id: &str, is_input: bool) -> Option<cpal::Device>
             }
         }
     } else {
-        if device_id == "output_default" {
+        if device_id == PLACEHOLDER_OUTPUT_ID {
             return host.default_output_device();
         }
         
@@ -137,6 +169,86 @@ pub fn get_device_by_id(device_id: &str, is_input: bool) -> Option<cpal::Device>
     None
 }
 
+/// Number of channels the input device captures by default
+/// 
+/// Used to offer a channel choice for multichannel interfaces; `None` when the
+/// device isn't connected or can't report its configuration.
+pub fn input_channel_count(device_id: &str) -> Option<u16> {
+    let device = get_device_by_id(device_id, true)?;
+    device.default_input_config().ok().map(|config| config.channels())
+}
+
+/// Find a stream configuration running at `sample_rate`, if the device supports one
+/// 
+/// Used when the pipeline targets a sample rate other than the device default
+/// (e.g. 16kHz for telephony); callers fall back to the default config otherwise.
+pub fn config_for_sample_rate(device: &cpal::Device, is_input: bool, sample_rate: u32) -> Option<cpal::SupportedStreamConfig> {
+    let rate = cpal::SampleRate(sample_rate);
+    let ranges: Vec<cpal::SupportedStreamConfigRange> = if is_input {
+        device.supported_input_configs().ok()?.collect()
+    } else {
+        device.supported_output_configs().ok()?.collect()
+    };
+    
+    ranges.into_iter()
+        .filter(|range| range.sample_format() == cpal::SampleFormat::F32)
+        .find(|range| range.min_sample_rate() <= rate && rate <= range.max_sample_rate())
+        .map(|range| range.with_sample_rate(rate))
+}
+
+/// Input device IDs to try, in order, for the configured device and policy
+/// 
+/// Best-effort adds the system default and then the first listed device,
+/// skipping any ID already in the chain.
+pub fn input_fallback_chain(configured: &str, devices: &[AudioDeviceInfo], policy: DeviceFallback) -> Vec<String> {
+    let mut chain = vec![configured.to_string()];
+    if policy == DeviceFallback::BestEffort {
+        let candidates = devices.iter().find(|d| d.is_default).into_iter().chain(devices.first());
+        for device in candidates {
+            if !chain.contains(&device.id) {
+                chain.push(device.id.clone());
+            }
+        }
+    }
+    chain
+}
+
+/// Whether an input device exists and reports a usable configuration
+fn input_device_usable(device_id: &str) -> Result<(), String> {
+    if is_test_signal_device(device_id) {
+        return Ok(());
+    }
+    let device = get_device_by_id(device_id, true).ok_or("device not found")?;
+    device.default_input_config().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Pick the input device to open, walking the fallback chain
+/// 
+/// Each attempt is logged. Returns the first usable device ID, or an error
+/// listing every failure once the chain is exhausted.
+pub fn resolve_input_device(configured: &str, policy: DeviceFallback) -> Result<String, String> {
+    let chain = input_fallback_chain(configured, &list_input_devices(), policy);
+    let mut failures = Vec::new();
+    
+    for device_id in chain {
+        match input_device_usable(&device_id) {
+            Ok(()) => {
+                if device_id != configured {
+                    log::warn!("⚠️ Input '{}' unavailable - falling back to '{}'", configured, device_id);
+                }
+                return Ok(device_id);
+            }
+            Err(e) => {
+                log::warn!("Input device '{}' can't be opened: {}", device_id, e);
+                failures.push(format!("{} ({})", device_id, e));
+            }
+        }
+    }
+    
+    Err(format!("No usable input device (tried {})", failures.join(", ")))
+}
+
 pub fn find_virtual_output_device() -> Option<cpal::Device> {
     let host = cpal::default_host();
     
@@ -151,4 +263,34 @@ pub fn find_virtual_output_device() -> Option<cpal::Device> {
     }
     
     None
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(id: &str, is_default: bool) -> AudioDeviceInfo {
+        AudioDeviceInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            is_default,
+            is_virtual: false,
+        }
+    }
+
+    #[test]
+    fn test_input_fallback_chain() {
+        let devices = [device("input_0", false), device("input_1", true), device("input_2", false)];
+
+        assert_eq!(input_fallback_chain("input_2", &devices, DeviceFallback::Strict), vec!["input_2"]);
+        assert_eq!(
+            input_fallback_chain("input_5", &devices, DeviceFallback::BestEffort),
+            vec!["input_5", "input_1", "input_0"]
+        );
+        // The configured device is the default: only the first device is added
+        assert_eq!(
+            input_fallback_chain("input_1", &devices, DeviceFallback::BestEffort),
+            vec!["input_1", "input_0"]
+        );
+        assert_eq!(input_fallback_chain("input_0", &[], DeviceFallback::BestEffort), vec!["input_0"]);
+    }
+}
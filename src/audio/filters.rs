@@ -0,0 +1,214 @@
+//! # Input Filters Module
+//!
+//! Small stateful filters applied to the signal around the denoiser. They run
+//! on the audio threads, so they must stay allocation-free and keep their
+//! state between callbacks.
+
+use std::collections::VecDeque;
+
+/// Corner frequency of the DC-blocking high-pass filter (Hz)
+pub const DC_BLOCKER_CUTOFF_HZ: f32 = 5.0;
+
+/// Lowest configurable denoiser crossover (Hz)
+pub const MIN_CROSSOVER_HZ: u32 = 2000;
+/// Highest configurable denoiser crossover (Hz)
+pub const MAX_CROSSOVER_HZ: u32 = 16000;
+
+/// One-pole DC-blocking high-pass filter
+///
+/// `y[n] = x[n] - x[n-1] + r * y[n-1]` with `r = exp(-2π·fc/fs)`. At a 5Hz
+/// corner it is inaudible but removes the constant offset some USB microphones
+/// add, which otherwise wastes headroom and skews RNNoise's band energies.
+#[derive(Debug, Clone)]
+pub struct DcBlocker {
+    coefficient: f32,
+    previous_input: f32,
+    previous_output: f32,
+}
+
+impl DcBlocker {
+    /// Create a DC blocker for audio at `sample_rate`
+    pub fn new(sample_rate: u32) -> Self {
+        let coefficient = (-2.0 * std::f32::consts::PI * DC_BLOCKER_CUTOFF_HZ / sample_rate.max(1) as f32).exp();
+        Self {
+            coefficient,
+            previous_input: 0.0,
+            previous_output: 0.0,
+        }
+    }
+
+    /// Filter a block of samples in place, carrying state across calls
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            let input = *sample;
+            let output = input - self.previous_input + self.coefficient * self.previous_output;
+            self.previous_input = input;
+            self.previous_output = output;
+            *sample = output;
+        }
+    }
+}
+
+/// Second-order IIR section (transposed direct form II)
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    /// Low-pass section with corner `cutoff_hz` and quality factor `q` (RBJ cookbook)
+    pub fn low_pass(cutoff_hz: f32, q: f32, sample_rate: u32) -> Self {
+        let omega = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate.max(1) as f32;
+        let alpha = omega.sin() / (2.0 * q);
+        let cos = omega.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 - cos) / 2.0 / a0,
+            b1: (1.0 - cos) / a0,
+            b2: (1.0 - cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// Filter one sample
+    pub fn process_sample(&mut self, input: f32) -> f32 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+}
+
+/// Splits frames at a crossover so only the low band goes through the denoiser
+///
+/// Full-band RNNoise can dull sibilance. The low band is a 4th-order
+/// Linkwitz-Riley low-pass and the high band is the remainder (`input - low`),
+/// so the two bands add back up to the original signal exactly. The high band
+/// is held back by the denoiser's latency so it lines up with the processed
+/// low band when the two are recombined.
+#[derive(Debug, Clone)]
+pub struct BandSplitter {
+    low_pass: [Biquad; 2],
+    /// High band samples waiting to be recombined, `delay` samples deep
+    pending_high: VecDeque<f32>,
+    /// High band matching the frame most recently passed to `split`
+    delayed_high: Vec<f32>,
+}
+
+impl BandSplitter {
+    /// Create a splitter at `crossover_hz`, delaying the high band by `delay` samples
+    pub fn new(crossover_hz: u32, sample_rate: u32, delay: usize) -> Self {
+        let cutoff = crossover_hz.clamp(MIN_CROSSOVER_HZ, MAX_CROSSOVER_HZ).min(sample_rate / 2 - 1) as f32;
+        let section = Biquad::low_pass(cutoff, std::f32::consts::FRAC_1_SQRT_2, sample_rate);
+        Self {
+            low_pass: [section.clone(), section],
+            pending_high: std::iter::repeat(0.0).take(delay).collect(),
+            delayed_high: Vec::new(),
+        }
+    }
+
+    /// Write the low band of `frame` into `low` and queue its high band
+    pub fn split(&mut self, frame: &[f32], low: &mut Vec<f32>) {
+        low.clear();
+        for &sample in frame {
+            let low_sample = self.low_pass.iter_mut().fold(sample, |s, section| section.process_sample(s));
+            low.push(low_sample);
+            self.pending_high.push_back(sample - low_sample);
+        }
+        self.delayed_high.clear();
+        self.delayed_high.extend(self.pending_high.drain(..frame.len()));
+    }
+
+    /// Add the high band back onto the processed low band of the last split frame
+    pub fn recombine(&self, processed_low: &mut [f32]) {
+        for (sample, high) in processed_low.iter_mut().zip(&self.delayed_high) {
+            *sample += high;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dc_blocker_removes_constant_offset() {
+        let mut blocker = DcBlocker::new(48000);
+        let mean = |frame: &[f32]| frame.iter().sum::<f32>() / frame.len() as f32;
+
+        // A 400Hz tone (whole cycles per frame) riding on a 0.2 offset, processed in 480-sample callbacks
+        let mut position = 0usize;
+        let mut last_mean = f32::MAX;
+        for _ in 0..200 {
+            let mut frame: Vec<f32> = (0..480)
+                .map(|i| {
+                    let t = (position + i) as f32 / 48000.0;
+                    0.2 + 0.1 * (2.0 * std::f32::consts::PI * 400.0 * t).sin()
+                })
+                .collect();
+            position += frame.len();
+            blocker.process(&mut frame);
+            last_mean = mean(&frame);
+        }
+
+        // After two seconds the offset is gone (time constant ~32ms)
+        assert!(last_mean.abs() < 1e-3, "Residual DC offset: {}", last_mean);
+    }
+
+    /// Amplitude of the `frequency` component of `samples` (Goertzel)
+    fn tone_amplitude(samples: &[f32], frequency: f32) -> f32 {
+        let coefficient = 2.0 * (2.0 * std::f32::consts::PI * frequency / 48000.0).cos();
+        let (mut s1, mut s2) = (0.0f32, 0.0f32);
+        for &sample in samples {
+            let s0 = sample + coefficient * s1 - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+        (s1 * s1 + s2 * s2 - coefficient * s1 * s2).sqrt() * 2.0 / samples.len() as f32
+    }
+
+    #[test]
+    fn test_band_split_keeps_high_band_through_denoiser() {
+        // 200 Hz hum as low-band noise plus 8 kHz sibilance
+        let signal: Vec<f32> = (0..48000)
+            .map(|i| {
+                let t = i as f32 / 48000.0;
+                0.3 * (2.0 * std::f32::consts::PI * 200.0 * t).sin() + 0.2 * (2.0 * std::f32::consts::PI * 8000.0 * t).sin()
+            })
+            .collect();
+
+        let mut splitter = BandSplitter::new(4000, 48000, 480);
+        let mut passthrough = BandSplitter::new(4000, 48000, 0);
+        let mut low = Vec::new();
+        let mut output = Vec::new();
+        let mut reconstructed = Vec::new();
+        for frame in signal.chunks(480) {
+            // Stand-in denoiser that removes 90% of the low band
+            splitter.split(frame, &mut low);
+            let mut processed: Vec<f32> = low.iter().map(|s| s * 0.1).collect();
+            splitter.recombine(&mut processed);
+            output.extend(processed);
+
+            // Untouched low band without latency: the bands add back up to the input
+            passthrough.split(frame, &mut low);
+            let mut unprocessed = low.clone();
+            passthrough.recombine(&mut unprocessed);
+            reconstructed.extend(unprocessed);
+        }
+
+        let tail = &output[24000..];
+        let input_tail = &signal[24000..];
+        assert!(tone_amplitude(tail, 200.0) < 0.2 * tone_amplitude(input_tail, 200.0), "Low-band noise not reduced");
+        assert!((tone_amplitude(tail, 8000.0) - 0.2).abs() < 0.02, "High band lost: {}", tone_amplitude(tail, 8000.0));
+        assert!(reconstructed.iter().zip(&signal).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+}
@@ -0,0 +1,679 @@
+//! # Gain Staging Module
+//!
+//! This module holds the small, allocation-free gain and mixing helpers used by the
+//! processing thread around the denoiser. Keeping them out of the thread closure
+//! makes the behaviour easy to unit test without an audio device.
+//!
+//! ## Processing Order
+//!
+//! 1. Input pre-gain boosts quiet sources before the denoiser sees them
+//! 2. Denoiser output is scaled by the speech/noise gain
+//! 3. The processed frame is blended with the raw input (dry/wet mix)
+//! 4. Output trim and limiting are applied last
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Default dry/wet mix (fully processed output)
+pub const DEFAULT_MIX: f32 = 1.0;
+
+/// Highest peak the input pre-gain may drive a frame to
+pub const PRE_GAIN_CEILING: f32 = 1.0;
+
+/// Highest accepted gain floor (keeps at least half of the background)
+pub const MAX_GAIN_FLOOR: f32 = 0.5;
+
+/// Longest accepted speech onset protection window (ms)
+pub const MAX_ONSET_PROTECTION_MS: u32 = 100;
+
+/// VAD rise over the recent history that counts as a speech onset
+pub const ONSET_VAD_RISE: f32 = 0.2;
+
+/// Frames of VAD history the onset detector compares against
+const ONSET_HISTORY_FRAMES: usize = 3;
+
+/// Widest accepted VAD deadband around the speech threshold
+pub const MAX_VAD_DEADBAND: f32 = 0.3;
+
+/// Default time for the applied gain to rise towards a higher target (ms)
+pub const DEFAULT_GAIN_ATTACK_MS: u32 = 10;
+
+/// Default time for the applied gain to fall towards a lower target (ms)
+pub const DEFAULT_GAIN_RELEASE_MS: u32 = 50;
+
+/// Longest accepted gain attack or release time (ms)
+pub const MAX_GAIN_SMOOTHING_MS: u32 = 500;
+
+/// Default time the gate stays open after the VAD drops (ms)
+pub const DEFAULT_GATE_HOLD_MS: u32 = 150;
+
+/// Longest accepted gate hold time (ms)
+pub const MAX_GATE_HOLD_MS: u32 = 1000;
+
+/// VAD gate settings for one audio channel
+/// 
+/// The mono pipeline uses a single gate built from `vad_threshold`/`noise_gain`;
+/// the stereo path can give each channel its own gate, e.g. to suppress a noisy
+/// left channel harder than the right.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChannelGate {
+    /// Frames scoring below this VAD value are treated as noise
+    pub vad_threshold: f32,
+    /// Gain applied to noise frames
+    pub noise_gain: f32,
+}
+
+impl ChannelGate {
+    /// Gain for a frame with the given VAD score
+    pub fn gain(&self, vad_score: f32, speech_gain: f32) -> f32 {
+        if vad_score < self.vad_threshold {
+            self.noise_gain
+        } else {
+            speech_gain
+        }
+    }
+}
+
+/// Independent gates for the left and right channels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StereoGates {
+    /// Gate for the left channel
+    pub left: ChannelGate,
+    /// Gate for the right channel
+    pub right: ChannelGate,
+}
+
+impl StereoGates {
+    /// Both channels using the same gate
+    pub fn linked(gate: ChannelGate) -> Self {
+        Self { left: gate, right: gate }
+    }
+
+    /// Gate for channel `index` (0 = left, anything else = right)
+    pub fn channel(&self, index: usize) -> ChannelGate {
+        if index == 0 { self.left } else { self.right }
+    }
+}
+
+/// Envelope follower with separate attack and release times
+///
+/// Tracks a level that rises with the attack time constant and falls with the
+/// release time constant. `rate` is the number of `process` calls per second:
+/// the sample rate for per-sample use, or the frame rate for per-frame levels.
+#[derive(Debug, Clone)]
+pub struct EnvelopeFollower {
+    attack_coeff: f32,
+    release_coeff: f32,
+    envelope: f32,
+}
+
+impl EnvelopeFollower {
+    pub fn new(attack_secs: f32, release_secs: f32, rate: f32) -> Self {
+        Self {
+            attack_coeff: (-1.0 / (attack_secs * rate)).exp(),
+            release_coeff: (-1.0 / (release_secs * rate)).exp(),
+            envelope: 0.0,
+        }
+    }
+
+    /// Feed the next level and return the updated envelope
+    pub fn process(&mut self, level: f32) -> f32 {
+        let coeff = if level > self.envelope { self.attack_coeff } else { self.release_coeff };
+        self.envelope = level + (self.envelope - level) * coeff;
+        self.envelope
+    }
+
+    /// Current envelope level
+    pub fn envelope(&self) -> f32 {
+        self.envelope
+    }
+
+    /// Continue from `level` without a transition
+    pub fn reset(&mut self, level: f32) {
+        self.envelope = level;
+    }
+}
+
+/// Ramps the applied gain between frames instead of switching it
+///
+/// The gate picks one gain per 480-sample frame, and jumping between the noise
+/// and speech gain at a frame boundary is heard as a click. The smoother moves
+/// the gain towards each frame's target sample by sample, rising with the attack
+/// time and falling with the release time. Zero times switch instantly.
+#[derive(Debug, Clone)]
+pub struct GainSmoother {
+    envelope: EnvelopeFollower,
+    started: bool,
+}
+
+impl GainSmoother {
+    pub fn new(attack_ms: u32, release_ms: u32, sample_rate: u32) -> Self {
+        Self {
+            envelope: EnvelopeFollower::new(
+                attack_ms.min(MAX_GAIN_SMOOTHING_MS) as f32 / 1000.0,
+                release_ms.min(MAX_GAIN_SMOOTHING_MS) as f32 / 1000.0,
+                sample_rate.max(1) as f32,
+            ),
+            started: false,
+        }
+    }
+
+    /// Multiply `frame` by a gain moving towards `target`
+    ///
+    /// The first frame starts at its target, so the stream doesn't fade in.
+    pub fn apply(&mut self, frame: &mut [f32], target: f32) {
+        if !self.started {
+            self.jump_to(target);
+        }
+        for sample in frame.iter_mut() {
+            *sample *= self.envelope.process(target);
+        }
+    }
+
+    /// Continue from `gain` without a ramp, e.g. after frames that bypassed the gate
+    pub fn jump_to(&mut self, gain: f32) {
+        self.envelope.reset(gain);
+        self.started = true;
+    }
+
+    /// Gain applied to the most recent sample
+    pub fn gain(&self) -> f32 {
+        self.envelope.envelope()
+    }
+}
+
+/// Raise a computed frame gain to the user's gain floor
+///
+/// The floor keeps a little room tone under heavy gating so the result doesn't
+/// sound dead. It only ever raises the gain; speech frames above the floor are
+/// unaffected. The floor is clamped to `0.0..=MAX_GAIN_FLOOR`.
+pub fn apply_gain_floor(gain: f32, floor: f32) -> f32 {
+    gain.max(floor.clamp(0.0, MAX_GAIN_FLOOR))
+}
+
+/// Hysteresis for the speech/noise decision
+///
+/// In noisy rooms the VAD score hovers around the threshold and a plain
+/// comparison flips the gain every few frames. Scores inside a band of
+/// `deadband` centered on the threshold keep the previous decision; only a
+/// score clearly above or below the band switches it. A deadband of 0.0 is the
+/// plain threshold comparison.
+#[derive(Debug, Clone)]
+pub struct GateHysteresis {
+    deadband: f32,
+    speech: bool,
+}
+
+impl GateHysteresis {
+    /// Create a hysteresis gate; the deadband is clamped to `0.0..=MAX_VAD_DEADBAND`
+    pub fn new(deadband: f32) -> Self {
+        Self {
+            deadband: deadband.clamp(0.0, MAX_VAD_DEADBAND),
+            speech: false,
+        }
+    }
+
+    /// Decide whether the frame with `vad_score` is speech
+    pub fn is_speech(&mut self, vad_score: f32, vad_threshold: f32) -> bool {
+        let half_band = self.deadband / 2.0;
+        if vad_score >= vad_threshold + half_band {
+            self.speech = true;
+        } else if vad_score < vad_threshold - half_band {
+            self.speech = false;
+        }
+        self.speech
+    }
+}
+
+/// Keeps the gate open for a while after speech stops
+///
+/// The VAD score dips in the short pauses between words and in unvoiced
+/// consonants. Without a hold the gate closes in every such dip and the next
+/// syllable starts gated. After the last speech frame the gate stays open for
+/// `hold_frames` more frames, and only then does the release ramp begin.
+#[derive(Debug, Clone)]
+pub struct GateHold {
+    hold_frames: u32,
+    remaining: u32,
+}
+
+impl GateHold {
+    /// Create a hold of `hold_frames` frames (0 disables it)
+    pub fn new(hold_frames: u32) -> Self {
+        Self { hold_frames, remaining: 0 }
+    }
+
+    /// Apply the hold to one frame's speech decision
+    pub fn is_open(&mut self, is_speech: bool) -> bool {
+        if is_speech {
+            self.remaining = self.hold_frames;
+            true
+        } else if self.remaining > 0 {
+            self.remaining -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Protects the attack of the first syllable from the noise gate
+///
+/// RNNoise's VAD needs a few frames to become confident, so the first frames of
+/// a word score below the speech threshold and get gated like noise. When the
+/// VAD score rises sharply compared to the last few frames, the protector holds
+/// the gain at the speech level for a short window.
+#[derive(Debug, Clone)]
+pub struct OnsetProtector {
+    window_frames: u32,
+    remaining: u32,
+    history: VecDeque<f32>,
+}
+
+impl OnsetProtector {
+    /// Create a protector holding the gain for `window_frames` frames (0 disables it)
+    pub fn new(window_frames: u32) -> Self {
+        Self {
+            window_frames,
+            remaining: 0,
+            history: VecDeque::with_capacity(ONSET_HISTORY_FRAMES),
+        }
+    }
+
+    /// Start a protection window as if an onset had just been detected
+    ///
+    /// Used when the denoiser re-engages after an idle bypass, when its VAD has
+    /// no recent history to compare against. Does nothing when disabled.
+    pub fn trigger(&mut self) {
+        self.remaining = self.window_frames;
+        self.history.clear();
+    }
+
+    /// Observe one frame's VAD score and return the gain floor for that frame
+    ///
+    /// Returns `protected_gain` while protection is active, otherwise 0.0.
+    pub fn floor(&mut self, vad_score: f32, protected_gain: f32) -> f32 {
+        if self.window_frames == 0 {
+            return 0.0;
+        }
+
+        let recent_low = self.history.iter().copied().fold(f32::INFINITY, f32::min);
+        if vad_score - recent_low >= ONSET_VAD_RISE {
+            self.remaining = self.window_frames;
+        }
+
+        self.history.push_back(vad_score);
+        if self.history.len() > ONSET_HISTORY_FRAMES {
+            self.history.pop_front();
+        }
+
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            protected_gain
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Raw input delayed to line up with the denoiser's output
+///
+/// RNNoise's overlap-add returns each frame one frame late (the same latency
+/// `filters::BandSplitter` compensates for), so blending the undelayed input
+/// into its output comb-filters the voice at any mix between 0 and 1.
+#[derive(Debug, Clone)]
+pub struct DryDelay {
+    /// Raw samples waiting to be mixed, `delay` samples deep
+    pending: VecDeque<f32>,
+    /// Raw input matching the frame most recently passed to `push`
+    delayed: Vec<f32>,
+}
+
+impl DryDelay {
+    /// Create a delay line of `delay` samples (a whole frame for RNNoise)
+    pub fn new(delay: usize) -> Self {
+        Self {
+            pending: VecDeque::from(vec![0.0; delay]),
+            delayed: Vec::new(),
+        }
+    }
+
+    /// Queue the raw `frame` and return the raw input aligned with the denoiser's output for it
+    pub fn push(&mut self, frame: &[f32]) -> &[f32] {
+        self.pending.extend(frame.iter().copied());
+        self.delayed.clear();
+        self.delayed.extend(self.pending.drain(..frame.len()));
+        &self.delayed
+    }
+}
+
+/// Keeps the denoiser's input contiguous across frames it didn't process
+///
+/// When a frame passes the denoiser by (deadline skip, idle bypass, A/B
+/// comparison), RNNoise still holds the frame before it in its overlap window.
+/// Feeding it the missed frame just before the next one it processes lines the
+/// window up again, so its output continues exactly where the (delayed)
+/// passthrough stopped instead of repeating a frame.
+#[derive(Debug, Clone)]
+pub struct DenoiserCatchUp {
+    /// Last frame the denoiser didn't see
+    missed: Vec<f32>,
+    pending: bool,
+    /// Output of the catch-up call, which is discarded
+    scratch: Vec<f32>,
+}
+
+impl DenoiserCatchUp {
+    /// Create a catch-up buffer for frames of `frame_size` samples
+    pub fn new(frame_size: usize) -> Self {
+        Self {
+            missed: Vec::with_capacity(frame_size),
+            pending: false,
+            scratch: vec![0.0; frame_size],
+        }
+    }
+
+    /// Remember a frame that bypassed the denoiser
+    pub fn missed(&mut self, input: &[f32]) {
+        self.missed.clear();
+        self.missed.extend_from_slice(input);
+        self.pending = true;
+    }
+
+    /// Feed the last missed frame to `denoise(output, input)` before the denoiser
+    /// processes the next one; does nothing if no frame was missed
+    pub fn catch_up(&mut self, denoise: impl FnOnce(&mut [f32], &[f32])) {
+        if !self.pending {
+            return;
+        }
+        self.pending = false;
+        self.scratch.resize(self.missed.len(), 0.0);
+        denoise(&mut self.scratch, &self.missed);
+    }
+}
+
+/// Blend the raw input (`dry`) into the processed frame (`wet`) in place
+///
+/// A `mix` of 0.0 passes the raw input through unchanged, 1.0 keeps only the
+/// processed signal. Values in between interpolate linearly, which gives users a
+/// middle ground when full suppression sounds unnatural.
+///
+/// The blend is `input * (1 - mix) + processed * mix`, so the raw noise comes
+/// back along with the raw voice: at a mix of 0.8 noise is at best reduced to
+/// 20% of its original level, whatever the denoiser and gate do.
+///
+/// `dry` must be aligned with `wet`: pass denoised frames the input from a
+/// `DryDelay`, not the frame that was just fed to the denoiser.
+pub fn apply_dry_wet_mix(dry: &[f32], wet: &mut [f32], mix: f32) {
+    let mix = mix.clamp(0.0, 1.0);
+    if mix >= 1.0 {
+        return;
+    }
+
+    for (wet_sample, &dry_sample) in wet.iter_mut().zip(dry.iter()) {
+        *wet_sample = dry_sample + (*wet_sample - dry_sample) * mix;
+    }
+}
+
+/// Apply the input pre-gain to a frame in place, with clip protection
+///
+/// If boosting by `gain` would push the frame's peak above `PRE_GAIN_CEILING`,
+/// the gain for this frame is lowered so the peak lands on the ceiling instead
+/// of being hard-clipped, which would hand the denoiser distorted input.
+/// Returns the gain that was actually applied.
+pub fn apply_pre_gain(frame: &mut [f32], gain: f32) -> f32 {
+    if gain == 1.0 {
+        return gain;
+    }
+
+    let peak = frame.iter().fold(0.0f32, |peak, &sample| peak.max(sample.abs()));
+    let applied = if peak * gain > PRE_GAIN_CEILING {
+        PRE_GAIN_CEILING / peak
+    } else {
+        gain
+    };
+
+    for sample in frame.iter_mut() {
+        *sample *= applied;
+    }
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadband_holds_decision_near_threshold() {
+        // VAD jitters around the 0.5 threshold
+        let scores = [0.45, 0.55, 0.48, 0.53, 0.46, 0.54, 0.49, 0.52, 0.47, 0.56];
+        let flips = |deadband: f32| {
+            let mut hysteresis = GateHysteresis::new(deadband);
+            let decisions: Vec<bool> = scores.iter().map(|&s| hysteresis.is_speech(s, 0.5)).collect();
+            decisions.windows(2).filter(|w| w[0] != w[1]).count()
+        };
+
+        // Without a deadband the decision follows every crossing
+        assert_eq!(flips(0.0), scores.len() - 1);
+        // A 0.2 band holds the previous decision throughout
+        assert_eq!(flips(0.2), 0);
+
+        // Clear speech and clear noise still switch
+        let mut hysteresis = GateHysteresis::new(0.2);
+        assert!(hysteresis.is_speech(0.9, 0.5));
+        assert!(hysteresis.is_speech(0.45, 0.5));
+        assert!(!hysteresis.is_speech(0.2, 0.5));
+        assert!(!hysteresis.is_speech(0.55, 0.5));
+    }
+
+    #[test]
+    fn test_gain_smoother_ramps_monotonically() {
+        let mut smoother = GainSmoother::new(DEFAULT_GAIN_ATTACK_MS, DEFAULT_GAIN_RELEASE_MS, 48000);
+        let mut frame = vec![1.0f32; 480];
+        smoother.apply(&mut frame, 0.1);
+        assert!(frame.iter().all(|&g| (g - 0.1).abs() < 1e-6), "First frame starts at its target");
+
+        // Noise to speech: a rising ramp, not a jump
+        let mut frame = vec![1.0f32; 480];
+        smoother.apply(&mut frame, 0.8);
+        assert!(frame[0] < 0.15, "Gain jumped to {}", frame[0]);
+        assert!(frame.windows(2).all(|w| w[1] >= w[0]));
+        assert!(frame[479] > 0.5, "Most of the way up after one attack time: {}", frame[479]);
+
+        // Speech to noise: the slower release is still falling at the end of the frame
+        let mut frame = vec![1.0f32; 480];
+        smoother.apply(&mut frame, 0.1);
+        assert!(frame.windows(2).all(|w| w[1] <= w[0]));
+        assert!(frame[479] > 0.3, "Release ended at {}", frame[479]);
+
+        // Zero times keep the old instant switching
+        let mut instant = GainSmoother::new(0, 0, 48000);
+        let mut frame = vec![1.0f32; 4];
+        instant.apply(&mut frame, 0.1);
+        instant.apply(&mut frame, 0.8);
+        assert!(frame.iter().all(|&g| (g - 0.08).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_mix_zero_is_passthrough() {
+        let dry = vec![0.5, -0.25, 0.1, 0.0];
+        let mut wet = vec![0.05, -0.01, 0.0, 0.3];
+
+        apply_dry_wet_mix(&dry, &mut wet, 0.0);
+        assert_eq!(wet, dry);
+    }
+
+    #[test]
+    fn test_stereo_gates_are_independent() {
+        let mut gates = StereoGates::linked(ChannelGate { vad_threshold: 0.5, noise_gain: 0.1 });
+        assert_eq!(gates.channel(0).gain(0.3, 0.8), gates.channel(1).gain(0.3, 0.8));
+
+        // Same VAD score on both channels, stricter gate on the noisy left channel
+        gates.left = ChannelGate { vad_threshold: 0.7, noise_gain: 0.02 };
+        assert_eq!(gates.channel(0).gain(0.6, 0.8), 0.02);
+        assert_eq!(gates.channel(1).gain(0.6, 0.8), 0.8);
+    }
+
+    #[test]
+    fn test_gain_floor_only_raises() {
+        let gate = ChannelGate { vad_threshold: 0.5, noise_gain: 0.1 };
+        assert_eq!(apply_gain_floor(gate.gain(0.2, 0.8), 0.2), 0.2);
+        assert_eq!(apply_gain_floor(gate.gain(0.9, 0.8), 0.2), 0.8);
+        assert_eq!(apply_gain_floor(gate.gain(0.2, 0.8), 0.0), 0.1);
+        assert_eq!(apply_gain_floor(0.0, 2.0), MAX_GAIN_FLOOR);
+    }
+
+    #[test]
+    fn test_onset_protection_preserves_attack() {
+        let gate = ChannelGate { vad_threshold: 0.5, noise_gain: 0.1 };
+        // Silence, then a word whose VAD score takes a few frames to ramp up
+        let vad_scores = [0.02, 0.03, 0.02, 0.25, 0.45, 0.6, 0.9, 0.95];
+
+        let gains = |window_frames| {
+            let mut protector = OnsetProtector::new(window_frames);
+            vad_scores.iter()
+                .map(|&vad| gate.gain(vad, 0.8).max(protector.floor(vad, 0.8)))
+                .collect::<Vec<f32>>()
+        };
+
+        let unprotected = gains(0);
+        assert_eq!(&unprotected[3..5], &[0.1, 0.1], "Without protection the attack is gated");
+
+        let protected = gains(3);
+        assert_eq!(&protected[..3], &[0.1, 0.1, 0.1], "Silence is still suppressed");
+        assert!(protected[3..].iter().all(|&g| g >= 0.8), "First speech frames keep speech gain");
+
+        // Re-engaging after an idle bypass protects the next frames without a VAD rise
+        let mut protector = OnsetProtector::new(2);
+        protector.trigger();
+        assert_eq!(protector.floor(0.1, 0.8), 0.8);
+        assert_eq!(protector.floor(0.1, 0.8), 0.8);
+        assert_eq!(protector.floor(0.1, 0.8), 0.0);
+    }
+
+    #[test]
+    fn test_gate_hold_keeps_speech_gain_between_words() {
+        let gate = ChannelGate { vad_threshold: 0.5, noise_gain: 0.1 };
+        // Speech, a 3-frame pause between words, speech, then a long silence
+        let vad_scores = [0.9, 0.9, 0.1, 0.2, 0.1, 0.9, 0.9, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1];
+
+        let gains = |hold_frames| {
+            let mut hold = GateHold::new(hold_frames);
+            vad_scores.iter()
+                .map(|&vad| if hold.is_open(vad >= gate.vad_threshold) { 0.8 } else { gate.noise_gain })
+                .collect::<Vec<f32>>()
+        };
+
+        let unheld = gains(0);
+        assert_eq!(&unheld[2..5], &[0.1, 0.1, 0.1], "Without a hold every pause is gated");
+
+        // 150 ms hold at 10 ms per frame
+        let held = gains(15);
+        assert!(held[..7].iter().all(|&g| g == 0.8), "Gain stays high through the pause");
+        assert!(held[7..].iter().all(|&g| g == 0.8), "Gain stays high for the hold time after speech");
+
+        let short = gains(3);
+        assert_eq!(&short[7..], &[0.8, 0.8, 0.8, 0.1, 0.1, 0.1], "Gate closes once the hold runs out");
+    }
+
+    #[test]
+    fn test_pre_gain_scales_frame() {
+        let mut frame = vec![0.05, -0.1, 0.02];
+        let applied = apply_pre_gain(&mut frame, 4.0);
+
+        assert_eq!(applied, 4.0);
+        assert!((frame[0] - 0.2).abs() < 1e-6);
+        assert!((frame[1] + 0.4).abs() < 1e-6);
+        assert!((frame[2] - 0.08).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pre_gain_clip_protection() {
+        let mut frame = vec![0.5, -0.25, 0.1];
+        let applied = apply_pre_gain(&mut frame, 4.0);
+
+        // A 4x boost would reach 2.0; the frame is scaled so its peak sits on the ceiling
+        assert!((applied - 2.0).abs() < 1e-6);
+        assert!(frame.iter().all(|s| s.abs() <= PRE_GAIN_CEILING));
+        assert!((frame[0] - PRE_GAIN_CEILING).abs() < 1e-6);
+        assert!((frame[1] + 0.5).abs() < 1e-6, "Waveform shape is preserved");
+    }
+
+    #[test]
+    fn test_mix_blends_linearly() {
+        let dry = vec![1.0, -1.0];
+
+        let mut fully_wet = vec![0.0, 0.0];
+        apply_dry_wet_mix(&dry, &mut fully_wet, 1.0);
+        assert_eq!(fully_wet, vec![0.0, 0.0]);
+
+        let mut half = vec![0.0, 0.0];
+        apply_dry_wet_mix(&dry, &mut half, 0.5);
+        assert!((half[0] - 0.5).abs() < 1e-6);
+        assert!((half[1] + 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_delayed_dry_mix_keeps_tone_level() {
+        // 150Hz: a 480-sample frame is one and a half periods, so a frame of misalignment inverts it
+        let tone = |frame: usize| -> Vec<f32> {
+            (0..480).map(|i| (2.0 * std::f32::consts::PI * 150.0 * (frame * 480 + i) as f32 / 48000.0).sin() * 0.5).collect()
+        };
+        let rms = |frame: &[f32]| (frame.iter().map(|&s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+
+        let mut dry_delay = DryDelay::new(480);
+        let mut aligned = Vec::new();
+        let mut misaligned = Vec::new();
+        for frame in 0..10 {
+            // A perfect denoiser that, like RNNoise, returns the previous frame
+            let wet = if frame == 0 { vec![0.0; 480] } else { tone(frame - 1) };
+            let dry = tone(frame);
+
+            let mut mixed = wet.clone();
+            apply_dry_wet_mix(dry_delay.push(&dry), &mut mixed, 0.5);
+            aligned = mixed;
+
+            let mut mixed = wet;
+            apply_dry_wet_mix(&dry, &mut mixed, 0.5);
+            misaligned = mixed;
+        }
+
+        let tone_rms = rms(&tone(0));
+        assert!((rms(&aligned) - tone_rms).abs() < 1e-3, "aligned mix keeps the tone at full level");
+        assert!(rms(&misaligned) < tone_rms * 0.01, "the undelayed input cancels the tone");
+    }
+
+    #[test]
+    fn test_passthrough_frames_stay_continuous() {
+        // A ramp makes any repeated or dropped sample visible
+        let frame = |index: usize| -> Vec<f32> { (0..480).map(|i| (index * 480 + i) as f32).collect() };
+        // Like RNNoise, the denoiser returns the previous frame it was fed
+        let mut denoiser_state = vec![0.0f32; 480];
+        let mut denoise = |output: &mut [f32], input: &[f32]| {
+            output.copy_from_slice(&denoiser_state);
+            denoiser_state.copy_from_slice(input);
+        };
+
+        let mut dry_delay = DryDelay::new(480);
+        let mut catch_up = DenoiserCatchUp::new(480);
+        let mut output = Vec::new();
+        for index in 0..8 {
+            let input = frame(index);
+            let aligned_dry = dry_delay.push(&input);
+            let mut frame_output = vec![0.0; 480];
+            // A lone skipped frame, then a run of bypassed ones
+            if index == 2 || (4..6).contains(&index) {
+                frame_output.copy_from_slice(aligned_dry);
+                catch_up.missed(&input);
+            } else {
+                catch_up.catch_up(&mut denoise);
+                denoise(&mut frame_output, &input);
+            }
+            output.extend(frame_output);
+        }
+
+        // One frame late, with no sample repeated or dropped
+        let expected: Vec<f32> = (0..7).flat_map(frame).collect();
+        assert_eq!(output[480..], expected[..]);
+    }
+}
@@ -0,0 +1,106 @@
+//! # Idle Bypass Module
+//!
+//! During long quiet stretches (muted in a meeting, away from the desk) RNNoise
+//! keeps running on every frame even though there is nothing to clean up. With
+//! an idle timeout configured, the processing thread stops running the
+//! denoiser once no speech has been detected for that long. Bypassed frames
+//! still go through the noise gate, so the output sounds the same as gated
+//! background noise.
+//!
+//! While bypassed, the analyzer VAD (much cheaper than RNNoise) watches for
+//! speech; the first speech frame re-engages the denoiser, and the onset
+//! protection keeps the first syllable from being gated while RNNoise's VAD
+//! catches up. If the analyzer VAD can't be created, idle bypass stays off.
+//!
+//! Bypassed frames are delayed by one frame like RNNoise's output, so entering
+//! and leaving the bypass neither repeats nor drops audio.
+
+/// Longest configurable idle timeout (seconds)
+pub const MAX_IDLE_BYPASS_SECS: u32 = 600;
+
+/// Frames per second of 480-sample frames at 48kHz
+const FRAMES_PER_SECOND: u64 = 100;
+
+/// Tracks frames since the last speech and decides when to bypass the denoiser
+#[derive(Debug, Clone)]
+pub struct IdleBypass {
+    /// Frames without speech before bypassing (0 = never bypass)
+    timeout_frames: u64,
+    frames_since_speech: u64,
+    bypassed: bool,
+}
+
+impl IdleBypass {
+    /// Create a tracker bypassing after `timeout_secs` without speech (0 only tracks)
+    pub fn new(timeout_secs: u32) -> Self {
+        Self {
+            timeout_frames: timeout_secs.min(MAX_IDLE_BYPASS_SECS) as u64 * FRAMES_PER_SECOND,
+            frames_since_speech: 0,
+            bypassed: false,
+        }
+    }
+
+    /// Record whether the latest frame contained speech
+    ///
+    /// Returns `true` if this frame started a bypass.
+    pub fn update(&mut self, speech: bool) -> bool {
+        if speech {
+            self.frames_since_speech = 0;
+            self.bypassed = false;
+            return false;
+        }
+        self.frames_since_speech = self.frames_since_speech.saturating_add(1);
+        let start = self.timeout_frames > 0 && !self.bypassed && self.frames_since_speech >= self.timeout_frames;
+        self.bypassed |= start;
+        start
+    }
+
+    /// Leave the bypass because speech was detected before the denoiser ran
+    pub fn wake(&mut self) {
+        self.frames_since_speech = 0;
+        self.bypassed = false;
+    }
+
+    /// Whether the denoiser is currently bypassed
+    pub fn is_bypassed(&self) -> bool {
+        self.bypassed
+    }
+
+    /// Frames processed since the last speech frame
+    pub fn frames_since_speech(&self) -> u64 {
+        self.frames_since_speech
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bypasses_after_timeout_and_wakes_on_speech() {
+        let mut idle = IdleBypass::new(2);
+        assert!(!idle.update(true));
+
+        // Just under two seconds of silence keeps the denoiser running
+        for _ in 0..199 {
+            assert!(!idle.update(false));
+        }
+        assert!(!idle.is_bypassed());
+        assert!(idle.update(false), "The 200th silent frame starts the bypass");
+        assert!(!idle.update(false), "Only the first bypassed frame reports the start");
+        assert!(idle.is_bypassed());
+        assert_eq!(idle.frames_since_speech(), 201);
+
+        idle.wake();
+        assert!(!idle.is_bypassed());
+        assert_eq!(idle.frames_since_speech(), 0);
+
+        // A timeout of 0 only counts
+        let mut tracking = IdleBypass::new(0);
+        for _ in 0..100_000 {
+            tracking.update(false);
+        }
+        assert!(!tracking.is_bypassed());
+        assert_eq!(tracking.frames_since_speech(), 100_000);
+    }
+}
@@ -0,0 +1,282 @@
+//! # Latency Preset Module
+//!
+//! RNNoise always works on 480-sample (10ms) frames, so perceived latency is decided
+//! by how much audio is allowed to queue up between the threads rather than by the
+//! frame size. This module bundles those buffering knobs into a few understandable
+//! presets.
+//!
+//! ## Knobs
+//!
+//! - **Channel capacity**: Slots in the input and processed-audio channels
+//! - **Jitter depth**: Frames the output thread buffers before (re)starting playback
+//! - **Overflow policy**: Which frame is dropped when the processed channel is full
+//!
+//! ## Presets
+//!
+//! | Preset   | Capacity | Jitter | Overflow     |
+//! |----------|----------|--------|--------------|
+//! | UltraLow | 2        | 0      | Drop oldest  |
+//! | Low      | 3        | 0      | Drop oldest  |
+//! | Balanced | 4        | 0      | Drop newest  |
+//! | Stable   | 8        | 3      | Drop newest  |
+//!
+//! `Balanced` is the default and matches the historical pipeline behaviour.
+//! Power users can override individual knobs in the configuration file.
+//!
+//! ## Measuring It
+//!
+//! Audio travels between the threads as `AudioFrame`s stamped with their
+//! capture time. The processing thread carries the stamp of each frame's oldest
+//! sample through framing (`CaptureTimestamps`), and the output callback turns
+//! it into the end-to-end latency reported in the AI metrics.
+
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// What to do with a processed frame when the output channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// Drop the incoming frame and keep what is already queued (lowest CPU, more latency)
+    DropNewest,
+    /// Discard the oldest queued frame to make room (keeps latency bounded)
+    DropOldest,
+}
+
+/// User-facing latency/stability trade-off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LatencyPreset {
+    /// Smallest buffers; may drop audio on busy systems
+    UltraLow,
+    /// Small buffers with bounded latency
+    Low,
+    /// Default buffering (historical behaviour)
+    #[default]
+    Balanced,
+    /// Deep buffers for systems with scheduling hiccups
+    Stable,
+}
+
+impl LatencyPreset {
+    /// All presets in display order
+    pub const ALL: [LatencyPreset; 4] = [
+        LatencyPreset::UltraLow,
+        LatencyPreset::Low,
+        LatencyPreset::Balanced,
+        LatencyPreset::Stable,
+    ];
+
+    /// Human readable preset name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LatencyPreset::UltraLow => "Ultra Low",
+            LatencyPreset::Low => "Low",
+            LatencyPreset::Balanced => "Balanced",
+            LatencyPreset::Stable => "Stable",
+        }
+    }
+
+    /// Short description for tooltips
+    pub fn description(&self) -> &'static str {
+        match self {
+            LatencyPreset::UltraLow => "Lowest delay, may crackle on busy systems",
+            LatencyPreset::Low => "Low delay with bounded buffering",
+            LatencyPreset::Balanced => "Recommended balance of delay and stability",
+            LatencyPreset::Stable => "Extra buffering to avoid dropouts",
+        }
+    }
+
+    /// Buffering settings bundled by this preset
+    pub fn settings(&self) -> LatencySettings {
+        match self {
+            LatencyPreset::UltraLow => LatencySettings {
+                channel_capacity: 2,
+                jitter_frames: 0,
+                overflow_policy: OverflowPolicy::DropOldest,
+            },
+            LatencyPreset::Low => LatencySettings {
+                channel_capacity: 3,
+                jitter_frames: 0,
+                overflow_policy: OverflowPolicy::DropOldest,
+            },
+            LatencyPreset::Balanced => LatencySettings {
+                channel_capacity: 4,
+                jitter_frames: 0,
+                overflow_policy: OverflowPolicy::DropNewest,
+            },
+            LatencyPreset::Stable => LatencySettings {
+                channel_capacity: 8,
+                jitter_frames: 3,
+                overflow_policy: OverflowPolicy::DropNewest,
+            },
+        }
+    }
+}
+
+/// Optional per-knob overrides layered on top of a preset
+///
+/// Not exposed in the GUI; intended for power users editing `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyOverrides {
+    /// Override for the inter-thread channel capacity (slots)
+    pub channel_capacity: Option<usize>,
+    /// Override for the output jitter buffer depth (frames)
+    pub jitter_frames: Option<usize>,
+    /// Override for the processed-channel overflow policy
+    pub overflow_policy: Option<OverflowPolicy>,
+}
+
+/// Resolved buffering settings used by the audio threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySettings {
+    /// Slots in the input and processed-audio channels (minimum 1)
+    pub channel_capacity: usize,
+    /// Frames buffered by the output thread before playback (re)starts
+    pub jitter_frames: usize,
+    /// Drop policy when the processed channel is full
+    pub overflow_policy: OverflowPolicy,
+}
+
+impl Default for LatencySettings {
+    fn default() -> Self {
+        LatencyPreset::default().settings()
+    }
+}
+
+impl LatencySettings {
+    /// Resolve a preset plus any user overrides into concrete settings
+    pub fn resolve(preset: LatencyPreset, overrides: &LatencyOverrides) -> Self {
+        let base = preset.settings();
+        Self {
+            channel_capacity: overrides.channel_capacity.unwrap_or(base.channel_capacity).max(1),
+            jitter_frames: overrides.jitter_frames.unwrap_or(base.jitter_frames),
+            overflow_policy: overrides.overflow_policy.unwrap_or(base.overflow_policy),
+        }
+    }
+}
+
+/// Audio passed between the pipeline threads
+#[derive(Debug, Clone)]
+pub struct AudioFrame {
+    /// 48kHz samples, interleaved for stereo
+    pub samples: Vec<f32>,
+    /// When the capture callback delivered the oldest sample
+    pub captured_at: Instant,
+}
+
+impl AudioFrame {
+    pub fn new(samples: Vec<f32>, captured_at: Instant) -> Self {
+        Self { samples, captured_at }
+    }
+}
+
+/// Capture times of the samples waiting to be cut into processing frames
+///
+/// Capture callbacks rarely deliver exactly one frame, so a frame can span
+/// several captured blocks. Each block's stamp is kept until all of its samples
+/// have been taken, and a frame is stamped with its oldest block.
+#[derive(Debug, Default)]
+pub struct CaptureTimestamps {
+    /// Remaining sample count and capture time of each buffered block, oldest first
+    blocks: VecDeque<(usize, Instant)>,
+}
+
+impl CaptureTimestamps {
+    /// Note `len` newly buffered samples captured at `captured_at`
+    pub fn push(&mut self, len: usize, captured_at: Instant) {
+        if len > 0 {
+            self.blocks.push_back((len, captured_at));
+        }
+    }
+
+    /// Consume the next `len` samples, returning the capture time of the oldest
+    pub fn take(&mut self, mut len: usize) -> Option<Instant> {
+        let oldest = self.blocks.front().map(|&(_, captured_at)| captured_at);
+        while len > 0 {
+            let Some(block) = self.blocks.front_mut() else { break };
+            let taken = block.0.min(len);
+            block.0 -= taken;
+            len -= taken;
+            if block.0 == 0 {
+                self.blocks.pop_front();
+            }
+        }
+        oldest
+    }
+}
+
+/// Send a frame without blocking, applying the overflow policy if the channel is full
+///
+/// `drain` must be a receiver for the same channel; it is used to discard the
+/// oldest queued frame under `OverflowPolicy::DropOldest`. Returns whether the
+/// frame was queued.
+pub fn send_with_policy<T>(sender: &Sender<T>, drain: &Receiver<T>, item: T, policy: OverflowPolicy) -> bool {
+    match sender.try_send(item) {
+        Ok(()) => true,
+        Err(TrySendError::Full(item)) => match policy {
+            OverflowPolicy::DropNewest => false,
+            OverflowPolicy::DropOldest => {
+                let _ = drain.try_recv();
+                sender.try_send(item).is_ok()
+            }
+        },
+        Err(TrySendError::Disconnected(_)) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::bounded;
+
+    #[test]
+    fn test_balanced_matches_historical_pipeline() {
+        let settings = LatencySettings::default();
+        assert_eq!(settings.channel_capacity, 4);
+        assert_eq!(settings.jitter_frames, 0);
+        assert_eq!(settings.overflow_policy, OverflowPolicy::DropNewest);
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let overrides = LatencyOverrides {
+            channel_capacity: Some(0),
+            jitter_frames: Some(5),
+            overflow_policy: None,
+        };
+        let settings = LatencySettings::resolve(LatencyPreset::UltraLow, &overrides);
+        assert_eq!(settings.channel_capacity, 1, "Capacity must stay at least 1");
+        assert_eq!(settings.jitter_frames, 5);
+        assert_eq!(settings.overflow_policy, OverflowPolicy::DropOldest);
+    }
+
+    #[test]
+    fn test_send_with_policy() {
+        let (tx, rx) = bounded::<u32>(2);
+        assert!(send_with_policy(&tx, &rx, 1, OverflowPolicy::DropNewest));
+        assert!(send_with_policy(&tx, &rx, 2, OverflowPolicy::DropNewest));
+        assert!(!send_with_policy(&tx, &rx, 3, OverflowPolicy::DropNewest));
+        assert!(send_with_policy(&tx, &rx, 4, OverflowPolicy::DropOldest));
+
+        let queued: Vec<u32> = rx.try_iter().collect();
+        assert_eq!(queued, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_frames_take_oldest_capture_time() {
+        let start = Instant::now();
+        let at = |ms| start + std::time::Duration::from_millis(ms);
+        let mut timestamps = CaptureTimestamps::default();
+
+        // 441-sample callbacks cut into 480-sample frames
+        timestamps.push(441, at(0));
+        timestamps.push(441, at(9));
+        assert_eq!(timestamps.take(480), Some(at(0)));
+        timestamps.push(441, at(18));
+        assert_eq!(timestamps.take(480), Some(at(9)));
+        // 363 samples of the third block remain
+        assert_eq!(timestamps.take(363), Some(at(18)));
+        assert_eq!(timestamps.take(480), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! # Round-Trip Latency Probe
+//!
+//! The latency figures shown elsewhere are estimates built from buffer sizes. This
+//! module measures the real round trip instead: the output thread plays a short
+//! impulse and the capture thread looks for it coming back through a loopback path
+//! (speakers into the microphone, or a virtual cable routed back to the input).
+//!
+//! ## Measurement Sequence
+//!
+//! 1. The GUI calls `request()` (development mode only)
+//! 2. The output callback bypasses processed audio, plays the impulse on its first
+//!    sample and timestamps it
+//! 3. Output stays silent while the capture callback cross-correlates incoming
+//!    audio against the impulse template
+//! 4. The delay between emission and detection is published as the result
+//!
+//! Timestamps are taken in the stream callbacks and corrected by the sample offset
+//! of the impulse within each buffer, so the result includes device buffering on
+//! both sides - which is exactly the delay a listener experiences.
+
+use crate::logger::log;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+/// Impulse length in samples
+pub const IMPULSE_LEN: usize = 64;
+
+/// Give up if the impulse hasn't come back within this time
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Minimum normalized cross-correlation accepted as a detection
+pub const DETECTION_THRESHOLD: f32 = 0.6;
+
+const STATE_IDLE: u8 = 0;
+const STATE_REQUESTED: u8 = 1;
+const STATE_EMITTED: u8 = 2;
+const STATE_MEASURED: u8 = 3;
+const STATE_TIMED_OUT: u8 = 4;
+
+/// Observable state of a latency measurement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProbeStatus {
+    /// No measurement has been requested
+    Idle,
+    /// Impulse queued or in flight
+    Measuring,
+    /// Round-trip delay in milliseconds
+    Measured(f32),
+    /// The impulse never came back (no loopback path?)
+    TimedOut,
+}
+
+/// Shared state between the GUI, output callback and capture callback
+#[derive(Debug)]
+pub struct LatencyProbe {
+    state: AtomicU8,
+    epoch: Instant,
+    /// Emission time in nanoseconds since `epoch`
+    emitted_at_ns: AtomicU64,
+    /// Measured round trip in milliseconds (f32 bits)
+    result_ms: AtomicU64,
+}
+
+impl Default for LatencyProbe {
+    fn default() -> Self {
+        Self {
+            state: AtomicU8::new(STATE_IDLE),
+            epoch: Instant::now(),
+            emitted_at_ns: AtomicU64::new(0),
+            result_ms: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LatencyProbe {
+    /// Ask the output thread to play an impulse
+    pub fn request(&self) {
+        self.state.store(STATE_REQUESTED, Ordering::Release);
+    }
+
+    /// Current measurement status
+    pub fn status(&self) -> ProbeStatus {
+        match self.state.load(Ordering::Acquire) {
+            STATE_REQUESTED | STATE_EMITTED => ProbeStatus::Measuring,
+            STATE_MEASURED => ProbeStatus::Measured(f32::from_bits(self.result_ms.load(Ordering::Relaxed) as u32)),
+            STATE_TIMED_OUT => ProbeStatus::TimedOut,
+            _ => ProbeStatus::Idle,
+        }
+    }
+
+    /// Whether the output should bypass processed audio (measurement in flight)
+    pub fn is_active(&self) -> bool {
+        matches!(self.state.load(Ordering::Acquire), STATE_REQUESTED | STATE_EMITTED)
+    }
+
+    /// Whether the capture callback should look for the impulse
+    pub fn awaiting_detection(&self) -> bool {
+        self.state.load(Ordering::Acquire) == STATE_EMITTED
+    }
+
+    /// Output callback hook: write the impulse if one was requested
+    ///
+    /// `data` is the interleaved device buffer with `channels` channels. While a
+    /// measurement is active the buffer is silenced so processed audio can't mask
+    /// the returning impulse. Returns whether the buffer was taken over.
+    pub fn fill_output(&self, data: &mut [f32], channels: usize) -> bool {
+        match self.state.load(Ordering::Acquire) {
+            STATE_REQUESTED => {
+                data.fill(0.0);
+                for (frame, &sample) in data.chunks_mut(channels.max(1)).zip(impulse_template().iter()) {
+                    frame.fill(sample);
+                }
+                self.emitted_at_ns.store(self.elapsed_ns(Instant::now()), Ordering::Relaxed);
+                self.state.store(STATE_EMITTED, Ordering::Release);
+                true
+            }
+            STATE_EMITTED => {
+                data.fill(0.0);
+                let emitted = Duration::from_nanos(self.emitted_at_ns.load(Ordering::Relaxed));
+                if self.epoch.elapsed().saturating_sub(emitted) > PROBE_TIMEOUT {
+                    log::warn!("⏱ Latency probe timed out - is there a loopback path from output to input?");
+                    self.state.store(STATE_TIMED_OUT, Ordering::Release);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Capture callback hook: report a detection at `offset` samples into a buffer
+    ///
+    /// `buffer_len` is the length of the searched window (mono samples) and the
+    /// callback is assumed to fire as the window's last sample arrives.
+    pub fn record_detection(&self, now: Instant, offset: usize, buffer_len: usize, sample_rate: u32) {
+        let samples_ago = buffer_len.saturating_sub(offset) as f64;
+        let detected_ns = self.elapsed_ns(now).saturating_sub((samples_ago / sample_rate as f64 * 1e9) as u64);
+        let emitted_ns = self.emitted_at_ns.load(Ordering::Relaxed);
+        let round_trip_ms = detected_ns.saturating_sub(emitted_ns) as f32 / 1_000_000.0;
+
+        self.result_ms.store(round_trip_ms.to_bits() as u64, Ordering::Relaxed);
+        self.state.store(STATE_MEASURED, Ordering::Release);
+        log::info!("⏱ Measured round-trip latency: {:.1}ms", round_trip_ms);
+    }
+
+    fn elapsed_ns(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.epoch).as_nanos() as u64
+    }
+}
+
+/// The probe impulse: a Hann-windowed 1-10 kHz chirp
+///
+/// A short sweep survives speaker/microphone band-limiting far better than a
+/// single-sample click, and unlike a pure tone its autocorrelation has a single
+/// sharp peak, so the detected offset is unambiguous.
+pub fn impulse_template() -> [f32; IMPULSE_LEN] {
+    const START_HZ: f32 = 1000.0;
+    const END_HZ: f32 = 10000.0;
+    let duration = IMPULSE_LEN as f32 / 48000.0;
+
+    let mut template = [0.0f32; IMPULSE_LEN];
+    for (i, sample) in template.iter_mut().enumerate() {
+        let t = i as f32 / 48000.0;
+        let window = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (IMPULSE_LEN - 1) as f32).cos();
+        let phase = 2.0 * std::f32::consts::PI * (START_HZ * t + (END_HZ - START_HZ) * t * t / (2.0 * duration));
+        *sample = 0.8 * window * phase.sin();
+    }
+    template
+}
+
+/// Find the impulse in `samples` by normalized cross-correlation
+///
+/// Returns the offset of the best match if it correlates above `threshold`.
+pub fn detect_impulse(samples: &[f32], template: &[f32], threshold: f32) -> Option<usize> {
+    if template.is_empty() || samples.len() < template.len() {
+        return None;
+    }
+
+    let template_energy: f32 = template.iter().map(|t| t * t).sum();
+    let mut best: Option<(usize, f32)> = None;
+
+    for offset in 0..=(samples.len() - template.len()) {
+        let window = &samples[offset..offset + template.len()];
+        let window_energy: f32 = window.iter().map(|s| s * s).sum();
+        if window_energy <= f32::EPSILON {
+            continue;
+        }
+
+        let dot: f32 = window.iter().zip(template).map(|(s, t)| s * t).sum();
+        let correlation = dot / (window_energy * template_energy).sqrt();
+        if correlation >= threshold && !matches!(best, Some((_, c)) if c >= correlation) {
+            best = Some((offset, correlation));
+        }
+    }
+
+    best.map(|(offset, _)| offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn noise(len: usize, level: f32) -> Vec<f32> {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..len).map(|_| rng.gen_range(-level..level)).collect()
+    }
+
+    #[test]
+    fn test_detects_attenuated_impulse_in_noise() {
+        let mut samples = noise(1024, 0.01);
+        for (i, t) in impulse_template().iter().enumerate() {
+            samples[300 + i] += t * 0.3;
+        }
+
+        assert_eq!(detect_impulse(&samples, &impulse_template(), DETECTION_THRESHOLD), Some(300));
+    }
+
+    #[test]
+    fn test_noise_alone_is_not_detected() {
+        let samples = noise(4800, 0.2);
+        assert_eq!(detect_impulse(&samples, &impulse_template(), DETECTION_THRESHOLD), None);
+    }
+
+    #[test]
+    fn test_output_bypass_sequence() {
+        let probe = LatencyProbe::default();
+        let mut buffer = vec![0.5f32; 256];
+        assert!(!probe.fill_output(&mut buffer, 2), "Idle probe must leave output alone");
+
+        probe.request();
+        assert!(probe.fill_output(&mut buffer, 2));
+        assert!(probe.awaiting_detection());
+        assert_eq!(buffer[0], buffer[1], "Impulse is duplicated to every channel");
+        assert_eq!(buffer[200], 0.0, "Processed audio is bypassed during measurement");
+
+        probe.record_detection(Instant::now(), 0, 0, 48000);
+        assert!(matches!(probe.status(), ProbeStatus::Measured(ms) if ms >= 0.0));
+    }
+}
@@ -0,0 +1,244 @@
+//! # Level Meter Module
+//!
+//! Turns the raw sample stream at the capture and output taps into a readable
+//! level. A bare per-callback peak flickers too much to judge levels by, so the
+//! meter applies one of a few common ballistics with a configurable integration
+//! time:
+//!
+//! - **Peak**: instantaneous block peak, no smoothing
+//! - **Peak with decay** (default): jumps to new peaks, then falls 20 dB per
+//!   integration time, like most DAW and OS meters
+//! - **RMS**: exponentially averaged power, tracks loudness rather than peaks
+//! - **VU**: averaged rectified signal scaled to read RMS on a sine, with the
+//!   same rise and fall time (classic VU is 300 ms)
+//!
+//! The callbacks update the meter under `try_lock` and publish the level as an
+//! atomic, so neither side ever waits on the other.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+/// Default integration time of the meter ballistics (ms)
+pub const DEFAULT_METER_INTEGRATION_MS: u32 = 300;
+/// Shortest configurable integration time (ms)
+pub const MIN_METER_INTEGRATION_MS: u32 = 10;
+/// Longest configurable integration time (ms)
+pub const MAX_METER_INTEGRATION_MS: u32 = 3000;
+
+/// Level reported for silence (dBFS)
+pub const METER_FLOOR_DB: f32 = -100.0;
+
+/// Average-to-RMS ratio of a sine wave, so the VU reading matches RMS on tones
+const VU_SINE_SCALE: f32 = std::f32::consts::PI / (2.0 * std::f32::consts::SQRT_2);
+
+/// How the meter turns samples into a displayed level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MeterBallistics {
+    /// Instantaneous peak of each block
+    Peak,
+    /// Peak hold that decays 20 dB per integration time (default)
+    #[default]
+    PeakDecay,
+    /// Exponentially averaged RMS
+    Rms,
+    /// VU-style averaged level with symmetric rise and fall
+    Vu,
+}
+
+impl MeterBallistics {
+    /// All ballistics, in display order
+    pub const ALL: [MeterBallistics; 4] = [
+        MeterBallistics::Peak,
+        MeterBallistics::PeakDecay,
+        MeterBallistics::Rms,
+        MeterBallistics::Vu,
+    ];
+
+    /// Human readable name for the settings UI
+    pub fn label(&self) -> &'static str {
+        match self {
+            MeterBallistics::Peak => "Peak",
+            MeterBallistics::PeakDecay => "Peak with decay",
+            MeterBallistics::Rms => "RMS",
+            MeterBallistics::Vu => "VU",
+        }
+    }
+}
+
+/// Stateful level meter with configurable ballistics
+#[derive(Debug, Clone)]
+pub struct LevelMeter {
+    ballistics: MeterBallistics,
+    integration_ms: u32,
+    /// Linear level (amplitude) for the peak modes and VU, mean square for RMS
+    state: f32,
+}
+
+impl Default for LevelMeter {
+    fn default() -> Self {
+        Self::new(MeterBallistics::default(), DEFAULT_METER_INTEGRATION_MS)
+    }
+}
+
+impl LevelMeter {
+    pub fn new(ballistics: MeterBallistics, integration_ms: u32) -> Self {
+        Self {
+            ballistics,
+            integration_ms: integration_ms.clamp(MIN_METER_INTEGRATION_MS, MAX_METER_INTEGRATION_MS),
+            state: 0.0,
+        }
+    }
+
+    /// Change the ballistics, keeping the current reading when possible
+    pub fn configure(&mut self, ballistics: MeterBallistics, integration_ms: u32) {
+        if ballistics != self.ballistics {
+            self.state = 0.0;
+        }
+        self.ballistics = ballistics;
+        self.integration_ms = integration_ms.clamp(MIN_METER_INTEGRATION_MS, MAX_METER_INTEGRATION_MS);
+    }
+
+    /// Feed one block of samples recorded at `sample_rate`
+    pub fn process(&mut self, block: &[f32], sample_rate: u32) {
+        if block.is_empty() {
+            return;
+        }
+        // Integration time expressed in samples
+        let window = self.integration_ms as f32 / 1000.0 * sample_rate.max(1) as f32;
+        let n = block.len() as f32;
+
+        match self.ballistics {
+            MeterBallistics::Peak => {
+                self.state = block_peak(block);
+            }
+            MeterBallistics::PeakDecay => {
+                // Falls by a factor of 10 (20 dB) over one integration window
+                let decay = 10f32.powf(-n / window);
+                self.state = block_peak(block).max(self.state * decay);
+            }
+            MeterBallistics::Rms => {
+                let mean_square = block.iter().map(|s| s * s).sum::<f32>() / n;
+                self.state += smoothing(n, window) * (mean_square - self.state);
+            }
+            MeterBallistics::Vu => {
+                let mean_abs = block.iter().map(|s| s.abs()).sum::<f32>() / n;
+                // Reaches 99% of a step within the integration time
+                self.state += smoothing(n, window / 100f32.ln()) * (mean_abs - self.state);
+            }
+        }
+    }
+
+    /// Current level as linear amplitude
+    pub fn level(&self) -> f32 {
+        match self.ballistics {
+            MeterBallistics::Rms => self.state.max(0.0).sqrt(),
+            MeterBallistics::Vu => self.state * VU_SINE_SCALE,
+            _ => self.state,
+        }
+    }
+
+    /// Current level in dBFS (`METER_FLOOR_DB` for silence)
+    pub fn level_db(&self) -> f32 {
+        linear_to_db(self.level())
+    }
+}
+
+/// Meter shared between an audio callback and the GUI
+#[derive(Debug)]
+pub struct LevelTap {
+    meter: Mutex<LevelMeter>,
+    /// Latest level in dBFS as f32 bits
+    level_db: AtomicU32,
+}
+
+impl Default for LevelTap {
+    fn default() -> Self {
+        Self {
+            meter: Mutex::new(LevelMeter::default()),
+            level_db: AtomicU32::new(METER_FLOOR_DB.to_bits()),
+        }
+    }
+}
+
+impl LevelTap {
+    /// Update the meter from a callback; skips the block if the GUI holds the lock
+    pub fn record(&self, block: &[f32], sample_rate: u32) {
+        if let Ok(mut meter) = self.meter.try_lock() {
+            meter.process(block, sample_rate);
+            self.level_db.store(meter.level_db().to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Latest level in dBFS
+    pub fn level_db(&self) -> f32 {
+        f32::from_bits(self.level_db.load(Ordering::Relaxed))
+    }
+
+    /// Change the ballistics used from the next block
+    pub fn configure(&self, ballistics: MeterBallistics, integration_ms: u32) {
+        if let Ok(mut meter) = self.meter.lock() {
+            meter.configure(ballistics, integration_ms);
+        }
+    }
+}
+
+fn block_peak(block: &[f32]) -> f32 {
+    block.iter().fold(0.0f32, |peak, s| peak.max(s.abs()))
+}
+
+/// One-pole smoothing coefficient for a block of `n` samples and time constant `tau` (samples)
+fn smoothing(n: f32, tau: f32) -> f32 {
+    1.0 - (-n / tau.max(1.0)).exp()
+}
+
+fn linear_to_db(level: f32) -> f32 {
+    if level <= 0.0 {
+        METER_FLOOR_DB
+    } else {
+        (20.0 * level.log10()).max(METER_FLOOR_DB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine_block(amplitude: f32, start: usize, len: usize) -> Vec<f32> {
+        (start..start + len)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / 48000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_ballistics_settle_on_a_sine() {
+        // Half-scale 1kHz sine: peak -6 dBFS, RMS -9 dBFS
+        for (ballistics, expected_db) in [
+            (MeterBallistics::Peak, -6.0),
+            (MeterBallistics::PeakDecay, -6.0),
+            (MeterBallistics::Rms, -9.0),
+            (MeterBallistics::Vu, -9.0),
+        ] {
+            let mut meter = LevelMeter::new(ballistics, 300);
+            for frame in 0..300 {
+                meter.process(&sine_block(0.5, frame * 480, 480), 48000);
+            }
+            assert!((meter.level_db() - expected_db).abs() < 0.3,
+                    "{:?} read {:.2} dBFS, expected {:.1}", ballistics, meter.level_db(), expected_db);
+        }
+    }
+
+    #[test]
+    fn test_peak_decay_falls_20db_per_integration_time() {
+        let mut meter = LevelMeter::new(MeterBallistics::PeakDecay, 100);
+        meter.process(&[1.0], 48000);
+        // 100 ms of silence at 48 kHz
+        for _ in 0..10 {
+            meter.process(&[0.0; 480], 48000);
+        }
+        assert!((meter.level_db() + 20.0).abs() < 0.5, "Read {:.2} dBFS", meter.level_db());
+
+        let tap = LevelTap::default();
+        assert_eq!(tap.level_db(), METER_FLOOR_DB);
+    }
+}
@@ -50,12 +50,31 @@ pub mod models;     // Enhanced AI model support with multiple algorithms
 pub mod analysis;   // Advanced audio analysis with VAD and spectral analysis
 pub mod pipeline;   // Multi-stage AI noise suppression pipeline
 pub mod resampling; // Audio resampling and frame adaptation utilities
+pub mod gain;       // Gain staging and dry/wet mixing helpers
+pub mod latency;    // Latency presets (channel depth, jitter buffer, overflow policy)
+pub mod test_signal; // Synthesized test inputs (noise generators, WAV loop) for development
+pub mod affinity;   // Optional CPU core pinning for the processing thread
+pub mod latency_probe; // Round-trip latency measurement via a loopback impulse
+pub mod music;      // Automatic passthrough while music is detected
+pub mod watchdog;   // Rebuilds the pipeline when frames stop flowing
+pub mod parallel;   // Optional multi-threaded per-channel processing
+pub mod replay;     // Rolling buffer of processed output for "instant replay" clips
+pub mod recorder;   // Streams the processed output to a WAV file on a writer thread
+pub mod filters;    // Stateful input filters (DC blocking) applied at capture
+pub mod deadline;   // Optional per-frame processing budget that skips the denoiser when exceeded
+pub mod meter;      // Level meter ballistics (peak, RMS, VU) for the input/output taps
+pub mod sidechain;  // Output ducking keyed off an external sidechain level
+pub mod idle;       // Bypass the denoiser after prolonged silence
+pub mod stereo;     // Independent per-channel denoising for stereo inputs
+pub mod adaptive;   // Auto sensitivity derived from the measured noise floor
+pub mod comfort_noise; // Faint pink noise filling the closed gate
 
 // External dependencies for audio processing
 use std::sync::Arc;
 use crate::logger::log;
 use crate::ai_metrics::{SharedAiMetrics, create_shared_metrics};
-use crate::audio::models::NoiseModel;
+use crate::config::KwiteConfig;
+use crate::audio::models::{ModelStatistics, NoiseModel};
 #[cfg(feature = "ai-enhanced")]
 use crate::audio::models::EnhancedAudioProcessor;
 #[cfg(feature = "ai-enhanced")]
@@ -63,7 +82,6 @@ use crate::audio::analysis::AudioAnalyzer;
 use crossbeam_channel::bounded;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::thread;
-#[cfg(feature = "ai-enhanced")]
 use std::sync::Mutex;
 
 /// Global flag for maximum test mode - can be toggled from GUI
@@ -125,6 +143,71 @@ pub fn is_pipeline_verification_mode_enabled() -> bool {
     PIPELINE_VERIFICATION_MODE.load(Ordering::Relaxed)
 }
 
+/// Bundled states of the diagnostic toggles for guided support sessions
+/// 
+/// Support staff can ask a user to pick one preset instead of flipping several
+/// individual switches, which keeps diagnostic sessions repeatable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticPreset {
+    /// Normal operation: no test tone, normal suppression
+    Off,
+    /// Verification tone only, to confirm audio routing without changing suppression
+    Minimal,
+    /// Maximum test mode plus verification tone
+    FullDiagnostics,
+}
+
+impl DiagnosticPreset {
+    /// All presets in display order
+    pub const ALL: [DiagnosticPreset; 3] = [
+        DiagnosticPreset::Off,
+        DiagnosticPreset::Minimal,
+        DiagnosticPreset::FullDiagnostics,
+    ];
+
+    /// Human readable preset name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticPreset::Off => "Off",
+            DiagnosticPreset::Minimal => "Minimal",
+            DiagnosticPreset::FullDiagnostics => "Full Diagnostics",
+        }
+    }
+
+    /// `(max_test_mode, pipeline_verification_mode)` for this preset
+    pub fn flags(&self) -> (bool, bool) {
+        match self {
+            DiagnosticPreset::Off => (false, false),
+            DiagnosticPreset::Minimal => (false, true),
+            DiagnosticPreset::FullDiagnostics => (true, true),
+        }
+    }
+}
+
+/// Apply a diagnostic preset to the global diagnostic toggles
+/// 
+/// Both flags are written before returning, so the processing thread picks up the
+/// complete preset on its next frame.
+pub fn apply_diagnostic_preset(preset: DiagnosticPreset) {
+    let (max_test, verification) = preset.flags();
+    log::info!("🩺 Applying diagnostic preset: {}", preset.name());
+    set_max_test_mode(max_test);
+    set_pipeline_verification_mode(verification);
+}
+
+/// Identify which preset matches the current toggles, if any
+pub fn current_diagnostic_preset() -> Option<DiagnosticPreset> {
+    let flags = (is_max_test_mode_enabled(), is_pipeline_verification_mode_enabled());
+    DiagnosticPreset::ALL.into_iter().find(|preset| preset.flags() == flags)
+}
+
+/// Total frames processed by all pipelines in this process
+/// 
+/// Used by the stream watchdog to detect a pipeline that has stopped flowing.
+pub fn diagnostic_frame_count() -> u64 {
+    DIAGNOSTIC_FRAME_COUNTER.load(Ordering::Relaxed)
+}
+
 /// Add comprehensive audio pipeline diagnostics
 /// This helps users determine exactly what's happening with their audio setup
 pub fn log_comprehensive_diagnostics() {
@@ -180,6 +263,292 @@ pub fn log_comprehensive_diagnostics() {
     log::warn!("=== END DIAGNOSTICS ===");
 }
 
+/// Lowest output gain trim accepted by the processing thread (dB)
+pub const MIN_OUTPUT_GAIN_DB: f32 = -24.0;
+
+/// Highest output gain trim accepted by the processing thread (dB)
+pub const MAX_OUTPUT_GAIN_DB: f32 = 12.0;
+
+/// Lowest input pre-gain accepted by the processing thread (dB)
+pub const MIN_INPUT_GAIN_DB: f32 = -12.0;
+
+/// Highest input pre-gain accepted by the processing thread (dB)
+pub const MAX_INPUT_GAIN_DB: f32 = 24.0;
+
+/// Lowest analyzer sensitivity (most aggressive voice detection)
+pub const MIN_SENSITIVITY: f32 = 0.01;
+
+/// Highest analyzer sensitivity (least aggressive voice detection)
+pub const MAX_SENSITIVITY: f32 = 0.5;
+
+/// Lowest accepted VAD threshold
+pub const MIN_VAD_THRESHOLD: f32 = 0.05;
+
+/// Highest accepted VAD threshold
+pub const MAX_VAD_THRESHOLD: f32 = 0.95;
+
+/// Default VAD score below which a frame is treated as background noise
+pub const DEFAULT_VAD_THRESHOLD: f32 = 0.5;
+
+/// Default gain applied to background noise frames (90% suppression)
+pub const DEFAULT_NOISE_GAIN: f32 = 0.1;
+
+/// Default gain applied to frames classified as speech
+pub const DEFAULT_SPEECH_GAIN: f32 = 0.8;
+
+/// Longest accepted denoiser warm-up (ms)
+pub const MAX_DENOISER_WARMUP_MS: u32 = 500;
+
+/// Largest processing block, in 10ms RNNoise frames (80ms)
+pub const MAX_PROCESSING_BLOCK_FRAMES: usize = 8;
+
+/// Default length of the optional startup Maximum Test Mode window (seconds)
+pub const DEFAULT_STARTUP_MAX_TEST_SECS: u32 = 10;
+
+/// Longest accepted startup Maximum Test Mode window (seconds)
+pub const MAX_STARTUP_MAX_TEST_SECS: u32 = 60;
+
+/// How long `AudioManager::drop` waits for its threads before detaching them
+pub const SHUTDOWN_JOIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Join `handle` if it finishes before `deadline`, otherwise detach it
+/// 
+/// `JoinHandle::join` has no timeout, so this polls `is_finished` instead.
+/// Returns whether the thread exited in time.
+fn join_with_deadline(handle: thread::JoinHandle<()>, name: &str, deadline: std::time::Instant) -> bool {
+    while !handle.is_finished() {
+        if std::time::Instant::now() >= deadline {
+            log::warn!("⚠️ {} thread did not exit within {:?} - detaching it", name, SHUTDOWN_JOIN_TIMEOUT);
+            return false;
+        }
+        thread::sleep(std::time::Duration::from_millis(10));
+    }
+    
+    if handle.join().is_err() {
+        log::warn!("⚠️ {} thread panicked during shutdown", name);
+    }
+    true
+}
+
+/// Convert a gain in decibels to a linear multiplier
+pub fn db_to_linear(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+/// Analyze a frame for the Geek Mode spectrum while the display is open
+/// 
+/// `frame` is `None` while the display is closed. The analyzer is created on
+/// first use and dropped again when the display closes, so no FFT runs while
+/// nobody is looking.
+fn update_spectrum(analyzer: &mut Option<analysis::SpectralAnalyzer>, frame: Option<&[f32]>) -> Option<Vec<f32>> {
+    let Some(frame) = frame else {
+        *analyzer = None;
+        return None;
+    };
+    let analyzer = analyzer.get_or_insert_with(|| analysis::SpectralAnalyzer::new(frame.len(), 48000.0));
+    analyzer.analyze(frame);
+    analyzer.display_bands()
+}
+
+/// Audio pipeline settings derived from the user configuration
+/// 
+/// Collects the configuration values the processing threads need at startup so
+/// `AudioManager::new` doesn't depend on the full application config.
+#[derive(Debug, Clone)]
+pub struct AudioSettings {
+    /// Voice activity smoothing window length in frames
+    pub vad_history_len: usize,
+    /// Whether a missing input device falls back to another one
+    pub device_fallback: devices::DeviceFallback,
+    /// Remove the input's DC offset with a 5Hz high-pass at capture
+    pub dc_filter: bool,
+    /// Zero-based channel of a multichannel input that carries the microphone
+    pub input_channel: usize,
+    /// What the output plays when processed audio runs out
+    pub underrun_fill: output::UnderrunFill,
+    /// Spectral analysis window in samples (a multiple of the 480-sample frame works best)
+    pub analysis_window: usize,
+    /// Output gain trim in decibels
+    pub output_gain_db: f32,
+    /// Input pre-gain in decibels applied before the denoiser
+    pub input_gain_db: f32,
+    /// Speech detection threshold for the denoiser VAD score
+    pub vad_threshold: f32,
+    /// Gain applied to background noise frames
+    pub noise_gain: f32,
+    /// Gain applied to speech frames
+    pub speech_gain: f32,
+    /// Minimum frame gain, keeping some room tone under heavy gating
+    pub gain_floor: f32,
+    /// Comfort noise level in dBFS mixed in while the gate is closed (`None` = off)
+    pub comfort_noise_db: Option<f32>,
+    /// Speech gain held after a VAD onset so the first syllable isn't clipped (ms, 0 = off)
+    pub onset_protection_ms: u32,
+    /// Time for the applied gain to ramp up towards a higher target (ms, 0 = instant)
+    pub gain_attack_ms: u32,
+    /// Time for the applied gain to ramp down towards a lower target (ms, 0 = instant)
+    pub gain_release_ms: u32,
+    /// Time the gate stays open after the VAD drops (ms, 0 = off)
+    pub gate_hold_ms: u32,
+    /// Voice activity detector that drives the gain decision
+    pub vad_source: analysis::VadSource,
+    /// Seconds of processed output kept for instant replay (0 = off)
+    pub replay_seconds: u32,
+    /// Per-frame denoiser budget in microseconds; slower frames pass through (0 = off)
+    pub frame_deadline_us: u32,
+    /// Crossover above which audio bypasses the denoiser in Hz (0 = full band)
+    pub crossover_hz: u32,
+    /// Ballistics of the input/output level meters
+    pub meter_ballistics: meter::MeterBallistics,
+    /// Integration time of the level meters in milliseconds
+    pub meter_integration_ms: u32,
+    /// VAD band around the threshold in which the previous speech/noise decision is held
+    pub vad_deadband: f32,
+    /// Dry/wet mix between raw input (0.0) and processed output (1.0)
+    pub mix: f32,
+    /// Inter-thread buffering resolved from the latency preset
+    pub latency: latency::LatencySettings,
+    /// Optional custom RNNoise model file (built-in weights are used if unset or invalid)
+    pub custom_model_path: Option<String>,
+    /// CPU core pinning for the processing thread
+    pub thread_affinity: affinity::ThreadAffinity,
+    /// End-to-end sample rate; RNNoise still runs at 48kHz with resampling around it
+    pub target_sample_rate: u32,
+    /// Channels carried through the pipeline: 1 (mono, default) or 2 (stereo)
+    pub channels: u16,
+    /// Per-channel gates for stereo processing (`None` = both follow the mono gate)
+    pub stereo_gates: Option<gain::StereoGates>,
+    /// Thread limit for per-channel processing (1 = single-threaded; mono always is)
+    pub max_processing_threads: usize,
+    /// Automatic passthrough while music is detected
+    pub music_passthrough: music::MusicPassthroughSettings,
+    /// Output ducking while an external sidechain signal is loud
+    pub ducking: sidechain::DuckingSettings,
+    /// Second output device that also plays the processed audio (`None` = no monitor)
+    pub monitor_device_id: Option<String>,
+    /// Seconds without speech before the denoiser is bypassed (0 = never)
+    pub idle_bypass_secs: u32,
+    /// Muted frames fed to the denoiser at start so its recurrent state can settle (ms)
+    pub denoiser_warmup_ms: u32,
+    /// Seconds of Maximum Test Mode gains after start (0 = normal settings from frame one)
+    pub startup_max_test_secs: u32,
+    /// RNNoise frames accumulated before the processing thread works through them (1 = every frame)
+    pub processing_block_frames: usize,
+    /// Derive the analyzer sensitivity from the measured noise floor instead of the slider
+    pub auto_sensitivity: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            vad_history_len: analysis::DEFAULT_VAD_HISTORY_LEN,
+            device_fallback: devices::DeviceFallback::default(),
+            dc_filter: true,
+            input_channel: 0,
+            underrun_fill: output::UnderrunFill::default(),
+            analysis_window: analysis::DEFAULT_ANALYSIS_WINDOW,
+            output_gain_db: 0.0,
+            input_gain_db: 0.0,
+            vad_threshold: DEFAULT_VAD_THRESHOLD,
+            noise_gain: DEFAULT_NOISE_GAIN,
+            speech_gain: DEFAULT_SPEECH_GAIN,
+            gain_floor: 0.0,
+            comfort_noise_db: None,
+            onset_protection_ms: 0,
+            gain_attack_ms: gain::DEFAULT_GAIN_ATTACK_MS,
+            gain_release_ms: gain::DEFAULT_GAIN_RELEASE_MS,
+            gate_hold_ms: gain::DEFAULT_GATE_HOLD_MS,
+            vad_source: analysis::VadSource::default(),
+            replay_seconds: 0,
+            frame_deadline_us: 0,
+            crossover_hz: 0,
+            meter_ballistics: meter::MeterBallistics::default(),
+            meter_integration_ms: meter::DEFAULT_METER_INTEGRATION_MS,
+            vad_deadband: 0.0,
+            mix: gain::DEFAULT_MIX,
+            latency: latency::LatencySettings::default(),
+            custom_model_path: None,
+            thread_affinity: affinity::ThreadAffinity::default(),
+            target_sample_rate: resampling::DEFAULT_TARGET_SAMPLE_RATE,
+            channels: 1,
+            stereo_gates: None,
+            max_processing_threads: parallel::DEFAULT_MAX_PROCESSING_THREADS,
+            music_passthrough: music::MusicPassthroughSettings::default(),
+            ducking: sidechain::DuckingSettings::default(),
+            monitor_device_id: None,
+            idle_bypass_secs: 0,
+            denoiser_warmup_ms: 0,
+            startup_max_test_secs: 0,
+            processing_block_frames: 1,
+            auto_sensitivity: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Build audio settings from the persisted application configuration
+    pub fn from_config(config: &KwiteConfig) -> Self {
+        Self {
+            vad_history_len: config.vad_history_len,
+            device_fallback: config.device_fallback,
+            dc_filter: config.dc_filter_enabled,
+            input_channel: config.input_channel,
+            underrun_fill: config.underrun_fill,
+            analysis_window: config.analysis_window_size
+                .clamp(analysis::MIN_ANALYSIS_WINDOW, analysis::MAX_ANALYSIS_WINDOW),
+            output_gain_db: config.output_gain_db,
+            input_gain_db: config.input_gain_db,
+            vad_threshold: config.vad_threshold,
+            noise_gain: config.noise_gain,
+            speech_gain: config.speech_gain,
+            gain_floor: config.gain_floor,
+            comfort_noise_db: config.comfort_noise_level,
+            onset_protection_ms: config.onset_protection_ms.min(gain::MAX_ONSET_PROTECTION_MS),
+            gain_attack_ms: config.gain_attack_ms.min(gain::MAX_GAIN_SMOOTHING_MS),
+            gain_release_ms: config.gain_release_ms.min(gain::MAX_GAIN_SMOOTHING_MS),
+            gate_hold_ms: config.gate_hold_ms.min(gain::MAX_GATE_HOLD_MS),
+            vad_source: config.vad_source,
+            replay_seconds: config.instant_replay_secs.min(replay::MAX_REPLAY_SECONDS),
+            frame_deadline_us: config.frame_deadline_us.min(deadline::MAX_FRAME_DEADLINE_US),
+            crossover_hz: match config.denoiser_crossover_hz {
+                0 => 0,
+                hz => hz.clamp(filters::MIN_CROSSOVER_HZ, filters::MAX_CROSSOVER_HZ),
+            },
+            meter_ballistics: config.meter_ballistics,
+            meter_integration_ms: config.meter_integration_ms,
+            vad_deadband: config.vad_deadband.clamp(0.0, gain::MAX_VAD_DEADBAND),
+            mix: config.mix,
+            latency: latency::LatencySettings::resolve(config.latency_preset, &config.latency_overrides),
+            custom_model_path: config.custom_model_path.clone(),
+            thread_affinity: config.process_thread_affinity,
+            target_sample_rate: if resampling::is_supported_target_sample_rate(config.target_sample_rate) {
+                config.target_sample_rate
+            } else {
+                log::warn!("⚠️ Unsupported target sample rate {}Hz - using 48kHz", config.target_sample_rate);
+                resampling::DEFAULT_TARGET_SAMPLE_RATE
+            },
+            channels: if config.stereo_processing { stereo::STEREO_CHANNELS as u16 } else { 1 },
+            stereo_gates: config.stereo_channel_gates,
+            max_processing_threads: config.max_processing_threads.clamp(1, parallel::MAX_PROCESSING_THREADS),
+            music_passthrough: config.music_passthrough,
+            ducking: config.ducking.clone(),
+            monitor_device_id: config.monitor_enabled
+                .then(|| config.monitor_device_id.clone())
+                .flatten()
+                .filter(|id| !id.trim().is_empty()),
+            idle_bypass_secs: config.idle_bypass_secs.min(idle::MAX_IDLE_BYPASS_SECS),
+            denoiser_warmup_ms: config.denoiser_warmup_ms.min(MAX_DENOISER_WARMUP_MS),
+            startup_max_test_secs: if config.startup_max_test_enabled {
+                config.startup_max_test_secs.min(MAX_STARTUP_MAX_TEST_SECS)
+            } else {
+                0
+            },
+            processing_block_frames: config.processing_block_frames.clamp(1, MAX_PROCESSING_BLOCK_FRAMES),
+            auto_sensitivity: config.auto_sensitivity,
+        }
+    }
+}
+
 /// Audio processing manager that coordinates the entire audio pipeline
 /// 
 /// The AudioManager is responsible for:
@@ -192,7 +561,8 @@ pub fn log_comprehensive_diagnostics() {
 /// ## Thread Management
 /// 
 /// All threads are managed as `JoinHandle<()>` to ensure proper cleanup.
-/// The `running` atomic flag coordinates graceful shutdown across all threads.
+/// The `running` atomic flag coordinates graceful shutdown across all threads,
+/// and `drop` joins them with a bounded wait (`SHUTDOWN_JOIN_TIMEOUT`).
 /// Thread communication uses bounded channels to prevent memory buildup.
 /// 
 /// ## State Management
@@ -203,24 +573,55 @@ pub fn log_comprehensive_diagnostics() {
 pub struct AudioManager {
     /// Handle for the audio input capture thread
     /// Responsible for reading from microphone/input device
-    _input_thread: thread::JoinHandle<()>,
+    input_thread: Option<thread::JoinHandle<()>>,
     
     /// Handle for the audio output playback thread  
     /// Responsible for sending to speakers/virtual device
-    _output_thread: thread::JoinHandle<()>,
+    output_thread: Option<thread::JoinHandle<()>>,
     
     /// Handle for the audio processing thread
     /// Responsible for AI noise cancellation and filtering
-    _process_thread: thread::JoinHandle<()>,
+    process_thread: Option<thread::JoinHandle<()>>,
     
-    /// Noise cancellation sensitivity parameter (atomic for real-time updates)
-    /// Stored as u64 bits to allow atomic updates of floating-point values
+    /// Analyzer VAD sensitivity (atomic for real-time updates)
+    /// Stored as u64 bits to allow atomic updates of floating-point values.
+    /// Separate from `vad_threshold`, which decides the denoise gain.
     sensitivity: Arc<AtomicU64>,
     
+    /// Linear output gain trim applied after noise suppression (atomic f32 bits)
+    output_gain: Arc<AtomicU64>,
+    
+    /// Linear input pre-gain applied before the denoiser (atomic f32 bits)
+    input_gain: Arc<AtomicU64>,
+    
+    /// Speech detection threshold for the VAD score (atomic f32 bits)
+    /// Controls *when* a frame counts as speech
+    vad_threshold: Arc<AtomicU64>,
+    
+    /// Gain applied to background noise frames (atomic f32 bits)
+    /// Controls *how much* noise frames are suppressed
+    noise_gain: Arc<AtomicU64>,
+    
+    /// Gain applied to speech frames (atomic f32 bits)
+    speech_gain: Arc<AtomicU64>,
+    
+    /// Lowest gain any frame is attenuated to (atomic f32 bits)
+    gain_floor: Arc<AtomicU64>,
+    
+    /// Comfort noise amplitude while the gate is closed, 0.0 when off (atomic f32 bits)
+    comfort_noise_level: Arc<AtomicU64>,
+    
+    /// Dry/wet mix between raw input and processed output (atomic f32 bits)
+    mix: Arc<AtomicU64>,
+    
     /// Atomic flag for coordinating graceful shutdown across all threads
     /// Set to false when the AudioManager is dropped or stopped
     running: Arc<AtomicBool>,
     
+    /// A/B comparison flag: while set, the processing thread passes the input
+    /// through without RNNoise or gating (level controls still apply)
+    bypass: Arc<AtomicBool>,
+    
     /// AI audio analysis for intelligent model selection (GUI display only)
     /// Analyzes incoming audio to automatically choose optimal processing
     #[cfg(feature = "ai-enhanced")]
@@ -229,6 +630,85 @@ pub struct AudioManager {
     /// AI performance metrics for monitoring and display
     /// Tracks VAD scores, processing latency, and other AI indicators
     ai_metrics: SharedAiMetrics,
+    
+    /// Per-model denoiser statistics (frames, processing time, VAD average)
+    /// Updated by the processing thread with `try_lock` to avoid blocking audio
+    model_stats: Arc<Mutex<ModelStatistics>>,
+    
+    /// Denoiser weights the processing thread loaded, for display
+    model_name: String,
+    
+    /// Input capture statistics (clip counts) shared with the capture callback
+    input_stats: Arc<capture::InputStats>,
+    
+    /// Output statistics (sanitized sample count) shared with the output callback
+    output_stats: Arc<output::OutputStats>,
+    
+    /// Round-trip latency probe shared by the output and capture callbacks
+    latency_probe: Arc<latency_probe::LatencyProbe>,
+    
+    /// Set by the processing thread while music passthrough is engaged
+    music_passthrough_active: Arc<AtomicBool>,
+    
+    /// Request for the processing thread to reinitialize its denoiser state
+    denoiser_reset: Arc<AtomicBool>,
+    
+    /// Frames passed through unprocessed because the denoiser would miss the deadline
+    deadline_skips: Arc<AtomicU64>,
+    
+    /// Latest sidechain level driving the output ducking
+    sidechain_level: Arc<sidechain::SidechainLevel>,
+    
+    /// Current ducking gain applied by the processing thread (atomic f32 bits)
+    ducking_gain: Arc<AtomicU64>,
+    
+    /// Whether the noise floor drives the analyzer sensitivity instead of the slider
+    auto_sensitivity: Arc<AtomicBool>,
+    
+    /// Sensitivity derived from the noise floor by the processing thread (atomic f32 bits)
+    auto_sensitivity_value: Arc<AtomicU64>,
+    
+    /// Handle for the sidechain capture thread (`None` without a sidechain device)
+    sidechain_thread: Option<thread::JoinHandle<()>>,
+    
+    /// Handle for the monitor output thread (`None` without a monitor device)
+    monitor_thread: Option<thread::JoinHandle<()>>,
+    
+    /// Frames processed since the last speech frame
+    frames_since_speech: Arc<AtomicU64>,
+    
+    /// Set by the processing thread while the denoiser is bypassed for silence
+    idle_bypass_active: Arc<AtomicBool>,
+    
+    /// Set while the GUI shows the spectrum, so the processing thread only runs the FFT then
+    spectrum_enabled: Arc<AtomicBool>,
+    
+    /// Recent processed output for instant replay (`None` when disabled)
+    replay_buffer: Option<Arc<Mutex<replay::ReplayBuffer>>>,
+    
+    /// Processing-thread end of the active output recording (`None` when not recording)
+    recording_tap: Arc<Mutex<Option<recorder::RecordingTap>>>,
+    
+    /// Integrator hook fired when the classified noise type changes (`None` when unset)
+    noise_type_callback: Arc<Mutex<Option<analysis::NoiseTypeCallback>>>,
+    
+    /// Writer of the active output recording
+    recording: Option<recorder::RecordingWriter>,
+    
+    /// Averaged spectrum of frames the gate treated as noise
+    noise_profile: Arc<Mutex<analysis::NoiseProfileAccumulator>>,
+    
+    /// Set while dev mode is on, so the processing thread only runs the profile FFT then
+    noise_profile_enabled: Arc<AtomicBool>,
+    
+    /// Imported background profile for the noise classifier; picked up when `room_profile_changed` is set
+    room_profile: Arc<Mutex<Option<analysis::FrequencyProfile>>>,
+    room_profile_changed: Arc<AtomicBool>,
+    
+    /// Devices and settings this pipeline was built with (used by `rebuild`)
+    input_device_id: String,
+    output_device_id: String,
+    settings: AudioSettings,
 }
 
 impl AudioManager {
@@ -245,11 +725,14 @@ impl AudioManager {
     /// - `initial_sensitivity`: Starting sensitivity threshold (0.01 - 0.5)
     /// - `input_device_id`: Identifier for microphone or input device
     /// - `output_device_id`: Identifier for speakers or virtual audio device
+    /// - `settings`: Additional pipeline settings taken from the configuration
     /// 
     /// ## Channel Configuration
     /// 
-    /// Uses small bounded channels (4 slots) to minimize latency while preventing
-    /// memory buildup if processing can't keep up with input rate.
+    /// Uses small bounded channels (4 slots with the default `Balanced` latency preset)
+    /// to minimize latency while preventing memory buildup if processing can't keep up
+    /// with input rate. Capacity, output jitter depth and overflow policy come from
+    /// `settings.latency`.
     /// 
     /// ## Error Handling
     /// 
@@ -259,12 +742,27 @@ impl AudioManager {
     pub fn new(
         initial_sensitivity: f32, 
         input_device_id: &str, 
-        output_device_id: &str
+        output_device_id: &str,
+        settings: AudioSettings
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         log::info!("=== INITIALIZING KWITE AUDIO MANAGER ===");
         log::info!("Input device: {}", input_device_id);
         log::info!("Output device: {}", output_device_id);
         log::info!("Initial sensitivity: {}", initial_sensitivity);
+        log::info!("VAD history length: {} frames", settings.vad_history_len);
+        log::info!("Output gain trim: {:.1} dB", settings.output_gain_db);
+        log::info!("Input pre-gain: {:.1} dB", settings.input_gain_db);
+        log::info!("VAD threshold: {:.2} | Noise gain: {:.2} | Speech gain: {:.2}", settings.vad_threshold, settings.noise_gain, settings.speech_gain);
+        log::info!("Dry/wet mix: {:.0}% processed", settings.mix * 100.0);
+        log::info!("Latency: {:?}", settings.latency);
+        log::info!("Processing channels: {}", if settings.channels == 2 { "stereo" } else { "mono" });
+        if settings.channels == stereo::STEREO_CHANNELS as u16 {
+            log::warn!("🎧 Stereo processing skips mono-only stages, their settings are ignored: {}", stereo::MONO_ONLY_STAGES.join(", "));
+        }
+        
+        // Make sure the microphone can be opened, falling back to another one if allowed
+        let input_device_id = devices::resolve_input_device(input_device_id, settings.device_fallback)?;
+        let input_device_id = input_device_id.as_str();
         
         // Initialize maximum test mode from environment variable
         init_max_test_mode_from_env();
@@ -311,28 +809,86 @@ impl AudioManager {
         // Focus on reliable RNNoise processing that actually works consistently
         log::info!("✅ Simplified reliable audio processor initialized with direct RNNoise");
 
+        // Resolve custom denoiser weights, falling back to the built-in model on any problem
+        let custom_model = match settings.custom_model_path.as_deref().filter(|p| !p.trim().is_empty()) {
+            Some(path) => match models::load_custom_rnnoise_model(std::path::Path::new(path)) {
+                Ok(model) => {
+                    log::info!("✅ Using custom RNNoise model: {}", path);
+                    Some(model)
+                }
+                Err(e) => {
+                    log::warn!("⚠️ Custom RNNoise model rejected ({}) - falling back to the built-in model", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let model_name = match (custom_model, settings.custom_model_path.as_deref()) {
+            (Some(_), Some(path)) => {
+                let file = std::path::Path::new(path).file_name().map_or_else(|| path.to_string(), |name| name.to_string_lossy().into_owned());
+                format!("{} (custom: {})", NoiseModel::RNNoise.name(), file)
+            }
+            _ => format!("{} (built-in)", NoiseModel::RNNoise.name()),
+        };
+
         // For backwards compatibility, initialize a basic audio analyzer (for GUI display only)
         #[cfg(feature = "ai-enhanced")]
         let audio_analyzer = Arc::new(Mutex::new(
-            AudioAnalyzer::new(48000, 480, 0.1).map_err(|e| format!("Audio analyzer error: {}", e))?
+            AudioAnalyzer::with_analysis_window(
+                settings.target_sample_rate,
+                resampling::calculate_frame_size_for_sample_rate(settings.target_sample_rate),
+                settings.analysis_window,
+                initial_sensitivity,
+                settings.vad_history_len,
+            )
+                .map_err(|e| format!("Audio analyzer error: {}", e))?
         ));
         #[cfg(feature = "ai-enhanced")]
         log::info!("✅ AI audio analyzer initialized for GUI display only");
 
         // Initialize AI performance metrics
         let ai_metrics = create_shared_metrics();
+        if let Ok(mut metrics) = ai_metrics.lock() {
+            metrics.watchdog_restarts = watchdog::restart_count();
+        }
+        let model_stats = Arc::new(Mutex::new(ModelStatistics::new()));
         log::info!("✅ AI metrics system initialized");
 
         // Create bounded channels for inter-thread communication
-        // Small buffer sizes (4 slots) minimize latency at the cost of potential frame drops
+        // Small buffer sizes minimize latency at the cost of potential frame drops
         // This is acceptable for real-time audio where freshness is more important than completeness
-        let (audio_tx, audio_rx) = bounded::<Vec<f32>>(4);      // Raw audio input
-        let (processed_tx, processed_rx) = bounded::<Vec<f32>>(4); // Processed audio output
+        let latency_settings = settings.latency;
+        let rebuild_settings = settings.clone();
+        let target_sample_rate = settings.target_sample_rate; // Device-side rate; processing stays at 48kHz
+        // Frames carry their capture time so the output can measure end-to-end latency
+        let (audio_tx, audio_rx) = bounded::<latency::AudioFrame>(latency_settings.channel_capacity);      // Raw audio input
+        let (processed_tx, processed_rx) = bounded::<latency::AudioFrame>(latency_settings.channel_capacity); // Processed audio output
+        let processed_drain = processed_rx.clone(); // Lets the process thread drop the oldest frame
+        // The monitor has its own queue so it can never hold up the main output
+        let (monitor_tx, monitor_rx) = match settings.monitor_device_id {
+            Some(_) => {
+                let (tx, rx) = bounded::<latency::AudioFrame>(latency_settings.channel_capacity);
+                (Some(tx), Some(rx))
+            }
+            None => (None, None),
+        };
         log::info!("✅ Audio channels created for inter-thread communication");
 
         // Initialize shared state for thread coordination
         let sensitivity = Arc::new(AtomicU64::new(initial_sensitivity.to_bits() as u64));
+        let output_gain_db = settings.output_gain_db.clamp(MIN_OUTPUT_GAIN_DB, MAX_OUTPUT_GAIN_DB);
+        let output_gain = Arc::new(AtomicU64::new(db_to_linear(output_gain_db).to_bits() as u64));
+        let input_gain_db = settings.input_gain_db.clamp(MIN_INPUT_GAIN_DB, MAX_INPUT_GAIN_DB);
+        let input_gain = Arc::new(AtomicU64::new(db_to_linear(input_gain_db).to_bits() as u64));
+        let vad_threshold = Arc::new(AtomicU64::new(settings.vad_threshold.clamp(0.0, 1.0).to_bits() as u64));
+        let noise_gain = Arc::new(AtomicU64::new(settings.noise_gain.clamp(0.0, 1.0).to_bits() as u64));
+        let speech_gain = Arc::new(AtomicU64::new(settings.speech_gain.clamp(0.0, 1.0).to_bits() as u64));
+        let gain_floor = Arc::new(AtomicU64::new(settings.gain_floor.clamp(0.0, gain::MAX_GAIN_FLOOR).to_bits() as u64));
+        let mix = Arc::new(AtomicU64::new(settings.mix.clamp(0.0, 1.0).to_bits() as u64));
+        let comfort_noise_level = Arc::new(AtomicU64::new(settings.comfort_noise_db.map_or(0.0, comfort_noise::level_from_db).to_bits() as u64));
         let running = Arc::new(AtomicBool::new(true));
+        let bypass = Arc::new(AtomicBool::new(false));
+        let bypass_clone = bypass.clone();
         log::info!("✅ Thread coordination state initialized");
 
         // Start input capture thread
@@ -340,11 +896,21 @@ impl AudioManager {
         let audio_tx_clone = audio_tx.clone();
         let running_clone = running.clone();
         let input_device_id_clone = input_device_id.to_string();
+        let input_stats = Arc::new(capture::InputStats::default());
+        input_stats.level.configure(settings.meter_ballistics, settings.meter_integration_ms);
+        let input_stats_clone = input_stats.clone();
+        let latency_probe = Arc::new(latency_probe::LatencyProbe::default());
+        let input_probe = latency_probe.clone();
+        let dc_filter = settings.dc_filter;
+        let input_channel = settings.input_channel;
+        let stereo = settings.channels == stereo::STEREO_CHANNELS as u16;
         log::info!("🎤 Starting input capture thread for device: {}", input_device_id);
         let input_thread = thread::spawn(move || {
             log::info!("Input capture thread started");
-            if let Err(e) = capture::start_input_stream(audio_tx_clone, running_clone, &input_device_id_clone) {
+            if let Err(e) = capture::start_input_stream(audio_tx_clone, running_clone, &input_device_id_clone, input_stats_clone.clone(), input_probe, target_sample_rate, dc_filter, stereo, input_channel) {
                 log::error!("❌ Input stream error: {}", e);
+                // No audio will ever arrive; let the GUI stop instead of showing the pipeline as active
+                input_stats_clone.mark_stream_failed();
             } else {
                 log::info!("✅ Input stream completed successfully");
             }
@@ -353,11 +919,143 @@ impl AudioManager {
         // Start audio processing thread
         // Uses simplified, reliable RNNoise processing for consistent noise cancellation
         let ai_metrics_clone = ai_metrics.clone();
+        let model_stats_clone = model_stats.clone();
+        let output_gain_clone = output_gain.clone();
+        let input_gain_clone = input_gain.clone();
+        let vad_threshold_clone = vad_threshold.clone();
+        let analyzer_sensitivity_clone = sensitivity.clone();
+        let noise_gain_clone = noise_gain.clone();
+        let speech_gain_clone = speech_gain.clone();
+        let gain_floor_clone = gain_floor.clone();
+        let comfort_noise_level_clone = comfort_noise_level.clone();
+        let onset_window_frames = settings.onset_protection_ms / 10; // 10ms per 480-sample frame
+        let gate_hold_frames = settings.gate_hold_ms / 10;
+        let gain_smoother = gain::GainSmoother::new(settings.gain_attack_ms, settings.gain_release_ms, 48000);
+        let vad_source = settings.vad_source;
+        let vad_deadband = settings.vad_deadband;
+        let vad_history_len = settings.vad_history_len;
+        log::info!("VAD source for the gain decision: {}", vad_source.label());
+        let mix_clone = mix.clone();
         let running_clone = running.clone();
+        let thread_affinity = settings.thread_affinity;
+        let music_settings = settings.music_passthrough;
+        let analysis_window = settings.analysis_window;
+        let warmup_frames = (settings.denoiser_warmup_ms / 10) as u64; // 10ms per 480-sample frame
+        if warmup_frames > 0 {
+            log::info!("🔥 Denoiser warm-up: output muted for the first {}ms", warmup_frames * 10);
+        }
+        let processing_block_frames = settings.processing_block_frames.max(1);
+        if processing_block_frames > 1 {
+            log::info!("📦 Processing audio in {}ms blocks", processing_block_frames * 10);
+        }
+        let startup_max_test_frames = settings.startup_max_test_secs as u64 * 100; // 100 frames per second
+        if startup_max_test_frames > 0 {
+            log::warn!("🚨 Maximum Test Mode gains for the first {}s after start", settings.startup_max_test_secs);
+        }
+        let music_passthrough_active = Arc::new(AtomicBool::new(false));
+        let stereo_gates = settings.stereo_gates;
+        let max_processing_threads = settings.max_processing_threads;
+        
+        // Sidechain ducking: the level comes from a second capture device or is pushed by integrations
+        let ducking_settings = settings.ducking.clone();
+        let sidechain_level = Arc::new(sidechain::SidechainLevel::default());
+        let sidechain_level_clone = sidechain_level.clone();
+        let ducking_gain = Arc::new(AtomicU64::new(1.0f32.to_bits() as u64));
+        let ducking_gain_clone = ducking_gain.clone();
+        
+        // Auto sensitivity: estimated in the processing thread, switchable live from the GUI
+        let auto_sensitivity_enabled = Arc::new(AtomicBool::new(settings.auto_sensitivity));
+        let auto_sensitivity_enabled_clone = auto_sensitivity_enabled.clone();
+        let auto_sensitivity_value = Arc::new(AtomicU64::new(initial_sensitivity.to_bits() as u64));
+        let auto_sensitivity_value_clone = auto_sensitivity_value.clone();
+        if settings.auto_sensitivity {
+            log::info!("🎚 Auto sensitivity: following the measured noise floor");
+        }
+        let sidechain_thread = match ducking_settings.source_device_id.clone() {
+            Some(device_id) if ducking_settings.enabled => {
+                let level = sidechain_level.clone();
+                let running_clone = running.clone();
+                Some(thread::spawn(move || {
+                    if let Err(e) = capture::start_sidechain_stream(&device_id, level, running_clone) {
+                        log::error!("❌ Sidechain stream error: {} - ducking only follows external levels", e);
+                    }
+                }))
+            }
+            _ => None,
+        };
+        if ducking_settings.enabled {
+            log::info!("🦆 Output ducking: threshold {:.0} dBFS, ratio {:.1}:1", ducking_settings.threshold_db, ducking_settings.ratio);
+        }
+        let music_passthrough_active_clone = music_passthrough_active.clone();
+        let denoiser_reset = Arc::new(AtomicBool::new(false));
+        let denoiser_reset_clone = denoiser_reset.clone();
+        let deadline_skips = Arc::new(AtomicU64::new(0));
+        let deadline_skips_clone = deadline_skips.clone();
+        let frames_since_speech = Arc::new(AtomicU64::new(0));
+        let frames_since_speech_clone = frames_since_speech.clone();
+        let idle_bypass_active = Arc::new(AtomicBool::new(false));
+        let idle_bypass_active_clone = idle_bypass_active.clone();
+        let spectrum_enabled = Arc::new(AtomicBool::new(false));
+        let spectrum_enabled_clone = spectrum_enabled.clone();
+        let idle_bypass_secs = settings.idle_bypass_secs;
+        if idle_bypass_secs > 0 {
+            log::info!("💤 Idle bypass: denoiser pauses after {}s without speech", idle_bypass_secs);
+        }
+        let frame_deadline_us = settings.frame_deadline_us;
+        if frame_deadline_us > 0 {
+            log::info!("⏱ Frame deadline: {}µs - slower frames pass through unprocessed", frame_deadline_us);
+        }
+        let crossover_hz = settings.crossover_hz;
+        if crossover_hz > 0 {
+            log::info!("🎚 Denoiser crossover: {} Hz - higher frequencies bypass RNNoise", crossover_hz);
+        }
+        let replay_buffer = (settings.replay_seconds > 0 && !stereo)
+            .then(|| Arc::new(Mutex::new(replay::ReplayBuffer::new(settings.replay_seconds, 48000))));
+        let replay_buffer_clone = replay_buffer.clone();
+        // Holds the tap while a recording runs; the processing thread only ever try_locks it
+        let recording_tap: Arc<Mutex<Option<recorder::RecordingTap>>> = Arc::new(Mutex::new(None));
+        let recording_tap_clone = recording_tap.clone();
+        // Same arrangement for the noise type callback
+        let noise_type_callback: Arc<Mutex<Option<analysis::NoiseTypeCallback>>> = Arc::new(Mutex::new(None));
+        let noise_type_callback_clone = noise_type_callback.clone();
+        let noise_profile = Arc::new(Mutex::new(analysis::NoiseProfileAccumulator::default()));
+        let noise_profile_clone = noise_profile.clone();
+        let noise_profile_enabled = Arc::new(AtomicBool::new(false));
+        let noise_profile_enabled_clone = noise_profile_enabled.clone();
+        let room_profile: Arc<Mutex<Option<analysis::FrequencyProfile>>> = Arc::new(Mutex::new(None));
+        let room_profile_clone = room_profile.clone();
+        let room_profile_changed = Arc::new(AtomicBool::new(false));
+        let room_profile_changed_clone = room_profile_changed.clone();
+        if music_settings.enabled {
+            log::info!("🎵 Music passthrough enabled (engage after {}ms, release after {}ms of speech)",
+                      music_settings.engage_ms, music_settings.release_ms);
+        }
         log::info!("🧠 Starting SIMPLIFIED audio processing thread");
         let process_thread = thread::spawn(move || {
             log::info!("SIMPLIFIED audio processing thread started");
             
+            // Model weights for this thread's denoiser (None = built-in RNNoise weights)
+            thread_local! {
+                static DENOISER_MODEL: std::cell::Cell<Option<&'static nnnoiseless::RnnModel>> = std::cell::Cell::new(None);
+            }
+            DENOISER_MODEL.with(|model| model.set(custom_model));
+
+            // Build a denoiser with this thread's model and a clean recurrent state
+            fn fresh_denoiser() -> nnnoiseless::DenoiseState<'static> {
+                match DENOISER_MODEL.with(|model| model.get()) {
+                    // Custom weights are leaked by the loader, so they live for 'static
+                    Some(model) => *nnnoiseless::DenoiseState::with_model(model),
+                    None => unsafe {
+                        std::mem::transmute::<nnnoiseless::DenoiseState<'_>, nnnoiseless::DenoiseState<'static>>(
+                            *nnnoiseless::DenoiseState::new()
+                        )
+                    },
+                }
+            }
+
+            // Keep the thread off efficiency cores on hybrid CPUs if requested
+            affinity::pin_current_thread(thread_affinity);
+            
             // Apple Silicon M4 specific thread optimization
             #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
             {
@@ -373,20 +1071,89 @@ impl AudioManager {
             
             // Frame buffer to accumulate audio data into proper model-specific frames
             let mut frame_buffer = Vec::new();
+            // Capture time of the samples in the frame buffer
+            let mut capture_times = latency::CaptureTimestamps::default();
             let mut frame_count = 0u64; // Track frame count for diagnostic purposes
             
             // Use fixed frame size for reliable processing
             let current_frame_size = 480; // RNNoise standard frame size
             
+            // Noise-type classification feeds the metrics display and music passthrough;
+            // the analyzer is created here because the VAD it wraps can't be moved across threads
+            let mut noise_classifier = match analysis::AudioAnalyzer::with_analysis_window(48000, current_frame_size, analysis_window, initial_sensitivity, vad_history_len) {
+                Ok(analyzer) => Some((analyzer, analysis::NoiseTypeGate::new(music::NOISE_TYPE_GATE_FRAMES))),
+                Err(e) => {
+                    log::warn!("⚠️ Noise classification disabled: analyzer failed to start ({})", e);
+                    None
+                }
+            };
+            let mut noise_type_notifier = analysis::NoiseTypeNotifier::new(analysis::DEFAULT_NOISE_EVENT_INTERVAL);
+            let mut music_passthrough = music::MusicPassthrough::new(music_settings);
+            let mut ducker = ducking_settings.enabled.then(|| sidechain::Ducker::new(&ducking_settings));
+            let mut onset_protector = gain::OnsetProtector::new(onset_window_frames);
+            // The applied gain, ramped per sample so frame-to-frame gain changes don't click
+            let mut gain_smoother = gain_smoother;
+            // Fills the closed gate so listeners don't hear a dropped call
+            let mut comfort_noise = comfort_noise::ComfortNoise::default();
+            // Created only while the GUI shows the spectrum
+            let mut spectrum_analyzer: Option<analysis::SpectralAnalyzer> = None;
+            let mut gate_hysteresis = gain::GateHysteresis::new(vad_deadband);
+            let mut gate_hold = gain::GateHold::new(gate_hold_frames);
+            
+            // Second opinion on voice activity, shown next to RNNoise's VAD in the dev panel
+            // and optionally used for the gain decision (created here: it can't cross threads)
+            let mut noise_spectrum = analysis::SpectralAnalyzer::new(480, 48000.0);
+            let mut applied_analyzer_sensitivity = initial_sensitivity;
+            let mut auto_sensitivity = adaptive::AutoSensitivity::new(initial_sensitivity);
+            let mut frame_deadline = deadline::FrameDeadline::new(frame_deadline_us);
+            // The high band waits one frame, the latency of RNNoise's overlap-add
+            let mut band_splitter = (crossover_hz > 0)
+                .then(|| filters::BandSplitter::new(crossover_hz, 48000, current_frame_size));
+            let mut low_band = Vec::with_capacity(current_frame_size);
+            // The dry/wet mix and passthrough frames use the raw input from a frame ago, aligned with RNNoise's output
+            let mut dry_delay = gain::DryDelay::new(current_frame_size);
+            let mut denoiser_catch_up = gain::DenoiserCatchUp::new(current_frame_size);
+            let mut stereo_dry_delay = gain::DryDelay::new(current_frame_size * stereo::STEREO_CHANNELS);
+            let mut last_vad_score = 0.0f32;
+            // Stereo runs its own, simpler per-channel path; mono keeps everything below
+            let mut stereo_processor = stereo
+                .then(|| stereo::StereoProcessor::new(fresh_denoiser, current_frame_size, max_processing_threads, gain_smoother.clone()));
+            // It listens at the target rate, so narrowband setups get the matching WebRTC VAD mode
+            let mut analyzer_vad = match analysis::VoiceActivityDetector::new(target_sample_rate, initial_sensitivity, vad_history_len) {
+                Ok(detector) => Some(detector),
+                Err(e) => {
+                    log::warn!("⚠️ Analyzer VAD unavailable ({}) - gain follows the RNNoise VAD", e);
+                    None
+                }
+            };
+            // Only the analyzer VAD listens while RNNoise is bypassed, so without it the bypass could never wake
+            let idle_bypass_timeout = if analyzer_vad.is_some() {
+                idle_bypass_secs
+            } else {
+                if idle_bypass_secs > 0 {
+                    log::warn!("⚠️ Idle bypass disabled: it needs the analyzer VAD to detect returning speech");
+                }
+                0
+            };
+            let mut idle_bypass = idle::IdleBypass::new(idle_bypass_timeout);
+            
             while running_clone.load(Ordering::Relaxed) {
                 // Use short timeout to maintain responsiveness during shutdown
                 if let Ok(input_data) = audio_rx.recv_timeout(std::time::Duration::from_millis(5)) {
                     // Add incoming audio data to frame buffer
-                    frame_buffer.extend_from_slice(&input_data);
+                    capture_times.push(input_data.samples.len(), input_data.captured_at);
+                    frame_buffer.extend_from_slice(&input_data.samples);
+                    
+                    // Wait for a whole processing block; its frames are then denoised back to back.
+                    // RNNoise itself is always fed 480-sample frames.
+                    let buffer_channels = if stereo_processor.is_some() { stereo::STEREO_CHANNELS } else { 1 };
+                    if frame_buffer.len() < current_frame_size * processing_block_frames * buffer_channels {
+                        continue;
+                    }
                     
                     // Log first frame received to confirm audio is flowing
                     if frame_count == 0 {
-                        log::info!("🎵 First audio frame received ({} samples) - SIMPLIFIED noise cancellation starting", input_data.len());
+                        log::info!("🎵 First audio frame received ({} samples) - SIMPLIFIED noise cancellation starting", input_data.samples.len());
                         log::info!("🧠 SIMPLIFIED AI noise cancellation pipeline is now ACTIVE and processing audio");
                         log::info!("💡 IMPORTANT: Using reliable RNNoise processing - background noise should be significantly reduced");
                         log::info!("📊 Removed complex enhanced processor that was causing lock failures and silent errors");
@@ -402,13 +1169,110 @@ impl AudioManager {
                         }
                     }
                     
+                    // Stereo frames hold interleaved left/right samples
+                    if let Some(processor) = stereo_processor.as_mut() {
+                        let stereo_frame_size = current_frame_size * stereo::STEREO_CHANNELS;
+                        while frame_buffer.len() >= stereo_frame_size {
+                            let mut frame: Vec<f32> = frame_buffer.drain(0..stereo_frame_size).collect();
+                            let captured_at = capture_times.take(stereo_frame_size).unwrap_or_else(std::time::Instant::now);
+                            frame_count += 1;
+                            
+                            let input_gain = f32::from_bits(input_gain_clone.load(Ordering::Relaxed) as u32);
+                            gain::apply_pre_gain(&mut frame, input_gain);
+                            let dry = frame.clone();
+                            // The spectrum shows the mono downmix
+                            let downmix = spectrum_enabled_clone.load(Ordering::Relaxed).then(|| {
+                                dry.chunks_exact(stereo::STEREO_CHANNELS).map(|pair| (pair[0] + pair[1]) * 0.5).collect::<Vec<f32>>()
+                            });
+                            let spectrum = update_spectrum(&mut spectrum_analyzer, downmix.as_deref());
+                            
+                            if denoiser_reset_clone.swap(false, Ordering::Relaxed) {
+                                processor.reset(fresh_denoiser);
+                                log::info!("🔄 Denoiser state reset at frame #{}", frame_count);
+                            }
+                            
+                            // Without per-channel gates both channels follow the live mono gate
+                            let gates = stereo_gates.unwrap_or_else(|| gain::StereoGates::linked(gain::ChannelGate {
+                                vad_threshold: f32::from_bits(vad_threshold_clone.load(Ordering::Relaxed) as u32),
+                                noise_gain: f32::from_bits(noise_gain_clone.load(Ordering::Relaxed) as u32),
+                            }));
+                            let floor = f32::from_bits(gain_floor_clone.load(Ordering::Relaxed) as u32);
+                            
+                            let speech_gain = f32::from_bits(speech_gain_clone.load(Ordering::Relaxed) as u32);
+                            // The delay line sees every frame so it stays in step across A/B bypass
+                            let aligned_dry = stereo_dry_delay.push(&dry);
+                            let (vad_score, denoise_time) = if bypass_clone.load(Ordering::Relaxed) {
+                                // A/B comparison: both channels pass through untouched
+                                (0.0, std::time::Duration::ZERO)
+                            } else {
+                                let denoise_start = std::time::Instant::now();
+                                let vad_score = processor.process(&mut frame, gates, speech_gain, floor);
+                                let denoise_time = denoise_start.elapsed();
+                                if let Ok(mut stats) = model_stats_clone.try_lock() {
+                                    stats.record_processing(denoise_time, vad_score);
+                                }
+                                
+                                let dry_wet_mix = f32::from_bits(mix_clone.load(Ordering::Relaxed) as u32);
+                                gain::apply_dry_wet_mix(aligned_dry, &mut frame, dry_wet_mix);
+                                (vad_score, denoise_time)
+                            };
+                            
+                            // Every frame counts, bypassed or not, so the watchdog sees a live pipeline
+                            DIAGNOSTIC_FRAME_COUNTER.fetch_add(1, Ordering::Relaxed);
+                            
+                            let ducking = match ducker.as_mut() {
+                                Some(ducker) => {
+                                    let gain = ducker.next_gain(sidechain_level_clone.linear());
+                                    ducking_gain_clone.store(gain.to_bits() as u64, Ordering::Relaxed);
+                                    gain
+                                }
+                                None => 1.0,
+                            };
+                            let output_trim = f32::from_bits(output_gain_clone.load(Ordering::Relaxed) as u32) * ducking;
+                            for sample in frame.iter_mut() {
+                                *sample = (*sample * output_trim).clamp(-1.0, 1.0);
+                            }
+                            
+                            // The metrics show the louder channel's voice activity
+                            if let Ok(mut metrics) = ai_metrics_clone.try_lock() {
+                                metrics.record_frame(vad_score, denoise_time);
+                                metrics.record_input_level(&dry);
+                                metrics.spectrum = spectrum;
+                            }
+                            
+                            if frame_count <= warmup_frames {
+                                frame.fill(0.0);
+                                if frame_count == warmup_frames {
+                                    log::info!("🔥 Denoiser warm-up complete - unmuting output");
+                                }
+                            }
+                            
+                            if let Some(monitor) = &monitor_tx {
+                                let _ = monitor.try_send(latency::AudioFrame::new(frame.clone(), captured_at));
+                            }
+                            if let Ok(mut tap) = recording_tap_clone.try_lock() {
+                                if let Some(tap) = tap.as_mut() {
+                                    tap.push(&frame, stereo::STEREO_CHANNELS);
+                                }
+                            }
+                            latency::send_with_policy(&processed_tx, &processed_drain, latency::AudioFrame::new(frame, captured_at), latency_settings.overflow_policy);
+                        }
+                        continue;
+                    }
+                    
                     // Process complete frames from buffer
                     while frame_buffer.len() >= current_frame_size {
                         // Extract one complete frame with Apple Silicon M4 buffer validation
-                        let frame_input: Vec<f32> = frame_buffer.drain(0..current_frame_size).collect();
+                        let mut frame_input: Vec<f32> = frame_buffer.drain(0..current_frame_size).collect();
                         let mut frame_output = vec![0.0f32; current_frame_size];
+                        let captured_at = capture_times.take(current_frame_size).unwrap_or_else(std::time::Instant::now);
+                        let frame_start = std::time::Instant::now();
                         frame_count += 1;
 
+                        // Boost quiet sources before the denoiser, backing off instead of clipping
+                        let input_gain = f32::from_bits(input_gain_clone.load(Ordering::Relaxed) as u32);
+                        gain::apply_pre_gain(&mut frame_input, input_gain);
+
                         // Apple Silicon M4: Validate frame data integrity before processing
                         #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
                         {
@@ -436,69 +1300,204 @@ impl AudioManager {
                         
                         // Initialize per-thread RNNoise denoiser using proven reliable approach  
                         thread_local! {
-                            static RELIABLE_DENOISER: std::cell::RefCell<nnnoiseless::DenoiseState<'static>> = {
-                                let denoiser = unsafe {
-                                    std::mem::transmute::<nnnoiseless::DenoiseState<'_>, nnnoiseless::DenoiseState<'static>>(
-                                        *nnnoiseless::DenoiseState::new()
-                                    )
-                                };
-                                std::cell::RefCell::new(denoiser)
-                            };
+                            static RELIABLE_DENOISER: std::cell::RefCell<nnnoiseless::DenoiseState<'static>> =
+                                std::cell::RefCell::new(fresh_denoiser());
                         }
                         
-                        vad_score = RELIABLE_DENOISER.with(|denoiser| {
-                            let mut denoiser = denoiser.borrow_mut();
-                            
-                            // Validate frame sizes before processing
-                            if frame_input.len() != current_frame_size {
-                                log::warn!("🚨 Frame size mismatch: input={}, expected={}", 
-                                          frame_input.len(), current_frame_size);
-                                frame_output.copy_from_slice(&frame_input); // Pass through
-                                return 0.0;
+                        // Apply analyzer sensitivity changes; they never touch the denoise threshold
+                        let analyzer_sensitivity = if auto_sensitivity_enabled_clone.load(Ordering::Relaxed) {
+                            // Small glides are batched so the detectors aren't reconfigured every frame
+                            let auto = auto_sensitivity.sensitivity();
+                            if (auto - applied_analyzer_sensitivity).abs() >= adaptive::MIN_APPLIED_CHANGE { auto } else { applied_analyzer_sensitivity }
+                        } else {
+                            f32::from_bits(analyzer_sensitivity_clone.load(Ordering::Relaxed) as u32)
+                        };
+                        if analyzer_sensitivity != applied_analyzer_sensitivity {
+                            if let Some(detector) = analyzer_vad.as_mut() {
+                                detector.set_sensitivity(analyzer_sensitivity);
+                            }
+                            if let Some((analyzer, _)) = noise_classifier.as_mut() {
+                                analyzer.set_sensitivity(analyzer_sensitivity);
+                            }
+                            applied_analyzer_sensitivity = analyzer_sensitivity;
+                        }
+                        
+                        let spectrum = update_spectrum(&mut spectrum_analyzer, spectrum_enabled_clone.load(Ordering::Relaxed).then_some(&frame_input[..]));
+                        
+                        // Score the raw frame with the analyzer VAD and pick the score that drives the gate
+                        let analyzer_vad_score = analyzer_vad.as_mut().map(|detector| {
+                            if target_sample_rate == resampling::RNNOISE_SAMPLE_RATE {
+                                detector.detect(&frame_input)
+                            } else {
+                                detector.detect(&resampling::resample_frame(&frame_input, resampling::RNNOISE_SAMPLE_RATE, target_sample_rate))
+                            }
+                        });
+                        
+                        // While idle-bypassed, the analyzer VAD decides when speech is back
+                        let vad_threshold = f32::from_bits(vad_threshold_clone.load(Ordering::Relaxed) as u32);
+                        if idle_bypass.is_bypassed() && analyzer_vad_score.is_some_and(|score| score >= vad_threshold) {
+                            idle_bypass.wake();
+                            onset_protector.trigger();
+                            idle_bypass_active_clone.store(false, Ordering::Relaxed);
+                            log::info!("🗣 Speech detected - denoiser re-engaged after idle bypass");
+                        }
+                        let idle_bypassed = idle_bypass.is_bypassed();
+                        
+                        // A/B comparison: the raw input skips the denoiser and the gate
+                        let ab_bypassed = bypass_clone.load(Ordering::Relaxed);
+                        
+                        // Over budget: pass the frame through rather than fall behind, keeping the last VAD score
+                        let skip_denoiser = !idle_bypassed && !ab_bypassed && frame_deadline.as_mut().is_some_and(|deadline| deadline.should_skip());
+                        
+                        // With a crossover only the low band is denoised
+                        let denoiser_input: &[f32] = match band_splitter.as_mut() {
+                            Some(splitter) => {
+                                splitter.split(&frame_input, &mut low_band);
+                                &low_band
                             }
+                            None => &frame_input,
+                        };
+                        
+                        // The delay line sees every frame so it stays in step across bypasses. Passthrough
+                        // frames use it too, so entering or leaving the denoiser never repeats or drops audio.
+                        let aligned_dry = dry_delay.push(&frame_input);
+                        
+                        let mut denoise_start = std::time::Instant::now();
+                        let mut denoiser_modified = None;
+                        vad_score = if ab_bypassed {
+                            frame_output.copy_from_slice(aligned_dry);
+                            denoiser_catch_up.missed(denoiser_input);
+                            last_vad_score
+                        } else if idle_bypassed {
+                            // Nothing to clean up; the gate below still treats the frame as noise
+                            frame_output.copy_from_slice(aligned_dry);
+                            denoiser_catch_up.missed(denoiser_input);
+                            0.0
+                        } else if skip_denoiser {
+                            frame_output.copy_from_slice(aligned_dry);
+                            denoiser_catch_up.missed(denoiser_input);
+                            deadline_skips_clone.fetch_add(1, Ordering::Relaxed);
+                            last_vad_score
+                        } else {
+                            RELIABLE_DENOISER.with(|denoiser| {
+                                let mut denoiser = denoiser.borrow_mut();
                             
-                            // CRITICAL: The frame_output buffer should be initialized to zeros and passed as the output buffer
-                            // RNNoise will write the processed audio into this buffer
-                            // This is exactly how the working process.rs implementation does it
-                            frame_output.fill(0.0); // Ensure clean output buffer
+                                // Manual recovery when the RNN state is stuck (e.g. muffled after a loud transient)
+                                if denoiser_reset_clone.swap(false, Ordering::Relaxed) {
+                                    *denoiser = fresh_denoiser();
+                                    log::info!("🔄 Denoiser state reset at frame #{}", frame_count);
+                                }
+                            
+                                // Validate frame sizes before processing
+                                if frame_input.len() != current_frame_size {
+                                    log::warn!("🚨 Frame size mismatch: input={}, expected={}", 
+                                              frame_input.len(), current_frame_size);
+                                    frame_output.copy_from_slice(aligned_dry); // Pass through
+                                    return 0.0;
+                                }
                             
-                            // Apply RNNoise processing: input -> processing -> writes to output
-                            let vad = denoiser.process_frame(&mut frame_output, &frame_input);
+                                // Show RNNoise the frame it missed while bypassed; only this frame's
+                                // own processing time counts towards the deadline
+                                denoiser_catch_up.catch_up(|output, input| {
+                                    denoiser.process_frame(output, input);
+                                });
+                                denoise_start = std::time::Instant::now();
                             
-                            // Apple Silicon M4: Additional validation for ARM64 floating-point processing
-                            #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-                            {
-                                // On Apple Silicon, validate that RNNoise actually modified the output
-                                let output_changed = !frame_output.iter().zip(frame_input.iter()).all(|(o, i)| (o - i).abs() < 1e-10);
-                                if !output_changed && frame_count % 480 == 0 {
-                                    log::warn!("🚨 Apple Silicon M4: RNNoise output identical to input - processing may not be working!");
-                                    log::warn!("   Input sample: {:.6}, Output sample: {:.6}", frame_input[0], frame_output[0]);
-                                    log::warn!("   This suggests RNNoise is not actually processing the audio on ARM64");
-                                } else if frame_count % 480 == 0 {
-                                    log::info!("✅ Apple Silicon M4: RNNoise successfully modified audio (In: {:.6} -> Out: {:.6})", 
-                                               frame_input[0], frame_output[0]);
+                                // CRITICAL: The frame_output buffer should be initialized to zeros and passed as the output buffer
+                                // RNNoise will write the processed audio into this buffer
+                                // This is exactly how the working process.rs implementation does it
+                                frame_output.fill(0.0); // Ensure clean output buffer
+                            
+                                // Apply RNNoise processing: input -> processing -> writes to output
+                                let vad = denoiser.process_frame(&mut frame_output, denoiser_input);
+                            
+                                // Apple Silicon M4: Additional validation for ARM64 floating-point processing
+                                #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+                                {
+                                    // On Apple Silicon, validate that RNNoise actually modified the output
+                                    let output_changed = !frame_output.iter().zip(frame_input.iter()).all(|(o, i)| (o - i).abs() < 1e-10);
+                                    if !output_changed && frame_count % 480 == 0 {
+                                        log::warn!("🚨 Apple Silicon M4: RNNoise output identical to input - processing may not be working!");
+                                        log::warn!("   Input sample: {:.6}, Output sample: {:.6}", frame_input[0], frame_output[0]);
+                                        log::warn!("   This suggests RNNoise is not actually processing the audio on ARM64");
+                                    } else if frame_count % 480 == 0 {
+                                        log::info!("✅ Apple Silicon M4: RNNoise successfully modified audio (In: {:.6} -> Out: {:.6})", 
+                                                   frame_input[0], frame_output[0]);
+                                    }
                                 }
-                            }
                             
-                            // Validate output for any processing errors
-                            if frame_output.iter().any(|&x| !x.is_finite()) {
-                                log::warn!("🚨 RNNoise produced invalid output - using input passthrough");
-                                frame_output.copy_from_slice(&frame_input);
-                                return 0.0;
-                            }
+                                // Validate output for any processing errors
+                                if frame_output.iter().any(|&x| !x.is_finite()) {
+                                    log::warn!("🚨 RNNoise produced invalid output - using input passthrough");
+                                    frame_output.copy_from_slice(aligned_dry);
+                                    return 0.0;
+                                }
                             
-                            vad
-                        });
+                                // Cross-platform health check: did RNNoise actually change the audio?
+                                denoiser_modified = crate::ai_metrics::frame_modified(&frame_input, &frame_output);
+                            
+                                vad
+                            })
+                        };
+                        last_vad_score = vad_score;
+                        
+                        // Add the bypassed high band back; passthrough frames already hold the full band
+                        if denoiser_modified.is_some() {
+                            if let Some(splitter) = band_splitter.as_ref() {
+                                splitter.recombine(&mut frame_output);
+                            }
+                        }
+                        
+                        // Record per-model statistics for the dev panel
+                        if !skip_denoiser && !idle_bypassed && !ab_bypassed {
+                            let denoise_time = denoise_start.elapsed();
+                            if let Some(deadline) = frame_deadline.as_mut() {
+                                deadline.record(denoise_time);
+                            }
+                            if let Ok(mut stats) = model_stats_clone.try_lock() {
+                                stats.record_processing(denoise_time, vad_score);
+                            }
+                        }
                         
                         // Update diagnostic frame counter
                         DIAGNOSTIC_FRAME_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                         
                         // REMOVED: Apple Silicon M4 specific detection - using simplified processing for all platforms
                         
+                        // Pick the score that drives the gate
+                        let gate_vad_score = match (vad_source, analyzer_vad_score) {
+                            (analysis::VadSource::Analyzer, Some(score)) => score,
+                            _ => vad_score,
+                        };
+                        
+                        // Average the spectrum of background-only frames for noise profile export, in dev mode only
+                        // (bypassed frames count as neither: the profile and idle timer stay as they were)
+                        let noise_frame = !ab_bypassed && gate_vad_score < vad_threshold;
+                        if noise_frame {
+                            if noise_profile_enabled_clone.load(Ordering::Relaxed) {
+                                if let Ok(mut profile) = noise_profile_clone.try_lock() {
+                                    profile.add(&noise_spectrum.analyze(&frame_input));
+                                }
+                            }
+                            // Only background frames feed the noise floor, so speech never raises it
+                            let sensitivity = auto_sensitivity.update(&frame_input);
+                            auto_sensitivity_value_clone.store(sensitivity.to_bits() as u64, Ordering::Relaxed);
+                        }
+                        
+                        // Count silence towards the idle bypass
+                        if idle_bypass.update(!noise_frame) {
+                            idle_bypass_active_clone.store(true, Ordering::Relaxed);
+                            log::info!("💤 No speech for {}s - bypassing the denoiser until speech returns", idle_bypass_secs);
+                        }
+                        frames_since_speech_clone.store(idle_bypass.frames_since_speech(), Ordering::Relaxed);
+                        
                         // MAXIMUM AGGRESSIVENESS TEST MODE - for debugging noise cancellation issues
-                        // Check global flag set by GUI or environment variable
+                        // Check global flag set by GUI or environment variable. The optional startup
+                        // window gives the same exaggerated effect right after enabling, which made
+                        // it easy to hear whether audio was routed through Kwite at all; it is off by
+                        // default because the drop back to normal settings surprised users.
                         let use_max_test_mode = MAX_TEST_MODE_ENABLED.load(std::sync::atomic::Ordering::Relaxed) || 
-                                               frame_count < 480; // First 10 seconds also in max mode for immediate testing
+                                               frame_count <= startup_max_test_frames;
                         
                         // Check if pipeline verification mode is enabled
                         let use_verification_tone = PIPELINE_VERIFICATION_MODE.load(std::sync::atomic::Ordering::Relaxed);
@@ -512,19 +1511,86 @@ impl AudioManager {
                             }
                         } else {
                             // SIMPLIFIED: Use proven gain values from process.rs for ALL platforms
-                            // This removes the complex Apple Silicon M4 specific code that may be causing issues
-                            if vad_score < 0.5 { 
-                                0.1  // Low gain for background noise (same as process.rs)
-                            } else { 
-                                0.8  // High gain for detected speech (same as process.rs)
+                            // The VAD threshold decides *whether* a frame is speech, the noise gain
+                            // decides *how much* non-speech frames are suppressed
+                            // (low gain for background noise, default 0.1 as in process.rs; speech gets the speech gain)
+                            let gate = gain::ChannelGate {
+                                vad_threshold: f32::from_bits(vad_threshold_clone.load(Ordering::Relaxed) as u32),
+                                noise_gain: f32::from_bits(noise_gain_clone.load(Ordering::Relaxed) as u32),
+                            };
+                            // The gain floor keeps some room tone so heavy gating doesn't sound dead
+                            let floor = f32::from_bits(gain_floor_clone.load(Ordering::Relaxed) as u32);
+                            // Near the threshold the deadband holds the previous decision so the gate doesn't flap
+                            let is_speech = gate_hysteresis.is_speech(gate_vad_score, gate.vad_threshold);
+                            // ...and the hold keeps it open through the pauses between words
+                            let is_speech = gate_hold.is_open(is_speech);
+                            let speech_gain = f32::from_bits(speech_gain_clone.load(Ordering::Relaxed) as u32);
+                            let gain = gain::apply_gain_floor(if is_speech { speech_gain } else { gate.noise_gain }, floor);
+                            // Keep the attack of the first syllable while the VAD is still ramping up
+                            gain.max(onset_protector.floor(gate_vad_score, speech_gain))
+                        };
+                        
+                        // Classify the raw input and decide whether music should bypass RNNoise
+                        if room_profile_changed_clone.load(Ordering::Relaxed) {
+                            if let (Some((analyzer, _)), Ok(profile)) = (noise_classifier.as_mut(), room_profile_clone.try_lock()) {
+                                analyzer.set_room_profile(profile.clone());
+                                room_profile_changed_clone.store(false, Ordering::Relaxed);
+                            }
+                        }
+                        let noise_type = noise_classifier.as_mut().map(|(analyzer, gate)| {
+                            analyzer.analyze_audio_context(&frame_input);
+                            gate.update(analyzer.get_context_history())
+                        });
+                        // Debounced change events for integrators; skipped while a callback is being registered
+                        if let Some(event) = noise_type.and_then(|noise_type| noise_type_notifier.observe(noise_type, std::time::Instant::now())) {
+                            if let Ok(callback) = noise_type_callback_clone.try_lock() {
+                                if let Some(callback) = callback.as_ref() {
+                                    callback(event);
+                                }
                             }
+                        }
+                        let passthrough = match noise_type {
+                            Some(noise_type) => {
+                                let was_active = music_passthrough.is_active();
+                                let active = music_passthrough.update(noise_type, 10);
+                                if active != was_active {
+                                    if active {
+                                        log::info!("🎵 Sustained music detected - switching to passthrough");
+                                    } else {
+                                        log::info!("🗣 Speech resumed - noise suppression re-enabled");
+                                    }
+                                    music_passthrough_active_clone.store(active, Ordering::Relaxed);
+                                }
+                                active
+                            }
+                            None => false,
                         };
                         
-                        // Apply gain - simplified for all platforms
-                        for sample in frame_output.iter_mut() {
-                            *sample *= gain;
+                        if passthrough || ab_bypassed {
+                            // Leave music (or A/B bypassed audio) untouched; output trim and limiting still apply
+                            frame_output.copy_from_slice(aligned_dry);
+                            gain_smoother.jump_to(1.0);
+                        } else {
+                            // Apply gain - ramped from the previous frame's gain
+                            gain_smoother.apply(&mut frame_output, gain);
+                            
+                            // Blend the raw input back in according to the dry/wet mix; every
+                            // frame, denoised or passed through, is a frame late
+                            let dry_wet_mix = f32::from_bits(mix_clone.load(Ordering::Relaxed) as u32);
+                            gain::apply_dry_wet_mix(aligned_dry, &mut frame_output, dry_wet_mix);
                         }
                         
+                        // Comfort noise fades in as the smoothed gain closes towards the noise gain
+                        let comfort_level = f32::from_bits(comfort_noise_level_clone.load(Ordering::Relaxed) as u32);
+                        let closure = if passthrough || ab_bypassed {
+                            0.0
+                        } else {
+                            let load = |value: &AtomicU64| f32::from_bits(value.load(Ordering::Relaxed) as u32);
+                            let closed_gain = gain::apply_gain_floor(load(&noise_gain_clone), load(&gain_floor_clone));
+                            comfort_noise::gate_closure(gain_smoother.gain(), load(&speech_gain_clone), closed_gain)
+                        };
+                        comfort_noise.mix_into(&mut frame_output, comfort_level, closure);
+                        
                         // Add verification tone if pipeline verification mode is enabled
                         if use_verification_tone {
                             // Generate a subtle 440Hz test tone to verify audio routing
@@ -547,9 +1613,44 @@ impl AudioManager {
                             }
                         }
                         
+                        // Duck under a loud sidechain signal
+                        let ducking = match ducker.as_mut() {
+                            Some(ducker) => {
+                                let gain = ducker.next_gain(sidechain_level_clone.linear());
+                                ducking_gain_clone.store(gain.to_bits() as u64, Ordering::Relaxed);
+                                gain
+                            }
+                            None => 1.0,
+                        };
+                        
+                        // Apply the user output trim, then limit so a boost can't clip
+                        let output_trim = f32::from_bits(output_gain_clone.load(Ordering::Relaxed) as u32) * ducking;
+                        if output_trim != 1.0 {
+                            for sample in frame_output.iter_mut() {
+                                *sample *= output_trim;
+                            }
+                        }
+                        for sample in frame_output.iter_mut() {
+                            *sample = sample.clamp(-1.0, 1.0);
+                        }
+                        
                         // Update metrics with processing results
                         if let Ok(mut metrics) = ai_metrics_clone.try_lock() {
-                            metrics.record_frame(vad_score, std::time::Duration::from_millis(2));
+                            metrics.record_frame(vad_score, frame_start.elapsed());
+                            metrics.record_input_level(&frame_input);
+                            metrics.spectrum = spectrum;
+                            if let Some(modified) = denoiser_modified {
+                                metrics.record_frame_modified(modified);
+                            }
+                            if let Some(score) = analyzer_vad_score {
+                                metrics.analyzer_vad_score = Some(score);
+                            }
+                            if let Some(noise_type) = noise_type {
+                                metrics.update_noise_type(noise_type);
+                            }
+                            if noise_frame {
+                                metrics.record_noise_frame(&frame_input, &frame_output);
+                            }
                         }
                         
                         // Enhanced logging for debugging with MAX TEST MODE indicators
@@ -586,9 +1687,35 @@ impl AudioManager {
                             }
                         }
 
-                        // Always attempt to send processed data
-                        // Use try_send to avoid blocking if output thread is behind
-                        let _ = processed_tx.try_send(frame_output);
+                        // Keep the output muted while the denoiser's GRU state converges.
+                        // The frames above still went through RNNoise, which is the point.
+                        if frame_count <= warmup_frames {
+                            frame_output.fill(0.0);
+                            if frame_count == warmup_frames {
+                                log::info!("🔥 Denoiser warm-up complete - unmuting output");
+                            }
+                        }
+
+                        // Tap the final output for instant replay; skip the frame rather than wait for a save
+                        if let Some(replay) = &replay_buffer_clone {
+                            if let Ok(mut replay) = replay.try_lock() {
+                                replay.push(&frame_output);
+                            }
+                        }
+
+                        // Always attempt to send processed data without blocking
+                        // If the output thread is behind, the latency preset decides which frame is dropped
+                        // The monitor copy is dropped rather than waited for when its device falls behind
+                        if let Some(monitor) = &monitor_tx {
+                            let _ = monitor.try_send(latency::AudioFrame::new(frame_output.clone(), captured_at));
+                        }
+                        // Same for an active recording: its writer drops frames rather than stall us
+                        if let Ok(mut tap) = recording_tap_clone.try_lock() {
+                            if let Some(tap) = tap.as_mut() {
+                                tap.push(&frame_output, 1);
+                            }
+                        }
+                        latency::send_with_policy(&processed_tx, &processed_drain, latency::AudioFrame::new(frame_output, captured_at), latency_settings.overflow_policy);
                     }
                 }
             }
@@ -598,16 +1725,43 @@ impl AudioManager {
         // Routes processed audio to speakers or virtual audio device
         let running_clone = running.clone();
         let output_device_id_clone = output_device_id.to_string();
+        let output_stats = Arc::new(output::OutputStats {
+            latency_metrics: Some(ai_metrics.clone()),
+            ..Default::default()
+        });
+        output_stats.level.configure(settings.meter_ballistics, settings.meter_integration_ms);
+        let output_stats_clone = output_stats.clone();
+        let output_probe = latency_probe.clone();
+        let underrun_fill = settings.underrun_fill;
+        // Blocks arrive in bursts, so keep at least one block queued to bridge the gap between them
+        let prefill_samples = latency_settings.jitter_frames.max(processing_block_frames) * 480 * settings.channels as usize;
         log::info!("🔊 Starting audio output thread for device: {}", output_device_id);
         let output_thread = thread::spawn(move || {
             log::info!("Audio output thread started");
-            if let Err(e) = output::start_output_stream(processed_rx, running_clone, &output_device_id_clone) {
+            if let Err(e) = output::start_output_stream(processed_rx, running_clone, &output_device_id_clone, output_stats_clone, prefill_samples, output_probe, target_sample_rate, underrun_fill, stereo) {
                 log::error!("❌ Output stream error: {}", e);
             } else {
                 log::info!("✅ Output stream completed successfully");
             }
         });
 
+        // Optional monitor output so users can hear the processed audio locally
+        let monitor_thread = match (settings.monitor_device_id.clone(), monitor_rx) {
+            (Some(monitor_device_id), Some(monitor_rx)) => {
+                log::info!("🎧 Starting monitor output thread for device: {}", monitor_device_id);
+                let running_clone = running.clone();
+                // Own stats and an idle probe: the monitor is not metered or measured
+                let monitor_stats = Arc::new(output::OutputStats::default());
+                let monitor_probe = Arc::new(latency_probe::LatencyProbe::default());
+                Some(thread::spawn(move || {
+                    if let Err(e) = output::start_output_stream(monitor_rx, running_clone, &monitor_device_id, monitor_stats, prefill_samples, monitor_probe, target_sample_rate, underrun_fill, stereo) {
+                        log::error!("❌ Monitor output stream error: {}", e);
+                    }
+                }))
+            }
+            _ => None,
+        };
+
         log::info!("=== ✅ KWITE AUDIO MANAGER INITIALIZED SUCCESSFULLY ===");
         log::info!("🎤 Input: {} | 🔊 Output: {} | 🧠 AI: SIMPLIFIED Reliable Processing Ready", 
                   input_device_id, output_device_id);
@@ -616,25 +1770,63 @@ impl AudioManager {
             #[cfg(feature = "ai-enhanced")]
             _audio_analyzer: audio_analyzer,
             ai_metrics,
-            _input_thread: input_thread,
-            _output_thread: output_thread,
-            _process_thread: process_thread,
+            model_stats,
+            model_name,
+            input_stats,
+            output_stats,
+            latency_probe,
+            music_passthrough_active,
+            denoiser_reset,
+            deadline_skips,
+            sidechain_level,
+            ducking_gain,
+            auto_sensitivity: auto_sensitivity_enabled,
+            auto_sensitivity_value,
+            sidechain_thread,
+            monitor_thread,
+            frames_since_speech,
+            idle_bypass_active,
+            spectrum_enabled,
+            replay_buffer,
+            recording_tap,
+            noise_type_callback,
+            recording: None,
+            noise_profile,
+            noise_profile_enabled,
+            room_profile,
+            room_profile_changed,
+            input_device_id: input_device_id.to_string(),
+            output_device_id: output_device_id.to_string(),
+            settings: rebuild_settings,
+            input_thread: Some(input_thread),
+            output_thread: Some(output_thread),
+            process_thread: Some(process_thread),
             sensitivity,
+            output_gain,
+            input_gain,
+            vad_threshold,
+            noise_gain,
+            speech_gain,
+            gain_floor,
+            comfort_noise_level,
+            mix,
             running,
+            bypass,
         })
     }
 
-    /// Update noise cancellation sensitivity in real-time
+    /// Update the analyzer VAD sensitivity in real-time
     /// 
-    /// This method allows real-time adjustment of the noise cancellation threshold
-    /// without interrupting the audio processing pipeline. The new sensitivity
-    /// value is stored atomically and will be applied to subsequent audio frames.
+    /// Controls how aggressively the analyzer's voice activity detector (used for
+    /// the "Analyzer" VAD source and music detection) rejects noise. It does not
+    /// change the denoise gain threshold; see `update_vad_threshold` for that.
+    /// The new value is stored atomically and applied from the next frame.
     /// 
     /// ## Parameter Range
     /// 
-    /// - `0.01`: Very aggressive noise removal (may affect voice quality)
-    /// - `0.1`: Moderate noise removal (good balance)
-    /// - `0.5`: Conservative noise removal (preserves more original audio)
+    /// - `0.01`: Very aggressive VAD (more frames classified as noise)
+    /// - `0.1`: Default
+    /// - `0.5`: Conservative VAD
     /// 
     /// ## Implementation Note
     /// 
@@ -646,6 +1838,82 @@ impl AudioManager {
         log::debug!("Updated sensitivity to: {}", new_sensitivity);
     }
     
+    /// Update the output gain trim in real-time
+    /// 
+    /// The value is clamped to `MIN_OUTPUT_GAIN_DB..=MAX_OUTPUT_GAIN_DB` and
+    /// applied as the final multiplier before the output limiter.
+    pub fn update_output_gain_db(&mut self, gain_db: f32) {
+        let gain_db = gain_db.clamp(MIN_OUTPUT_GAIN_DB, MAX_OUTPUT_GAIN_DB);
+        self.output_gain.store(db_to_linear(gain_db).to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated output gain to: {:.1} dB", gain_db);
+    }
+    
+    /// Update the input pre-gain in real-time
+    /// 
+    /// The value is clamped to `MIN_INPUT_GAIN_DB..=MAX_INPUT_GAIN_DB` and applied
+    /// to each frame before it reaches the denoiser.
+    pub fn update_input_gain_db(&mut self, gain_db: f32) {
+        let gain_db = gain_db.clamp(MIN_INPUT_GAIN_DB, MAX_INPUT_GAIN_DB);
+        self.input_gain.store(db_to_linear(gain_db).to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated input pre-gain to: {:.1} dB", gain_db);
+    }
+    
+    /// Update the denoise gain threshold in real-time
+    /// 
+    /// Frames whose VAD score falls below this threshold are treated as noise and
+    /// attenuated. Independent of the analyzer sensitivity.
+    pub fn update_vad_threshold(&mut self, threshold: f32) {
+        let threshold = threshold.clamp(0.0, 1.0);
+        self.vad_threshold.store(threshold.to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated VAD threshold to: {:.2}", threshold);
+    }
+    
+    /// Update the gain applied to background noise frames in real-time
+    /// 
+    /// `0.0` removes noise frames entirely, `1.0` leaves them untouched.
+    pub fn update_noise_gain(&mut self, gain: f32) {
+        let gain = gain.clamp(0.0, 1.0);
+        self.noise_gain.store(gain.to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated noise gain to: {:.2}", gain);
+    }
+    
+    /// Update the gain applied to speech frames in real-time
+    /// 
+    /// `1.0` keeps speech at full level; lower values trade voice level for
+    /// a smaller jump between speech and suppressed noise.
+    pub fn update_speech_gain(&mut self, gain: f32) {
+        let gain = gain.clamp(0.0, 1.0);
+        self.speech_gain.store(gain.to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated speech gain to: {:.2}", gain);
+    }
+    
+    /// Update the gain floor in real-time
+    /// 
+    /// Frames are never attenuated below this gain, clamped to `0.0..=gain::MAX_GAIN_FLOOR`.
+    pub fn update_gain_floor(&mut self, floor: f32) {
+        let floor = floor.clamp(0.0, gain::MAX_GAIN_FLOOR);
+        self.gain_floor.store(floor.to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated gain floor to: {:.2}", floor);
+    }
+    
+    /// Update the comfort noise level in real-time
+    /// 
+    /// `None` turns comfort noise off; levels are clamped to the comfort noise range (dBFS).
+    pub fn update_comfort_noise_level(&mut self, level_db: Option<f32>) {
+        let level = level_db.map_or(0.0, comfort_noise::level_from_db);
+        self.comfort_noise_level.store(level.to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated comfort noise level to: {:?} dBFS", level_db);
+    }
+    
+    /// Update the dry/wet mix in real-time
+    /// 
+    /// 0.0 outputs the raw microphone signal, 1.0 the fully processed signal.
+    pub fn update_mix(&mut self, mix: f32) {
+        let mix = mix.clamp(0.0, 1.0);
+        self.mix.store(mix.to_bits() as u64, Ordering::Relaxed);
+        log::debug!("Updated dry/wet mix to: {:.2}", mix);
+    }
+    
     /// Switch to a different AI noise cancellation model
     /// 
     /// This method is simplified to always use RNNoise for reliability.
@@ -684,6 +1952,368 @@ impl AudioManager {
     pub fn get_ai_metrics(&self) -> SharedAiMetrics {
         self.ai_metrics.clone()
     }
+    
+    /// Get a snapshot of the denoiser model statistics
+    /// 
+    /// Returns `None` if the processing thread currently holds the lock; callers
+    /// should simply keep their previous snapshot in that case.
+    pub fn get_model_statistics(&self) -> Option<ModelStatistics> {
+        self.model_stats.try_lock().ok().map(|stats| stats.clone())
+    }
+    
+    /// Denoiser model the processing thread runs: the built-in weights or the custom file
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+    
+    /// Get the total number of clipped input samples since processing started
+    /// 
+    /// A rising count means the microphone gain is too hot; RNNoise handles
+    /// clipped input poorly, which users perceive as distortion.
+    pub fn input_clip_count(&self) -> u64 {
+        self.input_stats.clip_count()
+    }
+    
+    /// Whether the microphone has picked up anything above the noise floor
+    /// 
+    /// Stays false for a muted or disconnected mic, which lets usage statistics
+    /// tell silent sessions apart from real ones.
+    pub fn input_signal_detected(&self) -> bool {
+        self.input_stats.signal_detected()
+    }
+    
+    /// Native sample rate of the input device, once its stream has opened
+    /// 
+    /// Anything other than 48kHz is resampled before RNNoise and back on output.
+    pub fn input_sample_rate(&self) -> Option<u32> {
+        self.input_stats.sample_rate()
+    }
+    
+    /// Most recent raw input samples for the oscilloscope (mono, device rate)
+    pub fn input_scope(&self) -> Vec<f32> {
+        self.input_stats.scope_snapshot()
+    }
+    
+    /// Whether the OS denied access to the selected microphone
+    /// 
+    /// The input stream starts on its own thread, so a permission failure only
+    /// becomes visible here after `new` has already returned.
+    pub fn input_permission_denied(&self) -> bool {
+        self.input_stats.permission_denied()
+    }
+    
+    /// Whether the input stream died (device unplugged, or it never started)
+    /// 
+    /// The pipeline keeps its threads until dropped, but no audio arrives anymore.
+    pub fn input_stream_failed(&self) -> bool {
+        self.input_stats.stream_failed()
+    }
+    
+    /// Number of output samples that had to be clamped or replaced before the device write
+    /// 
+    /// Non-zero values point at an upstream stage producing NaN or out-of-range audio.
+    pub fn output_sanitized_count(&self) -> u64 {
+        self.output_stats.sanitized_count()
+    }
+    
+    /// Number of output callbacks that ran dry and played silence (dropouts)
+    pub fn output_underrun_count(&self) -> u64 {
+        self.output_stats.underrun_count()
+    }
+    
+    /// Number of callbacks the output device has run; the watchdog uses it to spot a dead output
+    pub fn output_callback_count(&self) -> u64 {
+        self.output_stats.callback_count()
+    }
+    
+    /// Current input level in dBFS, with the configured meter ballistics
+    pub fn input_level_db(&self) -> f32 {
+        self.input_stats.level.level_db()
+    }
+    
+    /// Current output level in dBFS, with the configured meter ballistics
+    pub fn output_level_db(&self) -> f32 {
+        self.output_stats.level.level_db()
+    }
+    
+    /// Change the level meter ballistics of both taps in real-time
+    pub fn configure_meters(&mut self, ballistics: meter::MeterBallistics, integration_ms: u32) {
+        self.input_stats.level.configure(ballistics, integration_ms);
+        self.output_stats.level.configure(ballistics, integration_ms);
+        self.settings.meter_ballistics = ballistics;
+        self.settings.meter_integration_ms = integration_ms;
+    }
+    
+    /// Number of frames passed through unprocessed to stay within the frame deadline
+    pub fn deadline_skip_count(&self) -> u64 {
+        self.deadline_skips.load(Ordering::Relaxed)
+    }
+    
+    /// Frames processed since the last frame the gate treated as speech (10ms each)
+    pub fn frames_since_speech(&self) -> u64 {
+        self.frames_since_speech.load(Ordering::Relaxed)
+    }
+    
+    /// Whether the denoiser is currently bypassed after prolonged silence
+    pub fn idle_bypass_active(&self) -> bool {
+        self.idle_bypass_active.load(Ordering::Relaxed)
+    }
+    
+    /// Switch between processed and raw audio without stopping the pipeline
+    /// 
+    /// While bypassed the input reaches the output without RNNoise, gating or the
+    /// dry/wet mix, so users can A/B the noise cancellation. Input and output gain
+    /// still apply to keep both sides at the same level. Takes effect from the next frame.
+    pub fn set_bypass(&self, bypass: bool) {
+        if self.bypass.swap(bypass, Ordering::Relaxed) != bypass {
+            log::info!("🔀 Noise cancellation {}", if bypass { "bypassed for A/B comparison" } else { "re-engaged" });
+        }
+    }
+    
+    /// Whether the A/B bypass is passing raw audio through
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass.load(Ordering::Relaxed)
+    }
+    
+    /// Turn the input spectrum analysis on or off (published in the AI metrics)
+    pub fn set_spectrum_enabled(&self, enabled: bool) {
+        self.spectrum_enabled.store(enabled, Ordering::Relaxed);
+    }
+    
+    /// Start a round-trip latency measurement
+    /// 
+    /// Needs a loopback path from the output back to the input (speakers audible to
+    /// the microphone, or a virtual cable routed back). Processed audio is muted for
+    /// the duration of the measurement.
+    pub fn measure_latency(&self) {
+        log::info!("⏱ Starting round-trip latency measurement");
+        self.latency_probe.request();
+    }
+    
+    /// Switch the diagnostic toggles to a preset in one step
+    /// 
+    /// Intended for support tooling; the toggles are process-wide, so this also
+    /// affects pipelines created later.
+    pub fn apply_diagnostic_preset(&self, preset: DiagnosticPreset) {
+        apply_diagnostic_preset(preset);
+    }
+    
+    /// Status of the most recent latency measurement
+    pub fn latency_probe_status(&self) -> latency_probe::ProbeStatus {
+        self.latency_probe.status()
+    }
+    
+    /// Zero the AI metrics, model statistics and stream counters in place
+    /// 
+    /// Audio keeps flowing and the denoiser keeps its state, so a settings change
+    /// can be measured before/after on the same continuous stream. The watchdog's
+    /// frame counter is left alone so a reset never looks like a stall.
+    pub fn reset_metrics(&self) {
+        if let Ok(mut metrics) = self.ai_metrics.lock() {
+            metrics.reset();
+        }
+        if let Ok(mut stats) = self.model_stats.lock() {
+            stats.reset();
+        }
+        self.input_stats.reset_counters();
+        self.output_stats.reset_counters();
+        self.deadline_skips.store(0, Ordering::Relaxed);
+        log::info!("📊 Processing metrics reset");
+    }
+    
+    /// Reinitialize the denoiser's recurrent state without restarting the streams
+    /// 
+    /// The processing thread picks the request up before its next frame. Cheaper
+    /// than a full stop/start when the output stays muffled after a loud transient.
+    pub fn reset_denoiser(&self) {
+        self.denoiser_reset.store(true, Ordering::Relaxed);
+        log::info!("Denoiser reset requested");
+    }
+    
+    /// Snapshot of the averaged background noise spectrum
+    /// 
+    /// `None` until at least one frame has been classified as noise.
+    pub fn noise_profile(&self) -> Option<analysis::NoiseProfile> {
+        self.noise_profile.lock().ok().and_then(|profile| profile.snapshot())
+    }
+    
+    /// Accumulate the noise profile (dev mode only; it costs an FFT per noise frame)
+    pub fn set_noise_profile_enabled(&self, enabled: bool) {
+        self.noise_profile_enabled.store(enabled, Ordering::Relaxed);
+    }
+    
+    /// Seed the running noise profile with a previously exported one
+    /// 
+    /// The noise classifier also uses it to recognise this room's background
+    /// (see `AudioAnalyzer::set_room_profile`).
+    pub fn seed_noise_profile(&self, profile: &analysis::NoiseProfile) {
+        if let Ok(mut accumulator) = self.noise_profile.lock() {
+            accumulator.seed(profile);
+            log::info!("Noise profile seeded from {} ({} frames)", profile.captured_at, profile.frames);
+        }
+        self.set_room_profile(Some(profile.profile.clone()));
+    }
+    
+    /// Hand the noise classifier a background profile to match against
+    fn set_room_profile(&self, profile: Option<analysis::FrequencyProfile>) {
+        if let Ok(mut slot) = self.room_profile.lock() {
+            *slot = profile;
+            self.room_profile_changed.store(true, Ordering::Relaxed);
+        }
+    }
+    
+    /// Whether instant replay is recording processed output
+    pub fn replay_enabled(&self) -> bool {
+        self.replay_buffer.is_some()
+    }
+    
+    /// Save the instant replay buffer to a 16-bit WAV file
+    /// 
+    /// Only the copy happens under the lock; the file is written afterwards so the
+    /// processing thread skips at most a frame of the replay while saving.
+    /// Returns the number of seconds written.
+    pub fn save_replay(&self, path: &std::path::Path) -> Result<f32, Box<dyn std::error::Error + Send + Sync>> {
+        let replay = self.replay_buffer.as_ref().ok_or("Instant replay is disabled")?;
+        let (samples, sample_rate) = {
+            let buffer = replay.lock().map_err(|_| "Replay buffer lock poisoned")?;
+            (buffer.snapshot(), buffer.sample_rate())
+        };
+        if samples.is_empty() {
+            return Err("Nothing recorded yet".into());
+        }
+        
+        replay::write_wav_pcm16(path, &samples, sample_rate)?;
+        let seconds = samples.len() as f32 / sample_rate as f32;
+        log::info!("💾 Saved {:.1}s instant replay to {}", seconds, path.display());
+        Ok(seconds)
+    }
+    
+    /// Register a callback fired when the classified noise type changes
+    /// 
+    /// Events are debounced (see `NoiseTypeNotifier`) and delivered on the
+    /// processing thread, so the callback must return quickly. `None` removes
+    /// it. The callback carries over to a rebuilt or switched pipeline.
+    pub fn set_noise_type_callback(&self, callback: Option<analysis::NoiseTypeCallback>) {
+        if let Ok(mut slot) = self.noise_type_callback.lock() {
+            *slot = callback;
+        }
+    }
+    
+    /// Start recording the processed output to a 48kHz mono WAV file at `path`
+    /// 
+    /// Any recording already running is finished first. Frames reach the file
+    /// through a bounded queue, so a slow disk drops frames instead of stalling
+    /// the audio path.
+    pub fn start_recording(&mut self, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.recording.is_some() {
+            self.stop_recording()?;
+        }
+        let (tap, writer) = recorder::start(path)?;
+        *self.recording_tap.lock().map_err(|_| "Recording tap lock poisoned")? = Some(tap);
+        self.recording = Some(writer);
+        Ok(())
+    }
+    
+    /// Stop the output recording and finalize the file
+    /// 
+    /// Returns the path and the number of seconds written, or `None` if no
+    /// recording was running.
+    pub fn stop_recording(&mut self) -> Result<Option<(std::path::PathBuf, f32)>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(writer) = self.recording.take() else {
+            return Ok(None);
+        };
+        // Dropping the tap closes the queue, so the writer drains it and finishes
+        let tap = self.recording_tap.lock().map_err(|_| "Recording tap lock poisoned")?.take();
+        let dropped_frames = tap.map_or(0, |tap| tap.dropped_frames());
+        
+        let path = writer.path().to_path_buf();
+        let seconds = writer.finish()?;
+        if dropped_frames > 0 {
+            log::warn!("⏺ Recording dropped {} frames because the writer fell behind", dropped_frames);
+        }
+        log::info!("⏺ Saved {:.1}s recording to {}", seconds, path.display());
+        Ok(Some((path, seconds)))
+    }
+    
+    /// Time since the active output recording started (`None` when not recording)
+    pub fn recording_elapsed(&self) -> Option<std::time::Duration> {
+        self.recording.as_ref().map(|writer| writer.elapsed())
+    }
+    
+    /// Feed the ducking sidechain a level in dBFS from an external source
+    /// 
+    /// For integrations without a capture device (e.g. a game overlay or a
+    /// control bridge). With a sidechain device configured, the device's next
+    /// block overwrites this value.
+    pub fn set_sidechain_level_db(&self, level_db: f32) {
+        self.sidechain_level.set_db(level_db);
+    }
+    
+    /// Gain reduction currently applied by sidechain ducking (dB, 0 = none)
+    pub fn ducking_reduction_db(&self) -> f32 {
+        let gain = f32::from_bits(self.ducking_gain.load(Ordering::Relaxed) as u32);
+        -20.0 * gain.max(1e-5).log10()
+    }
+    
+    /// Let the measured noise floor drive the analyzer sensitivity instead of the slider
+    /// 
+    /// Takes effect from the next frame; turning it off restores the manual value.
+    pub fn set_auto_sensitivity(&self, enabled: bool) {
+        if self.auto_sensitivity.swap(enabled, Ordering::Relaxed) != enabled {
+            log::info!("🎚 Auto sensitivity {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+    
+    /// Sensitivity currently derived from the noise floor (`None` while auto sensitivity is off)
+    pub fn auto_sensitivity(&self) -> Option<f32> {
+        self.auto_sensitivity.load(Ordering::Relaxed)
+            .then(|| f32::from_bits(self.auto_sensitivity_value.load(Ordering::Relaxed) as u32))
+    }
+    
+    /// Whether music passthrough is currently bypassing noise suppression
+    pub fn music_passthrough_active(&self) -> bool {
+        self.music_passthrough_active.load(Ordering::Relaxed)
+    }
+    
+    /// Tear this pipeline down and start a fresh one on the same devices
+    /// 
+    /// Live adjustments (sensitivity, gains, mix) carry over. The old streams are
+    /// stopped before the new ones open so exclusive-mode devices are free.
+    pub fn rebuild(self) -> Result<AudioManager, Box<dyn std::error::Error + Send + Sync>> {
+        let input_device_id = self.input_device_id.clone();
+        let output_device_id = self.output_device_id.clone();
+        self.switch_devices(&input_device_id, &output_device_id)
+    }
+    
+    /// Tear this pipeline down and start a fresh one on different devices
+    /// 
+    /// Used for live device swaps; settings and live adjustments carry over
+    /// exactly as with `rebuild`.
+    pub fn switch_devices(self, input_device_id: &str, output_device_id: &str) -> Result<AudioManager, Box<dyn std::error::Error + Send + Sync>> {
+        let load = |value: &AtomicU64| f32::from_bits(value.load(Ordering::Relaxed) as u32);
+        let sensitivity = load(&self.sensitivity);
+        let mut settings = self.settings.clone();
+        settings.output_gain_db = 20.0 * load(&self.output_gain).max(f32::MIN_POSITIVE).log10();
+        settings.input_gain_db = 20.0 * load(&self.input_gain).max(f32::MIN_POSITIVE).log10();
+        settings.vad_threshold = load(&self.vad_threshold);
+        settings.noise_gain = load(&self.noise_gain);
+        settings.speech_gain = load(&self.speech_gain);
+        settings.gain_floor = load(&self.gain_floor);
+        let comfort_noise_level = load(&self.comfort_noise_level);
+        settings.comfort_noise_db = (comfort_noise_level > 0.0).then(|| 20.0 * comfort_noise_level.log10());
+        settings.mix = load(&self.mix);
+        settings.auto_sensitivity = self.auto_sensitivity.load(Ordering::Relaxed);
+        let noise_type_callback = self.noise_type_callback.lock().ok().and_then(|mut callback| callback.take());
+        let room_profile = self.room_profile.lock().ok().and_then(|profile| profile.clone());
+        
+        drop(self);
+        let manager = AudioManager::new(sensitivity, input_device_id, output_device_id, settings)?;
+        manager.set_noise_type_callback(noise_type_callback);
+        if room_profile.is_some() {
+            manager.set_room_profile(room_profile);
+        }
+        Ok(manager)
+    }
 }
 
 impl Drop for AudioManager {
@@ -697,20 +2327,41 @@ impl Drop for AudioManager {
     /// 
     /// 1. Set the running flag to false (stops all thread loops)
     /// 2. Audio threads detect the flag and exit their main loops
-    /// 3. Device handles are released automatically
-    /// 4. Thread handles ensure cleanup completion
+    /// 3. Device handles are released as the threads return
+    /// 4. Threads are joined, sharing a `SHUTDOWN_JOIN_TIMEOUT` budget
     /// 
-    /// ## Thread Coordination
+    /// ## Misbehaving Drivers
     /// 
-    /// The atomic `running` flag provides a clean coordination mechanism that
-    /// doesn't require explicit thread joining or complex synchronization.
+    /// A thread stuck inside a device call never sees the flag. Rather than hang
+    /// the app on quit or device switch, such a thread is logged and detached.
     fn drop(&mut self) {
         // Signal all threads to stop processing
         self.running.store(false, Ordering::Relaxed);
-        log::info!("AudioManager stopped");
         
-        // Note: Thread handles will be automatically joined when dropped,
-        // ensuring clean shutdown without explicit thread management
+        let deadline = std::time::Instant::now() + SHUTDOWN_JOIN_TIMEOUT;
+        let mut clean = true;
+        for (name, handle) in [
+            ("Input", self.input_thread.take()),
+            ("Process", self.process_thread.take()),
+            ("Output", self.output_thread.take()),
+            ("Sidechain", self.sidechain_thread.take()),
+            ("Monitor", self.monitor_thread.take()),
+        ] {
+            if let Some(handle) = handle {
+                clean &= join_with_deadline(handle, name, deadline);
+            }
+        }
+        
+        // Finalize a running recording so its WAV header is valid
+        if let Err(e) = self.stop_recording() {
+            log::warn!("Failed to finish the output recording: {}", e);
+        }
+        
+        if clean {
+            log::info!("AudioManager stopped");
+        } else {
+            log::warn!("AudioManager stopped with detached threads");
+        }
     }
 }
 
@@ -21,6 +21,12 @@
 //! - CPU availability and performance requirements  
 //! - User preferences and use case requirements
 //! 
+//! ## Custom Model Weights
+//! 
+//! RNNoise weights trained by the community can be loaded from a file with
+//! `load_custom_rnnoise_model`. Invalid files are rejected so the caller can
+//! fall back to the built-in model.
+//! 
 //! ## Future Extensibility
 //! 
 //! The architecture supports adding new models by implementing support in the
@@ -30,8 +36,12 @@
 // Allow dead code for future AI model implementations
 #![allow(dead_code)]
 
-use nnnoiseless::DenoiseState;
+use nnnoiseless::{DenoiseState, RnnModel};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Duration;
 use crate::logger::log;
 
@@ -142,6 +152,45 @@ impl fmt::Display for NoiseModel {
     }
 }
 
+/// Largest custom model file accepted (the built-in weights are well under 1MB)
+const MAX_CUSTOM_MODEL_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Custom RNNoise models loaded so far, keyed by file path
+/// 
+/// `DenoiseState` borrows its model for its whole lifetime, so each model file is
+/// loaded (and leaked) once and then shared by every later pipeline start.
+static CUSTOM_MODELS: Lazy<Mutex<HashMap<PathBuf, &'static RnnModel>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Load and validate custom RNNoise model weights from a file
+/// 
+/// Returns an error if the file is missing, unreasonably large, or not a valid
+/// RNNoise model; callers should then fall back to the built-in model.
+pub fn load_custom_rnnoise_model(path: &Path) -> Result<&'static RnnModel, Box<dyn std::error::Error + Send + Sync>> {
+    let mut models = CUSTOM_MODELS.lock().map_err(|_| "Custom model cache is poisoned")?;
+    if let Some(model) = models.get(path) {
+        return Ok(*model);
+    }
+
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| format!("Cannot access model file {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", path.display()).into());
+    }
+    if metadata.len() > MAX_CUSTOM_MODEL_BYTES {
+        return Err(format!("{} is too large to be an RNNoise model", path.display()).into());
+    }
+
+    let bytes = std::fs::read(path)?;
+    let model = RnnModel::from_bytes(&bytes)
+        .ok_or_else(|| format!("{} is not a valid RNNoise model file", path.display()))?;
+
+    let model: &'static RnnModel = Box::leak(Box::new(model));
+    models.insert(path.to_path_buf(), model);
+    log::info!("🧠 Loaded custom RNNoise model from {}", path.display());
+    Ok(model)
+}
+
 /// Use case categories for model recommendation
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UseCase {
@@ -258,7 +307,7 @@ impl EnhancedAudioProcessor {
         
         if new_active_model != self.active_model {
             self.active_model = new_active_model;
-            self.model_stats.reset(); // Reset statistics for new model
+            self.model_stats.reset_for_model_switch(); // Reset statistics for new model
         }
         
         Ok(())
@@ -292,6 +341,7 @@ pub struct ModelStatistics {
     avg_processing_time: std::time::Duration,
     avg_vad_score: f32,
     peak_processing_time: std::time::Duration,
+    model_switches: u64,
 }
 
 impl ModelStatistics {
@@ -301,6 +351,7 @@ impl ModelStatistics {
             avg_processing_time: std::time::Duration::ZERO,
             avg_vad_score: 0.0,
             peak_processing_time: std::time::Duration::ZERO,
+            model_switches: 0,
         }
     }
     
@@ -326,11 +377,19 @@ impl ModelStatistics {
         *self = Self::new();
     }
     
+    /// Reset per-model statistics after a model switch, keeping the switch count
+    pub fn reset_for_model_switch(&mut self) {
+        let model_switches = self.model_switches + 1;
+        *self = Self::new();
+        self.model_switches = model_switches;
+    }
+    
     // Getters for statistics
     pub fn total_frames(&self) -> u64 { self.total_frames }
     pub fn avg_processing_time(&self) -> std::time::Duration { self.avg_processing_time }
     pub fn avg_vad_score(&self) -> f32 { self.avg_vad_score }
     pub fn peak_processing_time(&self) -> std::time::Duration { self.peak_processing_time }
+    pub fn model_switches(&self) -> u64 { self.model_switches }
 }
 
 #[cfg(test)]
@@ -347,6 +406,19 @@ mod tests {
         assert_eq!(NoiseModel::RNNoise.cpu_usage_level(), 2);
     }
     
+    #[test]
+    fn test_invalid_custom_model_is_rejected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        
+        let garbage = temp_dir.path().join("garbage.rnn");
+        std::fs::write(&garbage, b"definitely not rnnoise weights").unwrap();
+        assert!(load_custom_rnnoise_model(&garbage).is_err());
+        
+        let missing = temp_dir.path().join("missing.rnn");
+        assert!(load_custom_rnnoise_model(&missing).is_err());
+        assert!(load_custom_rnnoise_model(temp_dir.path()).is_err(), "Directories are not model files");
+    }
+    
     #[test]
     fn test_enhanced_processor_creation() {
         // Test RNNoise
@@ -0,0 +1,161 @@
+//! # Music Passthrough Module
+//!
+//! RNNoise is trained to keep speech and remove everything else, so music played
+//! into the microphone (or shared through a loopback input) comes out mangled. This
+//! module implements an opt-in state machine that switches the processing thread
+//! to passthrough while music is detected and back to noise suppression once
+//! speech resumes.
+//!
+//! ## State Machine
+//!
+//! ```text
+//!            Music for engage_ms
+//! Suppress ───────────────────────► Passthrough
+//!    ▲                                   │
+//!    └───────────────────────────────────┘
+//!            Speech for release_ms
+//! ```
+//!
+//! Dwell times are measured in consecutive analyzer frames. While passthrough is
+//! active, silence and other noise types neither extend nor cancel the speech
+//! dwell, but any music resets it, so a pause in a song doesn't re-enable
+//! suppression.
+
+use crate::audio::analysis::NoiseType;
+use serde::{Deserialize, Serialize};
+
+/// Default time music must be sustained before passthrough engages (ms)
+pub const DEFAULT_ENGAGE_MS: u32 = 3000;
+/// Default time speech must be sustained before suppression resumes (ms)
+pub const DEFAULT_RELEASE_MS: u32 = 1000;
+/// Consecutive frames the noise type gate needs before the machine sees a change
+pub const NOISE_TYPE_GATE_FRAMES: usize = 8;
+
+/// User configuration for automatic music passthrough
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MusicPassthroughSettings {
+    /// Switch to passthrough on sustained music (off by default)
+    pub enabled: bool,
+    /// Music dwell time before passthrough engages (ms)
+    pub engage_ms: u32,
+    /// Speech dwell time before suppression resumes (ms)
+    pub release_ms: u32,
+}
+
+impl Default for MusicPassthroughSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            engage_ms: DEFAULT_ENGAGE_MS,
+            release_ms: DEFAULT_RELEASE_MS,
+        }
+    }
+}
+
+/// Passthrough state machine driven by the smoothed noise type
+#[derive(Debug, Clone)]
+pub struct MusicPassthrough {
+    settings: MusicPassthroughSettings,
+    /// Consecutive music time while suppressing (ms)
+    music_ms: u32,
+    /// Accumulated speech time while passing through (ms)
+    speech_ms: u32,
+    /// Whether passthrough is currently engaged
+    active: bool,
+}
+
+impl MusicPassthrough {
+    /// Create a state machine in the suppressing state
+    pub fn new(settings: MusicPassthroughSettings) -> Self {
+        Self {
+            settings,
+            music_ms: 0,
+            speech_ms: 0,
+            active: false,
+        }
+    }
+
+    /// Feed one analyzed frame of `frame_ms` duration
+    ///
+    /// Returns whether passthrough is active after this frame.
+    pub fn update(&mut self, noise_type: NoiseType, frame_ms: u32) -> bool {
+        if !self.settings.enabled {
+            return false;
+        }
+
+        if self.active {
+            match noise_type {
+                NoiseType::Speech => self.speech_ms = self.speech_ms.saturating_add(frame_ms),
+                NoiseType::Music => self.speech_ms = 0,
+                _ => {}
+            }
+            if self.speech_ms >= self.settings.release_ms {
+                self.active = false;
+                self.speech_ms = 0;
+            }
+        } else {
+            if noise_type == NoiseType::Music {
+                self.music_ms = self.music_ms.saturating_add(frame_ms);
+            } else {
+                self.music_ms = 0;
+            }
+            if self.music_ms >= self.settings.engage_ms {
+                self.active = true;
+                self.music_ms = 0;
+            }
+        }
+
+        self.active
+    }
+
+    /// Whether passthrough is currently engaged
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(machine: &mut MusicPassthrough, noise_type: NoiseType, frames: usize) -> bool {
+        let mut active = machine.is_active();
+        for _ in 0..frames {
+            active = machine.update(noise_type, 10);
+        }
+        active
+    }
+
+    #[test]
+    fn test_passthrough_dwell_times() {
+        let mut machine = MusicPassthrough::new(MusicPassthroughSettings {
+            enabled: true,
+            engage_ms: 500,
+            release_ms: 200,
+        });
+
+        // Short bursts of music don't engage
+        assert!(!feed(&mut machine, NoiseType::Music, 40));
+        assert!(!feed(&mut machine, NoiseType::Speech, 1));
+        assert!(!feed(&mut machine, NoiseType::Music, 40));
+
+        // Sustained music does
+        assert!(feed(&mut machine, NoiseType::Music, 10));
+
+        // Silence between songs and brief speech keep passthrough engaged
+        assert!(feed(&mut machine, NoiseType::Silence, 100));
+        assert!(feed(&mut machine, NoiseType::Speech, 15));
+        assert!(feed(&mut machine, NoiseType::Music, 1));
+        assert!(feed(&mut machine, NoiseType::Speech, 15));
+
+        // Sustained speech reverts to suppression
+        assert!(!feed(&mut machine, NoiseType::Speech, 5));
+    }
+
+    #[test]
+    fn test_disabled_never_engages() {
+        let mut machine = MusicPassthrough::new(MusicPassthroughSettings::default());
+        assert!(!feed(&mut machine, NoiseType::Music, 1000));
+    }
+}
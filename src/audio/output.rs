@@ -12,6 +12,8 @@
 //! - **Format Adaptation**: Converts mono processed audio to device's required format
 //! - **Buffer Management**: Prevents audio dropouts with adaptive buffering
 //! - **Real-time Performance**: Optimized for low-latency audio delivery
+//! - **Sample Sanitizing**: Clamps out-of-range and NaN samples before they reach the driver
+//! - **Underrun Concealment**: Optionally fades out a repeat of the last frame instead of a hard gap
 //! 
 //! ## Virtual Audio Cable Integration
 //! 
@@ -29,11 +31,190 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use crossbeam_channel::Receiver;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::collections::VecDeque;
 use crate::logger::log;
-use crate::audio::devices::{get_device_by_id, find_virtual_output_device};
+use crate::audio::devices::{get_device_by_id, find_virtual_output_device, config_for_sample_rate};
+use crate::audio::resampling::{SimpleResampler, RNNOISE_SAMPLE_RATE};
+use crate::audio::latency_probe::LatencyProbe;
+use crate::audio::meter::LevelTap;
+use crate::audio::latency::AudioFrame;
+use crate::ai_metrics::SharedAiMetrics;
+use crate::audio::stereo;
 use cpal::{BufferSize, StreamConfig};
+use serde::{Deserialize, Serialize};
+
+/// Samples of recent output repeated to conceal an underrun (one 10ms frame at 48kHz)
+pub const CONCEALMENT_SAMPLES: usize = 480;
+
+/// What the output plays when it runs out of processed audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UnderrunFill {
+    /// Play silence until audio arrives again (default)
+    #[default]
+    Silence,
+    /// Repeat the last frame once, fading to silence, to soften brief dropouts
+    RepeatLastFrame,
+}
+
+/// Fills underruns with a fading repeat of the most recent output
+///
+/// Keeps a ring of the last `CONCEALMENT_SAMPLES` played samples. When the
+/// buffer runs dry the ring is replayed once with a linear fade to zero, after
+/// which it plays silence; real audio resets the fade.
+#[derive(Debug)]
+pub struct UnderrunConcealer {
+    history: Vec<f32>,
+    write_pos: usize,
+    conceal_pos: usize,
+}
+
+impl UnderrunConcealer {
+    pub fn new() -> Self {
+        Self {
+            history: vec![0.0; CONCEALMENT_SAMPLES],
+            write_pos: 0,
+            conceal_pos: CONCEALMENT_SAMPLES,
+        }
+    }
+
+    /// Remember a sample that was actually played
+    #[inline]
+    pub fn record(&mut self, sample: f32) {
+        self.history[self.write_pos] = sample;
+        self.write_pos = (self.write_pos + 1) % self.history.len();
+        self.conceal_pos = 0;
+    }
+
+    /// Next concealment sample for a missing one
+    #[inline]
+    pub fn conceal(&mut self) -> f32 {
+        let len = self.history.len();
+        if self.conceal_pos >= len {
+            return 0.0;
+        }
+        let fade = 1.0 - self.conceal_pos as f32 / len as f32;
+        let sample = self.history[(self.write_pos + self.conceal_pos) % len] * fade;
+        self.conceal_pos += 1;
+        sample
+    }
+}
+
+impl Default for UnderrunConcealer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Brings processed 48kHz frames to the output device's rate
+///
+/// Processed audio is always 48kHz; a narrowband target or a device fixed at
+/// 44.1kHz needs it resampled so playback speed and pitch are right. Stereo
+/// frames are resampled per channel, and at 48kHz frames pass through as is.
+pub(crate) struct DeviceRateConverter {
+    resample: bool,
+    stereo: bool,
+    downsampler: SimpleResampler,
+    right_downsampler: SimpleResampler,
+    left: Vec<f32>,
+    right: Vec<f32>,
+    resampled: Vec<f32>,
+    right_resampled: Vec<f32>,
+}
+
+impl DeviceRateConverter {
+    pub(crate) fn new(device_rate: u32, stereo: bool) -> Self {
+        Self {
+            resample: device_rate != RNNOISE_SAMPLE_RATE,
+            stereo,
+            downsampler: SimpleResampler::new(RNNOISE_SAMPLE_RATE, device_rate),
+            right_downsampler: SimpleResampler::new(RNNOISE_SAMPLE_RATE, device_rate),
+            left: Vec::new(),
+            right: Vec::new(),
+            resampled: Vec::new(),
+            right_resampled: Vec::new(),
+        }
+    }
+
+    /// Append one processed frame to `buffer` at the device rate
+    pub(crate) fn queue(&mut self, samples: Vec<f32>, buffer: &mut VecDeque<f32>) {
+        if !self.resample {
+            buffer.extend(samples);
+        } else if self.stereo {
+            stereo::deinterleave(&samples, &mut self.left, &mut self.right);
+            self.downsampler.process(&self.left, &mut self.resampled);
+            self.right_downsampler.process(&self.right, &mut self.right_resampled);
+            buffer.extend(stereo::interleave(&self.resampled, &self.right_resampled));
+        } else {
+            self.downsampler.process(&samples, &mut self.resampled);
+            buffer.extend(self.resampled.iter().copied());
+        }
+    }
+}
+
+/// Output statistics shared between the output callback and the GUI
+/// 
+/// All counters are atomics so the real-time callback never blocks.
+#[derive(Debug, Default)]
+pub struct OutputStats {
+    /// Total number of samples that were NaN, infinite or outside [-1.0, 1.0]
+    pub sanitized_samples: AtomicU64,
+    /// Callbacks that ran out of processed audio and had to play silence
+    pub underruns: AtomicU64,
+    /// Output callbacks run since the stream started (never reset)
+    pub callbacks: AtomicU64,
+    /// Level meter of the audio actually written to the device
+    pub level: LevelTap,
+    /// Metrics receiving the measured end-to-end latency (`None` records nothing)
+    pub latency_metrics: Option<SharedAiMetrics>,
+}
+
+impl OutputStats {
+    /// Total sanitized samples since the stream started
+    pub fn sanitized_count(&self) -> u64 {
+        self.sanitized_samples.load(Ordering::Relaxed)
+    }
+    
+    /// Total output underruns (audible dropouts) since the stream started
+    pub fn underrun_count(&self) -> u64 {
+        self.underruns.load(Ordering::Relaxed)
+    }
+    
+    /// Total output callbacks since the stream started; stops advancing if the device dies
+    pub fn callback_count(&self) -> u64 {
+        self.callbacks.load(Ordering::Relaxed)
+    }
+    
+    /// Record the capture-to-playback latency of one frame, skipping it if the metrics are busy
+    pub fn record_latency(&self, latency: std::time::Duration) {
+        if let Some(metrics) = &self.latency_metrics {
+            if let Ok(mut metrics) = metrics.try_lock() {
+                metrics.record_end_to_end_latency(latency);
+            }
+        }
+    }
+    
+    /// Zero the sanitized sample and underrun counters
+    pub fn reset_counters(&self) {
+        self.sanitized_samples.store(0, Ordering::Relaxed);
+        self.underruns.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Make a sample safe to hand to the audio driver
+/// 
+/// NaN and infinite values become silence and anything outside [-1.0, 1.0] is
+/// clamped. Returns the sanitized sample and whether it had to be changed.
+#[inline]
+pub fn sanitize_sample(sample: f32) -> (f32, bool) {
+    if !sample.is_finite() {
+        (0.0, true)
+    } else if sample.abs() > 1.0 {
+        (sample.clamp(-1.0, 1.0), true)
+    } else {
+        (sample, false)
+    }
+}
 
 /// Start audio output stream to the specified device
 /// 
@@ -46,6 +227,10 @@ use cpal::{BufferSize, StreamConfig};
 /// - `receiver`: Channel receiving processed audio from the AI pipeline
 /// - `running`: Atomic flag for coordinating graceful shutdown
 /// - `device_id`: Preferred output device identifier
+/// - `stats`: Shared output statistics (sanitized sample count) updated from the callback
+/// - `prefill_samples`: Jitter buffer depth; playback (re)starts only once this many samples are queued
+/// - `underrun_fill`: Whether gaps play silence or a fading repeat of the last frame
+/// - `stereo`: Received audio is interleaved left/right rather than mono
 /// 
 /// ## Device Selection Logic
 /// 
@@ -69,6 +254,9 @@ use cpal::{BufferSize, StreamConfig};
 /// - **Stereo devices**: Duplicate mono signal to both left and right channels
 /// - **Multi-channel**: Duplicate to all channels (rare for this use case)
 /// 
+/// With stereo processing the left and right channels go to the device's
+/// first two channels, and are averaged for a mono device.
+/// 
 /// ## Buffer Management
 /// 
 /// Uses a VecDeque for efficient audio buffering to handle timing differences
@@ -77,9 +265,15 @@ use cpal::{BufferSize, StreamConfig};
 /// - Buffer overruns when processing gets ahead of output
 /// - Clicks and pops from discontinuous audio
 pub fn start_output_stream(
-    receiver: Receiver<Vec<f32>>,
+    receiver: Receiver<AudioFrame>,
     running: Arc<AtomicBool>,
     device_id: &str,
+    stats: Arc<OutputStats>,
+    prefill_samples: usize,
+    probe: Arc<LatencyProbe>,
+    target_sample_rate: u32,
+    underrun_fill: UnderrunFill,
+    stereo: bool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Implement device selection with multiple fallback levels
     // This ensures the output works in various system configurations
@@ -100,7 +294,17 @@ pub fn start_output_stream(
 
     // Query the device's optimal output configuration
     // This ensures compatibility with the device's native format
-    let supported_config = device.default_output_config()?;
+    // Narrowband targets play at the target rate when the device supports it
+    let target_config = (target_sample_rate != RNNOISE_SAMPLE_RATE)
+        .then(|| config_for_sample_rate(&device, false, target_sample_rate))
+        .flatten();
+    if target_sample_rate != RNNOISE_SAMPLE_RATE && target_config.is_none() {
+        log::warn!("Output device doesn't support {}Hz - playing at its default rate", target_sample_rate);
+    }
+    let supported_config = match target_config {
+        Some(config) => config,
+        None => device.default_output_config()?,
+    };
 
     // Configure output stream to match device capabilities
     // Using device defaults minimizes format conversion overhead
@@ -124,11 +328,10 @@ pub fn start_output_stream(
             log::info!("This is CORRECT for noise cancellation setup!");
             log::info!("Make sure your communication app uses {} as INPUT to receive processed audio", device_type);
             
-            // Warn if sample rate is not optimal
+            // Other rates are resampled, so this is only a hint
             if config.sample_rate.0 != 48000 {
-                log::warn!("{} output sample rate is {} Hz, expected 48000 Hz for optimal performance", 
+                log::info!("{} output sample rate is {} Hz - resampling from 48kHz", 
                     device_type, config.sample_rate.0);
-                log::warn!("Consider setting {} to 48kHz in Audio MIDI Setup for best results", device_type);
             } else {
                 log::info!("{} configured optimally at 48kHz", device_type);
             }
@@ -148,6 +351,20 @@ pub fn start_output_stream(
     // Create audio buffer for handling timing differences between
     // the processing pipeline and audio output callback rates
     let mut buffer = VecDeque::new();
+    
+    // Jitter buffer state: with a non-zero prefill, output silence until enough audio
+    // has accumulated, and re-prime after an underrun empties the buffer
+    let mut primed = prefill_samples == 0;
+    let mut concealer = UnderrunConcealer::new();
+    let conceal = underrun_fill == UnderrunFill::RepeatLastFrame;
+    
+    // Processed audio is always 48kHz; bring it to the device's rate
+    let mut converter = DeviceRateConverter::new(config.sample_rate.0, stereo);
+    if config.sample_rate.0 != RNNOISE_SAMPLE_RATE {
+        log::info!("Output resampling: 48kHz -> {}Hz", config.sample_rate.0);
+    }
+    // Buffered samples played per second, to turn the queue depth into time
+    let buffer_rate = config.sample_rate.0 as f64 * if stereo { stereo::STEREO_CHANNELS as f64 } else { 1.0 };
 
     // Create the output stream with real-time audio callback
     // This callback runs on a high-priority audio thread
@@ -156,16 +373,56 @@ pub fn start_output_stream(
         move |data: &mut [f32], _| {
             // Continuously drain the receiver to fill our internal buffer
             // This prevents the processing pipeline from blocking on a full channel
-            while let Ok(audio_data) = receiver.try_recv() {
-                buffer.extend(audio_data);
+            while let Ok(frame) = receiver.try_recv() {
+                // The frame starts playing once the audio queued ahead of it has played
+                let queued = std::time::Duration::from_secs_f64(buffer.len() as f64 / buffer_rate);
+                stats.record_latency(frame.captured_at.elapsed() + queued);
+                
+                converter.queue(frame.samples, &mut buffer);
+            }
+            
+            if !primed && buffer.len() >= prefill_samples {
+                primed = true;
             }
 
             // Fill the output buffer by consuming from our internal buffer
             // The device expects interleaved samples for multi-channel output
-            for chunk in data.chunks_mut(config.channels as usize) {
+            let mut sanitized = 0u64;
+            let mut underrun = false;
+            let mut next_sample = || {
                 // Get the next processed audio sample (or silence if buffer is empty)
                 // Silence prevents audio glitches when processing temporarily falls behind
-                let sample = buffer.pop_front().unwrap_or(0.0);
+                let next = if primed { buffer.pop_front() } else { None };
+                underrun |= primed && next.is_none();
+                let (sample, changed) = sanitize_sample(next.unwrap_or(0.0));
+                if changed {
+                    sanitized += 1;
+                }
+                // With concealment, a gap fades out a repeat of the last frame instead
+                match (conceal, next) {
+                    (false, _) => sample,
+                    (true, Some(_)) => {
+                        concealer.record(sample);
+                        sample
+                    }
+                    (true, None) => concealer.conceal(),
+                }
+            };
+            for chunk in data.chunks_mut(config.channels as usize) {
+                if stereo {
+                    let (left, right) = (next_sample(), next_sample());
+                    match chunk {
+                        [mono] => *mono = 0.5 * (left + right),
+                        _ => {
+                            for (index, channel_sample) in chunk.iter_mut().enumerate() {
+                                *channel_sample = if index % 2 == 0 { left } else { right };
+                            }
+                        }
+                    }
+                    continue;
+                }
+                
+                let sample = next_sample();
                 
                 // Duplicate the mono sample to all output channels
                 // This ensures proper audio output regardless of device configuration
@@ -173,6 +430,25 @@ pub fn start_output_stream(
                     *channel_sample = sample;
                 }
             }
+            
+            stats.callbacks.fetch_add(1, Ordering::Relaxed);
+            if underrun {
+                stats.underruns.fetch_add(1, Ordering::Relaxed);
+                if prefill_samples > 0 {
+                    primed = false;
+                }
+            }
+            
+            // Latency measurement bypasses processed audio with the probe impulse/silence
+            probe.fill_output(data, config.channels as usize);
+            
+            // Channels carry the same mono signal, so meter the interleaved buffer as is
+            stats.level.record(data, config.sample_rate.0 * config.channels as u32);
+            
+            // Final guard for upstream stages (e.g. verification tone + max test mode)
+            if sanitized > 0 {
+                stats.sanitized_samples.fetch_add(sanitized, Ordering::Relaxed);
+            }
         },
         move |err| {
             // Log audio stream errors without panicking
@@ -192,4 +468,41 @@ pub fn start_output_stream(
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concealer_fades_out_last_frame() {
+        let mut concealer = UnderrunConcealer::new();
+        for _ in 0..CONCEALMENT_SAMPLES {
+            concealer.record(0.5);
+        }
+
+        let concealed: Vec<f32> = (0..CONCEALMENT_SAMPLES * 2).map(|_| concealer.conceal()).collect();
+        assert!((concealed[0] - 0.5).abs() < 1e-6);
+        // Monotonic fade, then silence once the repeated frame is used up
+        assert!(concealed[..CONCEALMENT_SAMPLES].windows(2).all(|w| w[1] <= w[0]));
+        assert!(concealed[CONCEALMENT_SAMPLES..].iter().all(|&s| s == 0.0));
+
+        // Real audio re-arms concealment
+        concealer.record(0.25);
+        assert!(concealer.conceal() > 0.0);
+    }
+
+    #[test]
+    fn test_stereo_output_resampled_per_channel() {
+        let mut converter = DeviceRateConverter::new(44100, true);
+        let mut buffer = VecDeque::new();
+        // One second of processed stereo frames: left constant 0.5, right constant -0.5
+        for _ in 0..100 {
+            converter.queue(stereo::interleave(&[0.5; 480], &[-0.5; 480]), &mut buffer);
+        }
+
+        let played: Vec<f32> = buffer.into_iter().collect();
+        assert!((44100 * 2 - played.len() as i64).abs() <= 2 * 2, "Played {} samples", played.len());
+        // Past the resampler's start-up, the channels stay on their own side
+        assert!(played[2..].chunks_exact(2).all(|pair| (pair[0] - 0.5).abs() < 1e-3 && (pair[1] + 0.5).abs() < 1e-3));
+    }
+}
@@ -0,0 +1,255 @@
+//! # Parallel Channel Processing Module
+//!
+//! Multi-channel processing runs one independent denoiser (and gate) per
+//! channel. On marginal hardware, running two RNNoise instances back to back can
+//! miss the 10ms frame deadline, so this module can spread channels across a
+//! small number of worker threads instead.
+//!
+//! ## Guarantees
+//!
+//! - Channel `i` is always processed with state `i`, so no state bleeds between channels
+//! - Channels are processed in place, so output order always matches input order
+//! - With a limit of one thread (the default) or a single channel, everything runs
+//!   inline on the calling thread, exactly like the mono pipeline
+//!
+//! The worker threads are started once with [`ChannelWorkers::new`] and live
+//! until it is dropped. Each frame, the channel buffers are moved to the workers
+//! over a channel and back, so nothing is spawned, copied or allocated per frame.
+//! The calling thread processes the first chunk of channels itself while the
+//! workers handle the rest (see the `parallel_channels` benchmark).
+
+use crate::logger::log;
+use crossbeam_channel::{bounded, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Default processing thread limit (single-threaded, the historical behaviour)
+pub const DEFAULT_MAX_PROCESSING_THREADS: usize = 1;
+
+/// Highest accepted processing thread limit
+pub const MAX_PROCESSING_THREADS: usize = 8;
+
+/// A contiguous run of channel states, shared with the worker that processes it
+type StateChunk<S> = Arc<Mutex<Vec<S>>>;
+
+/// A persistent thread processing one chunk of channels per frame
+struct Worker {
+    jobs: Sender<Vec<Vec<f32>>>,
+    done: Receiver<Vec<Vec<f32>>>,
+    handle: JoinHandle<()>,
+}
+
+impl Worker {
+    fn spawn<S: Send + 'static>(index: usize, chunk: StateChunk<S>, process: fn(&mut [f32], &mut S)) -> std::io::Result<Self> {
+        let (jobs, job_rx) = bounded::<Vec<Vec<f32>>>(1);
+        let (done_tx, done) = bounded(1);
+        let handle = std::thread::Builder::new()
+            .name(format!("kwite-channels-{}", index))
+            .spawn(move || {
+                // Runs until the sender is dropped with the `ChannelWorkers`
+                while let Ok(mut batch) = job_rx.recv() {
+                    if let Ok(mut states) = chunk.lock() {
+                        for (samples, state) in batch.iter_mut().zip(states.iter_mut()) {
+                            process(samples, state);
+                        }
+                    }
+                    if done_tx.send(batch).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        Ok(Self { jobs, done, handle })
+    }
+}
+
+/// Per-channel states processed by persistent worker threads
+///
+/// Chunk 0 always runs on the calling thread; chunk `i` runs on `workers[i - 1]`,
+/// or inline as well if that worker couldn't be started or has stopped.
+pub struct ChannelWorkers<S> {
+    chunks: Vec<StateChunk<S>>,
+    workers: Vec<Option<Worker>>,
+    /// Reused per-worker buffers that carry the channels to the worker and back
+    batches: Vec<Vec<Vec<f32>>>,
+    per_chunk: usize,
+    channel_count: usize,
+    process: fn(&mut [f32], &mut S),
+}
+
+impl<S: Send + 'static> ChannelWorkers<S> {
+    /// Split `states` into chunks and start a worker for every chunk but the first
+    ///
+    /// At most `max_threads` threads (including the caller) process a frame.
+    pub fn new(states: Vec<S>, max_threads: usize, process: fn(&mut [f32], &mut S)) -> Self {
+        let channel_count = states.len();
+        let threads = max_threads.clamp(1, MAX_PROCESSING_THREADS).min(channel_count.max(1));
+        let per_chunk = channel_count.div_ceil(threads).max(1);
+
+        let mut states = states.into_iter();
+        let mut chunks = Vec::new();
+        loop {
+            let chunk: Vec<S> = states.by_ref().take(per_chunk).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            chunks.push(Arc::new(Mutex::new(chunk)));
+        }
+
+        let workers: Vec<Option<Worker>> = chunks
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, chunk)| match Worker::spawn(index, chunk.clone(), process) {
+                Ok(worker) => Some(worker),
+                Err(e) => {
+                    log::warn!("Failed to start channel worker {}, processing its channels inline: {}", index, e);
+                    None
+                }
+            })
+            .collect();
+        let batches = workers.iter().map(|_| Vec::with_capacity(per_chunk)).collect();
+
+        Self { chunks, workers, batches, per_chunk, channel_count, process }
+    }
+
+    /// Number of threads processing a frame, including the calling thread
+    pub fn threads(&self) -> usize {
+        1 + self.workers.iter().filter(|worker| worker.is_some()).count()
+    }
+
+    /// Process every channel with its own state
+    ///
+    /// `channels` must hold one buffer per state. Returns once every channel
+    /// has been processed.
+    pub fn process(&mut self, channels: &mut [Vec<f32>]) {
+        debug_assert_eq!(channels.len(), self.channel_count, "One buffer per channel state is required");
+
+        // Hand the other chunks to their workers, moving the buffers rather than copying them
+        let mut sent = [false; MAX_PROCESSING_THREADS];
+        for (index, chunk_channels) in channels.chunks_mut(self.per_chunk).enumerate().skip(1) {
+            let Some(worker) = &self.workers[index - 1] else { continue };
+            let batch = &mut self.batches[index - 1];
+            batch.extend(chunk_channels.iter_mut().map(std::mem::take));
+            match worker.jobs.send(std::mem::take(batch)) {
+                Ok(()) => sent[index] = true,
+                Err(returned) => {
+                    // Worker has stopped; put the buffers back for inline processing
+                    *batch = returned.into_inner();
+                    for (slot, samples) in chunk_channels.iter_mut().zip(batch.drain(..)) {
+                        *slot = samples;
+                    }
+                }
+            }
+        }
+
+        // Meanwhile process the first chunk (and any without a running worker) here
+        for (index, chunk_channels) in channels.chunks_mut(self.per_chunk).enumerate() {
+            if !sent[index] {
+                self.process_inline(index, chunk_channels);
+            }
+        }
+
+        for (index, chunk_channels) in channels.chunks_mut(self.per_chunk).enumerate().skip(1) {
+            if !sent[index] {
+                continue;
+            }
+            let received = self.workers[index - 1].as_ref().and_then(|worker| worker.done.recv().ok());
+            match received {
+                Some(mut batch) => {
+                    for (slot, samples) in chunk_channels.iter_mut().zip(batch.drain(..)) {
+                        *slot = samples;
+                    }
+                    self.batches[index - 1] = batch;
+                }
+                None => {
+                    // The buffers went down with the worker; later frames run this chunk inline
+                    log::warn!("Channel worker {} stopped, processing its channels inline", index);
+                    self.workers[index - 1] = None;
+                }
+            }
+        }
+    }
+
+    /// Visit every channel state in channel order, e.g. to update gate settings
+    ///
+    /// Workers only hold a state while [`process`](Self::process) runs, so this
+    /// never waits on them.
+    pub fn for_each_state(&self, mut f: impl FnMut(usize, &mut S)) {
+        for (chunk_index, chunk) in self.chunks.iter().enumerate() {
+            let mut states = chunk.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            for (offset, state) in states.iter_mut().enumerate() {
+                f(chunk_index * self.per_chunk + offset, state);
+            }
+        }
+    }
+
+    fn process_inline(&self, index: usize, channels: &mut [Vec<f32>]) {
+        let mut states = self.chunks[index].lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (samples, state) in channels.iter_mut().zip(states.iter_mut()) {
+            (self.process)(samples, state);
+        }
+    }
+}
+
+impl<S> Drop for ChannelWorkers<S> {
+    fn drop(&mut self) {
+        for worker in self.workers.drain(..).flatten() {
+            // Closing the job channel ends the worker loop
+            drop(worker.jobs);
+            let _ = worker.handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Per-channel state: a gain and a running frame counter
+    struct ChannelState {
+        gain: f32,
+        frames: u32,
+    }
+
+    fn apply_gain(samples: &mut [f32], state: &mut ChannelState) {
+        state.frames += 1;
+        for sample in samples.iter_mut() {
+            *sample *= state.gain;
+        }
+    }
+
+    fn run(max_threads: usize) -> (Vec<Vec<f32>>, Vec<u32>) {
+        let mut channels = vec![vec![1.0; 4], vec![1.0; 4], vec![1.0; 4]];
+        let states: Vec<ChannelState> = (0..3)
+            .map(|i| ChannelState { gain: (i + 1) as f32, frames: 0 })
+            .collect();
+        let mut workers = ChannelWorkers::new(states, max_threads, apply_gain);
+        assert_eq!(workers.threads(), max_threads.min(3));
+
+        for _ in 0..2 {
+            workers.process(&mut channels);
+        }
+        let mut frames = Vec::new();
+        workers.for_each_state(|index, state| {
+            assert_eq!(index, frames.len());
+            frames.push(state.frames);
+        });
+        (channels, frames)
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let (serial, serial_frames) = run(1);
+        let (parallel, parallel_frames) = run(3);
+
+        // Channel order is preserved and each channel only saw its own state
+        assert_eq!(serial, vec![vec![1.0; 4], vec![4.0; 4], vec![9.0; 4]]);
+        assert_eq!(parallel, serial);
+        assert_eq!(serial_frames, vec![2, 2, 2]);
+        assert_eq!(parallel_frames, serial_frames);
+
+        // Two threads over three channels: chunks of two and one
+        let (uneven, _) = run(2);
+        assert_eq!(uneven, serial);
+    }
+}
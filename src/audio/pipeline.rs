@@ -48,7 +48,12 @@
 //! - **Professional Quality**: Enterprise-grade performance and monitoring
 
 use crate::audio::models::{EnhancedAudioProcessor, NoiseModel};
-use crate::audio::analysis::{AudioAnalyzer, AudioContext, NoiseType};
+use crate::audio::gain::EnvelopeFollower;
+use crate::audio::analysis::{
+    AudioAnalyzer, AudioContext, NoiseType, NoiseTypeCallback, NoiseTypeGate, NoiseTypeNotifier,
+    DEFAULT_NOISE_EVENT_INTERVAL,
+};
+use crate::logger::log;
 use crate::ai_metrics::SharedAiMetrics;
 use std::time::{Instant, Duration};
 
@@ -118,12 +123,8 @@ pub struct DynamicRangeProcessor {
     threshold: f32,
     /// Compression ratio
     ratio: f32,
-    /// Attack time constant
-    attack_coeff: f32,
-    /// Release time constant
-    release_coeff: f32,
-    /// Current envelope level
-    envelope: f32,
+    /// Level envelope with attack/release smoothing
+    envelope: EnvelopeFollower,
 }
 
 impl DynamicRangeProcessor {
@@ -135,29 +136,21 @@ impl DynamicRangeProcessor {
         Self {
             threshold: 0.5,
             ratio: 3.0,
-            attack_coeff: (-1.0 / (attack_time * sample_rate as f32)).exp(),
-            release_coeff: (-1.0 / (release_time * sample_rate as f32)).exp(),
-            envelope: 0.0,
+            envelope: EnvelopeFollower::new(attack_time, release_time, sample_rate as f32),
         }
     }
     
     /// Process audio through dynamic range processor
     pub fn process(&mut self, samples: &mut [f32]) {
         for sample in samples.iter_mut() {
-            let input_level = sample.abs();
-            
             // Update envelope follower
-            if input_level > self.envelope {
-                self.envelope = input_level + (self.envelope - input_level) * self.attack_coeff;
-            } else {
-                self.envelope = input_level + (self.envelope - input_level) * self.release_coeff;
-            }
+            let envelope = self.envelope.process(sample.abs());
             
             // Calculate compression gain
-            let gain = if self.envelope > self.threshold {
-                let over_threshold = self.envelope - self.threshold;
+            let gain = if envelope > self.threshold {
+                let over_threshold = envelope - self.threshold;
                 let compressed = over_threshold / self.ratio;
-                (self.threshold + compressed) / self.envelope
+                (self.threshold + compressed) / envelope
             } else {
                 1.0
             };
@@ -168,6 +161,10 @@ impl DynamicRangeProcessor {
     }
 }
 
+/// Consecutive frames of a new noise type required before the pipeline switches
+/// its gain strategy (8 frames = 80ms at 480 samples/48kHz)
+const NOISE_TYPE_SWITCH_FRAMES: usize = 8;
+
 /// Advanced multi-stage noise suppression pipeline
 /// 
 /// Combines multiple processing techniques for professional-grade noise cancellation
@@ -178,6 +175,15 @@ pub struct AdvancedNoisePipeline {
     /// AI-powered audio analyzer
     audio_analyzer: AudioAnalyzer,
     
+    /// Trend-aware gate smoothing noise type decisions
+    noise_type_gate: NoiseTypeGate,
+    
+    /// Debounced change notifications for the smoothed noise type
+    noise_type_notifier: NoiseTypeNotifier,
+    
+    /// Integrator hook fired on noise type changes
+    noise_type_callback: Option<NoiseTypeCallback>,
+    
     /// Enhanced AI denoiser with multiple model support
     ai_denoiser: EnhancedAudioProcessor,
     
@@ -214,6 +220,9 @@ impl AdvancedNoisePipeline {
         Ok(Self {
             pre_filter,
             audio_analyzer,
+            noise_type_gate: NoiseTypeGate::new(NOISE_TYPE_SWITCH_FRAMES),
+            noise_type_notifier: NoiseTypeNotifier::new(DEFAULT_NOISE_EVENT_INTERVAL),
+            noise_type_callback: None,
             ai_denoiser,
             post_processor,
             processing_params,
@@ -240,6 +249,13 @@ impl AdvancedNoisePipeline {
         
         // Stage 2: AI Analysis
         let audio_context = self.audio_analyzer.analyze_audio_context(output);
+        let smoothed_noise_type = self.noise_type_gate.update(self.audio_analyzer.get_context_history());
+        if let Some(event) = self.noise_type_notifier.observe(smoothed_noise_type, start_time) {
+            log::debug!("🔊 Noise type changed: {:?} -> {:?}", event.previous, event.current);
+            if let Some(callback) = &self.noise_type_callback {
+                callback(event);
+            }
+        }
         
         // Stage 3: AI Denoising (RNNoise)
         let mut temp_buffer = output.to_vec();
@@ -248,7 +264,7 @@ impl AdvancedNoisePipeline {
         
         // Stage 4: Adaptive gain control based on analysis
         if self.processing_params.adaptive_mode {
-            self.apply_adaptive_gain(output, &audio_context);
+            self.apply_adaptive_gain(output, &audio_context, smoothed_noise_type);
         } else {
             // Fallback to simple VAD-based gain
             let gain = if vad_score > 0.5 { 0.8 } else { 0.2 };
@@ -277,11 +293,14 @@ impl AdvancedNoisePipeline {
     }
     
     /// Apply intelligent adaptive gain based on audio analysis
-    fn apply_adaptive_gain(&mut self, samples: &mut [f32], context: &AudioContext) {
+    /// 
+    /// Uses the trend-smoothed noise type rather than the per-frame classification
+    /// so the gain strategy doesn't flap when the classifier is uncertain.
+    fn apply_adaptive_gain(&mut self, samples: &mut [f32], context: &AudioContext, noise_type: NoiseType) {
         let base_gain = context.recommended_gain;
         
         // Adjust gain based on noise type
-        let type_adjustment = match context.noise_type {
+        let type_adjustment = match noise_type {
             NoiseType::Speech => 1.0,      // No adjustment for speech
             NoiseType::Keyboard => 0.5,    // Extra reduction for keyboard
             NoiseType::HVAC => 0.7,        // Moderate reduction for HVAC
@@ -298,7 +317,9 @@ impl AdvancedNoisePipeline {
         }
     }
     
-    /// Update pipeline sensitivity
+    /// Update the analyzer VAD sensitivity
+    /// 
+    /// Only the analysis stage uses it; the denoiser's gain threshold is set separately.
     pub fn update_sensitivity(&mut self, sensitivity: f32) {
         self.processing_params.sensitivity = sensitivity;
         self.audio_analyzer.set_sensitivity(sensitivity);
@@ -321,6 +342,19 @@ impl AdvancedNoisePipeline {
         self.ai_denoiser.current_model()
     }
     
+    /// Get the trend-smoothed noise type used for gain decisions
+    pub fn smoothed_noise_type(&self) -> NoiseType {
+        self.noise_type_gate.stable_type()
+    }
+    
+    /// Register a callback fired when the smoothed noise type changes
+    /// 
+    /// Events are debounced (see `NoiseTypeNotifier`) and delivered on the
+    /// processing thread, so the callback must return quickly.
+    pub fn set_noise_type_callback(&mut self, callback: NoiseTypeCallback) {
+        self.noise_type_callback = Some(callback);
+    }
+    
     /// Get pipeline performance statistics
     pub fn get_statistics(&self) -> &PipelineStatistics {
         &self.pipeline_stats
@@ -330,12 +364,17 @@ impl AdvancedNoisePipeline {
     pub fn get_model_statistics(&self) -> &crate::audio::models::ModelStatistics {
         self.ai_denoiser.get_statistics()
     }
+    
+    /// Zero the pipeline statistics without touching the processing state
+    pub fn reset_statistics(&mut self) {
+        self.pipeline_stats.reset();
+    }
 }
 
 /// Processing parameters for pipeline configuration
 #[derive(Debug, Clone)]
 pub struct ProcessingParameters {
-    /// Noise cancellation sensitivity (0.0-1.0)
+    /// Analyzer VAD sensitivity (0.01 = aggressive, 0.5 = conservative)
     pub sensitivity: f32,
     /// Enable adaptive processing based on audio analysis
     pub adaptive_mode: bool,
@@ -407,6 +446,11 @@ impl PipelineStatistics {
         self.avg_voice_probability = self.avg_voice_probability * (frames_f - 1.0) as f32 / frames_f as f32 + context.voice_probability / frames_f as f32;
     }
     
+    /// Zero all counters and averages
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+    
     // Getters
     pub fn total_frames(&self) -> u64 { self.total_frames }
     pub fn avg_processing_time(&self) -> std::time::Duration { self.avg_processing_time }
@@ -51,6 +51,7 @@ use std::time::Instant;
 use crate::ai_metrics::SharedAiMetrics;
 use crate::audio::models::EnhancedAudioProcessor;
 use crate::audio::analysis::AudioContext;
+use crate::audio::{DEFAULT_NOISE_GAIN, DEFAULT_SPEECH_GAIN, DEFAULT_VAD_THRESHOLD};
 use nnnoiseless::DenoiseState;
 
 /// Process audio through AI noise cancellation
@@ -91,6 +92,7 @@ use nnnoiseless::DenoiseState;
 /// - **Noise (VAD ≤ 0.5)**: Low gain (0.1) to suppress background sounds
 /// 
 /// This approach provides more natural-sounding results than binary on/off switching.
+/// Use `process_audio_with_gains` to choose the two gains.
 /// 
 /// ## Frame Size Rationale
 /// 
@@ -104,6 +106,21 @@ pub fn process_audio(
     output: &mut [f32], 
     denoiser: &mut DenoiseState<'static>,
     metrics: Option<&SharedAiMetrics>
+) {
+    process_audio_with_gains(input, output, denoiser, metrics, DEFAULT_NOISE_GAIN, DEFAULT_SPEECH_GAIN);
+}
+
+/// `process_audio` with configurable gains for noise and speech frames
+/// 
+/// `noise_gain` is applied to frames whose VAD score is below 0.5 and
+/// `speech_gain` to the rest, matching `KwiteConfig::noise_gain`/`speech_gain`.
+pub fn process_audio_with_gains(
+    input: &[f32], 
+    output: &mut [f32], 
+    denoiser: &mut DenoiseState<'static>,
+    metrics: Option<&SharedAiMetrics>,
+    noise_gain: f32,
+    speech_gain: f32,
 ) {
     // Use the AI model's optimal frame size for processing
     // This constant is defined by the nnnoiseless library based on RNNoise requirements
@@ -137,10 +154,10 @@ pub fn process_audio(
 
         // Apply adaptive gain based on voice activity detection
         // This creates more natural-sounding noise suppression than binary switching
-        let gain = if vad < 0.5 { 
-            0.1  // Low gain for background noise (aggressive suppression)
+        let gain = if vad < DEFAULT_VAD_THRESHOLD { 
+            noise_gain  // Low gain for background noise (aggressive suppression)
         } else { 
-            0.8  // High gain for detected speech (preserve voice quality)
+            speech_gain // High gain for detected speech (preserve voice quality)
         };
 
         // Copy processed frame to output buffer with applied gain
@@ -0,0 +1,169 @@
+//! # Output Recording Module
+//!
+//! Records everything Kwite outputs to a 48kHz mono WAV file, for debugging a
+//! setup or capturing clean audio for content. Unlike instant replay, which
+//! only keeps the last few seconds in memory, a recording streams to disk for
+//! as long as it runs.
+//!
+//! The processing thread never touches the file. Each output frame is copied
+//! into a bounded channel and a dedicated writer thread drains it into the WAV
+//! file (32-bit float, so the recording matches the output exactly). If the
+//! disk falls behind and the channel fills up, frames are dropped and a warning
+//! is logged instead of stalling the audio path.
+
+use crate::logger::log;
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Sample rate of recorded files
+pub const RECORDING_SAMPLE_RATE: u32 = 48000;
+
+/// Output frames queued for the writer before new frames are dropped (~1s)
+pub const RECORDING_QUEUE_FRAMES: usize = 100;
+
+/// Processing-thread end of a recording
+///
+/// Dropping the tap closes the channel, which lets the writer finish the file.
+#[derive(Debug)]
+pub struct RecordingTap {
+    tx: Sender<Vec<f32>>,
+    dropped_frames: u64,
+}
+
+impl RecordingTap {
+    /// Queue one output frame without blocking
+    ///
+    /// Interleaved frames with more than one channel are averaged down to mono.
+    pub fn push(&mut self, frame: &[f32], channels: usize) {
+        let mono = if channels > 1 {
+            frame.chunks_exact(channels)
+                .map(|samples| samples.iter().sum::<f32>() / channels as f32)
+                .collect()
+        } else {
+            frame.to_vec()
+        };
+
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(mono) {
+            if self.dropped_frames == 0 {
+                log::warn!("⏺ Recording writer is falling behind - dropping frames");
+            }
+            self.dropped_frames += 1;
+        }
+    }
+
+    /// Frames dropped because the writer fell behind
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+}
+
+/// Writer end of a recording, owned by the `AudioManager`
+#[derive(Debug)]
+pub struct RecordingWriter {
+    path: PathBuf,
+    started: Instant,
+    handle: thread::JoinHandle<Result<u64, hound::Error>>,
+}
+
+impl RecordingWriter {
+    /// File being recorded to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Time since the recording started
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Wait for the writer to flush the queued frames and finalize the file
+    ///
+    /// The matching `RecordingTap` must have been dropped first, otherwise this
+    /// waits forever. Returns the number of seconds written.
+    pub fn finish(self) -> Result<f32, Box<dyn std::error::Error + Send + Sync>> {
+        let samples = self.handle.join().map_err(|_| "Recording writer panicked")??;
+        Ok(samples as f32 / RECORDING_SAMPLE_RATE as f32)
+    }
+}
+
+/// Create the WAV file at `path` and start its writer thread
+///
+/// The file is created before returning, so a bad path fails here rather than
+/// in the background.
+pub fn start(path: &Path) -> Result<(RecordingTap, RecordingWriter), Box<dyn std::error::Error + Send + Sync>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: RECORDING_SAMPLE_RATE,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let writer = hound::WavWriter::create(path, spec)?;
+    let (tx, rx) = bounded(RECORDING_QUEUE_FRAMES);
+
+    let handle = thread::Builder::new()
+        .name("kwite-recorder".to_string())
+        .spawn(move || write_frames(writer, rx))?;
+
+    log::info!("⏺ Recording output to {}", path.display());
+    Ok((
+        RecordingTap { tx, dropped_frames: 0 },
+        RecordingWriter { path: path.to_path_buf(), started: Instant::now(), handle },
+    ))
+}
+
+/// Write frames until the tap is dropped, then finalize the WAV header
+fn write_frames<W: std::io::Write + std::io::Seek>(mut writer: hound::WavWriter<W>, rx: Receiver<Vec<f32>>) -> Result<u64, hound::Error> {
+    let mut samples = 0u64;
+    for frame in rx {
+        for sample in frame {
+            writer.write_sample(sample)?;
+        }
+        samples = writer.len() as u64;
+    }
+    writer.finalize()?;
+    Ok(samples)
+}
+
+/// Timestamped file path for a new recording
+///
+/// Uses the user's audio (music) directory when available, falling back to home.
+pub fn default_recording_path() -> PathBuf {
+    let dir = dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("kwite-recording-{}.wav", stamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recording_writes_mono_wav() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("recording.wav");
+
+        let (mut tap, writer) = start(&path).unwrap();
+        for _ in 0..10 {
+            tap.push(&[0.25; 480], 1);
+        }
+        // Stereo frames are averaged to mono
+        tap.push(&[0.5, -0.5].repeat(480), 2);
+        drop(tap);
+
+        let seconds = writer.finish().unwrap();
+        assert!((seconds - 0.11).abs() < 1e-6);
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().channels, 1);
+        assert_eq!(reader.spec().sample_rate, RECORDING_SAMPLE_RATE);
+        let samples: Vec<f32> = reader.samples::<f32>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples.len(), 11 * 480);
+        assert!(samples[..4800].iter().all(|&s| s == 0.25));
+        assert!(samples[4800..].iter().all(|&s| s == 0.0));
+    }
+}
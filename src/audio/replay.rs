@@ -0,0 +1,141 @@
+//! # Instant Replay Module
+//!
+//! Keeps the last few seconds of processed output in memory so users can save
+//! a clip after something worth keeping happened ("save the last 30 seconds").
+//!
+//! The processing thread pushes every output frame into a fixed-capacity ring
+//! buffer, so memory is bounded by the configured duration (30s of 48kHz mono
+//! is ~5.6 MB of f32 samples). Saving copies the buffer out under a short lock
+//! and writes a 16-bit PCM WAV file off the audio thread.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+/// Longest replay duration that can be configured (seconds)
+pub const MAX_REPLAY_SECONDS: u32 = 120;
+
+/// Rolling buffer of the most recent processed samples
+#[derive(Debug)]
+pub struct ReplayBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+    sample_rate: u32,
+}
+
+impl ReplayBuffer {
+    /// Create a buffer holding up to `seconds` of audio at `sample_rate`
+    pub fn new(seconds: u32, sample_rate: u32) -> Self {
+        let capacity = seconds.min(MAX_REPLAY_SECONDS) as usize * sample_rate as usize;
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            sample_rate,
+        }
+    }
+
+    /// Append a frame, discarding the oldest samples once the buffer is full
+    pub fn push(&mut self, frame: &[f32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Only the newest `capacity` samples of an oversized frame can survive
+        let frame = &frame[frame.len().saturating_sub(self.capacity)..];
+        let overflow = (self.samples.len() + frame.len()).saturating_sub(self.capacity);
+        self.samples.drain(..overflow);
+        self.samples.extend(frame.iter().copied());
+    }
+
+    /// Seconds of audio currently held
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    /// Sample rate of the buffered audio
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Copy of the buffered audio, oldest sample first
+    pub fn snapshot(&self) -> Vec<f32> {
+        self.samples.iter().copied().collect()
+    }
+}
+
+/// Write mono samples as a 16-bit PCM WAV file
+pub fn write_wav_pcm16(path: &Path, samples: &[f32], sample_rate: u32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // Byte rate
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // Block align
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for &sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Timestamped file path for a saved replay clip
+///
+/// Uses the user's audio (music) directory when available, falling back to home.
+pub fn default_replay_path() -> PathBuf {
+    let dir = dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("kwite-replay-{}.wav", stamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::test_signal::load_wav_mono_48k;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_buffer_is_bounded_by_duration() {
+        let mut buffer = ReplayBuffer::new(1, 48000);
+        for i in 0..150 {
+            buffer.push(&[i as f32; 480]);
+        }
+
+        let samples = buffer.snapshot();
+        assert_eq!(samples.len(), 48000);
+        assert!((buffer.duration_secs() - 1.0).abs() < f32::EPSILON);
+        // Only the newest 100 frames remain, oldest first
+        assert_eq!(samples[0], 50.0);
+        assert_eq!(samples[samples.len() - 1], 149.0);
+
+        let mut disabled = ReplayBuffer::new(0, 48000);
+        disabled.push(&[0.5; 480]);
+        assert!(disabled.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_wav_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("replay.wav");
+        let samples: Vec<f32> = (0..4800).map(|i| (i as f32 * 0.01).sin() * 0.5).collect();
+
+        write_wav_pcm16(&path, &samples, 48000).unwrap();
+        let loaded = load_wav_mono_48k(&path).unwrap();
+
+        assert_eq!(loaded.len(), samples.len());
+        assert!(loaded.iter().zip(&samples).all(|(a, b)| (a - b).abs() < 1e-3));
+    }
+}
@@ -10,9 +10,27 @@
 //! - **Frame Size Calculation**: Calculate optimal frame sizes for different sample rates
 //! - **Simple Resampling**: Basic resampling for small sample rate differences
 //! - **Quality Preservation**: Maintain audio quality during adaptation
+//! - **Target Sample Rate**: Narrowband (8/16/32kHz) pipelines resampled around RNNoise
 
 use std::collections::VecDeque;
 
+/// Sample rate RNNoise runs at internally
+pub const RNNOISE_SAMPLE_RATE: u32 = 48000;
+
+/// Default end-to-end sample rate (no extra resampling)
+pub const DEFAULT_TARGET_SAMPLE_RATE: u32 = RNNOISE_SAMPLE_RATE;
+
+/// End-to-end sample rates the pipeline can target
+/// 
+/// Limited to the rates the WebRTC VAD supports so the analyzer always has a
+/// matching mode.
+pub const SUPPORTED_TARGET_SAMPLE_RATES: [u32; 4] = [8000, 16000, 32000, 48000];
+
+/// Check whether `rate` can be used as the pipeline's target sample rate
+pub fn is_supported_target_sample_rate(rate: u32) -> bool {
+    SUPPORTED_TARGET_SAMPLE_RATES.contains(&rate)
+}
+
 /// Audio resampler for handling sample rate differences
 pub struct SimpleResampler {
     /// Input sample rate
@@ -89,6 +107,28 @@ impl SimpleResampler {
     }
 }
 
+/// Resample one whole frame from `input_rate` to `output_rate` (linear interpolation)
+/// 
+/// Unlike `SimpleResampler` this keeps no state between frames, so the output
+/// always holds exactly `frame.len() * output_rate / input_rate` samples - what
+/// the WebRTC VAD needs to see a valid 10ms frame at the target rate.
+pub fn resample_frame(frame: &[f32], input_rate: u32, output_rate: u32) -> Vec<f32> {
+    if input_rate == output_rate || frame.is_empty() {
+        return frame.to_vec();
+    }
+    let output_len = frame.len() * output_rate as usize / input_rate as usize;
+    let ratio = input_rate as f64 / output_rate as f64;
+    (0..output_len)
+        .map(|i| {
+            let position = i as f64 * ratio;
+            let index = position as usize;
+            let fraction = (position - index as f64) as f32;
+            let next = frame[(index + 1).min(frame.len() - 1)];
+            frame[index] + (next - frame[index]) * fraction
+        })
+        .collect()
+}
+
 /// Calculate optimal frame size for RNNoise based on sample rate
 /// 
 /// RNNoise expects 10ms frames, so this calculates the number of samples
@@ -113,8 +153,8 @@ pub fn is_optimal_configuration(sample_rate: u32, frame_size: usize) -> bool {
 pub fn get_configuration_advice(sample_rate: u32) -> String {
     match sample_rate {
         48000 => "✅ Optimal configuration (48kHz) for AI noise cancellation".to_string(),
-        44100 => "⚠️  44.1kHz detected - consider setting to 48kHz for optimal AI performance".to_string(),
-        _ => format!("⚠️  {}Hz detected - 48kHz recommended for best noise cancellation", sample_rate),
+        44100 => "ℹ️  44.1kHz detected - resampling to 48kHz for AI processing".to_string(),
+        _ => format!("ℹ️  {}Hz detected - resampling to 48kHz for AI processing", sample_rate),
     }
 }
 
@@ -244,6 +284,30 @@ mod tests {
         assert!((output[0] - 0.1).abs() < 0.01);
     }
     
+    #[test]
+    fn test_target_sample_rate_validation() {
+        assert!(is_supported_target_sample_rate(16000));
+        assert!(is_supported_target_sample_rate(DEFAULT_TARGET_SAMPLE_RATE));
+        assert!(!is_supported_target_sample_rate(44100));
+        assert!(!is_supported_target_sample_rate(0));
+        
+        // A 16kHz round trip through RNNoise's rate keeps the frame length
+        let mut up = SimpleResampler::new(16000, RNNOISE_SAMPLE_RATE);
+        let mut down = SimpleResampler::new(RNNOISE_SAMPLE_RATE, 16000);
+        let mut upsampled = Vec::new();
+        let mut restored = Vec::new();
+        up.process(&vec![0.2; 1600], &mut upsampled);
+        down.process(&upsampled, &mut restored);
+        assert!(restored.len() > 1580 && restored.len() <= 1600);
+        
+        // Whole-frame conversion gives the VAD an exact 10ms frame at every supported rate
+        for rate in SUPPORTED_TARGET_SAMPLE_RATES {
+            let frame = resample_frame(&vec![0.2; 480], RNNOISE_SAMPLE_RATE, rate);
+            assert_eq!(frame.len(), calculate_frame_size_for_sample_rate(rate));
+            assert!(frame.iter().all(|&s| (s - 0.2).abs() < 1e-6));
+        }
+    }
+    
     #[test]
     fn test_simple_resampler() {
         let mut resampler = SimpleResampler::new(44100, 48000);
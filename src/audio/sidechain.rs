@@ -0,0 +1,180 @@
+//! # Sidechain Ducking Module
+//!
+//! Streamers often want their microphone to sit under loud game audio or music
+//! and come back when it calms down. With ducking enabled, the processing
+//! thread lowers Kwite's output while an external "sidechain" signal is above a
+//! threshold, like the sidechain input of a hardware compressor.
+//!
+//! ## Sidechain Sources
+//!
+//! - **Capture device**: a second input (e.g. a loopback of the game or desktop
+//!   audio) opened alongside the microphone; its block peak is published here
+//! - **External level**: integrations push a level in dBFS through
+//!   `AudioManager::set_sidechain_level_db`
+//!
+//! The level is smoothed once per 10ms frame with the same attack/release
+//! envelope follower the dynamic range processor uses, and everything above the
+//! threshold is reduced according to the ratio.
+
+use crate::audio::gain::EnvelopeFollower;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Default sidechain level above which ducking starts (dBFS)
+pub const DEFAULT_DUCK_THRESHOLD_DB: f32 = -30.0;
+/// Default ducking ratio
+pub const DEFAULT_DUCK_RATIO: f32 = 4.0;
+/// Default attack time (ms)
+pub const DEFAULT_DUCK_ATTACK_MS: u32 = 10;
+/// Default release time (ms)
+pub const DEFAULT_DUCK_RELEASE_MS: u32 = 300;
+
+/// Highest configurable ducking ratio
+pub const MAX_DUCK_RATIO: f32 = 20.0;
+/// Lowest configurable threshold (dBFS)
+pub const MIN_DUCK_THRESHOLD_DB: f32 = -60.0;
+/// Longest configurable attack or release time (ms)
+pub const MAX_DUCK_TIME_MS: u32 = 5000;
+
+/// Envelope updates per second (one per 480-sample frame)
+const FRAMES_PER_SECOND: f32 = 100.0;
+
+/// User configuration for sidechain ducking
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuckingSettings {
+    /// Duck the output while the sidechain is loud (off by default)
+    pub enabled: bool,
+    /// Capture device feeding the sidechain (`None` = external level only)
+    pub source_device_id: Option<String>,
+    /// Sidechain level above which the output is reduced (dBFS)
+    pub threshold_db: f32,
+    /// Sidechain dB above the threshold per dB of gain reduction
+    pub ratio: f32,
+    /// Time for the ducking to engage (ms)
+    pub attack_ms: u32,
+    /// Time for the ducking to recover (ms)
+    pub release_ms: u32,
+}
+
+impl Default for DuckingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source_device_id: None,
+            threshold_db: DEFAULT_DUCK_THRESHOLD_DB,
+            ratio: DEFAULT_DUCK_RATIO,
+            attack_ms: DEFAULT_DUCK_ATTACK_MS,
+            release_ms: DEFAULT_DUCK_RELEASE_MS,
+        }
+    }
+}
+
+/// Latest sidechain level, shared between its source and the processing thread
+#[derive(Debug, Default)]
+pub struct SidechainLevel {
+    /// Linear peak level as f32 bits (0 bits = silence)
+    level: AtomicU32,
+}
+
+impl SidechainLevel {
+    /// Publish the peak of a block of sidechain samples
+    pub fn record_block(&self, block: &[f32]) {
+        let peak = block.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        self.level.store(peak.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Publish a level in dBFS from an external source
+    pub fn set_db(&self, level_db: f32) {
+        let linear = if level_db.is_finite() { 10f32.powf(level_db.min(0.0) / 20.0) } else { 0.0 };
+        self.level.store(linear.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Latest linear level
+    pub fn linear(&self) -> f32 {
+        f32::from_bits(self.level.load(Ordering::Relaxed))
+    }
+}
+
+/// Output gain for a sidechain level (linear), using a compressor-style curve
+pub fn ducking_gain(level: f32, threshold_db: f32, ratio: f32) -> f32 {
+    if level <= 0.0 {
+        return 1.0;
+    }
+    let over_db = 20.0 * level.log10() - threshold_db;
+    if over_db <= 0.0 {
+        return 1.0;
+    }
+    let reduction_db = over_db * (1.0 - 1.0 / ratio.max(1.0));
+    10f32.powf(-reduction_db / 20.0)
+}
+
+/// Per-frame ducking gain computer for the processing thread
+#[derive(Debug, Clone)]
+pub struct Ducker {
+    threshold_db: f32,
+    ratio: f32,
+    envelope: EnvelopeFollower,
+}
+
+impl Ducker {
+    pub fn new(settings: &DuckingSettings) -> Self {
+        Self {
+            threshold_db: settings.threshold_db.clamp(MIN_DUCK_THRESHOLD_DB, 0.0),
+            ratio: settings.ratio.clamp(1.0, MAX_DUCK_RATIO),
+            envelope: EnvelopeFollower::new(
+                settings.attack_ms.min(MAX_DUCK_TIME_MS) as f32 / 1000.0,
+                settings.release_ms.min(MAX_DUCK_TIME_MS) as f32 / 1000.0,
+                FRAMES_PER_SECOND,
+            ),
+        }
+    }
+
+    /// Gain for the next frame given the current sidechain level (linear)
+    pub fn next_gain(&mut self, sidechain_level: f32) -> f32 {
+        let envelope = self.envelope.process(sidechain_level);
+        ducking_gain(envelope, self.threshold_db, self.ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gain_curve_follows_threshold_and_ratio() {
+        assert_eq!(ducking_gain(0.0, -30.0, 4.0), 1.0);
+        // -40 dBFS is below the threshold
+        assert_eq!(ducking_gain(0.01, -30.0, 4.0), 1.0);
+        // -10 dBFS is 20 dB over; 4:1 reduces by 15 dB
+        let gain_db = 20.0 * ducking_gain(10f32.powf(-0.5), -30.0, 4.0).log10();
+        assert!((gain_db + 15.0).abs() < 0.01, "Gain {:.2} dB", gain_db);
+    }
+
+    #[test]
+    fn test_ducker_engages_and_recovers() {
+        let mut ducker = Ducker::new(&DuckingSettings {
+            enabled: true,
+            attack_ms: 10,
+            release_ms: 300,
+            ..DuckingSettings::default()
+        });
+        let level = SidechainLevel::default();
+
+        // 24 dB over the threshold at 4:1 settles at 18 dB of reduction
+        level.set_db(-6.0);
+        let ducked = (0..10).map(|_| ducker.next_gain(level.linear())).last().unwrap();
+        assert!((20.0 * ducked.log10() + 18.0).abs() < 0.1, "Loud sidechain should duck the output, gain {}", ducked);
+
+        // Silence releases over ~300ms; after a second the output is back
+        level.record_block(&[0.0; 480]);
+        let mut gain = ducked;
+        for frame in 0..100 {
+            gain = ducker.next_gain(level.linear());
+            if frame == 2 {
+                assert!(gain < 0.5, "Release should be gradual, gain {}", gain);
+            }
+        }
+        assert!(gain > 0.95, "Output should recover, gain {}", gain);
+    }
+}
@@ -0,0 +1,189 @@
+//! # Stereo Processing Module
+//!
+//! USB microphones and virtual cables often present two channels that really
+//! differ (stereo mics, two-person setups sharing an interface). With stereo
+//! processing enabled the pipeline keeps both channels instead of collapsing
+//! them to mono:
+//!
+//! - Capture sends interleaved `[L, R, L, R, ...]` audio (a mono device feeds both sides)
+//! - Each channel runs through its own `DenoiseState` and gate, so no recurrent
+//!   state bleeds between channels
+//! - The per-channel VAD scores are combined with `max` for the metrics display
+//! - Output writes the channels back to a stereo device, or averages them for a mono one
+//!
+//! Channels can be spread over threads with `parallel::ChannelWorkers`. The
+//! mono pipeline is untouched; the stages in `MONO_ONLY_STAGES` are skipped in
+//! stereo, which is logged at startup and greys out their controls in the GUI.
+
+use super::gain::{self, ChannelGate, GainSmoother, StereoGates};
+use super::parallel;
+use nnnoiseless::DenoiseState;
+
+/// Mono pipeline stages the stereo path doesn't run; their settings are ignored in stereo
+pub const MONO_ONLY_STAGES: &[&str] = &[
+    "music passthrough",
+    "denoiser crossover",
+    "idle bypass",
+    "frame deadline",
+    "instant replay",
+    "noise profile",
+    "onset protection",
+    "gate hold",
+    "comfort noise",
+    "auto sensitivity",
+    "maximum test mode",
+    "pipeline verification tone",
+];
+
+/// Channel count of the stereo pipeline
+pub const STEREO_CHANNELS: usize = 2;
+
+/// Split interleaved stereo samples into the left and right channels
+pub fn deinterleave(interleaved: &[f32], left: &mut Vec<f32>, right: &mut Vec<f32>) {
+    left.clear();
+    right.clear();
+    for pair in interleaved.chunks_exact(STEREO_CHANNELS) {
+        left.push(pair[0]);
+        right.push(pair[1]);
+    }
+}
+
+/// Interleave a left and right channel of equal length
+pub fn interleave(left: &[f32], right: &[f32]) -> Vec<f32> {
+    left.iter().zip(right).flat_map(|(&l, &r)| [l, r]).collect()
+}
+
+/// Denoiser and gate for one channel
+struct ChannelState {
+    denoiser: Box<DenoiseState<'static>>,
+    gate: ChannelGate,
+    speech_gain: f32,
+    gain_floor: f32,
+    smoother: GainSmoother,
+    /// VAD score of the last processed frame
+    vad_score: f32,
+    output: Vec<f32>,
+}
+
+impl ChannelState {
+    fn new(denoiser: DenoiseState<'static>, frame_size: usize, smoother: GainSmoother) -> Self {
+        Self {
+            denoiser: Box::new(denoiser),
+            gate: ChannelGate {
+                vad_threshold: super::DEFAULT_VAD_THRESHOLD,
+                noise_gain: super::DEFAULT_NOISE_GAIN,
+            },
+            speech_gain: super::DEFAULT_SPEECH_GAIN,
+            gain_floor: 0.0,
+            smoother,
+            vad_score: 0.0,
+            output: vec![0.0; frame_size],
+        }
+    }
+
+    /// Denoise and gate one channel frame in place
+    fn process(&mut self, samples: &mut [f32]) {
+        self.output.fill(0.0);
+        self.vad_score = self.denoiser.process_frame(&mut self.output, samples);
+
+        // Pass the input through if the denoiser misbehaves, as the mono path does
+        if self.output.iter().any(|s| !s.is_finite()) {
+            self.vad_score = 0.0;
+            return;
+        }
+        let gain = gain::apply_gain_floor(self.gate.gain(self.vad_score, self.speech_gain), self.gain_floor);
+        samples.copy_from_slice(&self.output);
+        self.smoother.apply(samples, gain);
+    }
+}
+
+/// Runs one independent denoiser per channel over interleaved stereo frames
+pub struct StereoProcessor {
+    workers: parallel::ChannelWorkers<ChannelState>,
+    channels: Vec<Vec<f32>>,
+}
+
+impl StereoProcessor {
+    /// Create a processor for frames of `frame_size` samples per channel
+    ///
+    /// `make_denoiser` is called once per channel so each gets a fresh state
+    /// (and the configured model weights); each channel ramps its gain with its
+    /// own copy of `smoother`.
+    pub fn new(make_denoiser: impl Fn() -> DenoiseState<'static>, frame_size: usize, max_threads: usize, smoother: GainSmoother) -> Self {
+        let states = (0..STEREO_CHANNELS).map(|_| ChannelState::new(make_denoiser(), frame_size, smoother.clone())).collect();
+        Self {
+            workers: parallel::ChannelWorkers::new(states, max_threads, |samples: &mut [f32], state: &mut ChannelState| state.process(samples)),
+            channels: vec![Vec::with_capacity(frame_size); STEREO_CHANNELS],
+        }
+    }
+
+    /// Replace both denoisers with clean recurrent state
+    pub fn reset(&mut self, make_denoiser: impl Fn() -> DenoiseState<'static>) {
+        self.workers.for_each_state(|_, state| *state.denoiser = make_denoiser());
+    }
+
+    /// Denoise and gate one interleaved stereo frame in place
+    ///
+    /// Returns the highest per-channel VAD score.
+    pub fn process(&mut self, frame: &mut [f32], gates: StereoGates, speech_gain: f32, gain_floor: f32) -> f32 {
+        self.workers.for_each_state(|index, state| {
+            state.gate = gates.channel(index);
+            state.speech_gain = speech_gain;
+            state.gain_floor = gain_floor;
+        });
+
+        let (left, right) = self.channels.split_at_mut(1);
+        deinterleave(frame, &mut left[0], &mut right[0]);
+        self.workers.process(&mut self.channels);
+        for (pair, (&l, &r)) in frame.chunks_exact_mut(STEREO_CHANNELS).zip(self.channels[0].iter().zip(&self.channels[1])) {
+            pair[0] = l;
+            pair[1] = r;
+        }
+
+        let mut vad_score = 0.0f32;
+        self.workers.for_each_state(|_, state| vad_score = vad_score.max(state.vad_score));
+        vad_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleave_round_trip() {
+        let interleaved = [0.1, -0.1, 0.2, -0.2, 0.3, -0.3];
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        deinterleave(&interleaved, &mut left, &mut right);
+        assert_eq!(left, vec![0.1, 0.2, 0.3]);
+        assert_eq!(right, vec![-0.1, -0.2, -0.3]);
+        assert_eq!(interleave(&left, &right), interleaved.to_vec());
+    }
+
+    #[test]
+    fn test_channels_are_processed_independently() {
+        let frame_size = nnnoiseless::FRAME_SIZE;
+        let mut processor = StereoProcessor::new(|| *DenoiseState::new(), frame_size, 2, GainSmoother::new(0, 0, 48000));
+        // Noise on the left gets muted, the right channel has no gate at all
+        let gates = StereoGates {
+            left: ChannelGate { vad_threshold: 1.0, noise_gain: 0.0 },
+            right: ChannelGate { vad_threshold: 0.0, noise_gain: 1.0 },
+        };
+
+        let mut vad_score = 0.0;
+        let mut frame = Vec::new();
+        for i in 0..20 {
+            let tone: Vec<f32> = (0..frame_size)
+                .map(|n| 0.1 * (((i * frame_size + n) as f32) * 0.05).sin())
+                .collect();
+            frame = interleave(&tone, &tone);
+            vad_score = processor.process(&mut frame, gates, 0.8, 0.0);
+        }
+
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        deinterleave(&frame, &mut left, &mut right);
+        assert!(left.iter().all(|&s| s == 0.0), "Left gate should mute the channel");
+        assert!(right.iter().all(|s| s.is_finite()));
+        assert!((0.0..=1.0).contains(&vad_score));
+    }
+}
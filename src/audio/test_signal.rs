@@ -0,0 +1,394 @@
+//! # Test Signal Module
+//!
+//! Synthesizes deterministic input audio so the processing pipeline can be exercised
+//! without a microphone. Test signals appear as pseudo input devices and are generated
+//! by the capture thread in place of a real device stream. The noise generators are
+//! listed in development mode; a configured WAV loop is always listed, so users can
+//! reproduce issues or demo Kwite with their own recording.
+//!
+//! ## Signals
+//!
+//! - **White noise**: Flat spectrum, the classic "fan/hiss" stand-in
+//! - **Pink noise**: 1/f spectrum, closer to real room noise
+//! - **Speech-shaped noise**: Band-limited pink noise with a syllabic envelope
+//! - **WAV loop**: A user-supplied recording played in a loop, crossfaded at the loop point
+//!
+//! All noise generators use a fixed seed, so two runs produce identical audio and
+//! issues can be reproduced exactly.
+
+use crate::audio::devices::AudioDeviceInfo;
+use crate::audio::resampling::SimpleResampler;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::path::Path;
+
+/// Prefix that marks an input device ID as a synthesized test signal
+pub const TEST_SIGNAL_DEVICE_PREFIX: &str = "test_signal:";
+
+/// Sample rate of generated test signals (matches the RNNoise pipeline)
+pub const TEST_SIGNAL_SAMPLE_RATE: u32 = 48000;
+
+/// Peak amplitude of the synthesized noise signals
+const NOISE_AMPLITUDE: f32 = 0.1;
+
+/// Length of the crossfade that hides the WAV loop seam (20ms at 48kHz)
+pub const LOOP_CROSSFADE_SAMPLES: usize = 960;
+
+/// Fixed seed so generated noise is reproducible between runs
+const TEST_SIGNAL_SEED: u64 = 0x6b77_6974_65;
+
+/// Kind of synthesized input signal
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestSignalKind {
+    WhiteNoise,
+    PinkNoise,
+    SpeechShapedNoise,
+    /// Loop the given WAV file
+    WavLoop(String),
+}
+
+impl TestSignalKind {
+    /// Parse a pseudo device ID produced by [`TestSignalKind::device_id`]
+    pub fn from_device_id(device_id: &str) -> Option<Self> {
+        let kind = device_id.strip_prefix(TEST_SIGNAL_DEVICE_PREFIX)?;
+        match kind {
+            "white" => Some(TestSignalKind::WhiteNoise),
+            "pink" => Some(TestSignalKind::PinkNoise),
+            "speech" => Some(TestSignalKind::SpeechShapedNoise),
+            _ => kind
+                .strip_prefix("wav:")
+                .filter(|path| !path.is_empty())
+                .map(|path| TestSignalKind::WavLoop(path.to_string())),
+        }
+    }
+
+    /// Pseudo device ID used to select this signal as an input device
+    pub fn device_id(&self) -> String {
+        match self {
+            TestSignalKind::WhiteNoise => format!("{}white", TEST_SIGNAL_DEVICE_PREFIX),
+            TestSignalKind::PinkNoise => format!("{}pink", TEST_SIGNAL_DEVICE_PREFIX),
+            TestSignalKind::SpeechShapedNoise => format!("{}speech", TEST_SIGNAL_DEVICE_PREFIX),
+            TestSignalKind::WavLoop(path) => format!("{}wav:{}", TEST_SIGNAL_DEVICE_PREFIX, path),
+        }
+    }
+
+    /// Human readable name for device lists
+    pub fn name(&self) -> String {
+        match self {
+            TestSignalKind::WhiteNoise => "🧪 Test Signal: White Noise".to_string(),
+            TestSignalKind::PinkNoise => "🧪 Test Signal: Pink Noise".to_string(),
+            TestSignalKind::SpeechShapedNoise => "🧪 Test Signal: Speech-Shaped Noise".to_string(),
+            TestSignalKind::WavLoop(path) => {
+                let file_name = Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.clone());
+                format!("📁 File Loop: {}", file_name)
+            }
+        }
+    }
+}
+
+/// Pseudo input devices for all available test signals
+///
+/// The WAV loop entry is only included when a file path is configured.
+pub fn test_signal_devices(wav_path: Option<&str>) -> Vec<AudioDeviceInfo> {
+    let mut devices: Vec<AudioDeviceInfo> = [
+        TestSignalKind::WhiteNoise,
+        TestSignalKind::PinkNoise,
+        TestSignalKind::SpeechShapedNoise,
+    ]
+    .into_iter()
+    .map(|kind| pseudo_device(&kind))
+    .collect();
+    devices.extend(file_loop_device(wav_path));
+    devices
+}
+
+/// Pseudo input device looping the WAV file at `wav_path`, if one is configured
+pub fn file_loop_device(wav_path: Option<&str>) -> Option<AudioDeviceInfo> {
+    let path = wav_path.filter(|p| !p.is_empty())?;
+    Some(pseudo_device(&TestSignalKind::WavLoop(path.to_string())))
+}
+
+fn pseudo_device(kind: &TestSignalKind) -> AudioDeviceInfo {
+    AudioDeviceInfo {
+        id: kind.device_id(),
+        name: kind.name(),
+        is_default: false,
+        is_virtual: false,
+    }
+}
+
+/// Prepare a recording for seamless looping
+///
+/// The last `fade_len` samples are blended into the first `fade_len`, and
+/// playback restarts right after that head, so the waveform continues without
+/// a click at the seam. Recordings shorter than two fades are returned as is.
+pub fn crossfade_loop(samples: Vec<f32>, fade_len: usize) -> Vec<f32> {
+    if fade_len == 0 || samples.len() <= fade_len * 2 {
+        return samples;
+    }
+    let (head, rest) = samples.split_at(fade_len);
+    let mut looped = rest.to_vec();
+    let tail_start = looped.len() - fade_len;
+    for (i, (sample, &head_sample)) in looped[tail_start..].iter_mut().zip(head).enumerate() {
+        let t = (i + 1) as f32 / (fade_len + 1) as f32;
+        *sample = *sample * (1.0 - t) + head_sample * t;
+    }
+    looped
+}
+
+/// Whether a device ID refers to a synthesized test signal
+pub fn is_test_signal_device(device_id: &str) -> bool {
+    device_id.starts_with(TEST_SIGNAL_DEVICE_PREFIX)
+}
+
+/// Generator producing mono 48kHz test signal samples
+pub struct TestSignalGenerator {
+    kind: TestSignalKind,
+    rng: StdRng,
+    /// Paul Kellet pink noise filter state
+    pink_state: [f32; 7],
+    /// One-pole high-pass/low-pass state for speech shaping
+    highpass_prev_in: f32,
+    highpass_prev_out: f32,
+    lowpass_prev: f32,
+    sample_index: u64,
+    wav_samples: Vec<f32>,
+    wav_position: usize,
+}
+
+impl TestSignalGenerator {
+    /// Create a generator, loading the WAV file for `WavLoop`
+    pub fn new(kind: TestSignalKind) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let wav_samples = match &kind {
+            TestSignalKind::WavLoop(path) => crossfade_loop(load_wav_mono_48k(Path::new(path))?, LOOP_CROSSFADE_SAMPLES),
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            kind,
+            rng: StdRng::seed_from_u64(TEST_SIGNAL_SEED),
+            pink_state: [0.0; 7],
+            highpass_prev_in: 0.0,
+            highpass_prev_out: 0.0,
+            lowpass_prev: 0.0,
+            sample_index: 0,
+            wav_samples,
+            wav_position: 0,
+        })
+    }
+
+    /// Fill `output` with the next block of samples
+    pub fn fill(&mut self, output: &mut [f32]) {
+        for sample in output.iter_mut() {
+            *sample = match self.kind {
+                TestSignalKind::WhiteNoise => self.white() * NOISE_AMPLITUDE,
+                TestSignalKind::PinkNoise => self.pink() * NOISE_AMPLITUDE,
+                TestSignalKind::SpeechShapedNoise => self.speech_shaped(),
+                TestSignalKind::WavLoop(_) => self.next_wav_sample(),
+            };
+            self.sample_index += 1;
+        }
+    }
+
+    fn white(&mut self) -> f32 {
+        self.rng.gen_range(-1.0..=1.0)
+    }
+
+    /// Pink noise via Paul Kellet's refined filter (roughly unit peak)
+    fn pink(&mut self) -> f32 {
+        let white = self.white();
+        let b = &mut self.pink_state;
+        b[0] = 0.99886 * b[0] + white * 0.0555179;
+        b[1] = 0.99332 * b[1] + white * 0.0750759;
+        b[2] = 0.96900 * b[2] + white * 0.1538520;
+        b[3] = 0.86650 * b[3] + white * 0.3104856;
+        b[4] = 0.55000 * b[4] + white * 0.5329522;
+        b[5] = -0.7616 * b[5] - white * 0.0168980;
+        let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+        b[6] = white * 0.115926;
+        pink * 0.11
+    }
+
+    /// Pink noise band-limited to the speech band with a ~4Hz syllabic envelope
+    fn speech_shaped(&mut self) -> f32 {
+        let pink = self.pink();
+
+        // One-pole high-pass around 300Hz
+        let highpass = 0.96 * (self.highpass_prev_out + pink - self.highpass_prev_in);
+        self.highpass_prev_in = pink;
+        self.highpass_prev_out = highpass;
+
+        // One-pole low-pass around 3.4kHz
+        self.lowpass_prev += 0.36 * (highpass - self.lowpass_prev);
+
+        let t = self.sample_index as f32 / TEST_SIGNAL_SAMPLE_RATE as f32;
+        let envelope = (2.0 * std::f32::consts::PI * 4.0 * t).sin().max(0.0);
+        self.lowpass_prev * envelope * NOISE_AMPLITUDE * 4.0
+    }
+
+    fn next_wav_sample(&mut self) -> f32 {
+        if self.wav_samples.is_empty() {
+            return 0.0;
+        }
+        let sample = self.wav_samples[self.wav_position];
+        self.wav_position = (self.wav_position + 1) % self.wav_samples.len();
+        sample
+    }
+}
+
+/// Load a PCM16 or float32 WAV file as mono samples at 48kHz
+///
+/// Multi-channel audio is averaged to mono and other sample rates are resampled.
+pub fn load_wav_mono_48k(path: &Path) -> Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>> {
+    let bytes = std::fs::read(path)?;
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a RIFF/WAVE file".into());
+    }
+
+    let read_u16 = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let read_u32 = |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+
+    let mut format = None;
+    let mut data = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_len = read_u32(offset + 4) as usize;
+        let body = offset + 8;
+        let end = body.saturating_add(chunk_len).min(bytes.len());
+
+        if chunk_id == b"fmt " && chunk_len >= 16 {
+            // (audio format, channels, sample rate, bits per sample)
+            format = Some((read_u16(body), read_u16(body + 2), read_u32(body + 4), read_u16(body + 14)));
+        } else if chunk_id == b"data" {
+            data = Some(&bytes[body..end]);
+        }
+
+        // Chunks are padded to an even length
+        offset = body.saturating_add(chunk_len + (chunk_len & 1));
+    }
+
+    let (audio_format, channels, sample_rate, bits) = format.ok_or("WAV file has no fmt chunk")?;
+    let data = data.ok_or("WAV file has no data chunk")?;
+    if channels == 0 || sample_rate == 0 {
+        return Err("WAV file has an invalid format".into());
+    }
+
+    let interleaved: Vec<f32> = match (audio_format, bits) {
+        (1, 16) => data
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+            .collect(),
+        (3, 32) => data
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+        _ => return Err(format!("Unsupported WAV encoding (format {}, {} bits)", audio_format, bits).into()),
+    };
+
+    let mono: Vec<f32> = interleaved
+        .chunks(channels as usize)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    if mono.is_empty() {
+        return Err("WAV file contains no audio".into());
+    }
+
+    let mut resampler = SimpleResampler::new(sample_rate, TEST_SIGNAL_SAMPLE_RATE);
+    let mut resampled = Vec::new();
+    resampler.process(&mono, &mut resampled);
+    Ok(resampled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_device_id_roundtrip() {
+        let kinds = [
+            TestSignalKind::WhiteNoise,
+            TestSignalKind::PinkNoise,
+            TestSignalKind::SpeechShapedNoise,
+            TestSignalKind::WavLoop("/tmp/noise loop.wav".to_string()),
+        ];
+        for kind in kinds {
+            assert!(is_test_signal_device(&kind.device_id()));
+            assert_eq!(TestSignalKind::from_device_id(&kind.device_id()), Some(kind));
+        }
+
+        assert_eq!(TestSignalKind::from_device_id("input_0"), None);
+        assert_eq!(TestSignalKind::from_device_id("test_signal:wav:"), None);
+        assert_eq!(test_signal_devices(None).len(), 3);
+    }
+
+    #[test]
+    fn test_generators_are_deterministic_and_bounded() {
+        for kind in [TestSignalKind::WhiteNoise, TestSignalKind::PinkNoise, TestSignalKind::SpeechShapedNoise] {
+            let mut first = TestSignalGenerator::new(kind.clone()).unwrap();
+            let mut second = TestSignalGenerator::new(kind.clone()).unwrap();
+            let mut a = vec![0.0; 4800];
+            let mut b = vec![0.0; 4800];
+            first.fill(&mut a);
+            second.fill(&mut b);
+
+            assert_eq!(a, b, "{:?} should be reproducible", kind);
+            assert!(a.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+            assert!(a.iter().any(|&s| s != 0.0), "{:?} should not be silent", kind);
+        }
+    }
+
+    #[test]
+    fn test_wav_loop_playback() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("loop.wav");
+
+        // Stereo 16-bit PCM at 48kHz, two frames
+        let samples: [i16; 4] = [16384, 16384, -16384, -16384];
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&2u16.to_le_bytes()); // channels
+        wav.extend_from_slice(&48000u32.to_le_bytes());
+        wav.extend_from_slice(&(48000u32 * 4).to_le_bytes());
+        wav.extend_from_slice(&4u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&data);
+        std::fs::write(&path, wav).unwrap();
+
+        let kind = TestSignalKind::WavLoop(path.to_string_lossy().to_string());
+        let mut generator = TestSignalGenerator::new(kind).unwrap();
+        let mut output = vec![0.0; 5];
+        generator.fill(&mut output);
+        assert_eq!(output, vec![0.5, -0.5, 0.5, -0.5, 0.5]);
+
+        let missing = TestSignalKind::WavLoop(temp_dir.path().join("missing.wav").to_string_lossy().to_string());
+        assert!(TestSignalGenerator::new(missing).is_err());
+    }
+
+    #[test]
+    fn test_crossfade_loop_is_seamless() {
+        // A ramp jumps from 1.0 back to 0.0 at the loop point without a crossfade
+        let ramp: Vec<f32> = (0..4800).map(|i| i as f32 / 4799.0).collect();
+        let looped = crossfade_loop(ramp.clone(), LOOP_CROSSFADE_SAMPLES);
+
+        assert_eq!(looped.len(), ramp.len() - LOOP_CROSSFADE_SAMPLES);
+        let seam = (looped[looped.len() - 1] - looped[0]).abs();
+        let max_step = looped.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max);
+        assert!(seam <= max_step + 1e-6, "Loop seam ({}) should be no larger than a normal step ({})", seam, max_step);
+
+        // Too short to crossfade: unchanged
+        assert_eq!(crossfade_loop(vec![0.5, -0.5], LOOP_CROSSFADE_SAMPLES), vec![0.5, -0.5]);
+    }
+}
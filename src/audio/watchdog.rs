@@ -0,0 +1,215 @@
+//! # Stream Watchdog Module
+//!
+//! A driver reset or a sleep/wake cycle can kill the input or output stream
+//! without any error reaching Kwite: the `AudioManager` stays alive but no frames
+//! flow, or frames are processed but never played. The watchdog polls the
+//! diagnostic frame counter and the output callback counter and, when either
+//! stops advancing for longer than the configured timeout while processing is
+//! enabled, tears the manager down and rebuilds it with the same devices and
+//! current settings.
+//!
+//! The manager is taken out of the shared slot for the rebuild, so the GUI and
+//! the metrics endpoint aren't blocked while devices reopen. If processing is
+//! switched off or restarted meanwhile, the rebuilt pipeline is discarded.
+//!
+//! Restarts are counted process-wide and copied into the pipeline's `AiMetrics`
+//! so the GUI and the metrics endpoint can show them.
+
+use super::{diagnostic_frame_count, AudioManager};
+use crate::logger::log;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default time without processed frames before the pipeline is rebuilt (seconds)
+pub const DEFAULT_STALL_TIMEOUT_SECS: u64 = 5;
+
+/// How often the watchdog checks the frame counter
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of pipeline rebuilds performed by the watchdog in this process
+static RESTART_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Set while the watchdog holds a stalled manager outside the shared slot
+static REBUILD_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Set when processing is switched off during a rebuild
+static REBUILD_ABANDONED: AtomicBool = AtomicBool::new(false);
+
+/// Number of times the watchdog has rebuilt a stalled pipeline
+pub fn restart_count() -> u64 {
+    RESTART_COUNT.load(Ordering::Relaxed)
+}
+
+/// Whether the shared slot is empty because the watchdog is rebuilding the pipeline
+pub fn rebuild_in_progress() -> bool {
+    REBUILD_IN_PROGRESS.load(Ordering::Relaxed)
+}
+
+/// Keep a rebuild in progress from putting its pipeline back (processing was switched off)
+///
+/// Call this while holding the manager slot lock.
+pub fn abandon_rebuild() {
+    REBUILD_ABANDONED.store(true, Ordering::Relaxed);
+}
+
+/// Detects when a monotonically increasing counter stops advancing
+#[derive(Debug, Clone)]
+pub struct StallDetector {
+    timeout: Duration,
+    last_count: u64,
+    last_progress: Instant,
+}
+
+impl StallDetector {
+    /// Create a detector that reports a stall after `timeout` without progress
+    pub fn new(timeout: Duration, count: u64, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_count: count,
+            last_progress: now,
+        }
+    }
+
+    /// Restart the stall timer from `count`
+    pub fn reset(&mut self, count: u64, now: Instant) {
+        self.last_count = count;
+        self.last_progress = now;
+    }
+
+    /// Record the current counter value; returns true once the counter has stalled
+    pub fn observe(&mut self, count: u64, now: Instant) -> bool {
+        if count != self.last_count {
+            self.reset(count, now);
+            return false;
+        }
+        now.saturating_duration_since(self.last_progress) >= self.timeout
+    }
+}
+
+/// Background thread that rebuilds the audio pipeline when it stalls
+///
+/// The watchdog stops when dropped.
+pub struct Watchdog {
+    running: Arc<AtomicBool>,
+    _thread: thread::JoinHandle<()>,
+}
+
+impl Watchdog {
+    /// Start watching the manager slot shared with the GUI
+    ///
+    /// An empty slot means processing is disabled and is never treated as a stall.
+    pub fn spawn(manager: Arc<Mutex<Option<AudioManager>>>, timeout: Duration) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let running_clone = running.clone();
+        log::info!("🐕 Stream watchdog started (stall timeout {:?})", timeout);
+
+        let handle = thread::spawn(move || {
+            let mut frames = StallDetector::new(timeout, diagnostic_frame_count(), Instant::now());
+            let mut callbacks = StallDetector::new(timeout, 0, Instant::now());
+
+            while running_clone.load(Ordering::Relaxed) {
+                thread::sleep(POLL_INTERVAL);
+
+                let stalled = {
+                    let mut slot = match manager.lock() {
+                        Ok(slot) => slot,
+                        Err(_) => continue,
+                    };
+                    let frame_count = diagnostic_frame_count();
+                    let now = Instant::now();
+
+                    let callback_count = match slot.as_ref() {
+                        Some(audio_mgr) => audio_mgr.output_callback_count(),
+                        None => {
+                            frames.reset(frame_count, now);
+                            callbacks.reset(0, now);
+                            continue;
+                        }
+                    };
+                    // Observe both so neither timer falls behind
+                    let input_stalled = frames.observe(frame_count, now);
+                    let output_stalled = callbacks.observe(callback_count, now);
+                    if !input_stalled && !output_stalled {
+                        continue;
+                    }
+
+                    if input_stalled {
+                        log::warn!("🐕 No audio frames processed for {:?} - rebuilding the audio pipeline", timeout);
+                    } else {
+                        log::warn!("🐕 Output device stopped requesting audio for {:?} - rebuilding the audio pipeline", timeout);
+                    }
+                    REBUILD_ABANDONED.store(false, Ordering::Relaxed);
+                    REBUILD_IN_PROGRESS.store(true, Ordering::Relaxed);
+                    slot.take().expect("slot checked above")
+                };
+
+                // Devices can take seconds to reopen; don't hold the slot meanwhile
+                let result = stalled.rebuild();
+
+                let mut slot = match manager.lock() {
+                    Ok(slot) => slot,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                REBUILD_IN_PROGRESS.store(false, Ordering::Relaxed);
+                let discarded = match result {
+                    Ok(rebuilt) if slot.is_none() && !REBUILD_ABANDONED.load(Ordering::Relaxed) => {
+                        let restarts = RESTART_COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+                        if let Ok(mut metrics) = rebuilt.get_ai_metrics().lock() {
+                            metrics.watchdog_restarts = restarts;
+                        }
+                        *slot = Some(rebuilt);
+                        log::info!("✅ Audio pipeline recovered (watchdog restart #{})", restarts);
+                        None
+                    }
+                    Ok(rebuilt) => {
+                        log::info!("🐕 Processing changed during the rebuild - discarding the rebuilt pipeline");
+                        Some(rebuilt)
+                    }
+                    Err(e) => {
+                        log::error!("❌ Watchdog could not rebuild the audio pipeline: {}", e);
+                        None
+                    }
+                };
+                let now = Instant::now();
+                frames.reset(diagnostic_frame_count(), now);
+                callbacks.reset(slot.as_ref().map_or(0, |audio_mgr| audio_mgr.output_callback_count()), now);
+                drop(slot);
+                // Stop the discarded streams without holding the slot
+                drop(discarded);
+            }
+        });
+
+        Self {
+            running,
+            _thread: handle,
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stall_detector() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut detector = StallDetector::new(Duration::from_secs(5), 0, start);
+
+        assert!(!detector.observe(100, at(1)));
+        assert!(!detector.observe(100, at(5)), "Progress at 1s restarts the timer");
+        assert!(detector.observe(100, at(6)));
+
+        detector.reset(100, at(6));
+        assert!(!detector.observe(100, at(10)));
+        assert!(!detector.observe(200, at(12)));
+    }
+}
@@ -22,7 +22,9 @@
 
 use crate::config::AutoUpdateConfig;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::path::PathBuf;
+use std::sync::mpsc;
 use std::time::{Duration, SystemTime};
 
 /// Information about a software update
@@ -142,10 +144,15 @@ impl AutoUpdateManager {
         }
     }
 
+    /// Version manifest URL for the configured release channel
+    pub fn manifest_url(&self) -> String {
+        format!("{}/{}", self.config.update_endpoint.trim_end_matches('/'), self.config.channel.manifest_file())
+    }
+
     /// Fetch update information from remote server
     #[cfg(feature = "remote-logging")]
     async fn fetch_update_info(&self, client: &reqwest::Client) -> Result<UpdateInfo, Box<dyn std::error::Error + Send + Sync>> {
-        let url = format!("{}/version.json", self.config.update_endpoint);
+        let url = self.manifest_url();
         
         let response = client
             .get(&url)
@@ -162,16 +169,25 @@ impl AutoUpdateManager {
     }
 
     /// Compare version strings to determine if remote version is newer
+    /// 
+    /// Follows semver precedence: `1.2.0-beta.1` is newer than `1.1.0` but
+    /// older than `1.2.0`. Build metadata (`+...`) is ignored.
     fn is_newer_version(&self, remote_version: &str) -> bool {
         match (self.parse_version(&self.current_version), self.parse_version(remote_version)) {
-            (Ok(current), Ok(remote)) => remote > current,
+            (Ok(current), Ok(remote)) => match remote.cmp(&current) {
+                Ordering::Equal => compare_prerelease(prerelease(remote_version), prerelease(&self.current_version)) == Ordering::Greater,
+                ordering => ordering == Ordering::Greater,
+            },
             _ => false, // If we can't parse versions, assume no update
         }
     }
 
     /// Parse semantic version string into comparable tuple
+    /// 
+    /// Any pre-release or build suffix is ignored; see [`prerelease`].
     fn parse_version(&self, version: &str) -> Result<(u32, u32, u32), Box<dyn std::error::Error>> {
-        let parts: Result<Vec<u32>, _> = version
+        let core = version.split(['-', '+']).next().unwrap_or_default();
+        let parts: Result<Vec<u32>, _> = core
             .trim_start_matches('v') // Remove 'v' prefix if present
             .split('.')
             .take(3)
@@ -347,12 +363,101 @@ impl AutoUpdateManager {
 
     /// Force a manual update check regardless of interval
     pub async fn force_check(&mut self) -> UpdateCheckResult {
+        // Automatic checks may be off, in which case no client was created
+        #[cfg(feature = "remote-logging")]
+        if self.client.is_none() {
+            self.client = Some(reqwest::Client::new());
+        }
         let was_enabled = self.config.enabled;
         self.config.enabled = true; // Temporarily enable for manual check
         let result = self.check_for_updates().await;
         self.config.enabled = was_enabled; // Restore original setting
         result
     }
+
+    /// Fetch the release manifest and return it when it is newer than this build
+    pub async fn latest_update(&mut self) -> Result<Option<UpdateInfo>, String> {
+        match self.force_check().await {
+            UpdateCheckResult::NoUpdate => Ok(None),
+            UpdateCheckResult::UpdateAvailable(update_info) => Ok(Some(update_info)),
+            UpdateCheckResult::Error(e) => Err(e),
+        }
+    }
+}
+
+/// Whether this build can fetch the update manifest
+/// 
+/// Update checks use the HTTP client that comes with the `remote-logging`
+/// feature; without it the GUI disables the check instead of failing it.
+pub const UPDATE_CHECK_AVAILABLE: bool = cfg!(feature = "remote-logging");
+
+/// Run a manual update check on a background thread
+/// 
+/// The result arrives on the returned channel; poll it with `try_recv`.
+/// Builds without [`UPDATE_CHECK_AVAILABLE`] report an error right away.
+pub fn spawn_update_check(config: AutoUpdateConfig) -> mpsc::Receiver<UpdateCheckResult> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        #[cfg(feature = "remote-logging")]
+        let result = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(rt) => {
+                let mut manager = AutoUpdateManager::new(config);
+                match rt.block_on(manager.latest_update()) {
+                    Ok(Some(update_info)) => UpdateCheckResult::UpdateAvailable(update_info),
+                    Ok(None) => UpdateCheckResult::NoUpdate,
+                    Err(e) => UpdateCheckResult::Error(e),
+                }
+            }
+            Err(e) => UpdateCheckResult::Error(format!("failed to start update check: {}", e)),
+        };
+        #[cfg(not(feature = "remote-logging"))]
+        let result = {
+            let _ = config;
+            UpdateCheckResult::Error("Auto-update not enabled at compile time".to_string())
+        };
+        sender.send(result).ok();
+    });
+    receiver
+}
+
+/// Pre-release part of a version string (`"beta.1"` in `"1.2.0-beta.1+build"`)
+fn prerelease(version: &str) -> Option<&str> {
+    let version = version.split('+').next().unwrap_or_default();
+    version.split_once('-').map(|(_, prerelease)| prerelease)
+}
+
+/// Semver precedence of two pre-release parts of the same core version
+/// 
+/// A release (`None`) ranks above any pre-release; identifiers compare
+/// numerically when both are numbers, otherwise as text.
+fn compare_prerelease(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => {
+            let mut a = a.split('.');
+            let mut b = b.split('.');
+            loop {
+                match (a.next(), b.next()) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(x), Some(y)) => {
+                        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+                            (Ok(x), Ok(y)) => x.cmp(&y),
+                            (Ok(_), Err(_)) => Ordering::Less,
+                            (Err(_), Ok(_)) => Ordering::Greater,
+                            _ => x.cmp(y),
+                        };
+                        if ordering != Ordering::Equal {
+                            return ordering;
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 /// Get the default download directory for updates
@@ -427,6 +532,23 @@ mod tests {
         assert!(!manager.is_newer_version("0.0.9")); // Older version
     }
 
+    #[test]
+    fn test_prerelease_precedence() {
+        let mut manager = AutoUpdateManager::new(AutoUpdateConfig::default());
+        manager.current_version = "1.2.0-beta.2".to_string();
+
+        assert!(manager.is_newer_version("1.2.0"));
+        assert!(manager.is_newer_version("1.2.0-beta.10"));
+        assert!(manager.is_newer_version("1.2.0-rc.1+build.5"));
+        assert!(!manager.is_newer_version("1.2.0-beta.2"));
+        assert!(!manager.is_newer_version("1.2.0-alpha"));
+        assert!(!manager.is_newer_version("1.1.9"));
+
+        manager.current_version = "1.2.0".to_string();
+        assert!(!manager.is_newer_version("1.2.0-rc.1"));
+        assert!(manager.is_newer_version("1.3.0-beta.1"));
+    }
+
     #[test]
     fn test_update_check_timing() {
         let config = AutoUpdateConfig {
@@ -453,6 +575,20 @@ mod tests {
         assert!(!manager.is_check_due());
     }
 
+    #[test]
+    fn test_manifest_url_follows_channel() {
+        let mut config = AutoUpdateConfig {
+            update_endpoint: "https://updates.example.com/".to_string(),
+            ..AutoUpdateConfig::default()
+        };
+        let manager = AutoUpdateManager::new(config.clone());
+        assert_eq!(manager.manifest_url(), "https://updates.example.com/version.json");
+
+        config.channel = crate::config::UpdateChannel::Beta;
+        let manager = AutoUpdateManager::new(config);
+        assert_eq!(manager.manifest_url(), "https://updates.example.com/version-beta.json");
+    }
+
     #[test]
     fn test_file_size_formatting() {
         assert_eq!(format_file_size(512), "512 B");
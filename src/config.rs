@@ -18,14 +18,58 @@
 //! - **macOS**: `~/Library/Application Support/Kwite/config.toml`
 //! - **Linux**: `~/.config/kwite/config.toml`
 
-use crate::remote_logging::RemoteLoggingConfig;
+use crate::remote_logging::{RemoteLoggingConfig, DEVICE_NAME_FIELDS, MACHINE_ID_FIELDS};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::fs;
-use crate::constants::{DEFAULT_LOG_FLUSH_INTERVAL_SECONDS, DEFAULT_UPDATE_CHECK_INTERVAL_HOURS, PERFORMANCE_ENDPOINT, UPDATE_ENDPOINT};
+use crate::constants::{DEFAULT_BACKGROUND_FPS, DEFAULT_LOG_FLUSH_INTERVAL_SECONDS, DEFAULT_UPDATE_CHECK_INTERVAL_HOURS, PERFORMANCE_ENDPOINT, UPDATE_ENDPOINT};
+use crate::audio::analysis::{VadSource, DEFAULT_ANALYSIS_WINDOW, DEFAULT_VAD_HISTORY_LEN};
+use crate::audio::devices::DeviceFallback;
+use crate::audio::output::UnderrunFill;
+use crate::audio::meter::{MeterBallistics, DEFAULT_METER_INTEGRATION_MS};
+use crate::audio::{DEFAULT_NOISE_GAIN, DEFAULT_SPEECH_GAIN, DEFAULT_VAD_THRESHOLD};
+use crate::audio::gain::{DEFAULT_MIX, StereoGates};
+use crate::audio::latency::{LatencyOverrides, LatencyPreset};
+use crate::audio::affinity::ThreadAffinity;
+use crate::audio::resampling::DEFAULT_TARGET_SAMPLE_RATE;
+use crate::audio::music::MusicPassthroughSettings;
+use crate::audio::sidechain::DuckingSettings;
+use crate::audio::watchdog::DEFAULT_STALL_TIMEOUT_SECS;
+
+/// Release track queried by the update checker
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateChannel {
+    /// Tested releases recommended for everyone
+    #[default]
+    Stable,
+    /// Pre-releases for early adopters; may be unstable
+    Beta,
+}
+
+impl UpdateChannel {
+    /// All channels in display order
+    pub const ALL: [UpdateChannel; 2] = [UpdateChannel::Stable, UpdateChannel::Beta];
+
+    /// Human readable channel name
+    pub fn name(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "Stable",
+            UpdateChannel::Beta => "Beta",
+        }
+    }
+
+    /// Version manifest published for this channel on the update server
+    pub fn manifest_file(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "version.json",
+            UpdateChannel::Beta => "version-beta.json",
+        }
+    }
+}
 
 /// Auto-update configuration
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct AutoUpdateConfig {
     /// Whether to check for updates automatically
     pub enabled: bool,
@@ -35,10 +79,205 @@ pub struct AutoUpdateConfig {
     pub update_endpoint: String,
     /// Whether to notify user before downloading updates
     pub notify_before_download: bool,
+    /// Release track to follow (stable or beta)
+    #[serde(default)]
+    pub channel: UpdateChannel,
+}
+
+/// One-choice bundles of the remote logging and analytics settings
+///
+/// A preset writes the underlying `RemoteLoggingConfig`/`AnalyticsConfig`
+/// fields; it isn't stored itself. `detect` maps the current fields back to a
+/// preset, or `None` when they were customized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivacyPreset {
+    /// Nothing leaves the machine
+    Off,
+    /// Error logs only, without system information or usage/performance statistics
+    Minimal,
+    /// Errors, a sample of other events, system information and usage/performance statistics
+    Standard,
+    /// Every event plus system information and usage/performance statistics
+    Full,
+}
+
+impl PrivacyPreset {
+    /// All presets from least to most data shared
+    pub const ALL: [PrivacyPreset; 4] = [
+        PrivacyPreset::Off,
+        PrivacyPreset::Minimal,
+        PrivacyPreset::Standard,
+        PrivacyPreset::Full,
+    ];
+
+    /// Human readable preset name
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrivacyPreset::Off => "Off",
+            PrivacyPreset::Minimal => "Minimal",
+            PrivacyPreset::Standard => "Standard",
+            PrivacyPreset::Full => "Full",
+        }
+    }
+
+    /// Plain-language list of exactly what the preset sends
+    pub fn description(&self) -> &'static str {
+        match self {
+            PrivacyPreset::Off => "Nothing is sent.",
+            PrivacyPreset::Minimal => "Error logs only, with device names and network identifiers removed. No system information, usage statistics or performance data.",
+            PrivacyPreset::Standard => "Error logs (device names removed), 25% of other log events, weekly usage and performance statistics, crash reports you choose to send, and system information (OS, CPU, memory, hashed hardware ID, IP address).",
+            PrivacyPreset::Full => "All log events including device names, weekly usage and performance statistics, crash reports you choose to send, and system information (OS, CPU, memory, hashed hardware ID, IP address).",
+        }
+    }
+
+    /// Share of non-error log events sent under this preset
+    fn sampling_rate(&self) -> f32 {
+        match self {
+            PrivacyPreset::Off | PrivacyPreset::Minimal => 0.0,
+            PrivacyPreset::Standard => 0.25,
+            PrivacyPreset::Full => 1.0,
+        }
+    }
+
+    /// Log fields redacted under this preset; `None` leaves the list unchanged
+    fn redact_fields(&self) -> Option<Vec<String>> {
+        let fields: Vec<&str> = match self {
+            PrivacyPreset::Off => return None,
+            PrivacyPreset::Minimal => DEVICE_NAME_FIELDS.iter().chain(MACHINE_ID_FIELDS).copied().collect(),
+            PrivacyPreset::Standard => DEVICE_NAME_FIELDS.to_vec(),
+            PrivacyPreset::Full => Vec::new(),
+        };
+        Some(fields.into_iter().map(String::from).collect())
+    }
+
+    /// Write this preset's values into the privacy-related config fields
+    pub fn apply(&self, config: &mut KwiteConfig) {
+        let sharing = *self != PrivacyPreset::Off;
+        let detailed = matches!(self, PrivacyPreset::Standard | PrivacyPreset::Full);
+        config.analytics.enabled = detailed;
+        config.remote_logging.enabled = sharing;
+        config.remote_logging.include_system_info = detailed;
+        config.remote_logging.sampling_rate = self.sampling_rate();
+        if let Some(fields) = self.redact_fields() {
+            config.remote_logging.redact_fields = fields;
+        }
+    }
+
+    /// The preset matching the current settings, if they haven't been customized
+    pub fn detect(config: &KwiteConfig) -> Option<PrivacyPreset> {
+        Self::ALL.into_iter().find(|preset| {
+            let mut expected = config.clone();
+            preset.apply(&mut expected);
+            expected.analytics.enabled == config.analytics.enabled
+                && expected.remote_logging.enabled == config.remote_logging.enabled
+                && expected.remote_logging.include_system_info == config.remote_logging.include_system_info
+                && expected.remote_logging.sampling_rate == config.remote_logging.sampling_rate
+                && expected.remote_logging.redact_fields == config.remote_logging.redact_fields
+        })
+    }
+}
+
+/// Longest configurable delay before auto-start (ms)
+pub const MAX_AUTO_START_DELAY_MS: u32 = 10_000;
+
+/// Number of quick-swap device slots
+pub const DEVICE_SLOT_COUNT: usize = 2;
+
+/// A saved input/output device pair for one-click switching between setups
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct DeviceSlot {
+    /// Button label; empty shows "Slot N"
+    pub name: String,
+    /// Saved input device ID (empty = slot unused)
+    pub input_device_id: String,
+    /// Saved output device ID (empty = slot unused)
+    pub output_device_id: String,
+    /// Key pressed with Ctrl/Cmd+Alt to activate the slot (e.g. "1" or "F9")
+    pub hotkey: Option<String>,
+}
+
+impl DeviceSlot {
+    /// Whether no device pair has been saved in this slot
+    pub fn is_empty(&self) -> bool {
+        self.input_device_id.is_empty() || self.output_device_id.is_empty()
+    }
+
+    /// Label for the slot at `index`
+    pub fn label(&self, index: usize) -> String {
+        if self.name.trim().is_empty() {
+            format!("Slot {}", index + 1)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// Devices and processing parameters saved for one application (e.g. Discord or OBS)
+///
+/// Activating a profile switches to its input/output pair and restores its
+/// sensitivity and gains, so each virtual cable gets the tuning its consumer needs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct OutputProfile {
+    /// Name shown in the profile dropdown
+    pub name: String,
+    /// Input device ID used by this profile
+    pub input_device_id: String,
+    /// Output device ID this profile routes to
+    pub output_device_id: String,
+    /// Analyzer VAD sensitivity
+    pub sensitivity: f32,
+    /// Denoise gain threshold
+    pub vad_threshold: f32,
+    /// Gain applied to non-speech frames
+    pub noise_gain: f32,
+    /// Gain applied to speech frames
+    pub speech_gain: f32,
+    /// Pre-gain applied to the microphone (dB)
+    pub input_gain_db: f32,
+    /// Output volume trim (dB)
+    pub output_gain_db: f32,
+}
+
+impl Default for OutputProfile {
+    fn default() -> Self {
+        Self::capture("", &KwiteConfig::default())
+    }
+}
+
+impl OutputProfile {
+    /// Snapshot the devices and parameters of `config` under `name`
+    pub fn capture(name: &str, config: &KwiteConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            input_device_id: config.input_device_id.clone(),
+            output_device_id: config.output_device_id.clone(),
+            sensitivity: config.sensitivity,
+            vad_threshold: config.vad_threshold,
+            noise_gain: config.noise_gain,
+            speech_gain: config.speech_gain,
+            input_gain_db: config.input_gain_db,
+            output_gain_db: config.output_gain_db,
+        }
+    }
+
+    /// Copy this profile's devices and parameters into `config`
+    pub fn apply_to(&self, config: &mut KwiteConfig) {
+        config.input_device_id = self.input_device_id.clone();
+        config.output_device_id = self.output_device_id.clone();
+        config.sensitivity = self.sensitivity.clamp(crate::audio::MIN_SENSITIVITY, crate::audio::MAX_SENSITIVITY);
+        config.vad_threshold = self.vad_threshold.clamp(crate::audio::MIN_VAD_THRESHOLD, crate::audio::MAX_VAD_THRESHOLD);
+        config.noise_gain = self.noise_gain.clamp(0.0, 1.0);
+        config.speech_gain = self.speech_gain.clamp(0.0, 1.0);
+        config.input_gain_db = self.input_gain_db.clamp(crate::audio::MIN_INPUT_GAIN_DB, crate::audio::MAX_INPUT_GAIN_DB);
+        config.output_gain_db = self.output_gain_db.clamp(crate::audio::MIN_OUTPUT_GAIN_DB, crate::audio::MAX_OUTPUT_GAIN_DB);
+    }
 }
 
 /// Performance and analytics configuration  
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct AnalyticsConfig {
     /// Whether to send crash logs and performance data
     pub enabled: bool,
@@ -46,6 +285,8 @@ pub struct AnalyticsConfig {
     pub performance_endpoint: String,
     /// How often to send performance data (in seconds) - weekly
     pub performance_interval_seconds: u64,
+    /// Leave sessions in which the microphone never carried a signal out of the statistics
+    pub drop_silent_sessions: bool,
 }
 
 /// Application configuration structure
@@ -58,14 +299,74 @@ pub struct AnalyticsConfig {
 ///
 /// - `input_device_id`: Identifier for the preferred microphone/input device
 /// - `output_device_id`: Identifier for the preferred output device (often virtual cable)
-/// - `sensitivity`: Noise cancellation sensitivity threshold (0.01 - 0.5)
+/// - `sensitivity`: Analyzer VAD aggressiveness (0.01 - 0.5); separate from `vad_threshold`
 /// - `auto_start`: Whether to begin noise cancellation automatically on startup
+/// - `restore_last_enabled_state`: Re-enable noise cancellation only if it was active when last closed
+/// - `auto_start_delay_ms`: Extra wait after the window first appears before auto-starting (0 - 10000 ms)
 /// - `minimize_to_tray`: Whether to minimize to system tray instead of taskbar
 /// - `development_mode`: Enable advanced analytics and debug features
 /// - `remote_logging`: Configuration for remote logging and analytics
 /// - `usage_statistics`: Enable collection of usage statistics
 /// - `auto_update`: Configuration for automatic updates
+/// - `vad_history_len`: Voice activity smoothing window in frames (1 - 50)
+/// - `analysis_window_size`: Spectral analysis window for noise classification (480 - 3840 samples)
+/// - `output_gain_db`: Output volume trim applied to processed audio (-24 dB - +12 dB)
+/// - `input_gain_db`: Pre-gain applied to the microphone before denoising (-12 dB - +24 dB)
+/// - `vad_threshold`: Denoise gain threshold; frames whose VAD score falls below it are attenuated
+/// - `noise_gain`: Gain applied to non-speech frames (suppression strength = 1 - noise_gain)
+/// - `speech_gain`: Gain applied to speech frames (0.0 - 1.0, default 0.8)
+/// - `gain_floor`: Minimum gain kept on every frame for natural room tone (0.0 - 0.5, 0 = off)
+/// - `comfort_noise_level`: Faint pink noise mixed in while the gate is closed, in dBFS (-80 - -40, off by default)
+/// - `onset_protection_ms`: Speech gain held after a detected speech onset (0 - 100 ms, 0 = off)
+/// - `gain_attack_ms` / `gain_release_ms`: Ramp times between frame gains that prevent clicks (0 - 500 ms, default 10/50)
+/// - `gate_hold_ms`: Time the gate stays open after the VAD drops, before the release (0 - 1000 ms, default 150)
+/// - `idle_bypass_secs`: Pause the denoiser after this long without speech, resuming on speech (0 - 600 s, 0 = off)
+/// - `vad_source`: Voice activity detector driving the gain decision (RNNoise or the analyzer VAD)
+/// - `device_fallback`: `Strict` fails to start if the saved microphone is missing; `BestEffort` tries the system default, then the first input
+/// - `dc_filter_enabled`: Remove the microphone's DC offset with a 5 Hz high-pass at capture (default on)
+/// - `underrun_fill`: Output during underruns: `Silence` (default) or `RepeatLastFrame` with a fade
+/// - `vad_deadband`: VAD band around the threshold that holds the previous speech/noise decision (0.0 - 0.3, 0.0 = off)
+/// - `instant_replay_secs`: Seconds of processed audio kept for "Save Last N Seconds" (0 - 120, 0 = off)
+/// - `frame_deadline_us`: Per-frame denoiser budget; frames predicted to exceed it pass through unprocessed (0 - 10000 µs, 0 = off)
+/// - `denoiser_crossover_hz`: Only audio below this frequency goes through RNNoise; the high band passes through (2000 - 16000 Hz, 0 = full band)
+/// - `mix`: Dry/wet blend of raw and processed audio (0.0 = raw, 1.0 = fully processed); below 1.0 some noise passes through
+/// - `latency_preset`: Buffering trade-off between delay and stability (default Balanced)
+/// - `latency_overrides`: Optional per-knob overrides for the latency preset (advanced)
+/// - `test_signal_wav_path`: WAV file offered as a looping "File Loop" input device
+/// - `custom_model_path`: Optional custom RNNoise weights file (falls back to built-in weights)
+/// - `toggle_hotkey`: Global shortcut toggling noise cancellation, e.g. `"ctrl+shift+K"` (unbound by default)
+/// - `process_thread_affinity`: Optional CPU core pinning for the processing thread (off by default)
+/// - `local_playback_only`: Play processed audio on real speakers without a virtual device
+/// - `allow_placeholder_devices`: Let Enable try the system default even when no devices were enumerated
+/// - `target_sample_rate`: End-to-end sample rate (8000, 16000, 32000 or 48000 Hz)
+/// - `stereo_processing`: Keep both channels of a stereo input, each with its own denoiser (off by default)
+/// - `input_channel`: Zero-based channel of a multichannel input device that carries the microphone (default 0)
+/// - `stereo_channel_gates`: Optional per-channel VAD threshold/noise gain for stereo processing
+/// - `max_processing_threads`: Threads used to process independent channels in parallel (1 - 8, default 1)
+/// - `music_passthrough`: Bypass suppression while music is detected, with engage/release dwell times (off by default)
+/// - `monitor_enabled` / `monitor_device_id`: Also play the processed audio on a second output device (off by default)
+/// - `ducking`: Lower the output while a sidechain device or external level is above a threshold (threshold/ratio/attack/release, off by default)
+/// - `stream_watchdog_timeout_secs`: Rebuild the audio pipeline after this many seconds without frames (0 = disabled)
+/// - `background_fps`: UI refresh rate while the window is unfocused (1 - 30); audio is unaffected
+/// - `meter_ballistics`: Level meter behaviour: Peak, PeakDecay (default), Rms or Vu
+/// - `meter_integration_ms`: Level meter integration/decay time (10 - 3000 ms, default 300)
+/// - `device_slots`: Two saved input/output pairs for quick swapping, each with an optional Ctrl/Cmd+Alt hotkey
+/// - `profiles`: Named per-application profiles (devices, sensitivity and gains), e.g. one per virtual cable
+/// - `active_profile`: Name of the profile selected in the top panel, if any
+/// - `metrics_port`: Localhost port for the Prometheus `/metrics` endpoint (off by default, `metrics-server` builds only)
+/// - `auto_sensitivity`: Derive the analyzer sensitivity from the measured noise floor instead of `sensitivity` (off by default)
+/// - `denoiser_warmup_ms`: Mute output while the denoiser settles after enabling (0 - 500 ms, 0 = off)
+/// - `startup_max_test_enabled`: Use Maximum Test Mode gains right after enabling (debugging aid, off by default)
+/// - `startup_max_test_secs`: Length of that startup window (1 - 60 s)
+/// - `processing_block_frames`: 10ms frames processed per block (1 - 8, default 1); larger blocks lower CPU wakeups at the cost of latency
+///
+/// ## Schema Evolution
+///
+/// Every field falls back to its default when missing, and unknown fields are
+/// reported with a warning rather than rejected, so configs written by older or
+/// newer versions of Kwite still load without losing the user's other settings.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct KwiteConfig {
     /// Audio input device identifier
     /// Typically corresponds to microphone or line-in device
@@ -75,14 +376,28 @@ pub struct KwiteConfig {
     /// Preferably a virtual audio cable for use with communication apps
     pub output_device_id: String,
 
-    /// Noise cancellation sensitivity (0.01 = aggressive, 0.5 = conservative)
-    /// Lower values remove more background noise but may affect voice quality
+    /// Analyzer VAD sensitivity (0.01 = aggressive, 0.5 = conservative)
+    /// Tunes the analyzer's voice detection (Analyzer VAD source, music detection);
+    /// the denoise gain decision uses `vad_threshold` instead
     pub sensitivity: f32,
 
     /// Automatically start noise cancellation when application launches
     /// Useful for users who always want noise cancellation enabled
     pub auto_start: bool,
 
+    /// Restore the enabled state from the previous session on launch
+    /// Noise cancellation starts only if it was active when Kwite was last closed.
+    /// `auto_start` takes precedence: when it is set, processing always starts and
+    /// this option has no effect.
+    #[serde(default)]
+    pub restore_last_enabled_state: bool,
+
+    /// Extra delay before auto-start, counted from the first painted frame (ms)
+    /// Auto-start never runs before the window is visible; on machines where
+    /// audio devices come up late after login, a delay avoids a failed start
+    #[serde(default)]
+    pub auto_start_delay_ms: u32,
+
     /// Minimize to system tray instead of showing in taskbar
     /// Helps keep the application running unobtrusively
     pub minimize_to_tray: bool,
@@ -101,6 +416,339 @@ pub struct KwiteConfig {
 
     /// Auto-update configuration
     pub auto_update: AutoUpdateConfig,
+
+    /// Voice activity smoothing window length in 10ms frames (1 - 50)
+    /// Shorter windows react faster to speech onset, longer ones are more stable
+    #[serde(default = "default_vad_history_len")]
+    pub vad_history_len: usize,
+
+    /// Spectral analysis window in samples used for noise classification (480 - 3840)
+    /// Larger windows resolve frequencies more finely; denoising still uses 480-sample frames
+    #[serde(default = "default_analysis_window_size")]
+    pub analysis_window_size: usize,
+
+    /// Output gain trim in decibels applied to the processed signal
+    /// A limiter after the trim keeps boosted output from clipping
+    #[serde(default)]
+    pub output_gain_db: f32,
+
+    /// Input pre-gain in decibels applied before the denoiser
+    /// Helps RNNoise on quiet microphones; clip protection backs the gain off on loud frames
+    #[serde(default)]
+    pub input_gain_db: f32,
+
+    /// Denoise gain threshold applied to the gating VAD score (0.05 - 0.95)
+    /// Frames scoring below the threshold are treated as background noise;
+    /// independent of the analyzer `sensitivity`
+    #[serde(default = "default_vad_threshold")]
+    pub vad_threshold: f32,
+
+    /// Gain applied to frames classified as background noise (0.0 - 1.0)
+    /// Shown in the GUI as suppression strength (`1.0 - noise_gain`)
+    #[serde(default = "default_noise_gain")]
+    pub noise_gain: f32,
+
+    /// Gain applied to frames classified as speech (0.0 - 1.0)
+    /// Together with `noise_gain` sets how far noise drops below the voice
+    #[serde(default = "default_speech_gain")]
+    pub speech_gain: f32,
+
+    /// Minimum gain applied to any frame (0.0 - 0.5)
+    /// Keeps some room tone for naturalness; independent of suppression strength
+    #[serde(default)]
+    pub gain_floor: f32,
+
+    /// Comfort noise level in dBFS while the gate is closed (`None` = off)
+    /// Replaces near-silence between words, which listeners mistake for a dropped call
+    #[serde(default)]
+    pub comfort_noise_level: Option<f32>,
+
+    /// Milliseconds of speech gain held when the VAD score jumps (0 - 100)
+    /// Protects the attack of the first syllable, which the VAD scores late
+    #[serde(default)]
+    pub onset_protection_ms: u32,
+
+    /// Milliseconds for the applied gain to rise to a higher target (0 - 500)
+    /// Ramping instead of switching per frame removes clicks at word boundaries
+    #[serde(default = "default_gain_attack_ms")]
+    pub gain_attack_ms: u32,
+
+    /// Milliseconds for the applied gain to fall to a lower target (0 - 500)
+    #[serde(default = "default_gain_release_ms")]
+    pub gain_release_ms: u32,
+
+    /// Milliseconds the gate stays open after the VAD drops (0 - 1000)
+    /// Bridges the pauses between words so the next syllable isn't clipped
+    #[serde(default = "default_gate_hold_ms")]
+    pub gate_hold_ms: u32,
+
+    /// Voice activity detector that decides whether a frame is speech
+    /// RNNoise's per-frame VAD by default; the analyzer VAD is smoother but slower
+    #[serde(default)]
+    pub vad_source: VadSource,
+
+    /// Remove any DC offset from the input with an inaudible 5 Hz high-pass
+    /// Some USB microphones add one, which wastes headroom and degrades denoising
+    #[serde(default = "default_dc_filter_enabled")]
+    pub dc_filter_enabled: bool,
+
+    /// What the output plays when processed audio runs out
+    /// Repeating the last frame with a fade makes brief dropouts less audible
+    #[serde(default)]
+    pub underrun_fill: UnderrunFill,
+
+    /// Width of the VAD band centered on `vad_threshold` in which the gate keeps
+    /// its previous decision, so a jumpy VAD doesn't flap the suppression
+    #[serde(default)]
+    pub vad_deadband: f32,
+
+    /// Behavior when the saved input device can't be opened at startup
+    #[serde(default)]
+    pub device_fallback: DeviceFallback,
+
+    /// Seconds of processed output kept in memory for instant replay (0 = off)
+    /// Memory use is about 190 KB per second
+    #[serde(default)]
+    pub instant_replay_secs: u32,
+
+    /// Per-frame denoiser budget in microseconds (0 = off)
+    /// When recent frames took longer, the next one passes through unprocessed
+    /// instead of building a backlog; trades a little noise for no dropouts
+    #[serde(default)]
+    pub frame_deadline_us: u32,
+
+    /// Crossover above which audio bypasses the denoiser, in Hz (0 = full band)
+    /// Keeps sibilance crisp for voices that RNNoise dulls; the high band still
+    /// follows the speech/noise gate
+    #[serde(default)]
+    pub denoiser_crossover_hz: u32,
+
+    /// Dry/wet mix between raw input (0.0) and processed output (1.0)
+    /// Lets users keep some natural room sound instead of full suppression.
+    /// Anything below 1.0 also lets that share of the background noise through.
+    #[serde(default = "default_mix")]
+    pub mix: f32,
+
+    /// Latency preset bundling channel capacity, jitter depth and overflow policy
+    #[serde(default)]
+    pub latency_preset: LatencyPreset,
+
+    /// Individual latency knobs overriding the preset (edit `config.toml` directly)
+    #[serde(default)]
+    pub latency_overrides: LatencyOverrides,
+
+    /// WAV file offered as a looping "File Loop" input device (a virtual microphone for demos and repros)
+    #[serde(default)]
+    pub test_signal_wav_path: Option<String>,
+
+    /// Path to custom RNNoise model weights
+    /// Invalid or missing files fall back to the built-in model with a warning
+    #[serde(default)]
+    pub custom_model_path: Option<String>,
+
+    /// System-wide shortcut that toggles noise cancellation, e.g. `"ctrl+shift+K"`
+    /// Works while other windows (full-screen games) have focus; unbound by default
+    #[serde(default)]
+    pub toggle_hotkey: Option<String>,
+
+    /// CPU core pinning for the audio processing thread
+    /// Helps on hybrid CPUs where the scheduler may pick an efficiency core
+    #[serde(default)]
+    pub process_thread_affinity: ThreadAffinity,
+
+    /// Local playback only (no virtual device routing)
+    /// Output defaults to the real speakers and virtual-device setup warnings are hidden
+    #[serde(default)]
+    pub local_playback_only: bool,
+
+    /// Allow starting with the "Default Microphone/Speakers" placeholders when
+    /// enumeration finds no devices (some audio servers only expose a default)
+    #[serde(default)]
+    pub allow_placeholder_devices: bool,
+
+    /// How the input/output level meters respond to the signal
+    #[serde(default)]
+    pub meter_ballistics: MeterBallistics,
+
+    /// Integration time of the level meters in milliseconds
+    #[serde(default = "default_meter_integration_ms")]
+    pub meter_integration_ms: u32,
+
+    /// End-to-end sample rate for capture and playback (Hz)
+    /// RNNoise always runs at 48kHz; other rates are resampled around it
+    #[serde(default = "default_target_sample_rate")]
+    pub target_sample_rate: u32,
+
+    /// Process left and right independently instead of collapsing the input to mono
+    /// Each channel gets its own denoiser; mono-only stages are skipped
+    #[serde(default)]
+    pub stereo_processing: bool,
+
+    /// Channel of the input device to capture (0 = first/left)
+    /// For interfaces such as 8-channel aggregate devices whose microphone isn't on channel 1
+    #[serde(default)]
+    pub input_channel: usize,
+
+    /// Independent left/right gates for stereo processing
+    /// `None` means both channels follow `vad_threshold` and `noise_gain`
+    #[serde(default)]
+    pub stereo_channel_gates: Option<StereoGates>,
+
+    /// Maximum threads used to process independent channels in parallel (1 - 8)
+    /// Mono processing is always single-threaded; 1 keeps multi-channel serial too
+    #[serde(default = "default_max_processing_threads")]
+    pub max_processing_threads: usize,
+
+    /// Automatic passthrough while sustained music is detected
+    /// Keeps RNNoise from mangling shared music; reverts once speech resumes
+    #[serde(default)]
+    pub music_passthrough: MusicPassthroughSettings,
+
+    /// Also play the processed audio on `monitor_device_id` ("hear yourself")
+    /// Saves building a Multi-Output Device just to listen to Kwite's output
+    #[serde(default)]
+    pub monitor_enabled: bool,
+
+    /// Output device used for monitoring (kept while monitoring is off)
+    #[serde(default)]
+    pub monitor_device_id: Option<String>,
+
+    /// Duck the output while an external sidechain signal is loud
+    /// For streamers who want their voice under loud game audio
+    #[serde(default)]
+    pub ducking: DuckingSettings,
+
+    /// Seconds without speech before the denoiser is bypassed (0 = never)
+    /// Saves CPU and battery during long quiet stretches; the analyzer VAD
+    /// re-engages it as soon as speech returns
+    #[serde(default)]
+    pub idle_bypass_secs: u32,
+
+    /// Seconds without processed frames before the pipeline is rebuilt
+    /// Recovers from streams that die silently after sleep/wake; 0 disables the watchdog
+    #[serde(default = "default_stream_watchdog_timeout_secs")]
+    pub stream_watchdog_timeout_secs: u64,
+
+    /// UI refresh rate while the window is in the background (frames per second)
+    /// Saves CPU/battery during long calls; audio threads keep running at full speed
+    #[serde(default = "default_background_fps")]
+    pub background_fps: u32,
+
+    /// Milliseconds of input fed to the denoiser with output muted after enabling
+    /// Lets the RNNoise recurrent state settle so the first moments don't sound off
+    #[serde(default)]
+    pub denoiser_warmup_ms: u32,
+
+    /// Apply Maximum Test Mode gains for a short window after enabling
+    /// A debugging aid that makes routing problems obvious; normal settings apply from frame one when off
+    #[serde(default)]
+    pub startup_max_test_enabled: bool,
+
+    /// Length of the startup Maximum Test Mode window in seconds
+    #[serde(default = "default_startup_max_test_secs")]
+    pub startup_max_test_secs: u32,
+
+    /// Number of 10ms frames buffered before the processing thread works through them
+    /// Bigger blocks suit low-power machines but add latency; RNNoise still sees 480-sample frames
+    #[serde(default = "default_processing_block_frames")]
+    pub processing_block_frames: usize,
+
+    /// Saved device pairs for quick swapping between setups (e.g. headset / desk mic)
+    #[serde(default = "default_device_slots")]
+    pub device_slots: [DeviceSlot; DEVICE_SLOT_COUNT],
+
+    /// Saved per-application profiles, in dropdown order
+    #[serde(default)]
+    pub profiles: Vec<OutputProfile>,
+
+    /// Name of the active profile; `None` when the current settings belong to no profile
+    #[serde(default)]
+    pub active_profile: Option<String>,
+
+    /// Localhost port serving Prometheus metrics (`None` = off)
+    /// Only used by builds with the `metrics-server` feature; `--metrics-port` overrides it
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+
+    /// Let the measured noise floor set the analyzer sensitivity
+    /// While enabled `sensitivity` is kept but unused; it applies again once this is turned off
+    #[serde(default)]
+    pub auto_sensitivity: bool,
+}
+
+fn default_vad_history_len() -> usize {
+    DEFAULT_VAD_HISTORY_LEN
+}
+
+fn default_processing_block_frames() -> usize {
+    1
+}
+
+fn default_startup_max_test_secs() -> u32 {
+    crate::audio::DEFAULT_STARTUP_MAX_TEST_SECS
+}
+
+fn default_max_processing_threads() -> usize {
+    crate::audio::parallel::DEFAULT_MAX_PROCESSING_THREADS
+}
+
+fn default_analysis_window_size() -> usize {
+    DEFAULT_ANALYSIS_WINDOW
+}
+
+fn default_vad_threshold() -> f32 {
+    DEFAULT_VAD_THRESHOLD
+}
+
+fn default_noise_gain() -> f32 {
+    DEFAULT_NOISE_GAIN
+}
+
+fn default_speech_gain() -> f32 {
+    DEFAULT_SPEECH_GAIN
+}
+
+fn default_gate_hold_ms() -> u32 {
+    crate::audio::gain::DEFAULT_GATE_HOLD_MS
+}
+
+fn default_gain_attack_ms() -> u32 {
+    crate::audio::gain::DEFAULT_GAIN_ATTACK_MS
+}
+
+fn default_gain_release_ms() -> u32 {
+    crate::audio::gain::DEFAULT_GAIN_RELEASE_MS
+}
+
+fn default_mix() -> f32 {
+    DEFAULT_MIX
+}
+
+fn default_target_sample_rate() -> u32 {
+    DEFAULT_TARGET_SAMPLE_RATE
+}
+
+fn default_stream_watchdog_timeout_secs() -> u64 {
+    DEFAULT_STALL_TIMEOUT_SECS
+}
+
+fn default_background_fps() -> u32 {
+    DEFAULT_BACKGROUND_FPS
+}
+
+fn default_dc_filter_enabled() -> bool {
+    true
+}
+
+fn default_meter_integration_ms() -> u32 {
+    DEFAULT_METER_INTEGRATION_MS
+}
+
+fn default_device_slots() -> [DeviceSlot; DEVICE_SLOT_COUNT] {
+    std::array::from_fn(|index| DeviceSlot {
+        hotkey: Some((index + 1).to_string()),
+        ..DeviceSlot::default()
+    })
 }
 
 impl Default for AutoUpdateConfig {
@@ -110,6 +758,7 @@ impl Default for AutoUpdateConfig {
             check_interval_hours: DEFAULT_UPDATE_CHECK_INTERVAL_HOURS,
             update_endpoint: UPDATE_ENDPOINT.to_string(),
             notify_before_download: true,
+            channel: UpdateChannel::default(),
         }
     }
 }
@@ -120,6 +769,7 @@ impl Default for AnalyticsConfig {
             enabled: true,
             performance_endpoint: PERFORMANCE_ENDPOINT.to_string(),
             performance_interval_seconds: DEFAULT_LOG_FLUSH_INTERVAL_SECONDS,
+            drop_silent_sessions: false,
         }
     }
 }
@@ -139,11 +789,66 @@ impl Default for KwiteConfig {
             output_device_id: "output_default".to_string(),
             sensitivity: 0.1, // Moderate noise reduction as starting point
             auto_start: false,
+            restore_last_enabled_state: false,
+            auto_start_delay_ms: 0,
             minimize_to_tray: false, // Keep visible by default
             development_mode: false, // Hide advanced features from end users
             remote_logging: RemoteLoggingConfig::default(),
             analytics: AnalyticsConfig::default(), // Disabled by default for privacy
             auto_update: AutoUpdateConfig::default(),
+            vad_history_len: DEFAULT_VAD_HISTORY_LEN,
+            analysis_window_size: DEFAULT_ANALYSIS_WINDOW,
+            output_gain_db: 0.0,
+            input_gain_db: 0.0,
+            vad_threshold: DEFAULT_VAD_THRESHOLD,
+            noise_gain: DEFAULT_NOISE_GAIN,
+            speech_gain: DEFAULT_SPEECH_GAIN,
+            gain_floor: 0.0,
+            comfort_noise_level: None,
+            onset_protection_ms: 0,
+            gain_attack_ms: crate::audio::gain::DEFAULT_GAIN_ATTACK_MS,
+            gain_release_ms: crate::audio::gain::DEFAULT_GAIN_RELEASE_MS,
+            gate_hold_ms: crate::audio::gain::DEFAULT_GATE_HOLD_MS,
+            vad_source: VadSource::default(),
+            dc_filter_enabled: true,
+            underrun_fill: UnderrunFill::default(),
+            vad_deadband: 0.0,
+            device_fallback: DeviceFallback::default(),
+            instant_replay_secs: 0,
+            frame_deadline_us: 0,
+            denoiser_crossover_hz: 0,
+            mix: DEFAULT_MIX,
+            latency_preset: LatencyPreset::default(),
+            latency_overrides: LatencyOverrides::default(),
+            test_signal_wav_path: None,
+            custom_model_path: None,
+            toggle_hotkey: None,
+            process_thread_affinity: ThreadAffinity::default(),
+            local_playback_only: false,
+            allow_placeholder_devices: false,
+            meter_ballistics: MeterBallistics::default(),
+            meter_integration_ms: DEFAULT_METER_INTEGRATION_MS,
+            target_sample_rate: DEFAULT_TARGET_SAMPLE_RATE,
+            stereo_processing: false,
+            input_channel: 0,
+            stereo_channel_gates: None,
+            max_processing_threads: crate::audio::parallel::DEFAULT_MAX_PROCESSING_THREADS,
+            music_passthrough: MusicPassthroughSettings::default(),
+            monitor_enabled: false,
+            monitor_device_id: None,
+            ducking: DuckingSettings::default(),
+            idle_bypass_secs: 0,
+            stream_watchdog_timeout_secs: DEFAULT_STALL_TIMEOUT_SECS,
+            background_fps: DEFAULT_BACKGROUND_FPS,
+            denoiser_warmup_ms: 0,
+            startup_max_test_enabled: false,
+            startup_max_test_secs: crate::audio::DEFAULT_STARTUP_MAX_TEST_SECS,
+            processing_block_frames: 1,
+            device_slots: default_device_slots(),
+            profiles: Vec::new(),
+            active_profile: None,
+            metrics_port: None,
+            auto_sensitivity: false,
         }
     }
 }
@@ -165,8 +870,13 @@ impl KwiteConfig {
                 if path.exists() {
                     match fs::read_to_string(&path) {
                         Ok(content) => {
-                            match toml::from_str(&content) {
-                                Ok(config) => config,
+                            match Self::from_toml_str(&content) {
+                                Ok((config, unknown_fields)) => {
+                                    for field in unknown_fields {
+                                        eprintln!("Ignoring unknown config field '{}'", field);
+                                    }
+                                    config
+                                }
                                 Err(e) => {
                                     eprintln!("Failed to parse config: {}", e);
                                     Self::default()
@@ -189,6 +899,19 @@ impl KwiteConfig {
         }
     }
 
+    /// Parse configuration TOML, collecting the paths of unrecognised fields
+    ///
+    /// Missing fields take their default values. Unknown fields (e.g. from a newer
+    /// version, or typos) are skipped and returned as dotted paths so the caller
+    /// can warn about them; only malformed TOML or wrongly typed values fail.
+    pub fn from_toml_str(content: &str) -> Result<(Self, Vec<String>), toml::de::Error> {
+        let mut unknown_fields = Vec::new();
+        let config = serde_ignored::deserialize(toml::Deserializer::new(content), |path| {
+            unknown_fields.push(path.to_string());
+        })?;
+        Ok((config, unknown_fields))
+    }
+
     /// Save current configuration to disk
     ///
     /// This method persists the current configuration state to the platform-appropriate
@@ -245,6 +968,49 @@ impl KwiteConfig {
     /// on misconfigured systems), an error is returned rather than falling back
     /// to potentially inappropriate locations.
     fn config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Self::config_dir()?.join("config.toml"))
+    }
+
+    /// Saved profile with the given name
+    pub fn profile(&self, name: &str) -> Option<&OutputProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Save the current devices and parameters as `name` and make it the active profile
+    ///
+    /// A profile with the same name is overwritten in place.
+    pub fn save_profile(&mut self, name: &str) {
+        let profile = OutputProfile::capture(name, self);
+        match self.profiles.iter_mut().find(|existing| existing.name == name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+        self.active_profile = Some(name.to_string());
+    }
+
+    /// Apply the named profile and mark it active; `false` if no such profile exists
+    pub fn activate_profile(&mut self, name: &str) -> bool {
+        let Some(profile) = self.profile(name).cloned() else {
+            return false;
+        };
+        profile.apply_to(self);
+        self.active_profile = Some(profile.name);
+        true
+    }
+
+    /// Delete the named profile, deactivating it if it was active
+    pub fn remove_profile(&mut self, name: &str) {
+        self.profiles.retain(|profile| profile.name != name);
+        if self.active_profile.as_deref() == Some(name) {
+            self.active_profile = None;
+        }
+    }
+
+    /// Determine the platform-appropriate Kwite configuration directory
+    ///
+    /// Shared by other persisted files (such as the runtime state) so they live
+    /// alongside `config.toml`.
+    pub fn config_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let config_dir = if cfg!(target_os = "windows") {
             dirs::config_dir()
                 .ok_or("Could not find config directory")?
@@ -260,7 +1026,7 @@ impl KwiteConfig {
                 .join("kwite")
         };
 
-        Ok(config_dir.join("config.toml"))
+        Ok(config_dir)
     }
 
     /// Create a config for testing with all fields populated
@@ -271,11 +1037,66 @@ impl KwiteConfig {
             output_device_id: "test_output".to_string(),
             sensitivity: 0.1,
             auto_start: false,
+            restore_last_enabled_state: false,
+            auto_start_delay_ms: 0,
             minimize_to_tray: false,
             development_mode: false,
             remote_logging: RemoteLoggingConfig::default(),
             analytics: AnalyticsConfig::default(),
             auto_update: AutoUpdateConfig::default(),
+            vad_history_len: DEFAULT_VAD_HISTORY_LEN,
+            analysis_window_size: DEFAULT_ANALYSIS_WINDOW,
+            output_gain_db: 0.0,
+            input_gain_db: 0.0,
+            vad_threshold: DEFAULT_VAD_THRESHOLD,
+            noise_gain: DEFAULT_NOISE_GAIN,
+            speech_gain: DEFAULT_SPEECH_GAIN,
+            gain_floor: 0.0,
+            comfort_noise_level: None,
+            onset_protection_ms: 0,
+            gain_attack_ms: crate::audio::gain::DEFAULT_GAIN_ATTACK_MS,
+            gain_release_ms: crate::audio::gain::DEFAULT_GAIN_RELEASE_MS,
+            gate_hold_ms: crate::audio::gain::DEFAULT_GATE_HOLD_MS,
+            vad_source: VadSource::default(),
+            dc_filter_enabled: true,
+            underrun_fill: UnderrunFill::default(),
+            vad_deadband: 0.0,
+            device_fallback: DeviceFallback::default(),
+            instant_replay_secs: 0,
+            frame_deadline_us: 0,
+            denoiser_crossover_hz: 0,
+            mix: DEFAULT_MIX,
+            latency_preset: LatencyPreset::default(),
+            latency_overrides: LatencyOverrides::default(),
+            test_signal_wav_path: None,
+            custom_model_path: None,
+            toggle_hotkey: None,
+            process_thread_affinity: ThreadAffinity::default(),
+            local_playback_only: false,
+            allow_placeholder_devices: false,
+            meter_ballistics: MeterBallistics::default(),
+            meter_integration_ms: DEFAULT_METER_INTEGRATION_MS,
+            target_sample_rate: DEFAULT_TARGET_SAMPLE_RATE,
+            stereo_processing: false,
+            input_channel: 0,
+            stereo_channel_gates: None,
+            max_processing_threads: crate::audio::parallel::DEFAULT_MAX_PROCESSING_THREADS,
+            music_passthrough: MusicPassthroughSettings::default(),
+            monitor_enabled: false,
+            monitor_device_id: None,
+            ducking: DuckingSettings::default(),
+            idle_bypass_secs: 0,
+            stream_watchdog_timeout_secs: DEFAULT_STALL_TIMEOUT_SECS,
+            background_fps: DEFAULT_BACKGROUND_FPS,
+            denoiser_warmup_ms: 0,
+            startup_max_test_enabled: false,
+            startup_max_test_secs: crate::audio::DEFAULT_STARTUP_MAX_TEST_SECS,
+            processing_block_frames: 1,
+            device_slots: default_device_slots(),
+            profiles: Vec::new(),
+            active_profile: None,
+            metrics_port: None,
+            auto_sensitivity: false,
         }
     }
 }
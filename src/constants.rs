@@ -25,8 +25,50 @@ pub const DEFAULT_LOG_BATCH_SIZE: usize = 50;
 /// Default flush interval for remote logging (7 days in seconds)
 pub const DEFAULT_LOG_FLUSH_INTERVAL_SECONDS: u64 = 604800;
 
+/// Smallest and largest accepted remote logging batch sizes
+pub const MIN_LOG_BATCH_SIZE: usize = 1;
+pub const MAX_LOG_BATCH_SIZE: usize = 1000;
+
+/// Smallest accepted flush interval (one minute) to avoid hammering the endpoint
+pub const MIN_LOG_FLUSH_INTERVAL_SECONDS: u64 = 60;
+
+/// Largest accepted flush interval (30 days in seconds)
+pub const MAX_LOG_FLUSH_INTERVAL_SECONDS: u64 = 2_592_000;
+
+/// Default timeout for a single remote logging request
+pub const DEFAULT_LOG_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// Smallest and largest accepted remote logging request timeouts
+pub const MIN_LOG_REQUEST_TIMEOUT_SECONDS: u64 = 1;
+pub const MAX_LOG_REQUEST_TIMEOUT_SECONDS: u64 = 300;
+
+/// Default timeout for the endpoint health check run when analytics are enabled
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT_SECONDS: u64 = 5;
+
+/// Remote logging retry delay after the first failed upload, doubled per further failure
+pub const LOG_RETRY_BASE_DELAY_SECONDS: u64 = 2;
+
+/// Longest delay between remote logging retries (10 minutes)
+pub const LOG_RETRY_MAX_DELAY_SECONDS: u64 = 600;
+
+/// Consecutive failed uploads before the remote log backlog is written to disk
+pub const LOG_SPOOL_AFTER_FAILURES: u32 = 3;
+
+/// Largest remote log backlog kept on disk (1MB); the oldest entries are dropped first
+pub const MAX_LOG_SPOOL_BYTES: usize = 1024 * 1024;
+
 /// Default auto-update check interval (24 hours)
 pub const DEFAULT_UPDATE_CHECK_INTERVAL_HOURS: u64 = 24;
 
+/// Default UI refresh rate while the window is unfocused (frames per second)
+pub const DEFAULT_BACKGROUND_FPS: u32 = 2;
+
+/// Smallest and largest accepted background UI refresh rates
+pub const MIN_BACKGROUND_FPS: u32 = 1;
+pub const MAX_BACKGROUND_FPS: u32 = 30;
+
+/// How long the "Test" button plays processed microphone audio on the speakers
+pub const TEST_PLAYBACK_SECONDS: u64 = 5;
+
 /// Maximum payload size per logging request (2MB)
 pub const MAX_PAYLOAD_SIZE_BYTES: usize = 2 * 1024 * 1024;
\ No newline at end of file
@@ -0,0 +1,200 @@
+//! # Crash Report Module
+//!
+//! This module installs a panic hook that turns crashes into something actionable.
+//! When any thread panics the hook:
+//!
+//! 1. Logs the panic message, location and a full backtrace
+//! 2. Buffers a crash event for the remote logger (a no-op unless analytics is enabled)
+//! 3. Writes a crash report file next to `config.toml`
+//!
+//! On the next launch the GUI finds the report and offers to send it, so a crash
+//! that takes the process down before the log buffer is flushed isn't lost.
+//!
+//! ## Resilience
+//!
+//! Like the runtime state, the report is written atomically and any read or parse
+//! failure is treated as "no pending report". The hook itself never panics; every
+//! step is best effort and the previously installed hook still runs afterwards.
+
+use crate::config::KwiteConfig;
+use crate::logger::log;
+use crate::remote_logging::{log_remote, try_log_remote};
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Details of a panic, persisted until the user sends or dismisses it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// When the panic happened (RFC 3339)
+    pub timestamp: String,
+    /// Panic message
+    pub message: String,
+    /// Source location (`file:line:column`), if known
+    pub location: Option<String>,
+    /// Name of the panicking thread, if it had one
+    pub thread: Option<String>,
+    /// Captured backtrace
+    pub backtrace: String,
+    /// Kwite version that crashed
+    pub app_version: String,
+    /// Session ID of the run that crashed (empty for reports from older versions)
+    #[serde(default)]
+    pub session_id: String,
+}
+
+impl CrashReport {
+    /// Load the pending crash report from its default location
+    ///
+    /// Returns `None` if there is no report or it can't be read.
+    pub fn load_pending() -> Option<Self> {
+        Self::load_from(&Self::report_path().ok()?)
+    }
+
+    /// Load a crash report from a specific path, falling back silently
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    /// Save the report to its default location
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.save_to(&Self::report_path()?)
+    }
+
+    /// Save the report to a specific path (temporary file + rename)
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string(self)?;
+        let tmp_path = path.with_extension("toml.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Delete the pending report once it has been sent or dismissed
+    pub fn discard() {
+        if let Ok(path) = Self::report_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Send the report through the remote logger and flush it immediately
+    pub fn send(&self) {
+        log_remote("error", &format!("Crash: {}", self.message), Some("crash_report"), self.to_fields());
+        crate::remote_logging::flush_remote_logs();
+    }
+
+    /// Structured fields attached to the remote crash event
+    pub fn to_fields(&self) -> HashMap<String, String> {
+        let mut fields = HashMap::new();
+        fields.insert("crash_timestamp".to_string(), self.timestamp.clone());
+        fields.insert("location".to_string(), self.location.clone().unwrap_or_default());
+        fields.insert("thread".to_string(), self.thread.clone().unwrap_or_default());
+        fields.insert("backtrace".to_string(), self.backtrace.clone());
+        fields.insert("app_version".to_string(), self.app_version.clone());
+        fields.insert("crashed_session_id".to_string(), self.session_id.clone());
+        fields
+    }
+
+    /// Location of the crash report file (next to `config.toml`)
+    fn report_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(KwiteConfig::config_dir()?.join("crash_report.toml"))
+    }
+}
+
+/// Target of the panic hook's local log line
+///
+/// The remote log layer skips it: the hook buffers the crash itself, with
+/// structured fields, so it is only sent once.
+pub const PANIC_LOG_TARGET: &str = "kwite::crash_report::panic";
+
+/// Extract a readable message from a panic payload
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic payload".to_string()
+    }
+}
+
+/// Install the crash-reporting panic hook
+///
+/// Should be called once from `main.rs`, right after the logger is initialized.
+pub fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let report = CrashReport {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            message: panic_message(info.payload()),
+            location: info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            session_id: crate::system_info::session_id().to_string(),
+        };
+
+        log::error!(
+            target: PANIC_LOG_TARGET,
+            "💥 Panic in thread '{}' at {}: {}\n{}",
+            report.thread.as_deref().unwrap_or("<unnamed>"),
+            report.location.as_deref().unwrap_or("<unknown>"),
+            report.message,
+            report.backtrace
+        );
+
+        // Buffered for the flush timer; dropped silently if analytics is disabled or the
+        // logger is busy (the panicking thread may hold it), the saved report covers that
+        try_log_remote("error", &format!("Crash: {}", report.message), Some("crash_report"), report.to_fields());
+
+        if let Err(e) = report.save() {
+            log::error!("Failed to save crash report: {}", e);
+        }
+
+        previous_hook(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_crash_report_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("crash_report.toml");
+
+        let report = CrashReport {
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            message: "index out of bounds".to_string(),
+            location: Some("src/audio/mod.rs:10:5".to_string()),
+            thread: None,
+            backtrace: "0: kwite::main".to_string(),
+            app_version: "0.1.0".to_string(),
+            session_id: "kwite_1704067200_42".to_string(),
+        };
+        report.save_to(&path).unwrap();
+        assert_eq!(CrashReport::load_from(&path), Some(report));
+
+        fs::write(&path, "not toml [[[").unwrap();
+        assert!(CrashReport::load_from(&path).is_none());
+    }
+
+    #[test]
+    fn test_panic_message_extraction() {
+        let payload = std::panic::catch_unwind(|| panic!("static message")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "static message");
+
+        let payload = std::panic::catch_unwind(|| panic!("formatted {}", 42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "formatted 42");
+    }
+}
@@ -0,0 +1,259 @@
+//! # C FFI Module
+//!
+//! A small, stable C ABI around the denoise core so other applications (DAW
+//! plugins, OBS filters, C/C++ tools) can run Kwite's processing without the
+//! GUI or the cpal audio pipeline. Only compiled with the `ffi` feature:
+//!
+//! ```text
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! ## ABI
+//!
+//! - Engines are opaque handles (`KwiteEngine *`) created with
+//!   `kwite_engine_new` and released with `kwite_engine_free`
+//! - Every fallible function returns an `int32_t` status code: `KWITE_OK` (0)
+//!   on success, a negative `KWITE_ERR_*` value otherwise
+//! - No function unwinds across the boundary; internal panics are caught and
+//!   reported as `KWITE_ERR_PANIC`
+//! - `kwite_abi_version` is bumped whenever a signature or code changes meaning
+//!
+//! ## Frame Requirements
+//!
+//! - 48kHz mono `float` samples in the range -1.0 to 1.0
+//! - Exactly `KWITE_FRAME_SIZE` (480) samples, i.e. 10ms, per call
+//! - `input` and `output` may point to the same buffer for in-place processing
+//!
+//! The engine keeps recurrent state between frames, so feed consecutive frames
+//! of one stream to the same engine. An engine is not internally synchronized:
+//! use one engine per stream, or guard shared engines with a lock.
+//!
+//! ## C Declarations
+//!
+//! ```c
+//! typedef struct KwiteEngine KwiteEngine;
+//!
+//! uint32_t kwite_abi_version(void);
+//! KwiteEngine *kwite_engine_new(void);
+//! int32_t kwite_engine_process_frame(KwiteEngine *engine, const float *input,
+//!                                    float *output, size_t len, float *vad_out);
+//! int32_t kwite_engine_set_sensitivity(KwiteEngine *engine, float sensitivity);
+//! int32_t kwite_engine_set_noise_gain(KwiteEngine *engine, float noise_gain);
+//! void kwite_engine_free(KwiteEngine *engine);
+//! ```
+
+use crate::audio::gain::ChannelGate;
+use crate::audio::{DEFAULT_NOISE_GAIN, DEFAULT_SPEECH_GAIN, DEFAULT_VAD_THRESHOLD};
+use nnnoiseless::DenoiseState;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Version of this ABI
+pub const KWITE_ABI_VERSION: u32 = 1;
+
+/// Samples per frame accepted by `kwite_engine_process_frame` (10ms at 48kHz)
+pub const KWITE_FRAME_SIZE: usize = nnnoiseless::FRAME_SIZE;
+
+/// Success
+pub const KWITE_OK: i32 = 0;
+/// A required pointer argument was null
+pub const KWITE_ERR_NULL_POINTER: i32 = -1;
+/// The frame length was not `KWITE_FRAME_SIZE`
+pub const KWITE_ERR_FRAME_SIZE: i32 = -2;
+/// A numeric argument was out of range or not finite
+pub const KWITE_ERR_INVALID_ARGUMENT: i32 = -3;
+/// The engine panicked internally; the frame was not processed
+pub const KWITE_ERR_PANIC: i32 = -4;
+
+/// Opaque denoise engine handed out to C callers
+pub struct KwiteEngine {
+    denoiser: Box<DenoiseState<'static>>,
+    gate: ChannelGate,
+    /// Copy of the input frame, so callers may process in place
+    input: [f32; KWITE_FRAME_SIZE],
+}
+
+impl KwiteEngine {
+    fn new() -> Self {
+        Self {
+            denoiser: DenoiseState::new(),
+            gate: ChannelGate {
+                vad_threshold: DEFAULT_VAD_THRESHOLD,
+                noise_gain: DEFAULT_NOISE_GAIN,
+            },
+            input: [0.0; KWITE_FRAME_SIZE],
+        }
+    }
+
+    /// Denoise `self.input` into `output`, returning the VAD score
+    fn process(&mut self, output: &mut [f32]) -> f32 {
+        let vad_score = self.denoiser.process_frame(output, &self.input);
+
+        // Same gating as the application: pass the input through if the denoiser misbehaves
+        if output.iter().any(|s| !s.is_finite()) {
+            output.copy_from_slice(&self.input);
+            return vad_score;
+        }
+        let gain = self.gate.gain(vad_score, DEFAULT_SPEECH_GAIN);
+        for sample in output.iter_mut() {
+            *sample *= gain;
+        }
+        vad_score
+    }
+}
+
+/// Version of the C ABI implemented by this library
+#[no_mangle]
+pub extern "C" fn kwite_abi_version() -> u32 {
+    KWITE_ABI_VERSION
+}
+
+/// Create an engine with the default sensitivity and noise gain
+///
+/// Returns null if the engine could not be created.
+#[no_mangle]
+pub extern "C" fn kwite_engine_new() -> *mut KwiteEngine {
+    match catch_unwind(KwiteEngine::new) {
+        Ok(engine) => Box::into_raw(Box::new(engine)),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Denoise one frame of `len` samples from `input` into `output`
+///
+/// `len` must equal `KWITE_FRAME_SIZE`. If `vad_out` is not null it receives
+/// the frame's voice activity score (0.0 to 1.0). On error `output` is left
+/// untouched.
+///
+/// # Safety
+///
+/// `engine` must come from `kwite_engine_new` and not have been freed.
+/// `input` and `output` must each be valid for `len` floats; they may alias.
+/// `vad_out` must be null or valid for one float write.
+#[no_mangle]
+pub unsafe extern "C" fn kwite_engine_process_frame(
+    engine: *mut KwiteEngine,
+    input: *const f32,
+    output: *mut f32,
+    len: usize,
+    vad_out: *mut f32,
+) -> i32 {
+    if engine.is_null() || input.is_null() || output.is_null() {
+        return KWITE_ERR_NULL_POINTER;
+    }
+    if len != KWITE_FRAME_SIZE {
+        return KWITE_ERR_FRAME_SIZE;
+    }
+
+    let engine = &mut *engine;
+    // Copy before building the output slice, which may alias the input
+    engine.input.copy_from_slice(std::slice::from_raw_parts(input, len));
+    let output = std::slice::from_raw_parts_mut(output, len);
+
+    match catch_unwind(AssertUnwindSafe(|| engine.process(output))) {
+        Ok(vad_score) => {
+            if !vad_out.is_null() {
+                *vad_out = vad_score;
+            }
+            KWITE_OK
+        }
+        Err(_) => KWITE_ERR_PANIC,
+    }
+}
+
+/// Set the speech detection threshold (0.0 to 1.0)
+///
+/// Frames whose VAD score falls below the threshold are attenuated by the
+/// noise gain. Matches the "Speech Detection" setting in the application.
+///
+/// # Safety
+///
+/// `engine` must be null or come from `kwite_engine_new` and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn kwite_engine_set_sensitivity(engine: *mut KwiteEngine, sensitivity: f32) -> i32 {
+    if engine.is_null() {
+        return KWITE_ERR_NULL_POINTER;
+    }
+    if !(0.0..=1.0).contains(&sensitivity) {
+        return KWITE_ERR_INVALID_ARGUMENT;
+    }
+    (*engine).gate.vad_threshold = sensitivity;
+    KWITE_OK
+}
+
+/// Set the gain applied to noise frames (0.0 mutes them, 1.0 disables gating)
+///
+/// # Safety
+///
+/// `engine` must be null or come from `kwite_engine_new` and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn kwite_engine_set_noise_gain(engine: *mut KwiteEngine, noise_gain: f32) -> i32 {
+    if engine.is_null() {
+        return KWITE_ERR_NULL_POINTER;
+    }
+    if !(0.0..=1.0).contains(&noise_gain) {
+        return KWITE_ERR_INVALID_ARGUMENT;
+    }
+    (*engine).gate.noise_gain = noise_gain;
+    KWITE_OK
+}
+
+/// Release an engine; null is ignored
+///
+/// # Safety
+///
+/// `engine` must be null or come from `kwite_engine_new`, and must not be used
+/// again after this call.
+#[no_mangle]
+pub unsafe extern "C" fn kwite_engine_free(engine: *mut KwiteEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_arguments_return_error_codes() {
+        let mut frame = [0.0f32; KWITE_FRAME_SIZE];
+        unsafe {
+            assert_eq!(
+                kwite_engine_process_frame(std::ptr::null_mut(), frame.as_ptr(), frame.as_mut_ptr(), KWITE_FRAME_SIZE, std::ptr::null_mut()),
+                KWITE_ERR_NULL_POINTER
+            );
+            assert_eq!(kwite_engine_set_sensitivity(std::ptr::null_mut(), 0.5), KWITE_ERR_NULL_POINTER);
+
+            let engine = kwite_engine_new();
+            assert!(!engine.is_null());
+            assert_eq!(
+                kwite_engine_process_frame(engine, frame.as_ptr(), frame.as_mut_ptr(), 256, std::ptr::null_mut()),
+                KWITE_ERR_FRAME_SIZE
+            );
+            assert_eq!(kwite_engine_set_sensitivity(engine, 1.5), KWITE_ERR_INVALID_ARGUMENT);
+            assert_eq!(kwite_engine_set_noise_gain(engine, f32::NAN), KWITE_ERR_INVALID_ARGUMENT);
+            kwite_engine_free(engine);
+            kwite_engine_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_processes_frames_in_place() {
+        unsafe {
+            let engine = kwite_engine_new();
+            assert_eq!(kwite_engine_set_sensitivity(engine, 0.3), KWITE_OK);
+
+            let mut vad_score = -1.0f32;
+            for i in 0..20 {
+                let mut frame: Vec<f32> = (0..KWITE_FRAME_SIZE)
+                    .map(|n| 0.1 * (((i * KWITE_FRAME_SIZE + n) as f32) * 0.05).sin())
+                    .collect();
+                let ptr = frame.as_mut_ptr();
+                assert_eq!(kwite_engine_process_frame(engine, ptr, ptr, frame.len(), &mut vad_score), KWITE_OK);
+                assert!(frame.iter().all(|s| s.is_finite()));
+            }
+            assert!((0.0..=1.0).contains(&vad_score));
+            kwite_engine_free(engine);
+        }
+    }
+}
@@ -20,14 +20,32 @@
 use eframe::egui;
 use egui::{CentralPanel, TopBottomPanel, Button, Slider, ComboBox, Color32, RichText};
 use crate::logger::log;
-use crate::audio::{AudioManager, devices::{AudioDeviceInfo, list_input_devices, list_output_devices}};
-use crate::config::KwiteConfig;
-use crate::ai_metrics::{SharedAiMetrics, PerformanceSummary};
-use crate::virtual_audio::{get_virtual_audio_info, has_virtual_devices, get_setup_status_message, detect_os};
-use crate::remote_logging::{init_remote_logger, log_remote};
+use crate::audio::{AudioManager, AudioSettings, DiagnosticPreset, devices::{AudioDeviceInfo, DeviceFallback, has_usable_devices, input_channel_count, list_input_devices, list_output_devices}, output::UnderrunFill};
+use crate::config::{DeviceSlot, KwiteConfig, PrivacyPreset, UpdateChannel, DEVICE_SLOT_COUNT};
+use crate::ai_metrics::{SharedAiMetrics, PerformanceSummary, CumulativePerformance};
+use crate::audio::models::ModelStatistics;
+use crate::audio::latency::{LatencyOverrides, LatencyPreset, LatencySettings};
+use crate::audio::test_signal::{file_loop_device, test_signal_devices};
+use crate::audio::affinity::{ThreadAffinity, available_core_count};
+use crate::audio::meter::{MeterBallistics, METER_FLOOR_DB, MAX_METER_INTEGRATION_MS, MIN_METER_INTEGRATION_MS};
+use crate::audio::analysis::{NoiseProfile, VadSource, SPECTRUM_FLOOR_DB, SPECTRUM_MIN_HZ};
+use crate::audio::comfort_noise::{DEFAULT_COMFORT_NOISE_DB, MAX_COMFORT_NOISE_DB, MIN_COMFORT_NOISE_DB};
+use crate::gui::scope;
+use crate::gui::hotkey;
+use crate::gui::tray::{self, TrayCommand};
+use crate::audio::latency_probe::ProbeStatus;
+use crate::audio::resampling::{RNNOISE_SAMPLE_RATE, SUPPORTED_TARGET_SAMPLE_RATES};
+use crate::audio::watchdog::{self, Watchdog};
+use crate::virtual_audio::{get_virtual_audio_info, has_virtual_devices, OperatingSystem, get_setup_status_message, detect_os, microphone_privacy_settings_url, open_microphone_privacy_settings, select_default_output};
+use crate::remote_logging::{init_remote_logger, log_remote, remote_log_buffer_size, update_remote_logger_config};
+#[cfg(feature = "remote-logging")]
+use crate::remote_logging::{spawn_endpoint_health_check, EndpointHealth, Transport};
+use crate::constants::{TEST_PLAYBACK_SECONDS, MIN_BACKGROUND_FPS, MAX_BACKGROUND_FPS, MIN_LOG_BATCH_SIZE, MAX_LOG_BATCH_SIZE, MIN_LOG_FLUSH_INTERVAL_SECONDS, MAX_LOG_FLUSH_INTERVAL_SECONDS, MIN_LOG_REQUEST_TIMEOUT_SECONDS, MAX_LOG_REQUEST_TIMEOUT_SECONDS};
 use crate::usage_stats::UsageStatsManager;
-use crate::auto_update::AutoUpdateManager;
+use crate::auto_update::{spawn_update_check, AutoUpdateManager, UpdateCheckResult, UPDATE_CHECK_AVAILABLE};
 use crate::system_info::SystemInfo;
+use crate::runtime_state::RuntimeState;
+use crate::crash_report::CrashReport;
 use std::sync::{Arc, Mutex};
 
 /// Main Kwite App state
@@ -61,6 +79,9 @@ pub struct KwiteApp {
     /// Automatically prefers virtual audio devices when available
     selected_output_device: String,
     
+    /// Channel count reported by the selected input device, cached per device ID
+    input_channel_count: Option<(String, Option<u16>)>,
+    
     /// Noise cancellation sensitivity threshold (0.01 - 0.5)
     /// Lower values = more aggressive noise removal
     /// Higher values = preserve more original audio
@@ -90,12 +111,89 @@ pub struct KwiteApp {
     /// Updated periodically to avoid excessive mutex locking
     ai_performance: Option<PerformanceSummary>,
     
+    /// Performance totals from earlier runs in this session
+    /// Folded in whenever processing stops so quick toggles don't lose the stats
+    session_performance: CumulativePerformance,
+    
     /// Last time AI metrics were updated
     last_ai_update: std::time::Instant,
     
+    /// Cached denoiser model statistics for the dev-mode panel
+    /// Refreshed together with the AI metrics
+    model_statistics: Option<ModelStatistics>,
+    /// Denoiser model the running pipeline loaded
+    active_model_name: Option<String>,
+    
+    /// Last observed input clip count and when it last increased
+    /// The clip indicator latches for one second after new clipping
+    last_clip_count: u64,
+    
+    last_clip_time: Option<std::time::Instant>,
+    
+    /// Output samples clamped/replaced by the output guard (from the audio manager)
+    output_sanitized_count: u64,
+    
+    /// Frames the processing thread passed through to meet the frame deadline
+    deadline_skip_count: u64,
+    
+    /// Frames since the gate last saw speech, and whether the idle bypass is engaged
+    frames_since_speech: u64,
+    idle_bypass_active: bool,
+    
+    /// Latest input/output meter readings in dBFS
+    input_level_db: f32,
+    output_level_db: f32,
+    
+    /// Native rate of the running input stream, when known
+    input_sample_rate: Option<u32>,
+    
+    /// Status of the dev-mode round-trip latency measurement
+    latency_probe_status: ProbeStatus,
+    
+    /// Whether music passthrough is currently bypassing suppression
+    music_passthrough_active: bool,
+    
+    /// Gain reduction currently applied by sidechain ducking (dB)
+    ducking_reduction_db: f32,
+    
+    /// Temporary pipeline monitoring processed audio on the speakers, and when it ends
+    test_playback: Option<(AudioManager, std::time::Instant)>,
+    
+    /// A/B comparison: pass raw audio through the running pipeline (not saved)
+    ab_bypass: bool,
+    
+    /// Result of the last instant replay save, shown under the save button
+    replay_status: Option<String>,
+    
+    /// Result of the last output recording, shown under the record button
+    recording_status: Option<String>,
+    
+    /// Noise profile imported in dev mode, re-applied whenever processing starts
+    imported_noise_profile: Option<NoiseProfile>,
+    
+    /// Result of the last noise profile export/import
+    noise_profile_status: Option<String>,
+    
+    /// Result of the last quick device swap
+    device_swap_status: Option<String>,
+    
+    /// Shown as a banner after the input device stopped working during processing
+    input_device_error: Option<String>,
+    
+    /// Name typed into the profile dropdown for "Save current"
+    new_profile_name: String,
+    
+    /// Result of the last profile switch or save
+    profile_status: Option<String>,
+    
+    /// Latest raw input samples for the dev-mode oscilloscope
+    input_scope: Vec<f32>,
+    
     /// Track if sensitivity slider is being dragged (for update-on-release behavior)
     sensitivity_dragging: bool,
     sensitivity_pending_update: Option<f32>,
+    /// Sensitivity chosen by auto sensitivity, polled from the audio manager
+    auto_sensitivity_value: Option<f32>,
     
 
     
@@ -108,6 +206,12 @@ pub struct KwiteApp {
     /// Flag to show configuration dialog
     show_config_dialog: bool,
     
+    /// Flag to show the microphone permission dialog after access was denied
+    show_mic_permission_dialog: bool,
+    
+    /// Result of validating the configured custom model file (None = not checked yet)
+    custom_model_status: Option<Result<(), String>>,
+    
     /// Show advanced AI controls
     show_advanced_controls: bool,
 
@@ -127,6 +231,174 @@ pub struct KwiteApp {
 
     /// System information collected at startup
     system_info: SystemInfo,
+
+    /// Previous session state offered for restore after an unclean shutdown
+    pending_restore: Option<RuntimeState>,
+    
+    /// Crash report from a previous run, offered for sending
+    pending_crash_report: Option<CrashReport>,
+    
+    /// Running manual update check
+    update_check: Option<std::sync::mpsc::Receiver<UpdateCheckResult>>,
+    
+    /// Result of the last manual update check, shown in a dialog until dismissed
+    update_result: Option<UpdateCheckResult>,
+    
+    /// Asking the user to confirm a usage statistics reset
+    confirm_stats_reset: bool,
+    
+    /// Virtual sink created from the setup dialog on Linux, removed when Kwite exits
+    virtual_device: Option<crate::virtual_audio::LinuxVirtualDevice>,
+    
+    /// Result of the last virtual device creation attempt
+    virtual_device_status: Option<String>,
+    
+    /// Result of the last usage statistics export or reset
+    stats_status: Option<String>,
+    
+    /// Global toggle hotkey, created once a binding is configured
+    toggle_hotkey: Option<hotkey::ToggleHotkey>,
+    
+    /// Waiting for a key combination to bind as the toggle hotkey
+    capturing_hotkey: bool,
+    
+    /// Why the toggle hotkey couldn't be bound, shown in the settings
+    hotkey_status: Option<String>,
+    
+    /// System tray icon, created once `minimize_to_tray` is enabled
+    tray: Option<tray::Tray>,
+    
+    /// Window hidden to the tray
+    window_hidden: bool,
+    
+    /// Quit chosen from the tray, so the close request must not be intercepted
+    quit_requested: bool,
+    
+    /// Rebuilds the audio pipeline if frames stop flowing (None when disabled)
+    _stream_watchdog: Option<Watchdog>,
+    
+    /// Earliest time a configured auto-start may run (None once it ran or when not configured)
+    pending_auto_start: Option<std::time::Instant>,
+    
+    /// Whether at least one frame has been shown; auto-start waits for it
+    first_frame_shown: bool,
+    
+    /// Running endpoint health check, with the analytics setting to restore once it passes
+    #[cfg(feature = "remote-logging")]
+    endpoint_check: Option<(std::sync::mpsc::Receiver<EndpointHealth>, bool)>,
+    
+    /// Outcome of the last endpoint health check, shown under the analytics option
+    #[cfg(feature = "remote-logging")]
+    endpoint_health: Option<EndpointHealth>,
+}
+
+/// Enumerate input devices, adding the file loop and (in development mode) test-signal pseudo devices
+fn input_device_list(config: &KwiteConfig) -> Vec<AudioDeviceInfo> {
+    let mut devices = list_input_devices();
+    if config.development_mode {
+        devices.extend(test_signal_devices(config.test_signal_wav_path.as_deref()));
+    } else {
+        devices.extend(file_loop_device(config.test_signal_wav_path.as_deref()));
+    }
+    devices
+}
+
+/// Pick the preferred output device for the current use case and OS
+///
+/// Routing to other apps wants a virtual device; local playback wants the real speakers.
+fn preferred_output_device(devices: &[AudioDeviceInfo], local_playback_only: bool) -> String {
+    select_default_output(devices, local_playback_only, detect_os())
+        .map(|d| d.id.clone())
+        .unwrap_or_default()
+}
+
+/// Look up a device's display name in an already-enumerated device list
+///
+/// Dialogs use this instead of re-querying the audio backend, which can upset drivers.
+/// Ctrl/Cmd+Alt shortcut for a quick-swap slot, if its hotkey names a valid key
+fn device_slot_shortcut(slot: &DeviceSlot) -> Option<egui::KeyboardShortcut> {
+    let key = egui::Key::from_name(slot.hotkey.as_deref()?.trim())?;
+    Some(egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::ALT, key))
+}
+
+/// Horizontal level bar for a dBFS reading (-60 dBFS to 0 dBFS)
+fn level_meter(ui: &mut egui::Ui, label: &str, level_db: f32) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let fraction = ((level_db + 60.0) / 60.0).clamp(0.0, 1.0);
+        let color = if level_db > -1.0 {
+            Color32::from_rgb(220, 53, 69)
+        } else if level_db > -12.0 {
+            Color32::from_rgb(255, 193, 7)
+        } else {
+            Color32::from_rgb(40, 167, 69)
+        };
+        let text = if level_db <= -60.0 { "-∞ dBFS".to_string() } else { format!("{:.0} dBFS", level_db) };
+        ui.add(egui::ProgressBar::new(fraction).desired_width(160.0).fill(color).text(text));
+    });
+}
+
+/// Input VU bar fed from the processing thread's per-frame RMS and peak
+/// 
+/// The bar shows RMS on a -60..0 dBFS scale; the color follows the peak so
+/// clipping shows up red even when the average level looks fine.
+fn input_vu_meter(ui: &mut egui::Ui, rms: f32, peak: f32) {
+    let to_db = |level: f32| if level > 0.0 { 20.0 * level.log10() } else { METER_FLOOR_DB };
+    let (rms_db, peak_db) = (to_db(rms), to_db(peak));
+    let color = if peak_db > -1.0 {
+        Color32::from_rgb(220, 53, 69)
+    } else if peak_db > -12.0 {
+        Color32::from_rgb(255, 193, 7)
+    } else {
+        Color32::from_rgb(40, 167, 69)
+    };
+    let text = if rms_db <= -60.0 {
+        "No signal".to_string()
+    } else {
+        format!("RMS {:.0} dB · peak {:.0} dB", rms_db, peak_db)
+    };
+    ui.add(egui::ProgressBar::new(((rms_db + 60.0) / 60.0).clamp(0.0, 1.0)).fill(color).text(text))
+        .on_hover_text("Microphone level as the denoiser sees it. Aim for yellow peaks while speaking; red means the input is clipping.");
+}
+
+/// Bar-graph of the input spectrum bands (dBFS), lowest band on the left
+fn spectrum_display(ui: &mut egui::Ui, bands: &[f32]) {
+    let size = egui::vec2(ui.available_width().max(240.0), 70.0);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, Color32::from_gray(24));
+    
+    let bar_width = rect.width() / bands.len().max(1) as f32;
+    for (index, &level_db) in bands.iter().enumerate() {
+        let fraction = ((level_db - SPECTRUM_FLOOR_DB) / -SPECTRUM_FLOOR_DB).clamp(0.0, 1.0);
+        let left = rect.left() + index as f32 * bar_width;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(left + 1.0, rect.bottom() - fraction * rect.height()),
+            egui::pos2(left + bar_width - 1.0, rect.bottom()),
+        );
+        painter.rect_filled(bar, 0.0, Color32::from_rgb(70, 160, 230));
+    }
+    response.on_hover_text(format!("Input spectrum, {:.0} Hz to 24 kHz on a log scale, {:.0} to 0 dBFS", SPECTRUM_MIN_HZ, SPECTRUM_FLOOR_DB));
+}
+
+/// Hover text for controls whose stage the stereo pipeline skips
+const MONO_ONLY_HINT: &str = "Mono only - off while stereo processing is on";
+
+/// Add controls for a mono-only stage, greyed out while stereo processing is on
+/// 
+/// The stereo pipeline skips these stages (see `stereo::MONO_ONLY_STAGES`), so
+/// their settings would otherwise look editable while doing nothing.
+fn mono_only<R>(ui: &mut egui::Ui, stereo: bool, add_contents: impl FnOnce(&mut egui::Ui) -> R) -> R {
+    let response = ui.add_enabled_ui(!stereo, add_contents);
+    response.response.on_disabled_hover_text(MONO_ONLY_HINT);
+    response.inner
+}
+
+fn device_name(devices: &[AudioDeviceInfo], id: &str) -> String {
+    devices.iter()
+        .find(|d| d.id == id)
+        .map(|d| d.name.clone())
+        .unwrap_or_else(|| "Unknown".to_string())
 }
 
 impl KwiteApp {
@@ -141,9 +413,9 @@ impl KwiteApp {
     /// Device selection priority:
     /// - Input: Use saved device if available, otherwise use system default
     /// - Output: Prefer virtual audio devices, fallback to saved/default
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>, metrics_port: Option<u16>) -> Self {
         let config = KwiteConfig::load();
-        let input_devices = list_input_devices();
+        let input_devices = input_device_list(&config);
         let output_devices = list_output_devices();
         
         // Use config devices if they exist, otherwise select defaults
@@ -159,11 +431,7 @@ impl KwiteApp {
         let selected_output = if output_devices.iter().any(|d| d.id == config.output_device_id) {
             config.output_device_id.clone()
         } else {
-            output_devices.iter()
-                .find(|d| d.is_virtual)
-                .or_else(|| output_devices.iter().find(|d| d.is_default))
-                .map(|d| d.id.clone())
-                .unwrap_or_else(|| output_devices.first().map(|d| d.id.clone()).unwrap_or_default())
+            preferred_output_device(&output_devices, config.local_playback_only)
         };
 
         // Initialize remote logging if enabled
@@ -175,6 +443,7 @@ impl KwiteApp {
         // Initialize usage statistics if analytics enabled
         let usage_stats = if config.analytics.enabled {
             let mut stats = UsageStatsManager::new(true);
+            stats.set_drop_silent_sessions(config.analytics.drop_silent_sessions);
             stats.start_session();
             Some(stats)
         } else {
@@ -191,6 +460,18 @@ impl KwiteApp {
         // Collect system information
         let system_info = SystemInfo::collect();
 
+        // Detect an unclean shutdown of the previous session
+        let previous_state = RuntimeState::load();
+        let last_enabled = previous_state.as_ref().map(|state| state.enabled).unwrap_or(false);
+        let pending_restore = previous_state.filter(|state| state.was_unclean_shutdown());
+        if pending_restore.is_some() {
+            log::warn!("⚠️ Previous session did not shut down cleanly - offering to restore it");
+        }
+        let pending_crash_report = CrashReport::load_pending();
+        if let Some(ref report) = pending_crash_report {
+            log::warn!("💥 Found a crash report from {} - offering to send it", report.timestamp);
+        }
+
         // Log system information for analytics (if remote logging is enabled)
         if config.remote_logging.enabled {
             let mut fields = system_info.to_fields();
@@ -198,54 +479,247 @@ impl KwiteApp {
             log_remote("info", &system_info.to_log_string(), Some("system_info"), fields);
         }
 
+        // Watch for streams that die silently (driver resets, sleep/wake)
+        let audio_manager = Arc::new(Mutex::new(None));
+        let stream_watchdog = (config.stream_watchdog_timeout_secs > 0).then(|| {
+            Watchdog::spawn(audio_manager.clone(), std::time::Duration::from_secs(config.stream_watchdog_timeout_secs))
+        });
+        // The command line port wins over the configured one
+        if let Some(port) = metrics_port.or(config.metrics_port) {
+            if let Err(e) = crate::metrics_server::spawn(port, audio_manager.clone()) {
+                log::error!("❌ Could not start metrics endpoint on port {}: {}", port, e);
+            }
+        }
+
         let mut app = KwiteApp {
             enabled: false, // Will be set based on auto_start config below
             input_devices,
             output_devices,
             selected_input_device: selected_input,
             selected_output_device: selected_output,
+            input_channel_count: None,
             sensitivity: config.sensitivity,
-            audio_manager: Arc::new(Mutex::new(None)),
+            audio_manager,
             last_device_refresh: std::time::Instant::now(),
             config,
             config_changed: false,
             ai_metrics: None,
             ai_performance: None,
+            session_performance: CumulativePerformance::default(),
             last_ai_update: std::time::Instant::now(),
+            model_statistics: None,
+            active_model_name: None,
+            last_clip_count: 0,
+            output_sanitized_count: 0,
+            deadline_skip_count: 0,
+            frames_since_speech: 0,
+            idle_bypass_active: false,
+            input_level_db: METER_FLOOR_DB,
+            output_level_db: METER_FLOOR_DB,
+            input_sample_rate: None,
+            latency_probe_status: ProbeStatus::Idle,
+            music_passthrough_active: false,
+            ducking_reduction_db: 0.0,
+            test_playback: None,
+            ab_bypass: false,
+            replay_status: None,
+            recording_status: None,
+            imported_noise_profile: None,
+            noise_profile_status: None,
+            device_swap_status: None,
+            input_device_error: None,
+            new_profile_name: String::new(),
+            profile_status: None,
+            input_scope: Vec::new(),
+            last_clip_time: None,
             sensitivity_dragging: false,
             sensitivity_pending_update: None,
+            auto_sensitivity_value: None,
             show_advanced_controls: false,
             max_test_mode: std::env::var("KWITE_MAX_TEST").is_ok(), // Initialize from environment variable
             pipeline_verification_mode: false, // Disabled by default
             show_virtual_setup_dialog: false,
             show_macos_audio_dialog: false,
             show_config_dialog: false,
+            show_mic_permission_dialog: false,
+            custom_model_status: None,
             usage_stats,
             auto_update_manager,
             system_info,
+            pending_restore,
+            pending_crash_report,
+            update_check: None,
+            update_result: None,
+            confirm_stats_reset: false,
+            virtual_device: None,
+            virtual_device_status: None,
+            stats_status: None,
+            toggle_hotkey: None,
+            capturing_hotkey: false,
+            hotkey_status: None,
+            tray: None,
+            window_hidden: false,
+            quit_requested: false,
+            _stream_watchdog: stream_watchdog,
+            pending_auto_start: None,
+            first_frame_shown: false,
+            #[cfg(feature = "remote-logging")]
+            endpoint_check: None,
+            #[cfg(feature = "remote-logging")]
+            endpoint_health: None,
         };
 
         // Auto-start noise cancellation if configured
-        if app.config.auto_start {
-            log::info!("Auto-starting noise cancellation as configured");
-            log::info!("Input device: {} | Output device: {}", 
-                      &app.selected_input_device, &app.selected_output_device);
-            app.toggle_audio_processing();
-            
-            // Wait a moment and verify the processing started
-            std::thread::sleep(std::time::Duration::from_millis(100));
-            if app.enabled {
-                log::info!("✅ Auto-start successful - noise cancellation is ACTIVE");
+        // `auto_start` always starts processing; `restore_last_enabled_state` only
+        // starts it if it was active when the previous session ended. Opening the
+        // devices can take seconds on slow systems, so the start is deferred until
+        // the window has painted (see `run_pending_auto_start`).
+        let restore_enabled = app.config.restore_last_enabled_state && last_enabled;
+        if app.config.auto_start || restore_enabled {
+            if app.config.auto_start {
+                log::info!("Auto-starting noise cancellation as configured");
             } else {
-                log::warn!("❌ Auto-start failed - noise cancellation is NOT active");
+                log::info!("Restoring enabled state from previous session");
             }
+            let delay = app.config.auto_start_delay_ms.min(crate::config::MAX_AUTO_START_DELAY_MS);
+            app.pending_auto_start = Some(std::time::Instant::now() + std::time::Duration::from_millis(delay as u64));
+        } else if app.config.restore_last_enabled_state {
+            log::info!("Noise cancellation was inactive in the previous session - not starting");
         } else {
             log::info!("Auto-start disabled in configuration - noise cancellation will be started manually");
         }
 
+        app.apply_toggle_hotkey(&cc.egui_ctx);
+        app.apply_tray(&cc.egui_ctx);
+
+        // Mark this session as running so a crash can be detected on next launch
+        app.persist_runtime_state();
+
         app
     }
 
+    /// Run a deferred auto-start once the window is visible and the delay has passed
+    fn run_pending_auto_start(&mut self, ctx: &egui::Context) {
+        let Some(start_at) = self.pending_auto_start else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        if !self.first_frame_shown || now < start_at {
+            // Keep frames coming so the start isn't left waiting for input events
+            ctx.request_repaint_after(start_at.saturating_duration_since(now).max(std::time::Duration::from_millis(16)));
+            return;
+        }
+        
+        self.pending_auto_start = None;
+        if self.enabled {
+            return;
+        }
+        log::info!("Input device: {} | Output device: {}", 
+                  &self.selected_input_device, &self.selected_output_device);
+        self.toggle_audio_processing();
+        if self.enabled {
+            log::info!("✅ Auto-start successful - noise cancellation is ACTIVE");
+        } else {
+            log::warn!("❌ Auto-start failed - noise cancellation is NOT active");
+        }
+        self.persist_runtime_state();
+    }
+
+    /// Sidechain source and threshold/ratio/attack/release for output ducking
+    fn ducking_controls(&mut self, ui: &mut egui::Ui) {
+        use crate::audio::sidechain::{MAX_DUCK_RATIO, MAX_DUCK_TIME_MS, MIN_DUCK_THRESHOLD_DB};
+        let mut changed = false;
+        ui.indent("ducking", |ui| {
+            let source_name = self.config.ducking.source_device_id.as_deref()
+                .map(|id| device_name(&self.input_devices, id))
+                .unwrap_or_else(|| "External level only".to_string());
+            ComboBox::from_id_salt("ducking_source")
+                .selected_text(source_name)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.config.ducking.source_device_id, None, "External level only")
+                        .on_hover_text("No capture device; levels are fed in by integrations")
+                        .changed();
+                    // Only real capture devices; test signals and the file loop can't feed a sidechain
+                    let capture_devices = self.input_devices.iter()
+                        .filter(|d| !d.is_placeholder() && crate::audio::test_signal::TestSignalKind::from_device_id(&d.id).is_none());
+                    for device in capture_devices {
+                        changed |= ui.selectable_value(&mut self.config.ducking.source_device_id, Some(device.id.clone()), device.to_string()).changed();
+                    }
+                });
+            changed |= ui.add(Slider::new(&mut self.config.ducking.threshold_db, MIN_DUCK_THRESHOLD_DB..=0.0)
+                .text("Threshold")
+                .suffix(" dBFS"))
+                .changed();
+            changed |= ui.add(Slider::new(&mut self.config.ducking.ratio, 1.0..=MAX_DUCK_RATIO)
+                .text("Ratio")
+                .suffix(":1"))
+                .changed();
+            changed |= ui.add(Slider::new(&mut self.config.ducking.attack_ms, 0..=MAX_DUCK_TIME_MS)
+                .text("Attack")
+                .suffix(" ms")
+                .logarithmic(true))
+                .changed();
+            changed |= ui.add(Slider::new(&mut self.config.ducking.release_ms, 0..=MAX_DUCK_TIME_MS)
+                .text("Release")
+                .suffix(" ms")
+                .logarithmic(true))
+                .changed();
+        });
+        if changed {
+            self.config_changed = true;
+        }
+    }
+
+    /// Snapshot the current session for crash recovery
+    fn runtime_state(&self, clean_shutdown: bool) -> RuntimeState {
+        RuntimeState {
+            enabled: self.enabled,
+            input_device_id: self.selected_input_device.clone(),
+            output_device_id: self.selected_output_device.clone(),
+            sensitivity: self.sensitivity,
+            active_profile: self.config.active_profile.clone(),
+            clean_shutdown,
+        }
+    }
+
+    /// Write the "last known good" runtime state after a session change
+    /// 
+    /// Failures are only logged - the runtime state is best-effort and must never
+    /// interfere with audio processing.
+    fn persist_runtime_state(&self) {
+        if let Err(e) = self.runtime_state(false).save() {
+            log::debug!("Failed to write runtime state: {}", e);
+        }
+    }
+
+    /// Restore the session captured before an unclean shutdown
+    /// 
+    /// Devices are only restored if they are still present; processing is
+    /// restarted so the restored devices take effect.
+    fn restore_previous_session(&mut self, state: RuntimeState) {
+        log::info!("Restoring previous session (enabled: {}, input: {}, output: {})",
+                  state.enabled, state.input_device_id, state.output_device_id);
+
+        if self.enabled {
+            self.toggle_audio_processing();
+        }
+
+        if self.input_devices.iter().any(|d| d.id == state.input_device_id) {
+            self.selected_input_device = state.input_device_id;
+        }
+        if self.output_devices.iter().any(|d| d.id == state.output_device_id) {
+            self.selected_output_device = state.output_device_id;
+        }
+        self.sensitivity = state.sensitivity.clamp(crate::audio::MIN_SENSITIVITY, crate::audio::MAX_SENSITIVITY);
+        self.config_changed = true;
+
+        if state.enabled {
+            self.toggle_audio_processing();
+        } else {
+            self.persist_runtime_state();
+        }
+    }
+
     /// Persist current configuration to disk
     /// 
     /// This method ensures user preferences survive application restarts.
@@ -290,8 +764,9 @@ impl KwiteApp {
             return;
         }
         
-        self.input_devices = list_input_devices();
+        self.input_devices = input_device_list(&self.config);
         self.output_devices = list_output_devices();
+        self.input_channel_count = None;
         self.last_device_refresh = std::time::Instant::now();
         log::info!("Refreshed audio devices - Input: {}, Output: {}", 
                   self.input_devices.len(), self.output_devices.len());
@@ -305,15 +780,24 @@ impl KwiteApp {
         }
         
         if !self.output_devices.iter().any(|d| d.id == self.selected_output_device) {
-            self.selected_output_device = self.output_devices.iter()
-                .find(|d| d.is_virtual)
-                .or_else(|| self.output_devices.first())
-                .map(|d| d.id.clone())
-                .unwrap_or_default();
+            self.selected_output_device = preferred_output_device(&self.output_devices, self.config.local_playback_only);
             self.config_changed = true;
         }
+
+        if self.config_changed {
+            self.persist_runtime_state();
+        }
     }
 
+    /// Whether enumeration found no real input or output device
+    /// 
+    /// Only the placeholder entries are listed then, and starting would fail with a
+    /// confusing stream error, unless the user opted into trying them anyway.
+    fn audio_devices_missing(&self) -> bool {
+        !self.config.allow_placeholder_devices
+            && (!has_usable_devices(&self.input_devices) || !has_usable_devices(&self.output_devices))
+    }
+    
     /// Toggle the noise cancellation processing on/off
     /// 
     /// This is the core functionality that starts/stops the audio processing pipeline.
@@ -327,6 +811,14 @@ impl KwiteApp {
     /// 2. Releases audio device handles
     /// 3. Returns system to normal audio routing
     fn toggle_audio_processing(&mut self) {
+        // The test pipeline holds the microphone; release it first
+        self.stop_test_playback();
+        
+        if !self.enabled && self.audio_devices_missing() {
+            log::warn!("🔇 Not starting noise cancellation: no audio devices found");
+            return;
+        }
+        
         self.enabled = !self.enabled;
         log::info!("Noise cancellation toggled: {}", self.enabled);
 
@@ -355,15 +847,24 @@ impl KwiteApp {
 
         if self.enabled {
             // Start audio processing
-            match AudioManager::new(self.sensitivity, &self.selected_input_device, &self.selected_output_device) {
+            match AudioManager::new(
+                self.sensitivity,
+                &self.selected_input_device,
+                &self.selected_output_device,
+                AudioSettings::from_config(&self.config),
+            ) {
                 Ok(audio_mgr) => {
+                    if let Some(ref profile) = self.imported_noise_profile {
+                        audio_mgr.seed_noise_profile(profile);
+                    }
                     // Capture AI metrics reference for monitoring
                     self.ai_metrics = Some(audio_mgr.get_ai_metrics());
                     *manager = Some(audio_mgr);
+                    self.input_device_error = None;
                     log::info!("Audio processing started successfully with AI metrics monitoring");
                 }
                 Err(e) => {
-                    log::error!("Failed to start audio processing: {}", e);
+                    log::error!(error = %e, "Failed to start audio processing: {}", e);
                     self.enabled = false;
                     self.ai_metrics = None;
                     
@@ -371,39 +872,65 @@ impl KwiteApp {
                     if let Some(ref mut stats) = self.usage_stats {
                         stats.record_error("audio_start_failed", false);
                     }
-                    
-                    // Log error remotely if enabled
-                    if self.config.remote_logging.enabled {
-                        let mut fields = std::collections::HashMap::new();
-                        fields.insert("error".to_string(), e.to_string());
-                        log_remote("error", "Failed to start audio processing", Some("audio_processing"), fields);
-                    }
                 }
             }
         } else {
-            // Stop audio processing
+            // Stop audio processing, keeping this run's metrics in the session totals
             *manager = None;
+            watchdog::abandon_rebuild();
+            let final_summary = self.ai_metrics.as_ref()
+                .and_then(|metrics| metrics.lock().ok().map(|m| m.get_performance_summary()))
+                .or_else(|| self.ai_performance.clone());
+            if let Some(summary) = final_summary {
+                self.session_performance.absorb(&summary);
+            }
             self.ai_metrics = None;
             self.ai_performance = None;
+            self.model_statistics = None;
+            self.last_clip_count = 0;
+            self.output_sanitized_count = 0;
+            self.deadline_skip_count = 0;
+            self.frames_since_speech = 0;
+            self.idle_bypass_active = false;
+            self.input_level_db = METER_FLOOR_DB;
+            self.output_level_db = METER_FLOOR_DB;
+            self.input_sample_rate = None;
+            self.latency_probe_status = ProbeStatus::Idle;
+            self.music_passthrough_active = false;
+            self.ducking_reduction_db = 0.0;
+            self.last_clip_time = None;
             log::info!("Audio processing stopped");
         }
+        drop(manager);
+
+        self.persist_runtime_state();
+    }
+
+    /// Channel count of the selected input device, queried once per device
+    fn selected_input_channels(&mut self) -> Option<u16> {
+        let stale = self.input_channel_count.as_ref().is_none_or(|(id, _)| *id != self.selected_input_device);
+        if stale {
+            let count = input_channel_count(&self.selected_input_device);
+            self.input_channel_count = Some((self.selected_input_device.clone(), count));
+        }
+        self.input_channel_count.as_ref().and_then(|(_, count)| *count)
     }
 
-    /// Update noise cancellation sensitivity in real-time with rate limiting
+    /// Update the analyzer VAD sensitivity in real-time with rate limiting
     /// 
-    /// The sensitivity parameter controls how aggressively the AI model
-    /// removes background noise. This can be adjusted while processing
-    /// is active for immediate feedback.
+    /// The sensitivity parameter controls how aggressively the analyzer's voice
+    /// detector treats sound as noise. The denoise gain threshold is the separate
+    /// `vad_threshold` setting. This can be adjusted while processing is active.
     /// 
     /// Rate limiting prevents overwhelming the audio processing thread during
     /// rapid slider movements, batching updates to occur at most every 50ms.
     /// 
     /// Range: 0.01 (very aggressive) to 0.5 (preserve more audio)
     /// Logarithmic scale provides better control granularity
-    /// Update noise cancellation sensitivity 
+    /// Update analyzer sensitivity 
     /// Only called when the slider is released to avoid overwhelming the audio thread
     fn update_sensitivity(&mut self, new_sensitivity: f32) {
-        self.sensitivity = new_sensitivity.clamp(0.01, 0.5);
+        self.sensitivity = new_sensitivity.clamp(crate::audio::MIN_SENSITIVITY, crate::audio::MAX_SENSITIVITY);
         
         // Update the audio manager with new sensitivity
         if let Ok(mut manager) = self.audio_manager.lock() {
@@ -414,104 +941,967 @@ impl KwiteApp {
         }
         
         self.config_changed = true;
+        self.persist_runtime_state();
     }
     
-    /// Update AI performance metrics display
+    /// Play processed microphone audio on the default speakers for a few seconds
     /// 
-    /// Called periodically to refresh the AI metrics display without
-    /// excessive mutex locking that could impact audio performance
-    fn update_ai_metrics(&mut self) {
-        if self.last_ai_update.elapsed().as_millis() > 100 {  // Update every 100ms
-            if let Some(ref metrics) = self.ai_metrics {
-                if let Ok(metrics_guard) = metrics.lock() {
-                    self.ai_performance = Some(metrics_guard.get_performance_summary());
+    /// Runs a separate, temporary `AudioManager` with the current settings so users
+    /// can hear the effect before routing Kwite into a call app.
+    fn start_test_playback(&mut self) {
+        if self.enabled || self.test_playback.is_some() || self.audio_devices_missing() {
+            return;
+        }
+        
+        let speakers = preferred_output_device(&self.output_devices, true);
+        // Test playback already goes to the speakers; a monitor would only echo it
+        let mut settings = AudioSettings::from_config(&self.config);
+        settings.monitor_device_id = None;
+        match AudioManager::new(
+            self.sensitivity,
+            &self.selected_input_device,
+            &speakers,
+            settings,
+        ) {
+            Ok(audio_mgr) => {
+                log::info!("🎧 Test playback started on '{}' for {}s", device_name(&self.output_devices, &speakers), TEST_PLAYBACK_SECONDS);
+                let ends_at = std::time::Instant::now() + std::time::Duration::from_secs(TEST_PLAYBACK_SECONDS);
+                self.test_playback = Some((audio_mgr, ends_at));
+                if let Some(ref mut stats) = self.usage_stats {
+                    stats.record_feature_usage("test_playback");
                 }
             }
-            self.last_ai_update = std::time::Instant::now();
+            Err(e) => {
+                log::error!("Failed to start test playback: {}", e);
+            }
         }
     }
-}
-
-impl eframe::App for KwiteApp {
-    /// Main UI update loop - called every frame
-    /// 
-    /// This method implements the complete user interface using immediate mode GUI.
-    /// The UI is structured into logical sections:
-    /// 
-    /// 1. **Top Panel**: Application title and configuration status
-    /// 2. **Central Panel**: Main controls organized in groups
-    ///    - Device Selection: Input/output device choosers with refresh
-    ///    - Sensitivity Control: Real-time threshold adjustment
-    ///    - Processing Control: Main enable/disable button with status
-    /// 
-    /// The UI provides immediate feedback for all user actions and clearly
-    /// indicates system status through colors and icons.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Automatic device refresh every 5 seconds to handle hotplug events
-        // This ensures the device list stays current without manual intervention
-        // CRITICAL SAFETY: Skip device refresh if audio processing is active OR
-        // if there's been any recent sensitivity changes to prevent interference
-        // with active audio streams during rapid parameter adjustments
-        let _audio_manager_active = {
-            if let Ok(manager) = self.audio_manager.try_lock() {
-                manager.is_some()
-            } else {
-                true // If we can't check, assume it's active for safety
+    
+    /// Stop the test playback pipeline if it is running
+    fn stop_test_playback(&mut self) {
+        if self.test_playback.take().is_some() {
+            log::info!("🎧 Test playback stopped");
+        }
+    }
+    
+    /// Apply the analytics/remote logging config to the running stats and logger
+    fn sync_privacy_settings(&mut self) {
+        if self.config.analytics.enabled {
+            match self.usage_stats {
+                Some(ref mut stats) => stats.set_enabled(true),
+                None => {
+                    let mut stats = UsageStatsManager::new(true);
+                    stats.set_drop_silent_sessions(self.config.analytics.drop_silent_sessions);
+                    stats.start_session();
+                    self.usage_stats = Some(stats);
+                }
+            }
+        } else if let Some(ref mut stats) = self.usage_stats {
+            stats.set_enabled(false);
+        }
+        
+        update_remote_logger_config(self.config.remote_logging.clone());
+        self.config_changed = true;
+    }
+    
+    /// Hold remote logging back until the endpoint has answered a health check
+    ///
+    /// Called after a settings change that may have switched remote logging on.
+    /// Analytics and remote logging stay off while the check runs and are only
+    /// restored once the endpoint is reachable, so nothing is buffered against a
+    /// dead endpoint. Without the `remote-logging` feature nothing is transmitted
+    /// and there is nothing to check.
+    fn check_endpoint_before_enabling(&mut self, was_enabled: bool) {
+        #[cfg(feature = "remote-logging")]
+        {
+            let switched_on = self.config.remote_logging.enabled && !was_enabled;
+            let transmits = matches!(self.config.remote_logging.transport(), Ok(Transport::Http | Transport::Https));
+            if switched_on && transmits {
+                let analytics = self.config.analytics.enabled;
+                self.config.remote_logging.enabled = false;
+                self.config.analytics.enabled = false;
+                self.endpoint_health = None;
+                self.endpoint_check = Some((spawn_endpoint_health_check(self.config.remote_logging.clone()), analytics));
+            }
+        }
+        #[cfg(not(feature = "remote-logging"))]
+        let _ = was_enabled;
+    }
+    
+    /// Pick up the result of a finished manual update check
+    fn poll_update_check(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.update_check else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
+                return;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                UpdateCheckResult::Error("update check did not complete".to_string())
             }
         };
+        self.update_check = None;
         
-        // Auto-refresh devices every 5 seconds when not processing audio
-        let should_refresh = self.last_device_refresh.elapsed().as_secs() > 5 && !self.enabled;
-            
-        if should_refresh {
-            self.refresh_devices();
+        match &result {
+            UpdateCheckResult::NoUpdate => log::info!("Update check: Kwite {} is up to date", env!("CARGO_PKG_VERSION")),
+            UpdateCheckResult::UpdateAvailable(info) => log::info!("Update check: version {} is available", info.version),
+            UpdateCheckResult::Error(e) => log::warn!("Update check failed: {}", e),
         }
+        self.update_result = Some(result);
+    }
+    
+    /// Apply the result of a finished endpoint health check
+    #[cfg(feature = "remote-logging")]
+    fn poll_endpoint_check(&mut self, ctx: &egui::Context) {
+        let Some((receiver, analytics)) = &self.endpoint_check else {
+            return;
+        };
+        let health = match receiver.try_recv() {
+            Ok(health) => health,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
+                return;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                EndpointHealth::Unreachable("health check did not complete".to_string())
+            }
+        };
+        let analytics = *analytics;
+        self.endpoint_check = None;
 
-        // Top panel shows application branding and configuration status
-        // The configuration indicator helps users understand when settings need saving
-        TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.heading("Kwite — Intelligent AI Noise Cancellation");
-                
-                // Show current noise cancellation status
-                if self.enabled {
-                    ui.separator();
-                    ui.label(RichText::new("RNNoise Active").small().italics());
-                }
-                
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if self.config_changed {
-                        if ui.button("💾 Save Config").on_hover_text("Save current settings").clicked() {
-                            self.save_config();
-                        }
-                        ui.colored_label(egui::Color32::GRAY, "●");
-                    } else {
-                        ui.colored_label(egui::Color32::GREEN, "●");
-                    }
-                    ui.small("Config:");
-                    
-                    ui.separator();
-                    
-                    if ui.small_button("⚙ Settings").on_hover_text("Open application settings").clicked() {
-                        self.show_config_dialog = true;
-                    }
-                });
-            });
+        match &health {
+            EndpointHealth::Reachable => {
+                log::info!("📊 Analytics endpoint reachable - enabling remote logging");
+                self.config.remote_logging.enabled = true;
+                self.config.analytics.enabled = analytics;
+                self.sync_privacy_settings();
+            }
+            EndpointHealth::Unreachable(reason) => {
+                log::warn!("📊 Analytics endpoint unreachable ({}) - leaving analytics off", reason);
+            }
+        }
+        self.endpoint_health = Some(health);
+    }
+    
+    /// Location of the exported noise profile
+    fn noise_profile_path() -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+        Ok(KwiteConfig::config_dir()?.join("noise_profile.json"))
+    }
+    
+    /// Write the running pipeline's averaged noise spectrum to JSON
+    fn export_noise_profile(&mut self) {
+        let profile = match self.audio_manager.lock() {
+            Ok(manager) => manager.as_ref().and_then(|audio_mgr| audio_mgr.noise_profile()),
+            Err(_) => None,
+        };
+        
+        self.noise_profile_status = Some(match profile {
+            Some(profile) => match Self::noise_profile_path().and_then(|path| profile.save_to(&path).map(|_| path)) {
+                Ok(path) => format!("Exported {} noise frames to {}", profile.frames, path.display()),
+                Err(e) => format!("⚠️ Export failed: {}", e),
+            },
+            None => "No background noise analyzed yet".to_string(),
         });
-
-        // Central panel contains all main application controls
-        // Organized vertically with consistent spacing and grouping
-        CentralPanel::default().show(ctx, |ui| {
-            // Update AI metrics periodically for display
-            self.update_ai_metrics();
-            
-            ui.vertical_centered_justified(|ui| {
-                ui.add_space(20.0);
+    }
+    
+    /// Load the exported noise profile and seed the running pipeline with it
+    fn import_noise_profile(&mut self) {
+        let profile = match Self::noise_profile_path().and_then(|path| NoiseProfile::load_from(&path)) {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.noise_profile_status = Some(format!("⚠️ Import failed: {}", e));
+                return;
+            }
+        };
+        
+        let current = match self.audio_manager.lock() {
+            Ok(manager) => manager.as_ref().and_then(|audio_mgr| {
+                let current = audio_mgr.noise_profile();
+                audio_mgr.seed_noise_profile(&profile);
+                current
+            }),
+            Err(_) => None,
+        };
+        
+        self.noise_profile_status = Some(match current {
+            Some(current) => format!("Imported profile from {} ({:.0}% match with the current room) - noise classification now recognises it",
+                profile.captured_at, current.profile.similarity(&profile.profile) * 100.0),
+            None => format!("Imported profile from {} - noise classification now recognises it", profile.captured_at),
+        });
+        self.imported_noise_profile = Some(profile);
+    }
+    
+    /// Save the last few seconds of processed audio to a WAV file
+    fn save_instant_replay(&mut self) {
+        let path = crate::audio::replay::default_replay_path();
+        let result = match self.audio_manager.lock() {
+            Ok(manager) => match manager.as_ref() {
+                Some(audio_mgr) => audio_mgr.save_replay(&path),
+                None => Err("Noise cancellation is not running".into()),
+            },
+            Err(_) => Err("Audio manager is busy".into()),
+        };
+        
+        self.replay_status = Some(match result {
+            Ok(seconds) => {
+                if let Some(ref mut stats) = self.usage_stats {
+                    stats.record_feature_usage("instant_replay");
+                }
+                format!("💾 Saved {:.0}s to {}", seconds, path.display())
+            }
+            Err(e) => {
+                log::warn!("Instant replay save failed: {}", e);
+                format!("⚠️ Replay not saved: {}", e)
+            }
+        });
+    }
+    
+    /// Export the usage statistics to a timestamped JSON file
+    fn export_usage_stats(&mut self) {
+        let Some(ref stats) = self.usage_stats else {
+            return;
+        };
+        let path = crate::usage_stats::default_export_path();
+        self.stats_status = Some(match stats.export_json(&path) {
+            Ok(()) => format!("💾 Exported to {}", path.display()),
+            Err(e) => {
+                log::warn!("Usage statistics export failed: {}", e);
+                format!("⚠️ Export failed: {}", e)
+            }
+        });
+    }
+    
+    /// Register the configured global toggle hotkey, replacing the previous binding
+    /// 
+    /// The system hotkey service is only started once something is bound.
+    fn apply_toggle_hotkey(&mut self, ctx: &egui::Context) {
+        let binding = self.config.toggle_hotkey.clone();
+        if self.toggle_hotkey.is_none() && binding.is_some() {
+            match hotkey::ToggleHotkey::new(ctx) {
+                Ok(toggle_hotkey) => self.toggle_hotkey = Some(toggle_hotkey),
+                Err(e) => {
+                    log::warn!("Global hotkeys are unavailable: {}", e);
+                    self.hotkey_status = Some(format!("⚠️ Global hotkeys are unavailable: {}", e));
+                    return;
+                }
+            }
+        }
+        if let Some(toggle_hotkey) = self.toggle_hotkey.as_mut() {
+            self.hotkey_status = toggle_hotkey.set_binding(binding.as_deref()).err().map(|e| {
+                log::warn!("{}", e);
+                format!("⚠️ {}", e)
+            });
+        }
+    }
+    
+    /// Create the system tray icon if `minimize_to_tray` is enabled and it doesn't exist yet
+    fn apply_tray(&mut self, ctx: &egui::Context) {
+        if !self.config.minimize_to_tray || self.tray.is_some() {
+            return;
+        }
+        match tray::Tray::new(ctx) {
+            Ok(tray) => self.tray = Some(tray),
+            Err(e) => log::warn!("System tray is unavailable, closing the window will exit: {}", e),
+        }
+    }
+    
+    /// Show or hide the window; hidden windows keep running in the tray
+    fn set_window_hidden(&mut self, ctx: &egui::Context, hidden: bool) {
+        self.window_hidden = hidden;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(!hidden));
+        if !hidden {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+    }
+    
+    /// Start recording the processed output to a WAV file, or stop a running recording
+    fn toggle_recording(&mut self) {
+        let result = match self.audio_manager.lock() {
+            Ok(mut manager) => match manager.as_mut() {
+                Some(audio_mgr) if audio_mgr.recording_elapsed().is_some() => audio_mgr.stop_recording(),
+                Some(audio_mgr) => {
+                    let path = crate::audio::recorder::default_recording_path();
+                    audio_mgr.start_recording(&path).map(|()| None)
+                }
+                None => Err("Noise cancellation is not running".into()),
+            },
+            Err(_) => Err("Audio manager is busy".into()),
+        };
+        
+        self.recording_status = match result {
+            Ok(Some((path, seconds))) => Some(format!("⏺ Saved {:.0}s to {}", seconds, path.display())),
+            Ok(None) => {
+                if let Some(ref mut stats) = self.usage_stats {
+                    stats.record_feature_usage("output_recording");
+                }
+                None
+            }
+            Err(e) => {
+                log::warn!("Output recording failed: {}", e);
+                Some(format!("⚠️ Recording failed: {}", e))
+            }
+        };
+    }
+    
+    /// Save the selected input/output pair into a quick-swap slot
+    fn save_device_slot(&mut self, index: usize) {
+        let slot = &mut self.config.device_slots[index];
+        slot.input_device_id = self.selected_input_device.clone();
+        slot.output_device_id = self.selected_output_device.clone();
+        self.device_swap_status = Some(format!("💾 Saved current devices to {}", slot.label(index)));
+        self.config_changed = true;
+    }
+    
+    /// Switch to the device pair saved in a quick-swap slot
+    /// 
+    /// While processing is running the pipeline is rebuilt on the new devices,
+    /// so the swap is heard immediately.
+    fn apply_device_slot(&mut self, index: usize) {
+        let slot = self.config.device_slots[index].clone();
+        if slot.is_empty() {
+            return;
+        }
+        let label = slot.label(index);
+        let connected = self.input_devices.iter().any(|d| d.id == slot.input_device_id)
+            && self.output_devices.iter().any(|d| d.id == slot.output_device_id);
+        if !connected {
+            self.device_swap_status = Some(format!("⚠️ {}: a saved device is not connected", label));
+            return;
+        }
+        
+        self.selected_input_device = slot.input_device_id.clone();
+        self.selected_output_device = slot.output_device_id.clone();
+        self.config_changed = true;
+        
+        if self.enabled {
+            let mut manager = self.audio_manager.lock().unwrap();
+            if let Some(audio_mgr) = manager.take() {
+                match audio_mgr.switch_devices(&slot.input_device_id, &slot.output_device_id) {
+                    Ok(audio_mgr) => {
+                        if let Some(ref profile) = self.imported_noise_profile {
+                            audio_mgr.seed_noise_profile(profile);
+                        }
+                        self.ai_metrics = Some(audio_mgr.get_ai_metrics());
+                        *manager = Some(audio_mgr);
+                    }
+                    Err(e) => {
+                        log::error!("Quick device swap failed: {}", e);
+                        drop(manager);
+                        self.enabled = false;
+                        self.ai_metrics = None;
+                        self.device_swap_status = Some(format!("⚠️ Could not switch to {}: {}", label, e));
+                        if let Some(ref mut stats) = self.usage_stats {
+                            stats.record_error("device_swap_failed", false);
+                        }
+                        self.persist_runtime_state();
+                        return;
+                    }
+                }
+            }
+        }
+        
+        if let Some(ref mut stats) = self.usage_stats {
+            stats.record_feature_usage("device_swap");
+        }
+        self.persist_runtime_state();
+        self.device_swap_status = Some(format!("⇄ Switched to {}", label));
+    }
+    
+    /// Switch to a saved per-application profile
+    /// 
+    /// The profile's devices, sensitivity and gains replace the current ones.
+    /// While processing is running the pipeline is stopped and started again
+    /// with them, since the gains are baked into the new pipeline's settings.
+    fn activate_profile(&mut self, name: &str) {
+        let Some(profile) = self.config.profile(name).cloned() else {
+            return;
+        };
+        let connected = self.input_devices.iter().any(|d| d.id == profile.input_device_id)
+            && self.output_devices.iter().any(|d| d.id == profile.output_device_id);
+        if !connected {
+            self.profile_status = Some(format!("⚠️ {}: a saved device is not connected", name));
+            return;
+        }
+        
+        self.config.activate_profile(name);
+        self.selected_input_device = self.config.input_device_id.clone();
+        self.selected_output_device = self.config.output_device_id.clone();
+        self.sensitivity = self.config.sensitivity;
+        
+        if self.enabled {
+            let mut manager = self.audio_manager.lock().unwrap();
+            // Release the old devices before opening the profile's
+            manager.take();
+            match AudioManager::new(
+                self.sensitivity,
+                &self.selected_input_device,
+                &self.selected_output_device,
+                AudioSettings::from_config(&self.config),
+            ) {
+                Ok(audio_mgr) => {
+                    if let Some(ref profile) = self.imported_noise_profile {
+                        audio_mgr.seed_noise_profile(profile);
+                    }
+                    self.ai_metrics = Some(audio_mgr.get_ai_metrics());
+                    *manager = Some(audio_mgr);
+                }
+                Err(e) => {
+                    log::error!("Profile switch failed: {}", e);
+                    drop(manager);
+                    self.enabled = false;
+                    self.ai_metrics = None;
+                    self.profile_status = Some(format!("⚠️ Could not start profile {}: {}", name, e));
+                    if let Some(ref mut stats) = self.usage_stats {
+                        stats.record_error("profile_switch_failed", false);
+                    }
+                    self.save_config();
+                    self.persist_runtime_state();
+                    return;
+                }
+            }
+        }
+        
+        if let Some(ref mut stats) = self.usage_stats {
+            stats.record_feature_usage("profile_switch");
+        }
+        log::info!("⇄ Switched to profile {}", name);
+        self.save_config();
+        self.persist_runtime_state();
+        self.profile_status = Some(format!("⇄ Switched to {}", name));
+    }
+    
+    /// Save the current devices and parameters as a profile and make it active
+    fn save_profile(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        self.config.input_device_id = self.selected_input_device.clone();
+        self.config.output_device_id = self.selected_output_device.clone();
+        self.config.sensitivity = self.sensitivity;
+        self.config.save_profile(name);
+        self.save_config();
+        self.persist_runtime_state();
+        self.profile_status = Some(format!("💾 Saved profile {}", name));
+    }
+    
+    /// Delete a saved profile
+    fn delete_profile(&mut self, name: &str) {
+        self.config.remove_profile(name);
+        self.save_config();
+        self.persist_runtime_state();
+        self.profile_status = Some(format!("🗑 Deleted profile {}", name));
+    }
+    
+    /// Apply the level meter settings to the running pipeline
+    fn update_meter_ballistics(&mut self) {
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.configure_meters(self.config.meter_ballistics, self.config.meter_integration_ms);
+            }
+        }
+        self.config_changed = true;
+    }
+    
+    /// Update the speech detection threshold in real-time
+    fn update_vad_threshold(&mut self, threshold: f32) {
+        self.config.vad_threshold = threshold.clamp(crate::audio::MIN_VAD_THRESHOLD, crate::audio::MAX_VAD_THRESHOLD);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_vad_threshold(self.config.vad_threshold);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update the background noise gain (suppression strength) in real-time
+    fn update_noise_gain(&mut self, gain: f32) {
+        self.config.noise_gain = gain.clamp(0.0, 1.0);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_noise_gain(self.config.noise_gain);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update the gain applied to speech frames in real-time
+    fn update_speech_gain(&mut self, gain: f32) {
+        self.config.speech_gain = gain.clamp(0.0, 1.0);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_speech_gain(self.config.speech_gain);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update the gain floor (minimum kept background) in real-time
+    fn update_gain_floor(&mut self, floor: f32) {
+        self.config.gain_floor = floor.clamp(0.0, crate::audio::gain::MAX_GAIN_FLOOR);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_gain_floor(self.config.gain_floor);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update the comfort noise level in real-time (`None` = off)
+    fn update_comfort_noise_level(&mut self, level_db: Option<f32>) {
+        self.config.comfort_noise_level = level_db.map(|level| level.clamp(MIN_COMFORT_NOISE_DB, MAX_COMFORT_NOISE_DB));
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_comfort_noise_level(self.config.comfort_noise_level);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update the dry/wet mix in real-time
+    fn update_mix(&mut self, mix: f32) {
+        self.config.mix = mix.clamp(0.0, 1.0);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_mix(self.config.mix);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Zero the running pipeline's metrics and counters without stopping it
+    fn reset_metrics(&mut self) {
+        if let Ok(manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_ref() {
+                audio_mgr.reset_metrics();
+            }
+        }
+        
+        self.ai_performance = None;
+        self.model_statistics = None;
+        self.last_clip_count = 0;
+        self.last_clip_time = None;
+        self.output_sanitized_count = 0;
+        self.deadline_skip_count = 0;
+    }
+    
+    /// Ask the running pipeline to reinitialize its denoiser state
+    fn reset_denoiser(&mut self) {
+        if let Ok(manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_ref() {
+                audio_mgr.reset_denoiser();
+            }
+        }
+        
+        if let Some(ref mut stats) = self.usage_stats {
+            stats.record_feature_usage("denoiser_reset");
+        }
+    }
+    
+    /// Update the input pre-gain applied before the denoiser in real-time
+    fn update_input_gain(&mut self, gain_db: f32) {
+        self.config.input_gain_db = gain_db.clamp(crate::audio::MIN_INPUT_GAIN_DB, crate::audio::MAX_INPUT_GAIN_DB);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_input_gain_db(self.config.input_gain_db);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update the processed-output gain trim in real-time
+    /// 
+    /// Updating the trim is a single atomic store, so it is applied while the
+    /// slider is dragged rather than on release.
+    fn update_output_gain(&mut self, gain_db: f32) {
+        self.config.output_gain_db = gain_db.clamp(crate::audio::MIN_OUTPUT_GAIN_DB, crate::audio::MAX_OUTPUT_GAIN_DB);
+        
+        if let Ok(mut manager) = self.audio_manager.lock() {
+            if let Some(audio_mgr) = manager.as_mut() {
+                audio_mgr.update_output_gain_db(self.config.output_gain_db);
+            }
+        }
+        
+        self.config_changed = true;
+    }
+    
+    /// Update AI performance metrics display
+    /// 
+    /// Called periodically to refresh the AI metrics display without
+    /// excessive mutex locking that could impact audio performance
+    fn update_ai_metrics(&mut self) {
+        if self.last_ai_update.elapsed().as_millis() > 100 {  // Update every 100ms
+            if let Some(ref metrics) = self.ai_metrics {
+                if let Ok(metrics_guard) = metrics.lock() {
+                    self.ai_performance = Some(metrics_guard.get_performance_summary());
+                }
+            }
+            let mut permission_denied = false;
+            let mut input_failed = false;
+            let mut pipeline_lost = false;
+            if let Ok(manager) = self.audio_manager.try_lock() {
+                // The watchdog may have rebuilt the pipeline (new metrics) or failed to
+                pipeline_lost = self.enabled && manager.is_none() && !watchdog::rebuild_in_progress();
+                if let Some(audio_mgr) = manager.as_ref() {
+                    self.ai_metrics = Some(audio_mgr.get_ai_metrics());
+                    if let Some(stats) = audio_mgr.get_model_statistics() {
+                        self.model_statistics = Some(stats);
+                    }
+                    if self.active_model_name.as_deref() != Some(audio_mgr.model_name()) {
+                        self.active_model_name = Some(audio_mgr.model_name().to_string());
+                    }
+                    
+                    let clip_count = audio_mgr.input_clip_count();
+                    if clip_count > self.last_clip_count {
+                        self.last_clip_time = Some(std::time::Instant::now());
+                    }
+                    self.last_clip_count = clip_count;
+                    self.output_sanitized_count = audio_mgr.output_sanitized_count();
+                    self.deadline_skip_count = audio_mgr.deadline_skip_count();
+                    self.frames_since_speech = audio_mgr.frames_since_speech();
+                    self.idle_bypass_active = audio_mgr.idle_bypass_active();
+                    self.input_level_db = audio_mgr.input_level_db();
+                    self.output_level_db = audio_mgr.output_level_db();
+                    self.input_sample_rate = audio_mgr.input_sample_rate();
+                    self.auto_sensitivity_value = audio_mgr.auto_sensitivity();
+                    // The spectrum is only computed while the advanced panel shows it
+                    audio_mgr.set_spectrum_enabled(self.show_advanced_controls && self.config.development_mode);
+                    // Noise profile export lives in the dev panel, so only dev mode pays for its FFT
+                    audio_mgr.set_noise_profile_enabled(self.config.development_mode);
+                    // Re-applied every frame so a rebuilt pipeline keeps the A/B state
+                    audio_mgr.set_bypass(self.ab_bypass);
+                    self.latency_probe_status = audio_mgr.latency_probe_status();
+                    self.music_passthrough_active = audio_mgr.music_passthrough_active();
+                    self.ducking_reduction_db = audio_mgr.ducking_reduction_db();
+                    if self.config.development_mode {
+                        self.input_scope = audio_mgr.input_scope();
+                    }
+                    permission_denied = audio_mgr.input_permission_denied();
+                    input_failed = audio_mgr.input_stream_failed();
+                    if audio_mgr.input_signal_detected() {
+                        if let Some(ref mut stats) = self.usage_stats {
+                            stats.record_input_signal();
+                        }
+                    }
+                    let measured = self.session_performance.including(self.ai_performance.as_ref()).measured_noise_reduction_percent();
+                    if let (Some(percent), Some(stats)) = (measured, self.usage_stats.as_mut()) {
+                        stats.record_noise_reduction(percent);
+                    }
+                }
+            }
+            self.last_ai_update = std::time::Instant::now();
+            
+            if pipeline_lost {
+                log::error!("❌ Audio pipeline could not be restarted - noise cancellation stopped");
+                self.toggle_audio_processing();
+            }
+            
+            // A denied microphone never produces audio; stop and explain instead
+            if permission_denied && self.enabled {
+                log::error!("🔒 Stopping noise cancellation: microphone access denied");
+                self.toggle_audio_processing();
+                self.show_mic_permission_dialog = true;
+            }
+            
+            // An unplugged microphone kills the input stream; stop rather than show a dead pipeline as active
+            if input_failed && self.enabled {
+                log::error!("🔌 Stopping noise cancellation: the input device stopped working");
+                let device = device_name(&self.input_devices, &self.selected_input_device);
+                self.toggle_audio_processing();
+                self.input_device_error = Some(format!("{} stopped working (unplugged?). Noise cancellation was stopped.", device));
+                if let Some(ref mut stats) = self.usage_stats {
+                    stats.record_error("input_device_lost", false);
+                }
+                // Processing is off now, so the device list can be refreshed safely
+                self.refresh_devices();
+            }
+        }
+    }
+}
+
+impl eframe::App for KwiteApp {
+    /// Main UI update loop - called every frame
+    /// 
+    /// This method implements the complete user interface using immediate mode GUI.
+    /// The UI is structured into logical sections:
+    /// 
+    /// 1. **Top Panel**: Application title and configuration status
+    /// 2. **Central Panel**: Main controls organized in groups
+    ///    - Device Selection: Input/output device choosers with refresh
+    ///    - Sensitivity Control: Real-time threshold adjustment
+    ///    - Processing Control: Main enable/disable button with status
+    /// 
+    /// The UI provides immediate feedback for all user actions and clearly
+    /// indicates system status through colors and icons.
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Automatic device refresh every 5 seconds to handle hotplug events
+        // This ensures the device list stays current without manual intervention
+        // CRITICAL SAFETY: Skip device refresh if audio processing is active OR
+        // if there's been any recent sensitivity changes to prevent interference
+        // with active audio streams during rapid parameter adjustments
+        let _audio_manager_active = {
+            if let Ok(manager) = self.audio_manager.try_lock() {
+                manager.is_some()
+            } else {
+                true // If we can't check, assume it's active for safety
+            }
+        };
+        
+        // Instant replay shortcut
+        let save_replay_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::S);
+        if self.enabled && self.config.instant_replay_secs > 0 && !self.config.stereo_processing && ctx.input_mut(|i| i.consume_shortcut(&save_replay_shortcut)) {
+            self.save_instant_replay();
+        }
+        
+        // Global toggle hotkey (works while another application has focus)
+        if self.toggle_hotkey.as_ref().is_some_and(|toggle_hotkey| toggle_hotkey.pressed()) {
+            log::info!("⌨ Global hotkey pressed - toggling noise cancellation");
+            self.toggle_audio_processing();
+        }
+        
+        // Tray menu and icon clicks
+        let tray_commands = self.tray.as_ref().map(|tray| tray.commands()).unwrap_or_default();
+        for command in tray_commands {
+            match command {
+                TrayCommand::ShowWindow => self.set_window_hidden(ctx, false),
+                TrayCommand::ToggleWindow => self.set_window_hidden(ctx, !self.window_hidden),
+                TrayCommand::ToggleNoiseCancellation => self.toggle_audio_processing(),
+                TrayCommand::Quit => {
+                    self.quit_requested = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+        
+        // Closing the window hides it to the tray when minimize-to-tray is on
+        if self.config.minimize_to_tray && self.tray.is_some() && !self.quit_requested
+            && ctx.input(|i| i.viewport().close_requested()) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.set_window_hidden(ctx, true);
+            log::info!("🗔 Window hidden to the system tray");
+        }
+        
+        // A/B bypass shortcut
+        let ab_bypass_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::B);
+        if self.enabled && ctx.input_mut(|i| i.consume_shortcut(&ab_bypass_shortcut)) {
+            self.ab_bypass = !self.ab_bypass;
+        }
+        
+        // Deferred auto-start; the first frame is always painted before devices are opened
+        self.run_pending_auto_start(ctx);
+        self.first_frame_shown = true;
+        
+        #[cfg(feature = "remote-logging")]
+        self.poll_endpoint_check(ctx);
+        self.poll_update_check(ctx);
+        
+        // Quick device swap shortcuts
+        for index in 0..DEVICE_SLOT_COUNT {
+            if let Some(shortcut) = device_slot_shortcut(&self.config.device_slots[index]) {
+                if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+                    self.apply_device_slot(index);
+                }
+            }
+        }
+        
+        // Auto-refresh devices every 5 seconds when not processing audio
+        // Setup dialogs work from the current lists and only re-enumerate on an explicit refresh
+        let dialog_open = self.show_virtual_setup_dialog || self.show_macos_audio_dialog;
+        let should_refresh = self.last_device_refresh.elapsed().as_secs() > 5 && !self.enabled && !dialog_open
+            && self.test_playback.is_none();
+            
+        if should_refresh {
+            self.refresh_devices();
+        }
+
+        // End the test playback once its time is up
+        if let Some((_, ends_at)) = self.test_playback {
+            if std::time::Instant::now() >= ends_at {
+                self.stop_test_playback();
+            } else {
+                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+            }
+        }
+
+        // Keep live indicators (clip latch, metrics) updating while processing,
+        // at a much lower rate when nobody is looking at the window
+        if self.enabled {
+            let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+            let interval = if focused {
+                std::time::Duration::from_millis(100)
+            } else {
+                let fps = self.config.background_fps.clamp(MIN_BACKGROUND_FPS, MAX_BACKGROUND_FPS);
+                std::time::Duration::from_millis(1000 / fps as u64)
+            };
+            ctx.request_repaint_after(interval);
+        }
+
+        // Top panel shows application branding and configuration status
+        // The configuration indicator helps users understand when settings need saving
+        TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Kwite — Intelligent AI Noise Cancellation");
+                
+                // Show current noise cancellation status
+                if self.enabled {
+                    ui.separator();
+                    ui.label(RichText::new("RNNoise Active").small().italics());
+                }
+                
+                // Per-application profile quick switch
+                ui.separator();
+                let mut activate_profile = None;
+                let mut save_profile = false;
+                let mut delete_profile = None;
+                ComboBox::from_id_salt("active_profile")
+                    .selected_text(self.config.active_profile.clone().unwrap_or_else(|| "No profile".to_string()))
+                    .show_ui(ui, |ui| {
+                        for profile in &self.config.profiles {
+                            let active = self.config.active_profile.as_deref() == Some(profile.name.as_str());
+                            let hover = format!("🎤 {}\n🔊 {}",
+                                device_name(&self.input_devices, &profile.input_device_id),
+                                device_name(&self.output_devices, &profile.output_device_id));
+                            if ui.selectable_label(active, profile.name.as_str()).on_hover_text(hover).clicked() && !active {
+                                activate_profile = Some(profile.name.clone());
+                            }
+                        }
+                        if !self.config.profiles.is_empty() {
+                            ui.separator();
+                        }
+                        ui.horizontal(|ui| {
+                            ui.add(egui::TextEdit::singleline(&mut self.new_profile_name)
+                                .hint_text("Profile name")
+                                .desired_width(110.0));
+                            if ui.add_enabled(!self.new_profile_name.trim().is_empty(), Button::new("💾 Save current"))
+                                .on_hover_text("Save the current devices, sensitivity and gains under this name")
+                                .clicked() {
+                                save_profile = true;
+                            }
+                        });
+                        if let Some(active) = &self.config.active_profile {
+                            if ui.button(format!("🗑 Delete {}", active)).clicked() {
+                                delete_profile = Some(active.clone());
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Per-application profiles: each remembers its input/output devices, sensitivity and gains");
+                if let Some(name) = activate_profile {
+                    self.activate_profile(&name);
+                }
+                if save_profile {
+                    let name = std::mem::take(&mut self.new_profile_name);
+                    self.save_profile(&name);
+                }
+                if let Some(name) = delete_profile {
+                    self.delete_profile(&name);
+                }
+                if let Some(ref status) = self.profile_status {
+                    ui.small(RichText::new(status).color(Color32::GRAY));
+                }
+                
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if self.config_changed {
+                        if ui.button("💾 Save Config").on_hover_text("Save current settings").clicked() {
+                            self.save_config();
+                        }
+                        ui.colored_label(egui::Color32::GRAY, "●");
+                    } else {
+                        ui.colored_label(egui::Color32::GREEN, "●");
+                    }
+                    ui.small("Config:");
+                    
+                    ui.separator();
+                    
+                    if ui.small_button("⚙ Settings").on_hover_text("Open application settings").clicked() {
+                        self.show_config_dialog = true;
+                    }
+                });
+            });
+        });
+
+        // Track device selection changes so the runtime state can be updated after the panel
+        let mut device_selection_changed = false;
+
+        // Central panel contains all main application controls
+        // Organized vertically with consistent spacing and grouping
+        CentralPanel::default().show(ctx, |ui| {
+            // Update AI metrics periodically for display
+            self.update_ai_metrics();
+            
+            ui.vertical_centered_justified(|ui| {
+                ui.add_space(20.0);
+
+                // Explain an empty device list instead of offering non-functional placeholders
+                if self.audio_devices_missing() {
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.colored_label(Color32::YELLOW, RichText::new("🔇 No audio devices found").strong());
+                            let missing = match (has_usable_devices(&self.input_devices), has_usable_devices(&self.output_devices)) {
+                                (false, false) => "No microphone or speakers were detected.",
+                                (false, true) => "No microphone was detected.",
+                                _ => "No speakers or output device were detected.",
+                            };
+                            ui.label(format!("{} Connect a device or check that your system's audio service is running, then retry.", missing));
+                            if ui.add_enabled(!self.enabled, Button::new("🔄 Retry")).clicked() {
+                                self.refresh_devices();
+                            }
+                        });
+                    });
+                    ui.add_space(10.0);
+                }
+                
+                // Banner after the microphone disappeared mid-session
+                if let Some(error) = self.input_device_error.clone() {
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.colored_label(Color32::from_rgb(255, 100, 100), RichText::new("🔌 Input device disconnected").strong());
+                            ui.label(error);
+                            ui.horizontal(|ui| {
+                                if ui.button("🔄 Refresh Devices").clicked() {
+                                    self.refresh_devices();
+                                }
+                                if ui.button("✖ Dismiss").clicked() {
+                                    self.input_device_error = None;
+                                }
+                            });
+                        });
+                    });
+                    ui.add_space(10.0);
+                }
 
                 ui.group(|ui| {
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
                             ui.label("🎙 Input Device:");
+                            
+                            // Clip indicator latches for one second after new clipping
+                            if self.enabled {
+                                let clipping = self.last_clip_time
+                                    .map(|t| t.elapsed() < std::time::Duration::from_secs(1))
+                                    .unwrap_or(false);
+                                let clip_color = if clipping { Color32::RED } else { Color32::DARK_GRAY };
+                                ui.colored_label(clip_color, "● CLIP")
+                                    .on_hover_text(format!("Input clipping indicator ({} clipped samples). If it lights up, lower your microphone gain.", self.last_clip_count));
+                            }
+                            
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                 // Disable device refresh during active audio processing to prevent interference
                                 let refresh_enabled = !self.enabled;
@@ -541,9 +1931,34 @@ impl eframe::App for KwiteApp {
                                 for device in &self.input_devices {
                                     if ui.selectable_value(&mut self.selected_input_device, device.id.clone(), device.to_string()).clicked() {
                                         self.config_changed = true;
+                                        device_selection_changed = true;
                                     }
                                 }
                             });
+                        
+                        // Multichannel interfaces don't always carry the microphone on channel 1
+                        if let Some(channel_count) = self.selected_input_channels().filter(|&count| count > 1) {
+                            let channel_count = channel_count as usize;
+                            ui.horizontal(|ui| {
+                                ui.label("Channel:");
+                                let selected = self.config.input_channel.min(channel_count - 1);
+                                ComboBox::from_id_salt("input_channel")
+                                    .selected_text(format!("Channel {}", selected + 1))
+                                    .show_ui(ui, |ui| {
+                                        for channel in 0..channel_count {
+                                            if ui.selectable_value(&mut self.config.input_channel, channel, format!("Channel {}", channel + 1)).clicked() {
+                                                self.config_changed = true;
+                                            }
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text("Which of the device's channels carries your microphone. Takes effect the next time noise cancellation starts.");
+                            });
+                        }
+                        
+                        if let Some(performance) = self.ai_performance.as_ref().filter(|_| self.enabled) {
+                            input_vu_meter(ui, performance.input_rms, performance.input_peak);
+                        }
 
                         ui.add_space(10.0);
 
@@ -559,19 +1974,132 @@ impl eframe::App for KwiteApp {
                                 for device in &self.output_devices {
                                     if ui.selectable_value(&mut self.selected_output_device, device.id.clone(), device.to_string()).clicked() {
                                         self.config_changed = true;
+                                        device_selection_changed = true;
                                     }
                                 }
                             });
                             
+                        ui.add_space(5.0);
+                        let mut apply_slot = None;
+                        let mut save_slot = None;
+                        ui.horizontal(|ui| {
+                            ui.label("⇄ Quick Swap:");
+                            for index in 0..DEVICE_SLOT_COUNT {
+                                let slot = &self.config.device_slots[index];
+                                let active = !slot.is_empty()
+                                    && slot.input_device_id == self.selected_input_device
+                                    && slot.output_device_id == self.selected_output_device;
+                                let (text, mut hover) = if slot.is_empty() {
+                                    (format!("➕ {}", slot.label(index)), "Empty - click to save the current devices here".to_string())
+                                } else {
+                                    (slot.label(index), format!("🎤 {}\n🔊 {}",
+                                        device_name(&self.input_devices, &slot.input_device_id),
+                                        device_name(&self.output_devices, &slot.output_device_id)))
+                                };
+                                if let Some(shortcut) = device_slot_shortcut(slot) {
+                                    hover.push_str(&format!("\nShortcut: {}", ui.ctx().format_shortcut(&shortcut)));
+                                }
+                                hover.push_str("\nRight-click to save, rename or change the key");
+                                let is_empty = slot.is_empty();
+                                
+                                let response = ui.selectable_label(active, text).on_hover_text(hover);
+                                if response.clicked() {
+                                    if is_empty {
+                                        save_slot = Some(index);
+                                    } else {
+                                        apply_slot = Some(index);
+                                    }
+                                }
+                                response.context_menu(|ui| {
+                                    if ui.button("💾 Save current devices here").clicked() {
+                                        save_slot = Some(index);
+                                        ui.close();
+                                    }
+                                    let slot = &mut self.config.device_slots[index];
+                                    ui.horizontal(|ui| {
+                                        ui.label("Name:");
+                                        if ui.text_edit_singleline(&mut slot.name).changed() {
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Key (Ctrl/Cmd+Alt+):");
+                                        let mut key = slot.hotkey.clone().unwrap_or_default();
+                                        if ui.add(egui::TextEdit::singleline(&mut key).desired_width(40.0)).changed() {
+                                            slot.hotkey = (!key.trim().is_empty()).then_some(key);
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    if ui.add_enabled(!slot.is_empty(), egui::Button::new("🗑 Clear")).clicked() {
+                                        slot.input_device_id.clear();
+                                        slot.output_device_id.clear();
+                                        self.config_changed = true;
+                                        ui.close();
+                                    }
+                                });
+                            }
+                        });
+                        if let Some(index) = save_slot {
+                            self.save_device_slot(index);
+                        }
+                        if let Some(index) = apply_slot {
+                            self.apply_device_slot(index);
+                        }
+                        if let Some(ref status) = self.device_swap_status {
+                            ui.small(status);
+                        }
+                            
+                        ui.add_space(5.0);
+                        let mut local_playback_only = self.config.local_playback_only;
+                        if ui.checkbox(&mut local_playback_only, "🔈 Local playback only")
+                            .on_hover_text("Play cleaned audio on your speakers instead of routing it to Discord/Teams through a virtual device")
+                            .changed() {
+                            self.config.local_playback_only = local_playback_only;
+                            // Switch to the output that matches the new use case
+                            let preferred = preferred_output_device(&self.output_devices, local_playback_only);
+                            if preferred != self.selected_output_device && !self.enabled {
+                                self.selected_output_device = preferred;
+                                device_selection_changed = true;
+                            }
+                            self.config_changed = true;
+                        }
+                            
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.config.monitor_enabled, "🎧 Monitor (hear yourself)")
+                                .on_hover_text("Also play the processed audio on a second device such as your headphones, without building a Multi-Output Device. Takes effect the next time noise cancellation starts.")
+                                .changed() {
+                                self.config_changed = true;
+                            }
+                            if self.config.monitor_enabled {
+                                let monitor_name = self.config.monitor_device_id.as_deref()
+                                    .map(|id| device_name(&self.output_devices, id))
+                                    .unwrap_or_else(|| "Choose a device".to_string());
+                                ComboBox::from_id_salt("monitor_device")
+                                    .selected_text(monitor_name)
+                                    .show_ui(ui, |ui| {
+                                        for device in &self.output_devices {
+                                            let selected = self.config.monitor_device_id.as_deref() == Some(device.id.as_str());
+                                            if ui.selectable_label(selected, device.to_string()).clicked() {
+                                                self.config.monitor_device_id = Some(device.id.clone());
+                                                self.config_changed = true;
+                                            }
+                                        }
+                                    });
+                            }
+                        });
+                        if self.config.monitor_enabled && self.config.monitor_device_id.as_deref() == Some(self.selected_output_device.as_str()) {
+                            ui.small(RichText::new("⚠ The monitor is the same device as the output").color(Color32::YELLOW));
+                        }
+                            
                         // Enhanced virtual device setup guidance
                         ui.add_space(5.0);
                         let has_virtual = has_virtual_devices(&self.output_devices);
-                        let (status_message, status_color) = get_setup_status_message(has_virtual);
+                        let (status_message, status_color) = get_setup_status_message(has_virtual, self.config.local_playback_only);
                         
                         ui.horizontal(|ui| {
                             ui.colored_label(status_color, &status_message);
                             
-                            if !has_virtual {
+                            if !has_virtual && !self.config.local_playback_only {
                                 if ui.small_button("📋 Setup Guide").on_hover_text("Show detailed setup instructions").clicked() {
                                     self.show_virtual_setup_dialog = true;
                                 }
@@ -579,18 +2107,12 @@ impl eframe::App for KwiteApp {
                         });
                         
                         // macOS Virtual Audio Device Configuration Warning
-                        if cfg!(target_os = "macos") {
+                        if cfg!(target_os = "macos") && !self.config.local_playback_only {
                             ui.add_space(5.0);
                             
                             // Check for configuration issues
-                            let input_device_name = self.input_devices.iter()
-                                .find(|d| d.id == self.selected_input_device)
-                                .map(|d| d.name.clone())
-                                .unwrap_or_else(|| "Unknown".to_string());
-                            let output_device_name = self.output_devices.iter()
-                                .find(|d| d.id == self.selected_output_device)
-                                .map(|d| d.name.clone())
-                                .unwrap_or_else(|| "Unknown".to_string());
+                            let input_device_name = device_name(&self.input_devices, &self.selected_input_device);
+                            let output_device_name = device_name(&self.output_devices, &self.selected_output_device);
                             
                             let input_virtual_type = crate::virtual_audio::detect_virtual_device_type(&input_device_name);
                             let output_virtual_type = crate::virtual_audio::detect_virtual_device_type(&output_device_name);
@@ -620,31 +2142,256 @@ impl eframe::App for KwiteApp {
 
                 ui.group(|ui| {
                     ui.vertical(|ui| {
-                        ui.label("Sensitivity Threshold:");
-                        
-                        let slider_response = ui.add(Slider::new(&mut self.sensitivity, 0.01..=0.5)
-                            .text("Sensitivity")
-                            .logarithmic(true));
-
-                        // Track if user is dragging the slider
-                        if slider_response.is_pointer_button_down_on() {
-                            self.sensitivity_dragging = true;
-                            self.sensitivity_pending_update = Some(self.sensitivity);
-                        } else if self.sensitivity_dragging && !slider_response.is_pointer_button_down_on() {
-                            // User just released the slider - apply the update
-                            self.sensitivity_dragging = false;
-                            if let Some(pending_value) = self.sensitivity_pending_update.take() {
-                                self.update_sensitivity(pending_value);
+                        let stereo = self.config.stereo_processing;
+                        let mut auto_toggled = false;
+                        ui.horizontal(|ui| {
+                            ui.label("Analyzer Sensitivity:");
+                            auto_toggled = ui.add_enabled(!stereo, egui::Checkbox::new(&mut self.config.auto_sensitivity, "🤖 Auto"))
+                                .on_hover_text("Set the sensitivity from the measured background noise: conservative in a quiet room, aggressive in a loud one. Short noises like a door slam are ignored.")
+                                .on_disabled_hover_text(MONO_ONLY_HINT)
+                                .changed();
+                        });
+                        if auto_toggled {
+                            if let Ok(manager) = self.audio_manager.lock() {
+                                if let Some(audio_mgr) = manager.as_ref() {
+                                    audio_mgr.set_auto_sensitivity(self.config.auto_sensitivity);
+                                }
                             }
+                            self.config_changed = true;
                         }
+                        
+                        if self.config.auto_sensitivity && !stereo {
+                            // Read-only: shows the value the noise floor currently selects
+                            let mut auto_value = self.auto_sensitivity_value.unwrap_or(self.sensitivity);
+                            ui.add_enabled(false, Slider::new(&mut auto_value, crate::audio::MIN_SENSITIVITY..=crate::audio::MAX_SENSITIVITY)
+                                .text("Sensitivity")
+                                .logarithmic(true));
+                            if self.enabled {
+                                ui.small(format!("Current: {:.3} (auto)", auto_value));
+                            } else {
+                                ui.small(RichText::new("Measured once noise cancellation is running").color(Color32::GRAY));
+                            }
+                        } else {
+                            let slider_response = ui.add(Slider::new(&mut self.sensitivity, crate::audio::MIN_SENSITIVITY..=crate::audio::MAX_SENSITIVITY)
+                                .text("Sensitivity")
+                                .logarithmic(true))
+                                .on_hover_text("How aggressively the analyzer's voice detector treats sound as noise (lower = more aggressive). Affects the Analyzer VAD source and music detection, not the speech threshold below.");
+
+                            // Track if user is dragging the slider
+                            if slider_response.is_pointer_button_down_on() {
+                                self.sensitivity_dragging = true;
+                                self.sensitivity_pending_update = Some(self.sensitivity);
+                            } else if self.sensitivity_dragging && !slider_response.is_pointer_button_down_on() {
+                                // User just released the slider - apply the update
+                                self.sensitivity_dragging = false;
+                                if let Some(pending_value) = self.sensitivity_pending_update.take() {
+                                    self.update_sensitivity(pending_value);
+                                }
+                            }
 
-                        ui.small(format!("Current: {:.3}", self.sensitivity));
+                            ui.small(format!("Current: {:.3}", self.sensitivity));
+                        }
+                        
+                        ui.add_space(10.0);
+                        ui.label("Speech Detection:");
+                        if ui.add(Slider::new(&mut self.config.vad_threshold, crate::audio::MIN_VAD_THRESHOLD..=crate::audio::MAX_VAD_THRESHOLD)
+                            .text("Threshold"))
+                            .on_hover_text("Denoise gain threshold: how confident the VAD must be that a frame is speech before it passes at full volume. Higher values treat more sound as noise.")
+                            .changed()
+                        {
+                            self.update_vad_threshold(self.config.vad_threshold);
+                        }
+                        
+                        ui.label("Detection Deadband:");
+                        if ui.add(Slider::new(&mut self.config.vad_deadband, 0.0..=crate::audio::gain::MAX_VAD_DEADBAND)
+                            .text("Band"))
+                            .on_hover_text("Keep the previous speech/noise decision while the AI's confidence is this close to the threshold. Stops suppression from flapping in very noisy rooms. Takes effect the next time noise cancellation starts.")
+                            .changed()
+                        {
+                            self.config_changed = true;
+                        }
+                        
+                        ui.label("Suppression Strength:");
+                        let mut suppression_percent = (1.0 - self.config.noise_gain) * 100.0;
+                        if ui.add(Slider::new(&mut suppression_percent, 0.0..=100.0)
+                            .text("Strength")
+                            .suffix("%"))
+                            .on_hover_text("How much background noise is removed between words")
+                            .changed()
+                        {
+                            self.update_noise_gain(1.0 - suppression_percent / 100.0);
+                        }
+                        
+                        ui.label("Room Tone Floor:");
+                        let mut floor_percent = self.config.gain_floor * 100.0;
+                        if ui.add(Slider::new(&mut floor_percent, 0.0..=crate::audio::gain::MAX_GAIN_FLOOR * 100.0)
+                            .text("Keep")
+                            .suffix("%"))
+                            .on_hover_text("Always keep this much of the background so listeners don't hear dead silence between words. 0% allows full gating.")
+                            .changed()
+                        {
+                            self.update_gain_floor(floor_percent / 100.0);
+                        }
+                        
+                        mono_only(ui, stereo, |ui| {
+                            ui.label("Comfort Noise:");
+                            let mut comfort_enabled = self.config.comfort_noise_level.is_some();
+                            let mut comfort_level = self.config.comfort_noise_level.unwrap_or(DEFAULT_COMFORT_NOISE_DB);
+                            let mut comfort_changed = false;
+                            ui.horizontal(|ui| {
+                                comfort_changed |= ui.checkbox(&mut comfort_enabled, "")
+                                    .on_hover_text("Fill the pauses between words with a faint hiss, so listeners don't think the call dropped when the gate silences your background.")
+                                    .changed();
+                                comfort_changed |= ui.add_enabled(comfort_enabled, Slider::new(&mut comfort_level, MIN_COMFORT_NOISE_DB..=MAX_COMFORT_NOISE_DB)
+                                    .text("Level")
+                                    .suffix(" dBFS"))
+                                    .on_hover_text("How loud the comfort noise is while the gate is fully closed. It fades with the gate, so it never plays over your voice.")
+                                    .changed();
+                            });
+                            if comfort_changed {
+                                self.update_comfort_noise_level(comfort_enabled.then_some(comfort_level));
+                            }
+                        
+                            ui.label("Onset Protection:");
+                            if ui.add(Slider::new(&mut self.config.onset_protection_ms, 0..=crate::audio::gain::MAX_ONSET_PROTECTION_MS)
+                                .text("Hold")
+                                .suffix(" ms")
+                                .step_by(10.0))
+                                .on_hover_text("Keep the start of each word from being cut off by holding full volume briefly when speech begins. 0 disables. Takes effect the next time noise cancellation starts.")
+                                .changed()
+                            {
+                                self.config_changed = true;
+                            }
+                        
+                            ui.label("Gate Hold:");
+                            if ui.add(Slider::new(&mut self.config.gate_hold_ms, 0..=crate::audio::gain::MAX_GATE_HOLD_MS)
+                                .text("Hold")
+                                .suffix(" ms")
+                                .step_by(10.0))
+                                .on_hover_text("Keep full volume this long after you stop talking, so short pauses between words don't cut off the next syllable. 0 disables. Takes effect the next time noise cancellation starts.")
+                                .changed()
+                            {
+                                self.config_changed = true;
+                            }
+                        });
+                        
+                        ui.label("Gain Smoothing:");
+                        ui.horizontal(|ui| {
+                            let attack = ui.add(Slider::new(&mut self.config.gain_attack_ms, 0..=crate::audio::gain::MAX_GAIN_SMOOTHING_MS)
+                                .logarithmic(true)
+                                .text("Attack")
+                                .suffix(" ms"))
+                                .on_hover_text("How quickly volume comes back when speech starts. 0 switches instantly. Takes effect the next time noise cancellation starts.");
+                            let release = ui.add(Slider::new(&mut self.config.gain_release_ms, 0..=crate::audio::gain::MAX_GAIN_SMOOTHING_MS)
+                                .logarithmic(true)
+                                .text("Release")
+                                .suffix(" ms"))
+                                .on_hover_text("How gently background is faded down after speech. Longer values avoid pumping; 0 switches instantly and may click. Takes effect the next time noise cancellation starts.");
+                            if attack.changed() || release.changed() {
+                                self.config_changed = true;
+                            }
+                        });
+                        
+                        ui.label("Dry/Wet Mix:");
+                        let mut mix_percent = self.config.mix * 100.0;
+                        if ui.add(Slider::new(&mut mix_percent, 0.0..=100.0)
+                            .text("Processed")
+                            .suffix("%"))
+                            .on_hover_text("Blend of original and processed audio. 100% is fully noise-cancelled, 0% is your raw microphone. Anything below 100% also brings back part of the background noise.")
+                            .changed()
+                        {
+                            self.update_mix(mix_percent / 100.0);
+                        }
+                        
+                        ui.add_space(10.0);
+                        ui.label("Input Pre-Gain:");
+                        let input_gain_response = ui.add(Slider::new(
+                                &mut self.config.input_gain_db,
+                                crate::audio::MIN_INPUT_GAIN_DB..=crate::audio::MAX_INPUT_GAIN_DB)
+                            .text("Boost")
+                            .suffix(" dB")
+                            .step_by(0.5))
+                            .on_hover_text("Boost a quiet microphone before noise cancellation so the denoiser has more signal to work with. Loud frames are scaled back instead of clipping.");
+                        if input_gain_response.changed() {
+                            self.update_input_gain(self.config.input_gain_db);
+                        }
+                        
+                        ui.add_space(10.0);
+                        ui.label("Output Gain:");
+                        let gain_response = ui.add(Slider::new(
+                                &mut self.config.output_gain_db,
+                                crate::audio::MIN_OUTPUT_GAIN_DB..=crate::audio::MAX_OUTPUT_GAIN_DB)
+                            .text("Trim")
+                            .suffix(" dB")
+                            .step_by(0.5));
+                        if gain_response.changed() {
+                            self.update_output_gain(self.config.output_gain_db);
+                        }
+                        
+                        ui.add_space(10.0);
+                        mono_only(ui, stereo, |ui| {
+                            if ui.checkbox(&mut self.config.music_passthrough.enabled, "🎵 Music passthrough")
+                                .on_hover_text("Stop suppressing while music is playing into the microphone and resume when you speak again. Takes effect the next time noise cancellation starts.")
+                                .changed() {
+                                self.config_changed = true;
+                            }
+                            if self.music_passthrough_active {
+                                ui.small(RichText::new("🎵 Music detected - noise suppression paused").color(Color32::LIGHT_BLUE));
+                            }
+                        
+                            ui.add_space(10.0);
+                            ui.label("Idle Bypass:");
+                            if ui.add(Slider::new(&mut self.config.idle_bypass_secs, 0..=crate::audio::idle::MAX_IDLE_BYPASS_SECS)
+                                .text("After")
+                                .suffix(" s of silence"))
+                                .on_hover_text("Pause the AI model after this long without speech to save CPU and battery; it resumes as soon as you speak. Background noise stays gated meanwhile. 0 disables. Takes effect the next time noise cancellation starts.")
+                                .changed()
+                            {
+                                self.config_changed = true;
+                            }
+                            if self.idle_bypass_active {
+                                ui.small(RichText::new("💤 No speech - AI model paused until you speak").color(Color32::LIGHT_BLUE));
+                            }
+                        });
+                        
+                        ui.add_space(10.0);
+                        if ui.checkbox(&mut self.config.ducking.enabled, "🦆 Duck under sidechain")
+                            .on_hover_text("Lower your voice while another signal (e.g. game audio captured from a loopback device) is loud. Takes effect the next time noise cancellation starts.")
+                            .changed() {
+                            self.config_changed = true;
+                        }
+                        if self.config.ducking.enabled {
+                            self.ducking_controls(ui);
+                        }
+                        if self.ducking_reduction_db > 0.5 {
+                            ui.small(RichText::new(format!("🦆 Ducking -{:.1} dB", self.ducking_reduction_db)).color(Color32::LIGHT_BLUE));
+                        }
+                        
+                        ui.add_space(10.0);
+                        mono_only(ui, stereo, |ui| {
+                            ui.label("Instant Replay:");
+                            if ui.add(Slider::new(&mut self.config.instant_replay_secs, 0..=crate::audio::replay::MAX_REPLAY_SECONDS)
+                                .text("Keep")
+                                .suffix(" s"))
+                                .on_hover_text("Keep the last seconds of cleaned-up audio so you can save a clip after something happens (Ctrl+Shift+S). 0 disables. Takes effect the next time noise cancellation starts.")
+                                .changed()
+                            {
+                                self.config_changed = true;
+                            }
+                        });
                     });
                 });
 
                 ui.add_space(20.0);
 
-                let button_text = if self.enabled { "🛑 Disable" } else { "▶ Enable" };
+                let starting = self.pending_auto_start.is_some();
+                let button_text = if self.enabled {
+                    "🛑 Disable"
+                } else if starting {
+                    "⏳ Starting…"
+                } else {
+                    "▶ Enable"
+                };
                 let button_color = if self.enabled {
                     egui::Color32::from_rgb(220, 53, 69)
                 } else {
@@ -656,13 +2403,117 @@ impl eframe::App for KwiteApp {
                     ui.style_mut().visuals.widgets.hovered.bg_fill = button_color;
                     ui.style_mut().visuals.widgets.active.bg_fill = button_color;
 
-                    if ui.add_sized([200.0, 40.0], Button::new(button_text)).clicked() {
+                    let can_toggle = !starting && (self.enabled || !self.audio_devices_missing());
+                    if ui.add_enabled_ui(can_toggle, |ui| ui.add_sized([200.0, 40.0], Button::new(button_text))).inner
+                        .on_disabled_hover_text(if starting { "Noise cancellation is starting automatically" } else { "No audio devices found" })
+                        .clicked() {
                         self.toggle_audio_processing();
                     }
                 });
 
+                if self.enabled {
+                    ui.add_space(5.0);
+                    level_meter(ui, "🎤 In ", self.input_level_db);
+                    level_meter(ui, "🔊 Out", self.output_level_db);
+                    if let Some(rate) = self.input_sample_rate.filter(|&rate| rate != RNNOISE_SAMPLE_RATE) {
+                        ui.small(RichText::new(format!("ℹ Input at {:.1} kHz - resampled to 48 kHz", rate as f32 / 1000.0)).color(Color32::GRAY));
+                    }
+                }
+
+                // Headline benefit across every run since the app started
+                if let Some(percent) = self.session_performance.including(self.ai_performance.as_ref()).measured_noise_reduction_percent() {
+                    ui.label(RichText::new(format!("{:.0}% average noise reduction this session", percent)).strong())
+                        .on_hover_text("Measured from input vs. output energy during frames classified as background noise");
+                }
+
+                if !self.enabled {
+                    ui.add_space(5.0);
+                    let remaining = self.test_playback.as_ref()
+                        .map(|(_, ends_at)| ends_at.saturating_duration_since(std::time::Instant::now()));
+                    match remaining {
+                        Some(remaining) => {
+                            if ui.button(format!("⏹ Stop Test ({}s)", remaining.as_secs() + 1)).clicked() {
+                                self.stop_test_playback();
+                            }
+                        }
+                        None => {
+                            if ui.add_enabled(!self.audio_devices_missing(), Button::new(format!("🎧 Test ({}s)", TEST_PLAYBACK_SECONDS)))
+                                .on_hover_text("Hear yourself after noise cancellation on your speakers for a few seconds. Use headphones to avoid feedback.")
+                                .clicked() {
+                                self.start_test_playback();
+                            }
+                        }
+                    }
+                }
+
+                if self.enabled {
+                    ui.add_space(5.0);
+                    let label = if self.ab_bypass {
+                        RichText::new("🔀 Bypassed - hearing raw audio").color(Color32::from_rgb(255, 165, 0))
+                    } else {
+                        RichText::new("🔀 A/B: Processed")
+                    };
+                    if ui.selectable_label(self.ab_bypass, label)
+                        .on_hover_text("Flip between cleaned-up and raw audio without stopping noise cancellation, to hear the difference (Ctrl+Shift+B)")
+                        .clicked() {
+                        self.ab_bypass = !self.ab_bypass;
+                    }
+                }
+
+                if self.enabled && self.config.instant_replay_secs > 0 && !self.config.stereo_processing {
+                    ui.add_space(5.0);
+                    if ui.button(format!("💾 Save Last {}s", self.config.instant_replay_secs))
+                        .on_hover_text("Write the most recent processed audio to a WAV file (Ctrl+Shift+S)")
+                        .clicked() {
+                        self.save_instant_replay();
+                    }
+                    if let Some(ref status) = self.replay_status {
+                        ui.small(RichText::new(status).color(Color32::GRAY));
+                    }
+                }
+
+                if self.enabled {
+                    ui.add_space(5.0);
+                    let elapsed = self.audio_manager.lock().ok()
+                        .and_then(|manager| manager.as_ref().and_then(|audio_mgr| audio_mgr.recording_elapsed()));
+                    let clicked = match elapsed {
+                        Some(elapsed) => {
+                            // Tick the elapsed time even when nothing else changes
+                            ui.ctx().request_repaint_after(std::time::Duration::from_millis(500));
+                            let secs = elapsed.as_secs();
+                            ui.button(RichText::new(format!("⏹ Stop Recording ({}:{:02})", secs / 60, secs % 60)).color(Color32::RED))
+                                .on_hover_text("Stop and finish the WAV file")
+                                .clicked()
+                        }
+                        None => ui.button("⏺ Record Output")
+                            .on_hover_text("Record the cleaned-up audio to a WAV file in your Music folder until you press stop")
+                            .clicked(),
+                    };
+                    if clicked {
+                        self.toggle_recording();
+                    }
+                    if let Some(ref status) = self.recording_status {
+                        ui.small(RichText::new(status).color(Color32::GRAY));
+                    }
+                }
+
                 ui.add_space(20.0);
 
+                // Raw input waveform (dev mode) - shows clipping, DC bias and cable noise
+                if self.enabled && self.config.development_mode && !self.input_scope.is_empty() {
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("〰 Input Oscilloscope").strong());
+                            let offset = scope::dc_offset(&self.input_scope);
+                            let color = if offset.abs() > scope::DC_OFFSET_WARNING { Color32::YELLOW } else { Color32::GRAY };
+                            ui.colored_label(color, format!("DC offset: {:+.4}", offset))
+                                .on_hover_text("Average sample value. Anything clearly off zero points to a biased microphone or interface.");
+                        });
+                        scope::oscilloscope(ui, &self.input_scope, 80.0);
+                    });
+                    ui.add_space(10.0);
+                }
+                
                 // AI Performance Metrics Display (when active and in development mode)
                 if self.enabled && self.ai_performance.is_some() && self.config.development_mode {
                     if let Some(ref perf) = self.ai_performance {
@@ -686,6 +2537,7 @@ impl eframe::App for KwiteApp {
                                 ui.add_space(5.0);
                                 
                                 // Real-time AI metrics in columns
+                                ui.small("Current run:");
                                 ui.horizontal(|ui| {
                                     ui.vertical(|ui| {
                                         ui.small("Voice Activity:");
@@ -701,6 +2553,10 @@ impl eframe::App for KwiteApp {
                                         ui.small("Processing Latency:");
                                         ui.label(format!("{:.1}ms", perf.avg_latency_ms));
                                         
+                                        ui.small("End-to-End Latency:")
+                                            .on_hover_text("Capture to playback through Kwite's queues, processing and output buffer, averaged over recent frames");
+                                        ui.label(perf.end_to_end_latency_ms.map_or("—".to_string(), |ms| format!("{:.1}ms (peak {:.1}ms)", ms, perf.peak_end_to_end_latency_ms)));
+                                        
                                         ui.small("Noise Reduction:");
                                         ui.label(format!("{:.1}%", perf.noise_reduction_percent));
                                     });
@@ -714,6 +2570,55 @@ impl eframe::App for KwiteApp {
                                         ui.small("Est. Frame Rate:");
                                         ui.label(format!("{} fps", perf.estimated_fps));
                                     });
+                                    
+                                    ui.separator();
+                                    
+                                    ui.vertical(|ui| {
+                                        let driving = |source: VadSource| if self.config.vad_source == source { " ◀" } else { "" };
+                                        ui.small(format!("RNNoise VAD{}:", driving(VadSource::Denoiser)))
+                                            .on_hover_text("RNNoise's own per-frame speech probability");
+                                        ui.label(perf.denoiser_vad_score.map_or("—".to_string(), |v| format!("{:.0}%", v * 100.0)));
+                                        
+                                        ui.small(format!("Analyzer VAD{}:", driving(VadSource::Analyzer)))
+                                            .on_hover_text("Smoothed voice activity detector from the audio analyzer. ◀ marks the detector driving the gain.");
+                                        ui.label(perf.analyzer_vad_score.map_or("—".to_string(), |v| format!("{:.0}%", v * 100.0)));
+                                    });
+                                    
+                                    ui.separator();
+                                    
+                                    ui.vertical(|ui| {
+                                        ui.small("Processing Active:")
+                                            .on_hover_text("Share of non-silent frames the AI model actually changed. Near 0% means audio is flowing but not being denoised.");
+                                        match perf.processing_active_percent {
+                                            Some(percent) if percent < 5.0 => {
+                                                ui.colored_label(Color32::RED, format!("{:.0}%", percent));
+                                            }
+                                            Some(percent) => {
+                                                ui.label(format!("{:.0}%", percent));
+                                            }
+                                            None => {
+                                                ui.label("—");
+                                            }
+                                        }
+                                    });
+                                });
+                                
+                                ui.add_space(5.0);
+                                ui.label(format!("Detected environment: {}", perf.noise_type.as_str()))
+                                    .on_hover_text("What the audio analyzer currently hears in the background (speech, keyboard, HVAC/fans, music, ...)");
+                                
+                                // Totals across every run since the app started
+                                let session = self.session_performance.including(Some(perf));
+                                ui.add_space(5.0);
+                                ui.horizontal(|ui| {
+                                    ui.small(format!("This session ({} runs):", session.runs));
+                                    ui.small(format!("{} frames", session.frames_processed));
+                                    ui.small(format!("avg {:.1}ms / peak {:.1}ms", session.avg_latency_ms(), session.peak_latency_ms));
+                                    ui.small(format!("NR {:.1}%", session.noise_reduction_percent()));
+                                    if let Some(measured) = session.measured_noise_reduction_percent() {
+                                        ui.small(format!("{:.0}% average noise reduction this session", measured))
+                                            .on_hover_text("Measured from input vs. output energy during frames classified as background noise");
+                                    }
                                 });
                                 
                                 // Show simplified controls for advanced users
@@ -725,10 +2630,101 @@ impl eframe::App for KwiteApp {
                                     if let Some(ref ai_performance) = self.ai_performance {
                                         ui.horizontal(|ui| {
                                             ui.label("⚡ Performance:");
-                                            ui.colored_label(Color32::BLUE, format!("Latency: {:.1}ms", ai_performance.avg_latency_ms));
+                                            let latency = ai_performance.end_to_end_latency_ms
+                                                .map_or("Latency: —".to_string(), |ms| format!("Latency: {:.1}ms", ms));
+                                            ui.colored_label(Color32::BLUE, latency)
+                                                .on_hover_text(format!("Measured from capture until playback through Kwite (recent peak {:.1}ms), of which processing takes {:.2}ms per frame. Device and driver buffers add to this.",
+                                                    ai_performance.peak_end_to_end_latency_ms, ai_performance.avg_latency_ms));
                                             ui.colored_label(Color32::BLUE, format!("VAD: {:.1}%", ai_performance.avg_vad_score * 100.0));
                                         });
+                                        
+                                        if let Some(bands) = &ai_performance.spectrum {
+                                            ui.add_space(5.0);
+                                            ui.label(RichText::new("📊 Input Spectrum").strong());
+                                            spectrum_display(ui, bands);
+                                        } else if !cfg!(feature = "ai-enhanced") {
+                                            ui.small(RichText::new("📊 Spectrum display needs the ai-enhanced build").color(Color32::GRAY));
+                                        }
+                                    }
+                                    
+                                    // Denoiser model statistics
+                                    if let Some(ref stats) = self.model_statistics {
+                                        ui.add_space(5.0);
+                                        ui.label(RichText::new("🧩 Model Statistics").strong());
+                                        ui.horizontal(|ui| {
+                                            ui.vertical(|ui| {
+                                                ui.small("Active Model:");
+                                                ui.label(self.active_model_name.as_deref().unwrap_or("-"));
+                                            });
+                                            
+                                            ui.separator();
+                                            
+                                            ui.vertical(|ui| {
+                                                ui.small("Frames (this model):");
+                                                ui.label(format!("{}", stats.total_frames()));
+                                                
+                                                ui.small("Avg VAD Score:");
+                                                ui.label(format!("{:.1}%", stats.avg_vad_score() * 100.0));
+                                            });
+                                            
+                                            ui.separator();
+                                            
+                                            ui.vertical(|ui| {
+                                                ui.small("Avg Model Time:");
+                                                ui.label(format!("{:.3}ms", stats.avg_processing_time().as_secs_f64() * 1000.0));
+                                                
+                                                ui.small("Peak Model Time:");
+                                                ui.label(format!("{:.3}ms", stats.peak_processing_time().as_secs_f64() * 1000.0));
+                                            });
+                                        });
+                                    }
+                                    
+                                    // Output guard: non-zero means an upstream stage produced bad samples
+                                    let sanitized_color = if self.output_sanitized_count > 0 { Color32::YELLOW } else { Color32::GRAY };
+                                    ui.small(RichText::new(format!("🛡 Sanitized output samples: {}", self.output_sanitized_count)).color(sanitized_color))
+                                        .on_hover_text("Samples that were NaN or outside [-1, 1] and were fixed before reaching the output device");
+                                    
+                                    ui.small(RichText::new(format!("🤫 Since last speech: {:.1}s ({} frames)",
+                                            self.frames_since_speech as f32 / 100.0, self.frames_since_speech)).color(Color32::GRAY))
+                                        .on_hover_text("Time since the gate last treated a frame as speech. The idle bypass engages when this reaches its timeout.");
+                                    
+                                    if self.config.frame_deadline_us > 0 {
+                                        let skip_color = if self.deadline_skip_count > 0 { Color32::YELLOW } else { Color32::GRAY };
+                                        ui.small(RichText::new(format!("⏱ Deadline skips: {}", self.deadline_skip_count)).color(skip_color))
+                                            .on_hover_text("Frames passed through without noise suppression because the denoiser was running over the frame deadline");
                                     }
+                                    
+                                    let restarts = watchdog::restart_count();
+                                    let restart_color = if restarts > 0 { Color32::YELLOW } else { Color32::GRAY };
+                                    ui.small(RichText::new(format!("🐕 Watchdog restarts: {}", restarts)).color(restart_color))
+                                        .on_hover_text("Times the audio pipeline was rebuilt after frames stopped flowing (e.g. after sleep/wake)");
+                                    
+                                    // Empirical round-trip latency via an impulse and a loopback path
+                                    ui.horizontal(|ui| {
+                                        let measuring = self.latency_probe_status == ProbeStatus::Measuring;
+                                        if ui.add_enabled(!measuring, Button::new("⏱ Measure Latency"))
+                                            .on_hover_text("Plays a short chirp and listens for it on the input. Needs the output to be audible to the input (speakers near the mic, or a loopback cable).")
+                                            .clicked() {
+                                            if let Ok(manager) = self.audio_manager.try_lock() {
+                                                if let Some(audio_mgr) = manager.as_ref() {
+                                                    audio_mgr.measure_latency();
+                                                    self.latency_probe_status = ProbeStatus::Measuring;
+                                                }
+                                            }
+                                        }
+                                        match self.latency_probe_status {
+                                            ProbeStatus::Idle => {}
+                                            ProbeStatus::Measuring => {
+                                                ui.small("Measuring...");
+                                            }
+                                            ProbeStatus::Measured(ms) => {
+                                                ui.small(RichText::new(format!("Round trip: {:.1}ms", ms)).color(Color32::GREEN));
+                                            }
+                                            ProbeStatus::TimedOut => {
+                                                ui.small(RichText::new("No echo detected - check the loopback path").color(Color32::YELLOW));
+                                            }
+                                        }
+                                    });
                                 } else {
                                     // Show simple status for basic users
                                     ui.horizontal(|ui| {
@@ -774,28 +2770,302 @@ impl eframe::App for KwiteApp {
             });
         });
 
+        if device_selection_changed {
+            self.persist_runtime_state();
+        }
+
+        // Crash recovery dialog
+        if self.pending_restore.is_some() {
+            self.show_restore_session_window(ctx);
+        }
+        
+        // Crash report dialog
+        if self.pending_crash_report.is_some() {
+            self.show_crash_report_window(ctx);
+        }
+        
+        // Manual update check result
+        if self.update_result.is_some() {
+            self.show_update_result_window(ctx);
+        }
+        
+        // Usage statistics reset confirmation
+        if self.confirm_stats_reset {
+            self.show_reset_stats_window(ctx);
+        }
+
         // Virtual Audio Device Setup Dialog
+        // Dialogs receive the already-enumerated device lists instead of querying the backend
         if self.show_virtual_setup_dialog {
-            self.show_virtual_setup_window(ctx);
+            let output_devices = self.output_devices.clone();
+            self.show_virtual_setup_window(ctx, &output_devices);
         }
         
         // macOS Audio Configuration Dialog
         if self.show_macos_audio_dialog {
-            self.show_macos_audio_window(ctx);
+            let input_device_name = device_name(&self.input_devices, &self.selected_input_device);
+            let output_device_name = device_name(&self.output_devices, &self.selected_output_device);
+            self.show_macos_audio_window(ctx, &input_device_name, &output_device_name);
         }
 
         // Configuration Settings Dialog
         if self.show_config_dialog {
             self.show_config_window(ctx);
         }
+        
+        // Microphone Permission Dialog
+        if self.show_mic_permission_dialog {
+            self.show_mic_permission_window(ctx);
+        }
+    }
+}
+
+impl Drop for KwiteApp {
+    /// Mark the session as cleanly shut down
+    /// 
+    /// Skipped while unwinding from a panic so the next launch still detects the crash.
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        if let Err(e) = self.runtime_state(true).save() {
+            log::debug!("Failed to write runtime state on exit: {}", e);
+        }
     }
 }
 
 impl KwiteApp {
+    /// Show crash recovery dialog offering to restore the previous session
+    fn show_restore_session_window(&mut self, ctx: &egui::Context) {
+        let mut restore = false;
+        let mut dismiss = false;
+
+        if let Some(ref state) = self.pending_restore {
+            egui::Window::new("Restore Previous Session")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label("Kwite did not shut down cleanly last time.");
+                    ui.label("Would you like to restore your previous session?");
+                    ui.add_space(5.0);
+
+                    let device_name = |devices: &[AudioDeviceInfo], id: &str| {
+                        devices.iter()
+                            .find(|d| d.id == id)
+                            .map(|d| d.name.clone())
+                            .unwrap_or_else(|| format!("{} (unavailable)", id))
+                    };
+                    ui.small(format!("🎙 Input: {}", device_name(&self.input_devices, &state.input_device_id)));
+                    ui.small(format!("🔊 Output: {}", device_name(&self.output_devices, &state.output_device_id)));
+                    ui.small(format!("Sensitivity: {:.3}", state.sensitivity));
+                    ui.small(format!("Noise cancellation: {}", if state.enabled { "Active" } else { "Inactive" }));
+
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("↩ Restore").clicked() {
+                            restore = true;
+                        }
+                        if ui.button("Start Fresh").clicked() {
+                            dismiss = true;
+                        }
+                    });
+                });
+        }
+
+        if restore {
+            if let Some(state) = self.pending_restore.take() {
+                self.restore_previous_session(state);
+            }
+        } else if dismiss {
+            self.pending_restore = None;
+        }
+    }
+
+    /// Offer to send the crash report left behind by the previous run
+    fn show_crash_report_window(&mut self, ctx: &egui::Context) {
+        let mut send = false;
+        let mut dismiss = false;
+        let can_send = self.config.analytics.enabled && self.config.remote_logging.enabled;
+
+        if let Some(ref report) = self.pending_crash_report {
+            egui::Window::new("Kwite Crashed")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label("Kwite ran into a problem and closed unexpectedly last time.");
+                    ui.small(format!("💥 {}", report.message));
+                    if let Some(ref location) = report.location {
+                        ui.small(RichText::new(location).color(Color32::GRAY));
+                    }
+                    ui.add_space(5.0);
+
+                    if can_send {
+                        ui.label("Sending the crash report helps us fix it.");
+                    } else {
+                        ui.small(RichText::new("ℹ Enable analytics in Settings to send crash reports").color(Color32::GRAY));
+                    }
+
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(can_send, Button::new("📤 Send Report")).clicked() {
+                            send = true;
+                        }
+                        if ui.button("Dismiss").clicked() {
+                            dismiss = true;
+                        }
+                    });
+                });
+        }
+
+        if send {
+            if let Some(report) = self.pending_crash_report.take() {
+                report.send();
+                log::info!("Crash report sent");
+            }
+            CrashReport::discard();
+        } else if dismiss {
+            self.pending_crash_report = None;
+            CrashReport::discard();
+        }
+    }
+
+    /// Show the outcome of a manual update check
+    fn show_update_result_window(&mut self, ctx: &egui::Context) {
+        let mut close = false;
+
+        if let Some(ref result) = self.update_result {
+            egui::Window::new("Software Update")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    match result {
+                        UpdateCheckResult::NoUpdate => {
+                            ui.label(format!("✅ Kwite {} is up to date.", env!("CARGO_PKG_VERSION")));
+                        }
+                        UpdateCheckResult::UpdateAvailable(info) => {
+                            ui.label(RichText::new(format!("🆕 Kwite {} is available", info.version)).strong());
+                            ui.small(format!("You have {} · released {}", env!("CARGO_PKG_VERSION"), info.release_date));
+                            if info.is_critical {
+                                ui.colored_label(Color32::from_rgb(255, 100, 100), "⚠ This is a critical security update");
+                            }
+                            if !info.release_notes.is_empty() {
+                                ui.add_space(5.0);
+                                ui.label("Release notes:");
+                                egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                                    ui.label(&info.release_notes);
+                                });
+                            }
+                            ui.add_space(5.0);
+                            if ui.button("⬇ Download").on_hover_text(&info.download_url).clicked() {
+                                if let Err(e) = webbrowser::open(&info.download_url) {
+                                    log::warn!("Failed to open download page: {}", e);
+                                }
+                            }
+                        }
+                        UpdateCheckResult::Error(e) => {
+                            ui.label("❌ Could not check for updates.");
+                            ui.small(RichText::new(e).color(Color32::GRAY));
+                        }
+                    }
+
+                    ui.add_space(10.0);
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+        }
+
+        if close {
+            self.update_result = None;
+        }
+    }
+
+    /// Ask before wiping the usage statistics
+    fn show_reset_stats_window(&mut self, ctx: &egui::Context) {
+        let mut reset = false;
+        let mut cancel = false;
+
+        egui::Window::new("Reset Usage Statistics?")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("All collected usage statistics will be deleted.");
+                ui.small(RichText::new("Export them first if you want to keep a copy.").color(Color32::GRAY));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("🗑 Reset").clicked() {
+                        reset = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if reset {
+            if let Some(ref mut stats) = self.usage_stats {
+                stats.reset();
+                log::info!("📊 Usage statistics reset");
+                self.stats_status = Some("🗑 Usage statistics reset".to_string());
+            }
+            self.confirm_stats_reset = false;
+        } else if cancel {
+            self.confirm_stats_reset = false;
+        }
+    }
+
+    /// Show an actionable dialog when the OS denied microphone access
+    fn show_mic_permission_window(&mut self, ctx: &egui::Context) {
+        let mut close_dialog = false;
+        let mut retry = false;
+        let mut open = true;
+        
+        egui::Window::new("Microphone Access Denied")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("🔒 Kwite is not allowed to use your microphone.");
+                ui.label(format!("Grant microphone access to Kwite in the {} privacy settings, then try again.", detect_os()));
+                ui.add_space(10.0);
+                
+                ui.horizontal(|ui| {
+                    if microphone_privacy_settings_url().is_some() {
+                        if ui.button("🔐 Open Privacy Settings").clicked() {
+                            if let Err(e) = open_microphone_privacy_settings() {
+                                log::error!("Failed to open privacy settings: {}", e);
+                            }
+                        }
+                    }
+                    
+                    if ui.button("🔄 Try Again").clicked() {
+                        retry = true;
+                    }
+                    
+                    if ui.button("Close").clicked() {
+                        close_dialog = true;
+                    }
+                });
+            });
+        
+        if retry {
+            self.show_mic_permission_dialog = false;
+            if !self.enabled {
+                self.toggle_audio_processing();
+            }
+        } else if close_dialog || !open {
+            self.show_mic_permission_dialog = false;
+        }
+    }
+
     /// Show virtual audio device setup dialog with OS-specific instructions
-    fn show_virtual_setup_window(&mut self, ctx: &egui::Context) {
+    fn show_virtual_setup_window(&mut self, ctx: &egui::Context, output_devices: &[AudioDeviceInfo]) {
         let mut close_dialog = false;
+        let mut refresh_requested = false;
+        let mut create_requested = false;
+        let mut remove_requested = false;
         let mut open = true;
+        let has_virtual = has_virtual_devices(output_devices);
         
         egui::Window::new("Virtual Audio Device Setup")
             .open(&mut open)
@@ -843,13 +3113,47 @@ impl KwiteApp {
                         }
                     });
                 
+                // On Linux Kwite can create the device itself
+                if os == OperatingSystem::Linux {
+                    ui.add_space(10.0);
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(self.virtual_device.is_none(), Button::new("🔌 Create Virtual Device"))
+                            .on_hover_text("Creates a \"Kwite\" output and a \"Kwite-Microphone\" input with pactl. Select Kwite as the output here and Kwite-Microphone in your chat app. Removed again when Kwite exits.")
+                            .clicked() {
+                            create_requested = true;
+                        }
+                        if let Some(ref device) = self.virtual_device {
+                            if device.is_reused() {
+                                ui.small(RichText::new(format!("Already present on {}", device.server)).color(Color32::GRAY));
+                                if ui.small_button("🗑 Remove")
+                                    .on_hover_text("Unloads the existing Kwite sink and microphone modules")
+                                    .clicked() {
+                                    remove_requested = true;
+                                }
+                            } else {
+                                ui.small(RichText::new(format!("Created on {}", device.server)).color(Color32::GRAY));
+                            }
+                        }
+                    });
+                    if let Some(ref status) = self.virtual_device_status {
+                        ui.small(RichText::new(status).color(Color32::GRAY));
+                    }
+                }
+                
+                ui.add_space(10.0);
+                if has_virtual {
+                    ui.colored_label(Color32::GREEN, "✅ Virtual audio device detected");
+                } else {
+                    ui.colored_label(Color32::from_rgb(255, 165, 0), "⚠️ No virtual audio device detected yet - click Refresh after installing");
+                }
+                
                 ui.add_space(10.0);
                 ui.separator();
                 ui.add_space(10.0);
                 
                 ui.horizontal(|ui| {
                     if ui.button("🔄 Refresh Devices").on_hover_text("Check for newly installed devices").clicked() {
-                        close_dialog = true; // Mark for refresh and close
+                        refresh_requested = true;
                     }
                     
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -860,18 +3164,49 @@ impl KwiteApp {
                 });
             });
         
-        // Handle dialog state changes
+        // Handle dialog state changes; closing never re-enumerates devices
+        if create_requested {
+            match crate::virtual_audio::LinuxVirtualDevice::create() {
+                Ok(device) => {
+                    self.virtual_device_status = Some(if device.is_reused() {
+                        "✅ Reusing the existing Kwite virtual device".to_string()
+                    } else {
+                        "✅ Virtual device created".to_string()
+                    });
+                    self.virtual_device = Some(device);
+                    refresh_requested = true;
+                }
+                Err(e) => {
+                    log::warn!("Could not create a virtual device: {}", e);
+                    self.virtual_device_status = Some(format!("⚠️ {}", e));
+                }
+            }
+        }
+        if remove_requested {
+            match crate::virtual_audio::LinuxVirtualDevice::remove_existing() {
+                Ok(removed) => {
+                    self.virtual_device = None;
+                    self.virtual_device_status = Some(format!("🗑 Removed {} virtual device module(s)", removed));
+                    refresh_requested = true;
+                }
+                Err(e) => {
+                    log::warn!("Could not remove the existing virtual device: {}", e);
+                    self.virtual_device_status = Some(format!("⚠️ {}", e));
+                }
+            }
+        }
+        if refresh_requested {
+            self.refresh_devices();
+        }
         if close_dialog || !open {
             self.show_virtual_setup_dialog = false;
-            if close_dialog {
-                self.refresh_devices();
-            }
         }
     }
     
     /// Show macOS audio configuration dialog
-    fn show_macos_audio_window(&mut self, ctx: &egui::Context) {
+    fn show_macos_audio_window(&mut self, ctx: &egui::Context, input_device_name: &str, output_device_name: &str) {
         let mut open = true;
+        let mut refresh_requested = false;
         
         egui::Window::new("🍎 macOS Audio Configuration")
             .open(&mut open)
@@ -888,21 +3223,9 @@ impl KwiteApp {
                         ui.label(egui::RichText::new("📊 Current Configuration Status").heading());
                         ui.add_space(5.0);
                         
-                        // Find current input device info
-                        let input_device_name = self.input_devices.iter()
-                            .find(|d| d.id == self.selected_input_device)
-                            .map(|d| d.name.clone())
-                            .unwrap_or_else(|| "Unknown".to_string());
-                        
-                        // Find current output device info
-                        let output_device_name = self.output_devices.iter()
-                            .find(|d| d.id == self.selected_output_device)
-                            .map(|d| d.name.clone())
-                            .unwrap_or_else(|| "Unknown".to_string());
-                        
                         // Check for configuration issues using generic virtual device detection
-                        let input_virtual_type = crate::virtual_audio::detect_virtual_device_type(&input_device_name);
-                        let output_virtual_type = crate::virtual_audio::detect_virtual_device_type(&output_device_name);
+                        let input_virtual_type = crate::virtual_audio::detect_virtual_device_type(input_device_name);
+                        let output_virtual_type = crate::virtual_audio::detect_virtual_device_type(output_device_name);
                         
                         ui.horizontal(|ui| {
                             ui.label("Input Device:");
@@ -949,18 +3272,22 @@ impl KwiteApp {
                 // Sample Rate Configuration
                 ui.group(|ui| {
                     ui.vertical(|ui| {
-                        ui.label(egui::RichText::new("1. Set Virtual Audio Device to 48kHz Sample Rate").heading());
+                        ui.label(egui::RichText::new("1. Sample Rate (48kHz Recommended)").heading());
                         ui.add_space(5.0);
-                        ui.label("• Open Audio MIDI Setup (/Applications/Utilities/)");
-                        ui.label("• Select your virtual audio device (VB-Cable/BlackHole) in the device list");
-                        ui.label("• Set Format to: 48000.0 Hz, 32-bit Float");
-                        ui.label("• This ensures optimal AI processing frame alignment");
+                        ui.label("• Kwite resamples other rates (such as 44.1kHz) to 48kHz automatically");
+                        ui.label("• For the lowest latency, set your devices to 48000.0 Hz in Audio MIDI Setup");
                         
-                        ui.add_space(5.0);
-                        ui.horizontal(|ui| {
-                            ui.colored_label(Color32::from_rgb(255, 165, 0), "⚠️ Important:");
-                            ui.label("Using 44.1kHz can cause audio quality issues with AI noise cancellation");
-                        });
+                        if let Some(rate) = self.input_sample_rate {
+                            ui.add_space(5.0);
+                            ui.horizontal(|ui| {
+                                ui.colored_label(Color32::GREEN, "✅ Detected:");
+                                if rate == RNNOISE_SAMPLE_RATE {
+                                    ui.label("Input runs at 48kHz - no resampling needed");
+                                } else {
+                                    ui.label(format!("Input runs at {:.1}kHz - resampled to 48kHz", rate as f32 / 1000.0));
+                                }
+                            });
+                        }
                     });
                 });
                 
@@ -1004,7 +3331,6 @@ impl KwiteApp {
                         ui.add_space(5.0);
                         ui.label("If you still hear background noise:");
                         ui.label("• Verify input device is your MICROPHONE, not virtual audio device");
-                        ui.label("• Check that virtual audio device is set to 48kHz (not 44.1kHz)");
                         ui.label("• Verify your microphone input levels aren't too high");
                         ui.label("• Try adjusting Kwite's sensitivity slider");
                         ui.label("• Restart applications after changing audio settings");
@@ -1022,7 +3348,7 @@ impl KwiteApp {
                 
                 ui.horizontal(|ui| {
                     if ui.button("🔄 Refresh Devices").on_hover_text("Check audio device configurations").clicked() {
-                        self.refresh_devices();
+                        refresh_requested = true;
                     }
                     
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -1033,6 +3359,9 @@ impl KwiteApp {
                 });
             });
         
+        if refresh_requested {
+            self.refresh_devices();
+        }
         if !open {
             self.show_macos_audio_dialog = false;
         }
@@ -1063,30 +3392,230 @@ impl KwiteApp {
                                         .on_hover_text("Shows detailed AI metrics, performance data")
                                         .changed() {
                                         self.config_changed = true;
+                                        // Show or hide the test-signal pseudo input devices
+                                        self.refresh_devices();
                                     }
                                 });
                                 
                                 if self.config.development_mode {
+                                    let stereo = self.config.stereo_processing;
                                     ui.small(RichText::new("⚠ Geek mode shows advanced technical information").color(Color32::GRAY));
                                     
-                                    ui.add_space(5.0);
+                                    ui.add_space(5.0);
+                                    mono_only(ui, stereo, |ui| ui.horizontal(|ui| {
+                                        ui.label("🚨 Debug Testing:");
+                                        if ui.checkbox(&mut self.max_test_mode, "Maximum Test Mode")
+                                            .on_hover_text("EXTREME noise cancellation settings for debugging. Reduces background noise to 1% volume - should be VERY noticeable if noise cancellation is working at all.")
+                                            .changed() {
+                                            // Update the global flag so audio processing thread sees the change
+                                            crate::audio::set_max_test_mode(self.max_test_mode);
+                                        }
+                                    }));
+                                    
+                                    mono_only(ui, stereo, |ui| ui.horizontal(|ui| {
+                                        ui.label("⏱ Startup Test:");
+                                        if ui.checkbox(&mut self.config.startup_max_test_enabled, "Max test gains after enabling")
+                                            .on_hover_text("Use Maximum Test Mode gains for the first seconds after noise cancellation starts, then switch to your normal settings. Takes effect the next time noise cancellation starts.")
+                                            .changed() {
+                                            self.config_changed = true;
+                                        }
+                                        if ui.add_enabled(self.config.startup_max_test_enabled,
+                                                Slider::new(&mut self.config.startup_max_test_secs, 1..=crate::audio::MAX_STARTUP_MAX_TEST_SECS)
+                                                    .suffix(" s"))
+                                            .changed() {
+                                            self.config_changed = true;
+                                        }
+                                    }));
+                                    
+                                    mono_only(ui, stereo, |ui| ui.horizontal(|ui| {
+                                        ui.label("🔧 Audio Routing:");
+                                        if ui.checkbox(&mut self.pipeline_verification_mode, "Pipeline Verification Mode")
+                                            .on_hover_text("Adds a subtle 440Hz test tone to verify audio is flowing through the noise cancellation pipeline. If you can't hear the tone, audio routing is incorrect.")
+                                            .changed() {
+                                            // Update the global flag so audio processing thread sees the change
+                                            crate::audio::set_pipeline_verification_mode(self.pipeline_verification_mode);
+                                        }
+                                    }));
+                                    
+                                    ui.small(RichText::new("🧪 Test signals (white, pink, speech-shaped noise) are listed as input devices in Geek Mode").color(Color32::GRAY));
+                                    
+                                    ui.add_space(5.0);
+                                    ui.horizontal(|ui| {
+                                        ui.label("🧠 Custom Model:");
+                                        let mut model_path = self.config.custom_model_path.clone().unwrap_or_default();
+                                        let response = ui.add(egui::TextEdit::singleline(&mut model_path).hint_text("Built-in RNNoise"))
+                                            .on_hover_text("Path to community-trained RNNoise weights. Leave empty to use the built-in model.");
+                                        if response.changed() {
+                                            self.config.custom_model_path = if model_path.is_empty() { None } else { Some(model_path) };
+                                            self.custom_model_status = None;
+                                            self.config_changed = true;
+                                        }
+                                        if response.lost_focus() {
+                                            self.custom_model_status = self.config.custom_model_path.as_deref()
+                                                .map(|path| crate::audio::models::load_custom_rnnoise_model(std::path::Path::new(path))
+                                                    .map(|_| ())
+                                                    .map_err(|e| e.to_string()));
+                                        }
+                                    });
+                                    match &self.custom_model_status {
+                                        Some(Ok(())) => {
+                                            ui.small(RichText::new("✅ Model file is valid - restart noise cancellation to use it").color(Color32::GREEN));
+                                        }
+                                        Some(Err(e)) => {
+                                            ui.small(RichText::new(format!("⚠ {} - the built-in model will be used", e)).color(Color32::YELLOW));
+                                        }
+                                        None => {}
+                                    }
+                                    
+                                    ui.add_space(5.0);
+                                    ui.horizontal(|ui| {
+                                        ui.label("📌 Thread Affinity:");
+                                        let current = self.config.process_thread_affinity;
+                                        let mut selected = current;
+                                        egui::ComboBox::from_id_salt("thread_affinity")
+                                            .selected_text(current.label())
+                                            .show_ui(ui, |ui| {
+                                                ui.selectable_value(&mut selected, ThreadAffinity::Disabled, ThreadAffinity::Disabled.label());
+                                                ui.selectable_value(&mut selected, ThreadAffinity::PerformanceCore, ThreadAffinity::PerformanceCore.label());
+                                                for index in 0..available_core_count() {
+                                                    let option = ThreadAffinity::Core(index);
+                                                    ui.selectable_value(&mut selected, option, option.label());
+                                                }
+                                            })
+                                            .response
+                                            .on_hover_text("Pin the audio processing thread to a CPU core. Useful on hybrid CPUs where it may land on an efficiency core.");
+                                        if selected != current {
+                                            self.config.process_thread_affinity = selected;
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    ui.small(RichText::new("📌 Takes effect the next time noise cancellation starts").color(Color32::GRAY));
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🗣 Gain VAD:");
+                                        let current = self.config.vad_source;
+                                        let mut selected = current;
+                                        egui::ComboBox::from_id_salt("vad_source")
+                                            .selected_text(current.label())
+                                            .show_ui(ui, |ui| {
+                                                ui.selectable_value(&mut selected, VadSource::Denoiser, VadSource::Denoiser.label());
+                                                ui.selectable_value(&mut selected, VadSource::Analyzer, VadSource::Analyzer.label());
+                                            })
+                                            .response
+                                            .on_hover_text("Which voice detector decides speech vs. noise. RNNoise reacts per frame; the analyzer is smoother but slower. Takes effect the next time noise cancellation starts.");
+                                        if selected != current {
+                                            self.config.vad_source = selected;
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🎤 Device Fallback:");
+                                        let mut strict = self.config.device_fallback == DeviceFallback::Strict;
+                                        if ui.checkbox(&mut strict, "Strict (no fallback)")
+                                            .on_hover_text("When the saved microphone is missing, fail to start instead of trying the system default and then the first available input.")
+                                            .changed() {
+                                            self.config.device_fallback = if strict { DeviceFallback::Strict } else { DeviceFallback::BestEffort };
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🔇 No Devices:");
+                                        if ui.checkbox(&mut self.config.allow_placeholder_devices, "Try the system default anyway")
+                                            .on_hover_text("When no audio devices are listed, still allow starting with the system default. Some audio servers only expose a default device.")
+                                            .changed() {
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🔉 Noise Gain:");
+                                        let mut noise_gain = self.config.noise_gain;
+                                        if ui.add(Slider::new(&mut noise_gain, 0.0..=1.0).fixed_decimals(2))
+                                            .on_hover_text("Gain applied to frames the AI classifies as background noise. 0 removes them entirely, 1 leaves them untouched. Applies immediately.")
+                                            .changed() {
+                                            self.update_noise_gain(noise_gain);
+                                        }
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🗣 Speech Gain:");
+                                        let mut speech_gain = self.config.speech_gain;
+                                        if ui.add(Slider::new(&mut speech_gain, 0.0..=1.0).fixed_decimals(2))
+                                            .on_hover_text("Gain applied to frames the AI classifies as speech. Raise it to keep more of your voice, lower it to narrow the jump between speech and suppressed noise. Applies immediately.")
+                                            .changed() {
+                                            self.update_speech_gain(speech_gain);
+                                        }
+                                    });
+                                    
                                     ui.horizontal(|ui| {
-                                        ui.label("🚨 Debug Testing:");
-                                        if ui.checkbox(&mut self.max_test_mode, "Maximum Test Mode")
-                                            .on_hover_text("EXTREME noise cancellation settings for debugging. Reduces background noise to 1% volume - should be VERY noticeable if noise cancellation is working at all.")
+                                        ui.label("〰 Input Filter:");
+                                        if ui.checkbox(&mut self.config.dc_filter_enabled, "Remove DC offset")
+                                            .on_hover_text("Inaudible 5 Hz high-pass that removes the constant offset some USB microphones add. Takes effect the next time noise cancellation starts.")
                                             .changed() {
-                                            // Update the global flag so audio processing thread sees the change
-                                            crate::audio::set_max_test_mode(self.max_test_mode);
+                                            self.config_changed = true;
                                         }
                                     });
                                     
                                     ui.horizontal(|ui| {
-                                        ui.label("🔧 Audio Routing:");
-                                        if ui.checkbox(&mut self.pipeline_verification_mode, "Pipeline Verification Mode")
-                                            .on_hover_text("Adds a subtle 440Hz test tone to verify audio is flowing through the noise cancellation pipeline. If you can't hear the tone, audio routing is incorrect.")
+                                        ui.label("🎧 Channels:");
+                                        if ui.checkbox(&mut self.config.stereo_processing, "Process stereo")
+                                            .on_hover_text("Keep left and right separate, each with its own denoiser, instead of using only the left channel. Mono-only features (music passthrough, the crossover, idle bypass, the frame deadline, instant replay, the noise profile, onset protection, gate hold, comfort noise, auto sensitivity and the debug test modes) are greyed out while it is on. Takes effect the next time noise cancellation starts.")
                                             .changed() {
-                                            // Update the global flag so audio processing thread sees the change
-                                            crate::audio::set_pipeline_verification_mode(self.pipeline_verification_mode);
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    
+                                    mono_only(ui, stereo, |ui| ui.horizontal(|ui| {
+                                        ui.label("⏱ Frame Deadline:");
+                                        if ui.add(Slider::new(&mut self.config.frame_deadline_us, 0..=crate::audio::deadline::MAX_FRAME_DEADLINE_US)
+                                            .suffix(" µs"))
+                                            .on_hover_text("Pass a frame through unprocessed when recent frames took longer than this to denoise, instead of falling behind. For slow machines that prefer glitch-free audio. 0 disables. Takes effect the next time noise cancellation starts.")
+                                            .changed() {
+                                            self.config_changed = true;
+                                        }
+                                    }));
+                                    
+                                    mono_only(ui, stereo, |ui| ui.horizontal(|ui| {
+                                        ui.label("🎚 Denoise Below:");
+                                        let mut split = self.config.denoiser_crossover_hz > 0;
+                                        if ui.checkbox(&mut split, "")
+                                            .on_hover_text("Only denoise below the crossover and pass higher frequencies through, so sibilance stays crisp. The high band still follows the speech/noise gate. Takes effect the next time noise cancellation starts.")
+                                            .changed() {
+                                            self.config.denoiser_crossover_hz = if split { 8000 } else { 0 };
+                                            self.config_changed = true;
+                                        }
+                                        if split && ui.add(Slider::new(&mut self.config.denoiser_crossover_hz,
+                                                crate::audio::filters::MIN_CROSSOVER_HZ..=crate::audio::filters::MAX_CROSSOVER_HZ)
+                                            .suffix(" Hz")
+                                            .logarithmic(true))
+                                            .changed() {
+                                            self.config_changed = true;
+                                        }
+                                    }));
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🩹 Underruns:");
+                                        let mut conceal = self.config.underrun_fill == UnderrunFill::RepeatLastFrame;
+                                        if ui.checkbox(&mut conceal, "Repeat last frame")
+                                            .on_hover_text("When processing falls behind, fade out a repeat of the last 10 ms instead of playing silence. Makes brief dropouts less audible. Takes effect the next time noise cancellation starts.")
+                                            .changed() {
+                                            self.config.underrun_fill = if conceal { UnderrunFill::RepeatLastFrame } else { UnderrunFill::Silence };
+                                            self.config_changed = true;
+                                        }
+                                    });
+                                    
+                                    ui.horizontal(|ui| {
+                                        ui.label("🩺 Diagnostic Preset:");
+                                        let current = crate::audio::current_diagnostic_preset();
+                                        for preset in DiagnosticPreset::ALL {
+                                            if ui.selectable_label(current == Some(preset), preset.name())
+                                                .on_hover_text("Set Maximum Test Mode and Pipeline Verification together")
+                                                .clicked() {
+                                                crate::audio::apply_diagnostic_preset(preset);
+                                                (self.max_test_mode, self.pipeline_verification_mode) = preset.flags();
+                                            }
                                         }
                                     });
                                     
@@ -1098,8 +3627,35 @@ impl KwiteApp {
                                             crate::audio::log_comprehensive_diagnostics();
                                             log::warn!("📋 Comprehensive diagnostics logged - check the console/logs for detailed analysis");
                                         }
+                                        if ui.add_enabled(self.enabled, Button::new("🔄 Reset Denoiser"))
+                                            .on_hover_text("Clear the AI model's internal state if the output stays muffled, without restarting noise cancellation")
+                                            .clicked() {
+                                            self.reset_denoiser();
+                                        }
+                                        if ui.add_enabled(self.enabled, Button::new("📊 Reset Metrics"))
+                                            .on_hover_text("Zero the performance metrics and counters while audio keeps flowing, for a clean before/after comparison of a settings change")
+                                            .clicked() {
+                                            self.reset_metrics();
+                                        }
                                     });
                                     
+                                    mono_only(ui, stereo, |ui| ui.horizontal(|ui| {
+                                        ui.label("📈 Noise Profile:");
+                                        if ui.add_enabled(self.enabled, Button::new("Export"))
+                                            .on_hover_text("Save the averaged spectrum of your background noise to noise_profile.json in the config folder")
+                                            .clicked() {
+                                            self.export_noise_profile();
+                                        }
+                                        if ui.button("Import")
+                                            .on_hover_text("Load noise_profile.json: noise analysis starts from it, and the noise classifier recognises matching background (e.g. your keyboard) instead of mistaking it for music")
+                                            .clicked() {
+                                            self.import_noise_profile();
+                                        }
+                                    }));
+                                    if let Some(ref status) = self.noise_profile_status {
+                                        ui.small(RichText::new(status).color(Color32::GRAY));
+                                    }
+                                    
                                     if self.max_test_mode {
                                         ui.small(RichText::new("🔥 EXTREME settings active: 1% background noise volume").color(Color32::RED));
                                     }
@@ -1120,37 +3676,423 @@ impl KwiteApp {
                         ui.add_space(10.0);
                     }
                     
+                    // Startup Settings
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("🚀 Startup:");
+                                if ui.checkbox(&mut self.config.auto_start, "Always start noise cancellation")
+                                    .on_hover_text("Start noise cancellation every time Kwite launches")
+                                    .changed() {
+                                    self.config_changed = true;
+                                }
+                            });
+
+                            ui.add_enabled_ui(!self.config.auto_start, |ui| {
+                                if ui.checkbox(&mut self.config.restore_last_enabled_state, "Remember whether it was enabled")
+                                    .on_hover_text("Start noise cancellation only if it was active when Kwite was last closed")
+                                    .changed() {
+                                    self.config_changed = true;
+                                }
+                            });
+
+                            if self.config.auto_start && self.config.restore_last_enabled_state {
+                                ui.small(RichText::new("ℹ \"Always start\" overrides the remembered state").color(Color32::GRAY));
+                            }
+
+                            ui.add_enabled_ui(self.config.auto_start || self.config.restore_last_enabled_state, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label("⏳ Start delay:");
+                                    if ui.add(Slider::new(&mut self.config.auto_start_delay_ms, 0..=crate::config::MAX_AUTO_START_DELAY_MS)
+                                        .suffix(" ms")
+                                        .step_by(100.0))
+                                        .on_hover_text("Wait this long after the window appears before starting automatically. Helps on systems where audio devices become available late after login.")
+                                        .changed() {
+                                        self.config_changed = true;
+                                    }
+                                });
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.label("🔥 Denoiser warm-up:");
+                                if ui.add(Slider::new(&mut self.config.denoiser_warmup_ms, 0..=crate::audio::MAX_DENOISER_WARMUP_MS)
+                                    .suffix(" ms")
+                                    .step_by(10.0))
+                                    .on_hover_text("Keep the output muted for a moment after enabling while the AI model settles. Avoids the odd-sounding first fraction of a second. 0 disables.")
+                                    .changed() {
+                                    self.config_changed = true;
+                                }
+                            });
+
+                            if ui.checkbox(&mut self.config.minimize_to_tray, "Minimize to tray when closing")
+                                .on_hover_text("Closing the window keeps Kwite running in the system tray. Click the tray icon to bring it back.")
+                                .changed() {
+                                self.config_changed = true;
+                                self.apply_tray(ui.ctx());
+                            }
+                            if self.config.minimize_to_tray && self.tray.is_none() {
+                                ui.small(RichText::new("⚠️ No system tray available - closing the window exits Kwite").color(Color32::GRAY));
+                            }
+                        });
+                    });
+
+                    ui.add_space(5.0);
+
+                    // Global toggle hotkey
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("⌨ Toggle Hotkey:");
+                                if self.capturing_hotkey {
+                                    ui.label(RichText::new("Press a key combination… (Esc cancels)").italics());
+                                    let captured = ui.input(|i| i.events.iter().find_map(|event| match event {
+                                        egui::Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+                                        _ => None,
+                                    }));
+                                    match captured {
+                                        Some((egui::Key::Escape, _)) => self.capturing_hotkey = false,
+                                        Some((key, modifiers)) => match hotkey::binding_from_key(modifiers, key) {
+                                            Some(binding) => {
+                                                self.config.toggle_hotkey = Some(binding);
+                                                self.capturing_hotkey = false;
+                                                self.config_changed = true;
+                                                self.apply_toggle_hotkey(ui.ctx());
+                                            }
+                                            None => self.hotkey_status = Some("Hold Ctrl, Alt, Shift or Cmd together with the key".to_string()),
+                                        },
+                                        None => {}
+                                    }
+                                } else {
+                                    ui.monospace(self.config.toggle_hotkey.as_deref().unwrap_or("Unbound"));
+                                    if ui.button("Press to rebind")
+                                        .on_hover_text("Toggle noise cancellation from anywhere, even inside a full-screen game")
+                                        .clicked() {
+                                        self.capturing_hotkey = true;
+                                        self.hotkey_status = None;
+                                    }
+                                    if self.config.toggle_hotkey.is_some() && ui.button("Clear").clicked() {
+                                        self.config.toggle_hotkey = None;
+                                        self.config_changed = true;
+                                        self.apply_toggle_hotkey(ui.ctx());
+                                    }
+                                }
+                            });
+                            if let Some(ref status) = self.hotkey_status {
+                                ui.small(RichText::new(status).color(Color32::GRAY));
+                            }
+                        });
+                    });
+
+                    ui.add_space(5.0);
+
+                    // Background refresh rate
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("🖥 Background refresh:");
+                                if ui.add(Slider::new(&mut self.config.background_fps, MIN_BACKGROUND_FPS..=MAX_BACKGROUND_FPS)
+                                    .suffix(" fps"))
+                                    .on_hover_text("How often the window redraws while it is not focused. Lower values save CPU and battery during long calls.")
+                                    .changed() {
+                                    self.config_changed = true;
+                                }
+                            });
+                            ui.small(RichText::new("ℹ Noise cancellation runs at full speed regardless of this setting").color(Color32::GRAY));
+                        });
+                    });
+
+                    ui.add_space(5.0);
+
+                    // Latency Settings
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("⏱ Latency:");
+                                ComboBox::from_id_salt("latency_preset")
+                                    .selected_text(self.config.latency_preset.name())
+                                    .show_ui(ui, |ui| {
+                                        for preset in LatencyPreset::ALL {
+                                            if ui.selectable_value(&mut self.config.latency_preset, preset, preset.name())
+                                                .on_hover_text(preset.description())
+                                                .changed() {
+                                                self.config_changed = true;
+                                            }
+                                        }
+                                    });
+                            });
+
+                            let latency = LatencySettings::resolve(self.config.latency_preset, &self.config.latency_overrides);
+                            ui.small(RichText::new(format!(
+                                "ℹ {} (buffer: {} frames, jitter: {} frames)",
+                                self.config.latency_preset.description(),
+                                latency.channel_capacity,
+                                latency.jitter_frames
+                            )).color(Color32::GRAY));
+                            ui.horizontal(|ui| {
+                                ui.label("🎚 Sample Rate:");
+                                ComboBox::from_id_salt("target_sample_rate")
+                                    .selected_text(format!("{} kHz", self.config.target_sample_rate / 1000))
+                                    .show_ui(ui, |ui| {
+                                        for rate in SUPPORTED_TARGET_SAMPLE_RATES {
+                                            if ui.selectable_value(&mut self.config.target_sample_rate, rate, format!("{} kHz", rate / 1000))
+                                                .changed() {
+                                                self.config_changed = true;
+                                            }
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text("End-to-end rate for capture and playback. 48 kHz is recommended; 16 kHz suits telephony setups. Noise suppression always runs at 48 kHz internally.");
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("📦 Processing Block:");
+                                if ui.add(Slider::new(&mut self.config.processing_block_frames, 1..=crate::audio::MAX_PROCESSING_BLOCK_FRAMES)
+                                    .custom_formatter(|frames, _| format!("{} ms", frames as usize * 10)))
+                                    .on_hover_text("Audio gathered before it is processed. Larger blocks wake the CPU less often on low-power machines but add latency. Takes effect the next time noise cancellation starts.")
+                                    .changed() {
+                                    self.config_changed = true;
+                                }
+                            });
+                            if self.config.latency_overrides != LatencyOverrides::default() {
+                                ui.small(RichText::new("⚙ Custom overrides from config.toml are active").color(Color32::GRAY));
+                            }
+                            if self.enabled {
+                                ui.small(RichText::new("ℹ Changes apply the next time noise cancellation starts").color(Color32::GRAY));
+                            }
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Recording played in a loop as a virtual microphone
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("📁 Input File Loop:");
+                            let mut wav_path = self.config.test_signal_wav_path.clone().unwrap_or_default();
+                            let response = ui.add(egui::TextEdit::singleline(&mut wav_path).hint_text("/path/to/recording.wav"))
+                                .on_hover_text("16-bit PCM or 32-bit float WAV file listed as a looping input device. Useful for demos and for reproducing a problem with your own noisy recording.");
+                            if response.changed() {
+                                self.config.test_signal_wav_path = if wav_path.is_empty() { None } else { Some(wav_path) };
+                                self.config_changed = true;
+                            }
+                            if response.lost_focus() {
+                                self.refresh_devices();
+                            }
+                        });
+                        ui.small(RichText::new("Select \"📁 File Loop\" as the input device to play it through Kwite").color(Color32::GRAY));
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("📶 Level Meter:");
+                            let mut changed = false;
+                            ComboBox::from_id_salt("meter_ballistics")
+                                .selected_text(self.config.meter_ballistics.label())
+                                .show_ui(ui, |ui| {
+                                    for ballistics in MeterBallistics::ALL {
+                                        changed |= ui.selectable_value(&mut self.config.meter_ballistics, ballistics, ballistics.label()).changed();
+                                    }
+                                })
+                                .response
+                                .on_hover_text("Peak shows every transient, Peak with decay holds peaks briefly, RMS and VU follow perceived loudness");
+                            changed |= ui.add(Slider::new(&mut self.config.meter_integration_ms, MIN_METER_INTEGRATION_MS..=MAX_METER_INTEGRATION_MS)
+                                .logarithmic(true)
+                                .suffix(" ms"))
+                                .on_hover_text("Integration time: how quickly the meter rises and falls (peak decay falls 20 dB per this time)")
+                                .changed();
+                            if changed {
+                                self.update_meter_ballistics();
+                            }
+                        });
+                    });
+                    
+                    ui.add_space(10.0);
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("📈 Metrics Endpoint:");
+                                let mut enabled = self.config.metrics_port.is_some();
+                                if ui.checkbox(&mut enabled, "Serve Prometheus metrics")
+                                    .on_hover_text("Expose frames processed, VAD, latency and noise reduction at http://127.0.0.1:<port>/metrics for Grafana and similar tools")
+                                    .changed() {
+                                    self.config.metrics_port = enabled.then_some(crate::metrics_server::DEFAULT_METRICS_PORT);
+                                    self.config_changed = true;
+                                }
+                                if let Some(port) = self.config.metrics_port.as_mut() {
+                                    if ui.add(egui::DragValue::new(port).range(1..=u16::MAX).prefix("port ")).changed() {
+                                        self.config_changed = true;
+                                    }
+                                }
+                            });
+                            if !cfg!(feature = "metrics-server") {
+                                ui.small(RichText::new("📈 The metrics endpoint needs the metrics-server build").color(Color32::GRAY));
+                            } else if self.config.metrics_port.is_some() {
+                                ui.small(RichText::new("ℹ Listens on localhost only; takes effect the next time Kwite starts").color(Color32::GRAY));
+                            }
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
                     // Privacy & Analytics Settings
                     ui.heading("Privacy & Analytics");
                     ui.add_space(5.0);
                     
                     ui.group(|ui| {
                         ui.vertical(|ui| {
+                            // One dropdown for the whole privacy trade-off
+                            ui.horizontal(|ui| {
+                                ui.label("🔒 Privacy level:");
+                                let current = PrivacyPreset::detect(&self.config);
+                                let mut selected = current;
+                                egui::ComboBox::from_id_salt("privacy_preset")
+                                    .selected_text(current.map_or("Custom", |preset| preset.name()))
+                                    .show_ui(ui, |ui| {
+                                        for preset in PrivacyPreset::ALL {
+                                            ui.selectable_value(&mut selected, Some(preset), preset.name())
+                                                .on_hover_text(preset.description());
+                                        }
+                                    });
+                                if selected != current {
+                                    if let Some(preset) = selected {
+                                        let was_enabled = self.config.remote_logging.enabled;
+                                        preset.apply(&mut self.config);
+                                        self.check_endpoint_before_enabling(was_enabled);
+                                        self.sync_privacy_settings();
+                                    }
+                                }
+                            });
+                            ui.small(RichText::new(match PrivacyPreset::detect(&self.config) {
+                                Some(preset) => format!("ℹ {}", preset.description()),
+                                None => "ℹ Custom settings - see the options below for what is sent".to_string(),
+                            }).color(Color32::GRAY));
+                            ui.add_space(5.0);
+                            
                             // Combined Analytics Option
                             ui.horizontal(|ui| {
                                 ui.label("📊 Help us making it better:");
                                 if ui.checkbox(&mut self.config.analytics.enabled, "Send anonymous crash/performance logs")
                                     .on_hover_text("Sends performance data weekly and crash logs to help improve the application. Includes IP address for analytics.")
                                     .changed() {
+                                    // Remote logging carries the crash logs, so it follows the checkbox
+                                    let was_enabled = self.config.remote_logging.enabled;
+                                    self.config.remote_logging.enabled = self.config.analytics.enabled;
+                                    self.check_endpoint_before_enabling(was_enabled);
+                                    self.sync_privacy_settings();
+                                }
+                            });
+
+                            #[cfg(feature = "remote-logging")]
+                            if self.endpoint_check.is_some() {
+                                ui.horizontal(|ui| {
+                                    ui.spinner();
+                                    ui.small(RichText::new("Checking the analytics endpoint…").color(Color32::GRAY));
+                                });
+                            } else {
+                                match &self.endpoint_health {
+                                    Some(EndpointHealth::Reachable) if self.config.remote_logging.enabled => {
+                                        ui.small(RichText::new("✅ Analytics endpoint reachable").color(Color32::GREEN));
+                                    }
+                                    Some(EndpointHealth::Unreachable(reason)) if !self.config.remote_logging.enabled => {
+                                        ui.small(RichText::new(format!("❌ Analytics endpoint unreachable ({}) - analytics left off", reason))
+                                            .color(Color32::LIGHT_RED));
+                                    }
+                                    _ => {}
+                                }
+                            }
+
+                            if self.config.analytics.enabled {
+                                if ui.checkbox(&mut self.config.analytics.drop_silent_sessions, "Skip sessions with a muted microphone")
+                                    .on_hover_text("Sessions where the microphone never picked up any sound are left out of the usage statistics")
+                                    .changed() {
+                                    if let Some(ref mut stats) = self.usage_stats {
+                                        stats.set_drop_silent_sessions(self.config.analytics.drop_silent_sessions);
+                                    }
                                     self.config_changed = true;
-                                    
-                                    // Update usage stats manager and remote logging
-                                    if self.config.analytics.enabled {
-                                        if self.usage_stats.is_none() {
-                                            let mut stats = UsageStatsManager::new(true);
-                                            stats.start_session();
-                                            self.usage_stats = Some(stats);
-                                        }
-                                        // Also enable remote logging for crash logs
-                                        self.config.remote_logging.enabled = true;
-                                    } else {
-                                        if let Some(ref mut stats) = self.usage_stats {
-                                            stats.set_enabled(false);
-                                        }
-                                        self.config.remote_logging.enabled = false;
+                                }
+                            }
+
+                            if self.config.remote_logging.enabled {
+                                let mut batching_changed = false;
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Batch size:");
+                                    batching_changed |= ui.add(
+                                        egui::DragValue::new(&mut self.config.remote_logging.batch_size)
+                                            .range(MIN_LOG_BATCH_SIZE..=MAX_LOG_BATCH_SIZE)
+                                            .suffix(" entries")
+                                    ).on_hover_text("Number of buffered log entries that triggers a send").changed();
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Sampling:");
+                                    let mut sampling_percent = self.config.remote_logging.sampling_rate * 100.0;
+                                    if ui.add(Slider::new(&mut sampling_percent, 0.0..=100.0).suffix("%"))
+                                        .on_hover_text("Share of non-critical events that are sent. Errors are always sent. Lower this on metered connections.")
+                                        .changed() {
+                                        self.config.remote_logging.sampling_rate = sampling_percent / 100.0;
+                                        batching_changed = true;
+                                    }
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Flush interval:");
+                                    let mut minutes = self.config.remote_logging.flush_interval_seconds / 60;
+                                    if ui.add(
+                                        egui::DragValue::new(&mut minutes)
+                                            .range(MIN_LOG_FLUSH_INTERVAL_SECONDS / 60..=MAX_LOG_FLUSH_INTERVAL_SECONDS / 60)
+                                            .speed(10.0)
+                                            .suffix(" min")
+                                    ).on_hover_text("Maximum time buffered logs wait before being sent").changed() {
+                                        self.config.remote_logging.flush_interval_seconds = minutes * 60;
+                                        batching_changed = true;
                                     }
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Request timeout:");
+                                    batching_changed |= ui.add(
+                                        egui::DragValue::new(&mut self.config.remote_logging.request_timeout_seconds)
+                                            .range(MIN_LOG_REQUEST_TIMEOUT_SECONDS..=MAX_LOG_REQUEST_TIMEOUT_SECONDS)
+                                            .suffix(" s")
+                                    ).on_hover_text("Give up on an upload after this long instead of hanging on an unreachable endpoint").changed();
+                                });
+
+                                ui.horizontal(|ui| {
+                                    ui.label("Health check timeout:");
+                                    batching_changed |= ui.add(
+                                        egui::DragValue::new(&mut self.config.remote_logging.health_check_timeout_seconds)
+                                            .range(MIN_LOG_REQUEST_TIMEOUT_SECONDS..=MAX_LOG_REQUEST_TIMEOUT_SECONDS)
+                                            .suffix(" s")
+                                    ).on_hover_text("How long the reachability check waits when analytics are switched on").changed();
+                                });
+
+                                batching_changed |= ui.checkbox(&mut self.config.remote_logging.compress, "Compress uploads (gzip)")
+                                    .on_hover_text("Send log batches gzip-compressed to save bandwidth. Turn off for collectors that can't decode gzip bodies.")
+                                    .changed();
+
+                                batching_changed |= ui.checkbox(&mut self.config.remote_logging.allow_insecure_http, "Allow plain HTTP endpoints")
+                                    .on_hover_text("Only for self-hosted collectors on a trusted network. Logs are sent unencrypted.")
+                                    .changed();
+                                if let Err(e) = self.config.remote_logging.transport() {
+                                    ui.small(RichText::new(format!("⚠ {} - logs are kept locally", e)).color(Color32::YELLOW));
                                 }
-                            });
+
+                                if batching_changed {
+                                    self.config.remote_logging = self.config.remote_logging.clone().validated();
+                                    update_remote_logger_config(self.config.remote_logging.clone());
+                                    self.config_changed = true;
+                                }
+
+                                ui.small(RichText::new(format!(
+                                    "ℹ {} of {} entries buffered until the next send",
+                                    remote_log_buffer_size(),
+                                    self.config.remote_logging.batch_size
+                                )).color(Color32::GRAY));
+                                // Keep the buffer count live even when processing is stopped
+                                ui.ctx().request_repaint_after(std::time::Duration::from_secs(1));
+                            }
                             
                             // if self.config.analytics.enabled {
                             //     ui.small(RichText::new("ℹ Performance data sent weekly to www.amazon.com/joker").color(Color32::GRAY));
@@ -1161,6 +4103,63 @@ impl KwiteApp {
                     
                     ui.add_space(10.0);
                     
+                    // Usage statistics summary, export and reset
+                    ui.heading("Usage Statistics");
+                    ui.add_space(5.0);
+                    
+                    ui.group(|ui| {
+                        ui.vertical(|ui| {
+                            match self.usage_stats {
+                                Some(ref stats) => {
+                                    egui::ScrollArea::vertical()
+                                        .id_salt("usage_stats_summary")
+                                        .max_height(150.0)
+                                        .show(ui, |ui| {
+                                            ui.label(RichText::new(stats.generate_summary_report()).monospace().small());
+                                        });
+                                    ui.add_space(5.0);
+                                    ui.horizontal(|ui| {
+                                        if ui.button("📤 Export JSON")
+                                            .on_hover_text("Save the statistics to a JSON file in your documents folder")
+                                            .clicked() {
+                                            self.export_usage_stats();
+                                        }
+                                        if ui.button("🗑 Reset Statistics…").clicked() {
+                                            self.confirm_stats_reset = true;
+                                        }
+                                    });
+                                    if let Some(ref status) = self.stats_status {
+                                        ui.small(RichText::new(status).color(Color32::GRAY));
+                                    }
+                                }
+                                None => {
+                                    ui.small(RichText::new("ℹ Usage statistics are only collected while analytics are enabled").color(Color32::GRAY));
+                                }
+                            }
+                        });
+                    });
+                    
+                    ui.add_space(10.0);
+                    
+                    // Support correlation: the same ID tags remote logs, usage stats and crash reports
+                    ui.heading("Support");
+                    ui.add_space(5.0);
+                    
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            let session_id = crate::system_info::session_id();
+                            ui.label("🆔 Session ID:");
+                            ui.monospace(session_id);
+                            if ui.small_button("📋 Copy")
+                                .on_hover_text("Mention this ID when contacting support so we can find the logs from this session")
+                                .clicked() {
+                                ui.ctx().copy_text(session_id.to_string());
+                            }
+                        });
+                    });
+                    
+                    ui.add_space(10.0);
+                    
                     // Auto-Update Settings
                     ui.heading("Updates");
                     ui.add_space(5.0);
@@ -1185,12 +4184,50 @@ impl KwiteApp {
                                 }
                             });
                             
+                            ui.horizontal(|ui| {
+                                ui.label("📦 Release channel:");
+                                let mut channel = self.config.auto_update.channel;
+                                ComboBox::from_id_salt("update_channel")
+                                    .selected_text(channel.name())
+                                    .show_ui(ui, |ui| {
+                                        for option in UpdateChannel::ALL {
+                                            ui.selectable_value(&mut channel, option, option.name());
+                                        }
+                                    });
+                                if channel != self.config.auto_update.channel {
+                                    self.config.auto_update.channel = channel;
+                                    self.config_changed = true;
+                                    if let Some(manager) = self.auto_update_manager.as_mut() {
+                                        manager.update_config(self.config.auto_update.clone());
+                                    }
+                                }
+                            });
+                            if self.config.auto_update.channel == UpdateChannel::Beta {
+                                ui.small(RichText::new("⚠ Beta releases may be unstable").color(Color32::YELLOW));
+                            }
+                            
                             // Manual check for updates button
                             ui.horizontal(|ui| {
-                                if ui.button("🔍 Check for Updates").on_hover_text("Manually check for available updates").clicked() {
+                                let checking = self.update_check.is_some();
+                                let disabled_hover = if UPDATE_CHECK_AVAILABLE {
+                                    "An update check is already running"
+                                } else {
+                                    "This build can't check for updates: it was compiled without network support (the remote-logging feature). Download new versions from the releases page."
+                                };
+                                if ui.add_enabled(UPDATE_CHECK_AVAILABLE && !checking, Button::new("🔍 Check for Updates"))
+                                    .on_hover_text("Manually check for available updates")
+                                    .on_disabled_hover_text(disabled_hover)
+                                    .clicked()
+                                {
                                     log::info!("Manual update check triggered");
-                                    // Note: In a real implementation, this would trigger an async update check
-                                    // For now, we just log that the check was requested
+                                    self.update_result = None;
+                                    self.update_check = Some(spawn_update_check(self.config.auto_update.clone()));
+                                }
+                                if checking {
+                                    ui.spinner();
+                                    ui.small("Checking…");
+                                } else if !UPDATE_CHECK_AVAILABLE {
+                                    ui.small(RichText::new("Not available in this build").color(Color32::GRAY));
                                 }
                             });
                             
@@ -0,0 +1,112 @@
+//! # Global Hotkey Module
+//!
+//! A system-wide shortcut that toggles noise cancellation while another window
+//! (e.g. a full-screen game) has focus. The binding is stored in the config as
+//! a string such as `"ctrl+shift+K"` and is unbound by default, so Kwite never
+//! grabs a key combination some other application relies on.
+//!
+//! Hotkey events arrive on a background thread. They are forwarded to a channel
+//! the GUI drains every frame, and a repaint is requested so the toggle happens
+//! right away even while the window is minimized.
+
+use crate::logger::log;
+use crossbeam_channel::{unbounded, Receiver};
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+/// Registered toggle hotkey and the events it produces
+pub struct ToggleHotkey {
+    manager: GlobalHotKeyManager,
+    registered: Option<HotKey>,
+    events: Receiver<GlobalHotKeyEvent>,
+}
+
+impl ToggleHotkey {
+    /// Connect to the system hotkey service; fails where global hotkeys are
+    /// unavailable (e.g. Wayland sessions without X11)
+    pub fn new(ctx: &egui::Context) -> Result<Self, String> {
+        let manager = GlobalHotKeyManager::new().map_err(|e| e.to_string())?;
+        let (tx, events) = unbounded();
+        let ctx = ctx.clone();
+        GlobalHotKeyEvent::set_event_handler(Some(move |event| {
+            let _ = tx.send(event);
+            ctx.request_repaint();
+        }));
+        Ok(Self { manager, registered: None, events })
+    }
+
+    /// Replace the registered binding; `None` (or an empty string) unbinds it
+    pub fn set_binding(&mut self, binding: Option<&str>) -> Result<(), String> {
+        if let Some(hotkey) = self.registered.take() {
+            if let Err(e) = self.manager.unregister(hotkey) {
+                log::warn!("Failed to unregister hotkey {}: {}", hotkey, e);
+            }
+        }
+
+        let Some(binding) = binding.filter(|binding| !binding.trim().is_empty()) else {
+            return Ok(());
+        };
+        let hotkey: HotKey = binding.parse().map_err(|e| format!("Invalid hotkey {:?}: {}", binding, e))?;
+        self.manager.register(hotkey)
+            .map_err(|e| format!("Could not register {}: {} (another application may be using it)", binding, e))?;
+        self.registered = Some(hotkey);
+        log::info!("⌨ Global toggle hotkey registered: {}", binding);
+        Ok(())
+    }
+
+    /// Whether the hotkey was pressed since the last call
+    pub fn pressed(&self) -> bool {
+        let Some(hotkey) = self.registered else {
+            // Drain events left over from an earlier binding
+            self.events.try_iter().for_each(drop);
+            return false;
+        };
+        self.events.try_iter()
+            .filter(|event| event.id == hotkey.id() && event.state == HotKeyState::Pressed)
+            .count() > 0
+    }
+}
+
+/// Binding string for a key captured in the GUI, or `None` if it can't be a global hotkey
+///
+/// At least one of Ctrl, Alt, Shift or Cmd/Super is required so a bare key
+/// (which would stop working in every other application) is never bound.
+pub fn binding_from_key(modifiers: egui::Modifiers, key: egui::Key) -> Option<String> {
+    let mut binding = String::new();
+    if modifiers.ctrl {
+        binding.push_str("ctrl+");
+    }
+    if modifiers.alt {
+        binding.push_str("alt+");
+    }
+    if modifiers.shift {
+        binding.push_str("shift+");
+    }
+    if modifiers.mac_cmd {
+        binding.push_str("super+");
+    }
+    if binding.is_empty() {
+        return None;
+    }
+    binding.push_str(key.name());
+    binding.parse::<HotKey>().is_ok().then_some(binding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captured_keys_become_parseable_bindings() {
+        let ctrl_shift = egui::Modifiers { ctrl: true, shift: true, ..Default::default() };
+        assert_eq!(binding_from_key(ctrl_shift, egui::Key::K).as_deref(), Some("ctrl+shift+K"));
+        assert_eq!(binding_from_key(egui::Modifiers::ALT, egui::Key::F9).as_deref(), Some("alt+F9"));
+        assert_eq!(
+            "ctrl+shift+K".parse::<HotKey>().unwrap(),
+            HotKey::new(Some(global_hotkey::hotkey::Modifiers::CONTROL | global_hotkey::hotkey::Modifiers::SHIFT), global_hotkey::hotkey::Code::KeyK)
+        );
+
+        // A bare key would be swallowed system-wide
+        assert_eq!(binding_from_key(egui::Modifiers::NONE, egui::Key::K), None);
+    }
+}
@@ -1 +1,4 @@
-pub mod app;
\ No newline at end of file
+pub mod app;
+pub mod scope;
+pub mod hotkey;
+pub mod tray;
\ No newline at end of file
@@ -0,0 +1,53 @@
+//! # Oscilloscope Widget
+//!
+//! Plots the most recent raw input samples as a waveform for dev mode. Unlike
+//! the level meters it shows the shape of the signal, which makes clipping,
+//! DC bias and mechanical noise (cable crackle, hum) easy to spot.
+
+use egui::{Color32, Pos2, Sense, Shape, Stroke, Ui, Vec2};
+
+/// DC offset above which the readout is highlighted (about -40 dBFS)
+pub const DC_OFFSET_WARNING: f32 = 0.01;
+
+/// Mean sample value of the window (the DC component)
+pub fn dc_offset(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    samples.iter().sum::<f32>() / samples.len() as f32
+}
+
+/// Draw `samples` as a waveform spanning the available width
+///
+/// The vertical axis covers full scale (-1.0 to 1.0). A dashed line marks the
+/// DC offset so a biased signal visibly sits off the center line.
+pub fn oscilloscope(ui: &mut Ui, samples: &[f32], height: f32) {
+    let (response, painter) = ui.allocate_painter(Vec2::new(ui.available_width(), height), Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 4.0, Color32::from_gray(20));
+
+    // Clamped so clipped input stays inside the frame
+    let to_y = |value: f32| rect.center().y - value.clamp(-1.0, 1.0) * rect.height() / 2.0;
+    painter.hline(rect.x_range(), rect.center().y, Stroke::new(1.0, Color32::from_gray(60)));
+
+    if samples.len() < 2 {
+        return;
+    }
+
+    let step = rect.width() / (samples.len() - 1) as f32;
+    let points: Vec<Pos2> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &sample)| Pos2::new(rect.left() + i as f32 * step, to_y(sample)))
+        .collect();
+    painter.add(Shape::line(points, Stroke::new(1.0, Color32::LIGHT_GREEN)));
+
+    let offset = dc_offset(samples);
+    let offset_y = to_y(offset);
+    painter.extend(Shape::dashed_line(
+        &[Pos2::new(rect.left(), offset_y), Pos2::new(rect.right(), offset_y)],
+        Stroke::new(1.0, Color32::YELLOW),
+        6.0,
+        4.0,
+    ));
+}
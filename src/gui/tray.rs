@@ -0,0 +1,132 @@
+//! # System Tray Module
+//!
+//! The tray icon used by `minimize_to_tray`: closing the window hides it to
+//! the tray instead of exiting, and the icon's menu shows the window again,
+//! toggles noise cancellation or quits. Clicking the icon restores the window.
+//!
+//! Tray and menu events arrive on platform threads. They are translated into
+//! `TrayCommand`s on a channel the GUI drains every frame, and a repaint is
+//! requested so commands are handled while the window is hidden.
+//!
+//! On Linux the tray needs a running GTK main loop, so the icon is created and
+//! kept on a dedicated thread for the rest of the process. On Windows and macOS
+//! it lives on the GUI thread, whose event loop already drives it.
+
+use crate::logger::log;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+const SHOW_HIDE_ID: &str = "kwite-show-hide";
+const TOGGLE_NC_ID: &str = "kwite-toggle-nc";
+const QUIT_ID: &str = "kwite-quit";
+
+/// Something the user asked for from the tray
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayCommand {
+    /// Show the window (icon clicked)
+    ShowWindow,
+    /// Show the window if hidden, hide it otherwise
+    ToggleWindow,
+    /// Enable or disable noise cancellation
+    ToggleNoiseCancellation,
+    /// Exit the application
+    Quit,
+}
+
+/// Running tray icon and the commands it produces
+pub struct Tray {
+    commands: Receiver<TrayCommand>,
+    /// Keeps the icon alive; on Linux it is owned by the GTK thread instead
+    #[cfg(not(target_os = "linux"))]
+    _icon: TrayIcon,
+}
+
+impl Tray {
+    /// Create the tray icon; fails where no system tray is available
+    pub fn new(ctx: &egui::Context) -> Result<Self, String> {
+        let (tx, commands) = unbounded();
+        forward_events(tx, ctx.clone());
+
+        #[cfg(target_os = "linux")]
+        {
+            let (ready_tx, ready_rx) = crossbeam_channel::bounded(1);
+            std::thread::Builder::new()
+                .name("kwite-tray".to_string())
+                .spawn(move || {
+                    if let Err(e) = gtk::init() {
+                        let _ = ready_tx.send(Err(format!("GTK is unavailable: {}", e)));
+                        return;
+                    }
+                    match build_icon() {
+                        Ok(_icon) => {
+                            let _ = ready_tx.send(Ok(()));
+                            gtk::main();
+                        }
+                        Err(e) => {
+                            let _ = ready_tx.send(Err(e));
+                        }
+                    }
+                })
+                .map_err(|e| e.to_string())?;
+            ready_rx.recv().map_err(|_| "Tray thread exited".to_string())??;
+            Ok(Self { commands })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        Ok(Self { commands, _icon: build_icon()? })
+    }
+
+    /// Commands received since the last call
+    pub fn commands(&self) -> Vec<TrayCommand> {
+        self.commands.try_iter().collect()
+    }
+}
+
+/// Build the icon and its menu on the current thread
+fn build_icon() -> Result<TrayIcon, String> {
+    let image = eframe::icon_data::from_png_bytes(include_bytes!("../../assets/icon.png"))
+        .map_err(|e| format!("Invalid tray icon image: {}", e))?;
+    let icon = tray_icon::Icon::from_rgba(image.rgba, image.width, image.height).map_err(|e| e.to_string())?;
+
+    let menu = Menu::with_items(&[
+        &MenuItem::with_id(SHOW_HIDE_ID, "Show/Hide", true, None),
+        &MenuItem::with_id(TOGGLE_NC_ID, "Enable/Disable Noise Cancellation", true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id(QUIT_ID, "Quit", true, None),
+    ]).map_err(|e| e.to_string())?;
+
+    let tray = TrayIconBuilder::new()
+        .with_icon(icon)
+        .with_tooltip("Kwite — AI Noise Cancellation")
+        .with_menu(Box::new(menu))
+        .with_menu_on_left_click(false)
+        .build()
+        .map_err(|e| e.to_string())?;
+    log::info!("🗔 System tray icon created");
+    Ok(tray)
+}
+
+/// Translate tray and menu events into commands for the GUI
+fn forward_events(tx: Sender<TrayCommand>, ctx: egui::Context) {
+    let send = move |command| {
+        let _ = tx.send(command);
+        ctx.request_repaint();
+    };
+
+    let click = send.clone();
+    TrayIconEvent::set_event_handler(Some(move |event| {
+        if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+            click(TrayCommand::ShowWindow);
+        }
+    }));
+    MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+        let command = match event.id.as_ref() {
+            SHOW_HIDE_ID => TrayCommand::ToggleWindow,
+            TOGGLE_NC_ID => TrayCommand::ToggleNoiseCancellation,
+            QUIT_ID => TrayCommand::Quit,
+            _ => return,
+        };
+        send(command);
+    }));
+}
@@ -0,0 +1,183 @@
+//! # Headless Mode Module
+//!
+//! Runs noise cancellation without the GUI, for servers and bots that route
+//! audio on a machine without a display. Started with `--headless`:
+//!
+//! ```text
+//! kwite --headless [--input <device>] [--output <device>] [--sensitivity <0.01-0.5>]
+//! kwite --list-devices
+//! ```
+//!
+//! Settings come from the same `config.toml` as the GUI; the command line
+//! overrides are applied for this run only and never saved. Devices can be
+//! given by ID or by name (see `--list-devices`). The pipeline runs until
+//! Ctrl+C / SIGINT, then shuts down cleanly.
+
+use crate::audio::devices::{list_input_devices, list_output_devices, AudioDeviceInfo};
+use crate::audio::{AudioManager, AudioSettings};
+use crate::config::KwiteConfig;
+use crate::logger::log;
+use crate::metrics_server;
+use std::sync::{Arc, Mutex};
+
+/// Command line flag that runs without the GUI
+pub const HEADLESS_FLAG: &str = "--headless";
+/// Command line flag that prints the audio devices and exits
+pub const LIST_DEVICES_FLAG: &str = "--list-devices";
+
+/// Options parsed from the command line
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CliOptions {
+    /// Run without the GUI
+    pub headless: bool,
+    /// Print the input and output devices, then exit
+    pub list_devices: bool,
+    /// Input device ID or name overriding the configured one
+    pub input: Option<String>,
+    /// Output device ID or name overriding the configured one
+    pub output: Option<String>,
+    /// Analyzer sensitivity overriding the configured one
+    pub sensitivity: Option<f32>,
+}
+
+/// Parse the headless flags; unrelated arguments (such as `--metrics-port`) are ignored
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> CliOptions {
+    let mut options = CliOptions::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let value = |args: &mut I::IntoIter| inline_value.clone().or_else(|| args.next());
+
+        match flag.as_str() {
+            HEADLESS_FLAG => options.headless = true,
+            LIST_DEVICES_FLAG => options.list_devices = true,
+            "--input" => options.input = value(&mut args),
+            "--output" => options.output = value(&mut args),
+            "--sensitivity" => {
+                let raw = value(&mut args).unwrap_or_default();
+                match raw.parse::<f32>() {
+                    Ok(sensitivity) if sensitivity.is_finite() => options.sensitivity = Some(sensitivity.clamp(crate::audio::MIN_SENSITIVITY, crate::audio::MAX_SENSITIVITY)),
+                    _ => eprintln!("Warning: ignoring invalid --sensitivity value {:?}", raw),
+                }
+            }
+            _ => {}
+        }
+    }
+    options
+}
+
+/// Print the available input and output devices with their IDs
+pub fn print_devices() {
+    let print = |heading: &str, devices: &[AudioDeviceInfo]| {
+        println!("{}:", heading);
+        if devices.is_empty() {
+            println!("  (none)");
+        }
+        for device in devices {
+            println!("  {:<12} {}", device.id, device);
+        }
+    };
+    print("Input devices", &list_input_devices());
+    print("Output devices", &list_output_devices());
+}
+
+/// Match a device given on the command line by ID, then by name (case-insensitive)
+fn find_device(requested: &str, devices: &[AudioDeviceInfo]) -> Option<String> {
+    devices.iter()
+        .find(|device| device.id == requested)
+        .or_else(|| devices.iter().find(|device| device.name.eq_ignore_ascii_case(requested)))
+        .map(|device| device.id.clone())
+}
+
+/// Run noise cancellation until SIGINT; returns the process exit code
+pub fn run(options: &CliOptions, metrics_port: Option<u16>) -> i32 {
+    let mut config = KwiteConfig::load();
+
+    if let Some(input) = &options.input {
+        match find_device(input, &list_input_devices()) {
+            Some(id) => config.input_device_id = id,
+            None => {
+                eprintln!("Unknown input device {:?} - run with {} to see the available devices", input, LIST_DEVICES_FLAG);
+                return 2;
+            }
+        }
+    }
+    if let Some(output) = &options.output {
+        match find_device(output, &list_output_devices()) {
+            Some(id) => config.output_device_id = id,
+            None => {
+                eprintln!("Unknown output device {:?} - run with {} to see the available devices", output, LIST_DEVICES_FLAG);
+                return 2;
+            }
+        }
+    }
+    if let Some(sensitivity) = options.sensitivity {
+        config.sensitivity = sensitivity;
+    }
+
+    let (stop_tx, stop_rx) = crossbeam_channel::bounded(1);
+    if let Err(e) = ctrlc::set_handler(move || {
+        let _ = stop_tx.try_send(());
+    }) {
+        eprintln!("Failed to install the Ctrl+C handler: {}", e);
+        return 1;
+    }
+
+    log::info!("🖥 Starting headless noise cancellation: {} -> {}", config.input_device_id, config.output_device_id);
+    let manager = match AudioManager::new(
+        config.sensitivity,
+        &config.input_device_id,
+        &config.output_device_id,
+        AudioSettings::from_config(&config),
+    ) {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("Failed to start noise cancellation: {}", e);
+            return 1;
+        }
+    };
+    let manager = Arc::new(Mutex::new(Some(manager)));
+
+    if let Some(port) = metrics_port.or(config.metrics_port) {
+        if let Err(e) = metrics_server::spawn(port, manager.clone()) {
+            log::warn!("Could not start the metrics endpoint on port {}: {}", port, e);
+        }
+    }
+
+    println!("Noise cancellation running - press Ctrl+C to stop");
+    let _ = stop_rx.recv();
+
+    println!("Stopping noise cancellation");
+    // Dropping the manager stops and joins the audio threads
+    if let Ok(mut manager) = manager.lock() {
+        manager.take();
+    }
+    log::info!("🖥 Headless noise cancellation stopped");
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_headless_args() {
+        assert_eq!(parse_args(args(&["--metrics-port", "9464"])), CliOptions::default());
+
+        let options = parse_args(args(&["--headless", "--input", "input_1", "--output=Virtual Cable", "--sensitivity", "0.9"]));
+        assert!(options.headless && !options.list_devices);
+        assert_eq!(options.input.as_deref(), Some("input_1"));
+        assert_eq!(options.output.as_deref(), Some("Virtual Cable"));
+        assert_eq!(options.sensitivity, Some(0.5));
+
+        assert!(parse_args(args(&["--list-devices"])).list_devices);
+        assert_eq!(parse_args(args(&["--headless", "--sensitivity", "loud"])).sensitivity, None);
+    }
+}
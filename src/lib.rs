@@ -8,4 +8,10 @@ pub mod virtual_audio;
 pub mod system_info;
 pub mod remote_logging;
 pub mod usage_stats;
-pub mod auto_update;
\ No newline at end of file
+pub mod auto_update;
+pub mod runtime_state;
+pub mod metrics_server;
+pub mod crash_report;
+pub mod headless;
+#[cfg(feature = "ffi")]
+pub mod ffi;
\ No newline at end of file
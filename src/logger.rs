@@ -43,6 +43,9 @@ use once_cell::sync::Lazy;
 /// - **Thread information**: Includes thread IDs for multi-threaded debugging
 /// - **Source locations**: Shows file and line numbers for development builds
 /// - **Human-readable format**: Clean output suitable for console viewing
+/// - **Remote forwarding**: With the `remote-logging` feature, WARN+ events (plus
+///   INFO events from the targets in `remote_logging::FORWARDED_INFO_TARGETS`)
+///   are also buffered for the remote logger when it is enabled
 /// 
 /// ## Why Lazy Initialization?
 /// 
@@ -62,15 +65,20 @@ pub fn init_logger() -> Result<(), Box<dyn std::error::Error>> {
             .unwrap_or_else(|_| EnvFilter::new("kwite=debug,warn"));
 
         // Configure the tracing subscriber with multiple layers
-        tracing_subscriber::registry()
+        let registry = tracing_subscriber::registry()
             .with(env_filter) // Apply the environment-based filtering
             .with(fmt::layer()
                 .with_target(false)     // Don't show module paths (cleaner output)
                 .with_thread_ids(true)  // Include thread IDs for debugging multi-threaded code
                 .with_level(true)       // Show log levels (ERROR, WARN, INFO, DEBUG)
                 .with_line_number(true) // Include source line numbers for development
-            )
-            .init(); // Install as the global subscriber
+            );
+
+        // Forward events into the remote log buffer (no-op until remote logging is enabled)
+        #[cfg(feature = "remote-logging")]
+        let registry = registry.with(crate::remote_logging::RemoteLogLayer::new());
+
+        registry.init(); // Install as the global subscriber
     });
     
     // Force initialization of the lazy static
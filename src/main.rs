@@ -56,6 +56,10 @@ mod system_info; // System information collection for analytics
 mod remote_logging; // Remote logging and analytics
 mod usage_stats; // Usage statistics and performance tracking
 mod auto_update; // Automatic software updates
+mod runtime_state; // Session state persistence for crash recovery
+mod metrics_server; // Optional Prometheus-style metrics endpoint (metrics-server feature)
+mod crash_report; // Panic hook and crash reports offered on the next launch
+mod headless; // GUI-less noise cancellation (--headless) and --list-devices
 
 mod constants; // Application-wide constants and configuration values
 
@@ -69,6 +73,9 @@ use eframe::egui::ViewportBuilder;
 /// 2. Configure the native GUI framework with appropriate window settings
 /// 3. Launch the main application event loop
 /// 
+/// `--headless` runs noise cancellation without the GUI until Ctrl+C, and
+/// `--list-devices` prints the audio devices and exits.
+/// 
 /// ## Window Configuration
 /// 
 /// The application window is configured with:
@@ -86,6 +93,22 @@ fn main() -> eframe::Result<()> {
     // This ensures we can capture and debug any startup issues
     logger::init_logger().expect("Failed to initialize logger");
 
+    // Capture panics (with backtraces) as crash reports
+    crash_report::install_panic_hook();
+
+    // Optional localhost metrics endpoint for Prometheus-style scraping
+    let metrics_port = metrics_server::parse_metrics_port(std::env::args().skip(1));
+
+    // Command line modes that don't need a window
+    let cli = headless::parse_args(std::env::args().skip(1));
+    if cli.list_devices {
+        headless::print_devices();
+        return Ok(());
+    }
+    if cli.headless {
+        std::process::exit(headless::run(&cli, metrics_port));
+    }
+
     // Configure the native window and application options
     // These settings provide an optimal user experience for the control interface
     let options = eframe::NativeOptions {
@@ -106,6 +129,6 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "Kwite — AI Noise Cancellation", // Application identifier for the OS
         options,                         // Window and rendering configuration  
-        Box::new(|cc| Ok(Box::new(KwiteApp::new(cc)))), // Application factory function
+        Box::new(|cc| Ok(Box::new(KwiteApp::new(cc, metrics_port)))), // Application factory function
     )
 }
\ No newline at end of file
@@ -0,0 +1,283 @@
+//! # Metrics Endpoint Module
+//!
+//! Optional Prometheus-compatible metrics endpoint for homelab and monitoring
+//! setups. The server is only compiled with the `metrics-server` feature and is
+//! off by default: it starts when `metrics_port` is set in the config or Kwite
+//! is launched with `--metrics-port <port>` (which takes precedence). The
+//! listener is always bound to `127.0.0.1`.
+//!
+//! ## Exposed Metrics
+//!
+//! - `kwite_enabled`: 1 while noise cancellation is running
+//! - `kwite_frames_processed_total`: Frames processed by the current pipeline
+//! - `kwite_dropouts_total`: Output callbacks that ran out of processed audio
+//! - `kwite_processing_latency_ms`: Average and p95 per-frame processing time
+//! - `kwite_vad_score`: Most recent voice activity score
+//! - `kwite_avg_vad_score`: Voice activity score averaged over recent frames
+//! - `kwite_noise_reduction_percent`: Noise reduction measured over the current pipeline's noise frames
+//! - `kwite_noise_type`: 1 for the currently detected environment (`type` label), 0 for the others
+//! - `kwite_watchdog_restarts_total`: Pipeline rebuilds by the stream watchdog since Kwite started
+//!
+//! Numbers are read from the same shared metrics the GUI displays, so a scrape
+//! never touches the audio threads directly.
+
+use crate::audio::analysis::NoiseType;
+use crate::audio::AudioManager;
+#[cfg(feature = "metrics-server")]
+use crate::logger::log;
+use std::fmt::Write as _;
+#[cfg(feature = "metrics-server")]
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+#[cfg(feature = "metrics-server")]
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "metrics-server")]
+use std::thread;
+#[cfg(feature = "metrics-server")]
+use std::time::Duration;
+
+/// Command line flag that enables the endpoint
+pub const METRICS_PORT_FLAG: &str = "--metrics-port";
+
+/// Port suggested when the endpoint is switched on in the settings
+pub const DEFAULT_METRICS_PORT: u16 = 9464;
+
+/// Point-in-time values served on each scrape
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub enabled: bool,
+    pub frames_processed: u64,
+    pub dropouts: u64,
+    pub avg_latency_ms: f32,
+    pub p95_latency_ms: f32,
+    pub end_to_end_latency_ms: f32,
+    pub vad_score: f32,
+    pub avg_vad_score: f32,
+    pub noise_reduction_percent: f32,
+    /// Detected environment, `None` while noise cancellation is off
+    pub noise_type: Option<NoiseType>,
+    pub watchdog_restarts: u64,
+}
+
+impl MetricsSnapshot {
+    /// Read the current values from the manager slot shared with the GUI
+    pub fn collect(manager: &Mutex<Option<AudioManager>>) -> Self {
+        let slot = match manager.lock() {
+            Ok(slot) => slot,
+            Err(_) => return Self::default(),
+        };
+        let audio_mgr = match slot.as_ref() {
+            Some(audio_mgr) => audio_mgr,
+            // Also the case while the watchdog is rebuilding, so keep the restart count
+            None => return Self { watchdog_restarts: crate::audio::watchdog::restart_count(), ..Self::default() },
+        };
+
+        let mut snapshot = Self {
+            enabled: true,
+            dropouts: audio_mgr.output_underrun_count(),
+            ..Self::default()
+        };
+        if let Ok(metrics) = audio_mgr.get_ai_metrics().lock() {
+            snapshot.frames_processed = metrics.total_frames;
+            snapshot.avg_latency_ms = metrics.avg_latency_us as f32 / 1000.0;
+            snapshot.p95_latency_ms = metrics.latency_percentile_us(0.95) as f32 / 1000.0;
+            snapshot.end_to_end_latency_ms = metrics.avg_end_to_end_latency_ms().unwrap_or(0.0);
+            snapshot.vad_score = metrics.vad_scores.back().copied().unwrap_or(0.0);
+            snapshot.avg_vad_score = metrics.avg_vad_score;
+            snapshot.noise_reduction_percent = metrics.measured_noise_reduction_percent().unwrap_or(0.0);
+            snapshot.noise_type = Some(metrics.current_noise_type);
+            snapshot.watchdog_restarts = metrics.watchdog_restarts;
+        }
+        snapshot
+    }
+}
+
+/// Render a snapshot in the Prometheus text exposition format
+pub fn render(snapshot: &MetricsSnapshot) -> String {
+    let mut body = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, String)]| {
+        let _ = writeln!(body, "# HELP {} {}", name, help);
+        let _ = writeln!(body, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = writeln!(body, "{}{} {}", name, labels, value);
+        }
+    };
+
+    metric("kwite_enabled", "gauge", "Whether noise cancellation is running",
+           &[("", (snapshot.enabled as u8).to_string())]);
+    metric("kwite_frames_processed_total", "counter", "Audio frames processed by the current pipeline",
+           &[("", snapshot.frames_processed.to_string())]);
+    metric("kwite_dropouts_total", "counter", "Output callbacks that ran out of processed audio",
+           &[("", snapshot.dropouts.to_string())]);
+    metric("kwite_processing_latency_ms", "gauge", "Per-frame processing time in milliseconds",
+           &[("{stat=\"avg\"}", format!("{:.3}", snapshot.avg_latency_ms)),
+             ("{stat=\"p95\"}", format!("{:.3}", snapshot.p95_latency_ms))]);
+    metric("kwite_end_to_end_latency_ms", "gauge", "Average time from capture until playback through Kwite in milliseconds",
+           &[("", format!("{:.3}", snapshot.end_to_end_latency_ms))]);
+    metric("kwite_vad_score", "gauge", "Most recent voice activity score (0-1)",
+           &[("", format!("{:.3}", snapshot.vad_score))]);
+    metric("kwite_avg_vad_score", "gauge", "Voice activity score averaged over recent frames (0-1)",
+           &[("", format!("{:.3}", snapshot.avg_vad_score))]);
+    metric("kwite_noise_reduction_percent", "gauge", "Noise reduction measured from input vs. output energy over noise frames",
+           &[("", format!("{:.1}", snapshot.noise_reduction_percent))]);
+    if let Some(current) = snapshot.noise_type {
+        let samples: Vec<(String, String)> = NoiseType::ALL.iter()
+            .map(|noise_type| (format!("{{type=\"{}\"}}", noise_type.as_str()), ((*noise_type == current) as u8).to_string()))
+            .collect();
+        let samples: Vec<(&str, String)> = samples.iter().map(|(labels, value)| (labels.as_str(), value.clone())).collect();
+        metric("kwite_noise_type", "gauge", "Environment detected by the noise classifier (1 for the current type)", &samples);
+    }
+    metric("kwite_watchdog_restarts_total", "counter", "Audio pipeline rebuilds by the stream watchdog",
+           &[("", snapshot.watchdog_restarts.to_string())]);
+    body
+}
+
+/// Find `--metrics-port <port>` or `--metrics-port=<port>` in the arguments
+pub fn parse_metrics_port<I: IntoIterator<Item = String>>(args: I) -> Option<u16> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == METRICS_PORT_FLAG {
+            args.next()
+        } else if let Some(value) = arg.strip_prefix("--metrics-port=") {
+            Some(value.to_string())
+        } else {
+            continue;
+        };
+
+        return match value.as_deref().map(str::parse::<u16>) {
+            Some(Ok(port)) if port > 0 => Some(port),
+            _ => {
+                eprintln!("Warning: ignoring invalid {} value {:?}", METRICS_PORT_FLAG, value.unwrap_or_default());
+                None
+            }
+        };
+    }
+    None
+}
+
+/// Start serving metrics on `127.0.0.1:port` in a background thread
+///
+/// Returns the bound address; port 0 picks a free port.
+#[cfg(feature = "metrics-server")]
+pub fn spawn(port: u16, manager: Arc<Mutex<Option<AudioManager>>>) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    let addr = listener.local_addr()?;
+    log::info!("📈 Metrics endpoint listening on http://{}/metrics", addr);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = serve(stream, &manager) {
+                        log::debug!("Metrics request failed: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("⚠️ Metrics endpoint accept failed: {}", e),
+            }
+        }
+    });
+    Ok(addr)
+}
+
+/// Builds without the `metrics-server` feature never open a socket
+#[cfg(not(feature = "metrics-server"))]
+pub fn spawn(_port: u16, _manager: Arc<Mutex<Option<AudioManager>>>) -> std::io::Result<SocketAddr> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "this build of Kwite was compiled without the metrics-server feature",
+    ))
+}
+
+/// Answer a single scrape; every path returns the metrics page
+#[cfg(feature = "metrics-server")]
+fn serve(mut stream: TcpStream, manager: &Mutex<Option<AudioManager>>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let mut request = [0u8; 1024];
+    let _ = stream.read(&mut request)?;
+
+    let body = render(&MetricsSnapshot::collect(manager));
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_metrics_port() {
+        assert_eq!(parse_metrics_port(args(&["kwite"])), None);
+        assert_eq!(parse_metrics_port(args(&["kwite", "--metrics-port", "9464"])), Some(9464));
+        assert_eq!(parse_metrics_port(args(&["kwite", "--metrics-port=9100"])), Some(9100));
+        assert_eq!(parse_metrics_port(args(&["kwite", "--metrics-port", "nope"])), None);
+        assert_eq!(parse_metrics_port(args(&["kwite", "--metrics-port"])), None);
+    }
+
+    #[test]
+    fn test_render_exposition_format() {
+        let body = render(&MetricsSnapshot {
+            enabled: true,
+            frames_processed: 4800,
+            dropouts: 2,
+            avg_latency_ms: 1.5,
+            p95_latency_ms: 3.25,
+            end_to_end_latency_ms: 21.5,
+            vad_score: 0.9,
+            avg_vad_score: 0.75,
+            noise_reduction_percent: 87.5,
+            noise_type: Some(NoiseType::Keyboard),
+            watchdog_restarts: 1,
+        });
+
+        assert!(body.contains("# TYPE kwite_frames_processed_total counter\nkwite_frames_processed_total 4800\n"));
+        assert!(body.contains("kwite_enabled 1\n"));
+        assert!(body.contains("kwite_dropouts_total 2\n"));
+        assert!(body.contains("kwite_processing_latency_ms{stat=\"p95\"} 3.250\n"));
+        assert!(body.contains("kwite_end_to_end_latency_ms 21.500\n"));
+        assert!(body.contains("kwite_vad_score 0.900\n"));
+        assert!(body.contains("kwite_avg_vad_score 0.750\n"));
+        assert!(body.contains("kwite_noise_reduction_percent 87.5\n"));
+        assert!(body.contains("kwite_noise_type{type=\"Keyboard\"} 1\n"));
+        assert!(body.contains("kwite_noise_type{type=\"Music\"} 0\n"));
+        assert!(body.contains("kwite_watchdog_restarts_total 1\n"));
+
+        // No environment is reported while noise cancellation is off
+        assert!(!render(&MetricsSnapshot::default()).contains("kwite_noise_type"));
+    }
+
+    #[cfg(feature = "metrics-server")]
+    #[test]
+    fn test_scrape_endpoint() {
+        let addr = spawn(0, Arc::new(Mutex::new(None))).unwrap();
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        let frames: f64 = body.lines()
+            .find_map(|line| line.strip_prefix("kwite_frames_processed_total "))
+            .expect("frames counter missing")
+            .parse()
+            .unwrap();
+        assert_eq!(frames, 0.0);
+        let enabled: f64 = body.lines()
+            .find_map(|line| line.strip_prefix("kwite_enabled "))
+            .expect("enabled gauge missing")
+            .parse()
+            .unwrap();
+        assert_eq!(enabled, 0.0, "Nothing is running without an audio manager");
+    }
+}
@@ -12,31 +12,87 @@
 //! - **Conditional Logging**: Can be enabled/disabled via configuration
 //! - **System Information**: Includes system context with each batch
 //! - **Privacy Aware**: Hashes sensitive information like MAC addresses
+//! - **Tracing Integration**: With the `remote-logging` feature, regular `log::info!`/
+//!   `warn!`/`error!` events (and their span fields) are forwarded automatically
 //!
 //! ## Configuration
 //!
 //! Remote logging is controlled by configuration flags and is disabled by default
 //! to respect user privacy and minimize external dependencies.
+//!
+//! ## Transport
+//!
+//! The endpoint scheme decides the transport: `https://` is always accepted,
+//! plain `http://` only with `allow_insecure_http`, and an empty endpoint means
+//! nothing is transmitted. Each request is bounded by `request_timeout_seconds`.
+//! With `compress` (the default) batches are sent gzip-compressed with
+//! `Content-Encoding: gzip`, falling back to plain JSON if compression fails.
+//!
+//! ## Health Check
+//!
+//! With the `remote-logging` feature, enabling analytics from the GUI first runs
+//! a one-shot health check (`HEAD`, falling back to `GET`) bounded by
+//! `health_check_timeout_seconds`. Logging is only switched on once the endpoint
+//! answers, so logs are never buffered silently against a dead endpoint.
+//!
+//! ## Retries
+//!
+//! A failed upload puts the batch back in the buffer and delays the next attempt
+//! with exponential backoff plus jitter (see [`retry_backoff`]). After
+//! `LOG_SPOOL_AFTER_FAILURES` failures in a row the backlog is also written to
+//! `pending_logs.jsonl` next to `config.toml` (capped at `MAX_LOG_SPOOL_BYTES`),
+//! and reloaded on the next start so crash logs survive until they are sent.
 
 // Allow dead code for remote logging features that may be used conditionally
 #![allow(dead_code)]
 
-use crate::constants::{PERFORMANCE_ENDPOINT, DEFAULT_LOG_BATCH_SIZE, DEFAULT_LOG_FLUSH_INTERVAL_SECONDS, MAX_PAYLOAD_SIZE_BYTES};
+use crate::constants::{
+    PERFORMANCE_ENDPOINT, DEFAULT_LOG_BATCH_SIZE, DEFAULT_LOG_FLUSH_INTERVAL_SECONDS, MAX_PAYLOAD_SIZE_BYTES,
+    MIN_LOG_BATCH_SIZE, MAX_LOG_BATCH_SIZE, MIN_LOG_FLUSH_INTERVAL_SECONDS, MAX_LOG_FLUSH_INTERVAL_SECONDS,
+    DEFAULT_LOG_REQUEST_TIMEOUT_SECONDS, MIN_LOG_REQUEST_TIMEOUT_SECONDS, MAX_LOG_REQUEST_TIMEOUT_SECONDS,
+    DEFAULT_HEALTH_CHECK_TIMEOUT_SECONDS, LOG_RETRY_BASE_DELAY_SECONDS, LOG_RETRY_MAX_DELAY_SECONDS,
+    LOG_SPOOL_AFTER_FAILURES, MAX_LOG_SPOOL_BYTES,
+};
 use crate::system_info::SystemInfo;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime};
 use tracing::{debug, error, warn};
 
 #[cfg(feature = "remote-logging")]
 use serde_json;
 
+#[cfg(feature = "remote-logging")]
+use tracing::{field::Field, span, Event, Level, Subscriber};
+#[cfg(feature = "remote-logging")]
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
 /// Maximum payload size per request (2MB)
 const MAX_PAYLOAD_SIZE_BYTES_LOCAL: usize = MAX_PAYLOAD_SIZE_BYTES;
 
+/// Maximum size of a single log message (64KB)
+///
+/// Keeps one enormous entry from exceeding the payload limit on its own, which
+/// would otherwise leave `trim_to_size_limit` with nothing it could send.
+const MAX_LOG_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// Marker appended to messages that were cut to `MAX_LOG_MESSAGE_BYTES`
+const TRUNCATION_MARKER: &str = "… [truncated]";
+
+/// Value that replaces a field listed in `redact_fields`
+pub const REDACTED_VALUE: &str = "[redacted]";
+
+/// Log fields holding audio device names, which can include personal names
+pub const DEVICE_NAME_FIELDS: &[&str] = &["device", "device_input", "device_output"];
+
+/// Log fields that identify the machine or network
+pub const MACHINE_ID_FIELDS: &[&str] = &["ip_address", "mac_address_hash"];
+
 /// Configuration for remote logging
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RemoteLoggingConfig {
     /// Whether remote logging is enabled
     pub enabled: bool,
@@ -50,6 +106,36 @@ pub struct RemoteLoggingConfig {
     pub include_system_info: bool,
     /// API key or authentication token (if required)
     pub auth_token: Option<String>,
+    /// Fraction of non-error log entries that are buffered (0.0 - 1.0)
+    /// Errors are always kept regardless of the sampling rate
+    #[serde(default = "default_sampling_rate")]
+    pub sampling_rate: f32,
+    /// Timeout for a single batch upload (in seconds)
+    pub request_timeout_seconds: u64,
+    /// Permit plain `http://` endpoints (e.g. a self-hosted collector on a LAN)
+    pub allow_insecure_http: bool,
+    /// Timeout for the reachability check run when analytics are enabled (in seconds)
+    pub health_check_timeout_seconds: u64,
+    /// Gzip-compress batches; the payload limit then applies to the compressed size
+    pub compress: bool,
+    /// Field names whose values are replaced with `[redacted]` before buffering
+    /// (set by the privacy presets)
+    pub redact_fields: Vec<String>,
+}
+
+/// How log batches are transmitted for the configured endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// No endpoint configured; logs are only buffered locally
+    None,
+    /// Plain HTTP (explicitly allowed)
+    Http,
+    /// TLS-protected HTTPS
+    Https,
+}
+
+fn default_sampling_rate() -> f32 {
+    1.0
 }
 
 impl Default for RemoteLoggingConfig {
@@ -61,6 +147,98 @@ impl Default for RemoteLoggingConfig {
             flush_interval_seconds: DEFAULT_LOG_FLUSH_INTERVAL_SECONDS,
             include_system_info: true,
             auth_token: None,
+            sampling_rate: default_sampling_rate(),
+            request_timeout_seconds: DEFAULT_LOG_REQUEST_TIMEOUT_SECONDS,
+            allow_insecure_http: false,
+            health_check_timeout_seconds: DEFAULT_HEALTH_CHECK_TIMEOUT_SECONDS,
+            compress: true,
+            redact_fields: Vec::new(),
+        }
+    }
+}
+
+impl RemoteLoggingConfig {
+    /// Return a copy with batching limits clamped to sane ranges
+    ///
+    /// A batch size of zero would flush on every entry and a tiny flush interval
+    /// would hammer the endpoint, so user-supplied values are kept within bounds.
+    pub fn validated(mut self) -> Self {
+        self.batch_size = self.batch_size.clamp(MIN_LOG_BATCH_SIZE, MAX_LOG_BATCH_SIZE);
+        self.flush_interval_seconds = self
+            .flush_interval_seconds
+            .clamp(MIN_LOG_FLUSH_INTERVAL_SECONDS, MAX_LOG_FLUSH_INTERVAL_SECONDS);
+        self.sampling_rate = if self.sampling_rate.is_finite() {
+            self.sampling_rate.clamp(0.0, 1.0)
+        } else {
+            default_sampling_rate()
+        };
+        self.request_timeout_seconds = self
+            .request_timeout_seconds
+            .clamp(MIN_LOG_REQUEST_TIMEOUT_SECONDS, MAX_LOG_REQUEST_TIMEOUT_SECONDS);
+        self.health_check_timeout_seconds = self
+            .health_check_timeout_seconds
+            .clamp(MIN_LOG_REQUEST_TIMEOUT_SECONDS, MAX_LOG_REQUEST_TIMEOUT_SECONDS);
+        self
+    }
+
+    /// Determine the transport for the configured endpoint
+    ///
+    /// Returns an error for plain HTTP unless `allow_insecure_http` is set, and for
+    /// anything that isn't an HTTP(S) URL.
+    pub fn transport(&self) -> Result<Transport, String> {
+        let endpoint = self.endpoint.trim();
+        if endpoint.is_empty() {
+            return Ok(Transport::None);
+        }
+
+        let scheme = endpoint.split_once("://").map(|(scheme, _)| scheme.to_ascii_lowercase());
+        match scheme.as_deref() {
+            Some("https") => Ok(Transport::Https),
+            Some("http") if self.allow_insecure_http => Ok(Transport::Http),
+            Some("http") => Err(format!("refusing plain HTTP endpoint {} (enable allow_insecure_http to permit it)", endpoint)),
+            _ => Err(format!("unsupported remote logging endpoint {} (expected an https:// URL)", endpoint)),
+        }
+    }
+
+    /// Decide whether an entry at `level` should be buffered under the sampling rate
+    ///
+    /// Errors are never sampled out so crash reports stay complete.
+    fn should_sample(&self, level: &str) -> bool {
+        if level.eq_ignore_ascii_case("error") || self.sampling_rate >= 1.0 {
+            return true;
+        }
+        rand::random::<f32>() < self.sampling_rate
+    }
+
+    /// Replace the values of fields listed in `redact_fields`
+    fn redact(&self, fields: &mut std::collections::HashMap<String, String>) {
+        for (name, value) in fields.iter_mut() {
+            if self.redact_fields.iter().any(|redacted| redacted.eq_ignore_ascii_case(name)) {
+                *value = REDACTED_VALUE.to_string();
+            }
+        }
+    }
+}
+
+/// Result of the endpoint health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EndpointHealth {
+    /// The endpoint answered; logging can be enabled
+    Reachable,
+    /// The endpoint did not answer or reported a server error
+    Unreachable(String),
+}
+
+impl EndpointHealth {
+    /// Classify the HTTP status returned by the endpoint
+    ///
+    /// Any answer below 500 proves the collector is up, even if it rejects the
+    /// probe itself (e.g. 401 without a token, or 404 for a `HEAD` on a POST-only route).
+    pub fn from_status(status: u16) -> Self {
+        if status >= 500 {
+            EndpointHealth::Unreachable(format!("endpoint returned status {}", status))
+        } else {
+            EndpointHealth::Reachable
         }
     }
 }
@@ -80,6 +258,24 @@ pub struct LogEntry {
     pub fields: std::collections::HashMap<String, String>,
 }
 
+impl LogEntry {
+    /// Cap the message length, appending a marker if anything was removed
+    ///
+    /// Truncation happens on a UTF-8 character boundary so the message stays valid.
+    fn truncate_message(&mut self, max_bytes: usize) {
+        if self.message.len() <= max_bytes {
+            return;
+        }
+
+        let mut cut = max_bytes.saturating_sub(TRUNCATION_MARKER.len());
+        while cut > 0 && !self.message.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        self.message.truncate(cut);
+        self.message.push_str(TRUNCATION_MARKER);
+    }
+}
+
 /// Application information for logging context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppInfo {
@@ -130,31 +326,70 @@ impl LogBatch {
         base_size + log_size_estimate
     }
 
+    /// Serialize to JSON and gzip-compress it
+    #[cfg(feature = "remote-logging")]
+    fn to_gzip_json(&self) -> std::io::Result<Vec<u8>> {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let json = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&json)?;
+        encoder.finish()
+    }
+
+    /// Size of the request body, compressed when `compress` is set and compression works
+    fn encoded_size(&self, compress: bool) -> usize {
+        #[cfg(feature = "remote-logging")]
+        if compress {
+            if let Ok(body) = self.to_gzip_json() {
+                return body.len();
+            }
+        }
+        #[cfg(not(feature = "remote-logging"))]
+        let _ = compress;
+
+        self.estimated_size()
+    }
+
+    /// Copy of this batch holding only the logs from `start` on
+    fn with_logs_from(&self, start: usize) -> Self {
+        Self {
+            app_info: self.app_info.clone(),
+            system_info: self.system_info.clone(),
+            logs: self.logs[start..].to_vec(),
+            batch_timestamp: self.batch_timestamp.clone(),
+            session_id: self.session_id.clone(),
+        }
+    }
+
     /// Create a trimmed version with only the most recent logs that fit within size limit
-    fn trim_to_size_limit(&self, max_size: usize) -> Self {
-        let mut trimmed_logs = self.logs.clone();
-        
-        // Create a test batch to check size
-        let mut test_batch = self.clone();
-        test_batch.logs = trimmed_logs.clone();
-        
+    ///
+    /// With `compress` the limit applies to the gzip-compressed body, so more
+    /// logs fit in one request.
+    fn trim_to_size_limit(&self, max_size: usize, compress: bool) -> Self {
         // If the batch is already within limits, return as-is
-        if test_batch.estimated_size() <= max_size {
-            return test_batch;
-        }
-        
-        // Remove logs from the beginning (oldest first) until we're under the limit
-        while !trimmed_logs.is_empty() && test_batch.estimated_size() > max_size {
-            trimmed_logs.remove(0); // Remove oldest log
-            test_batch.logs = trimmed_logs.clone();
+        if self.encoded_size(compress) <= max_size {
+            return self.clone();
         }
-        
-        if trimmed_logs.len() < self.logs.len() {
-            debug!("Trimmed log batch from {} to {} entries to fit size limit", 
-                   self.logs.len(), trimmed_logs.len());
+
+        // Remove logs from the beginning (oldest first) until we're under the limit.
+        // Binary search for the fewest to remove, since measuring a candidate may
+        // mean compressing the whole batch.
+        let (mut low, mut high) = (1, self.logs.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.with_logs_from(mid).encoded_size(compress) <= max_size {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
         }
-        
-        test_batch
+
+        let trimmed = self.with_logs_from(low);
+        debug!("Trimmed log batch from {} to {} entries to fit size limit",
+               self.logs.len(), trimmed.logs.len());
+        trimmed
     }
 }
 
@@ -168,6 +403,97 @@ impl Default for AppInfo {
     }
 }
 
+/// Delay before retrying after `failures` consecutive failed uploads, without jitter
+///
+/// Doubles from `LOG_RETRY_BASE_DELAY_SECONDS` up to `LOG_RETRY_MAX_DELAY_SECONDS`;
+/// zero failures means no delay.
+pub fn retry_backoff(failures: u32) -> Duration {
+    if failures == 0 {
+        return Duration::ZERO;
+    }
+    let factor = 1u64 << (failures - 1).min(16);
+    Duration::from_secs(LOG_RETRY_BASE_DELAY_SECONDS.saturating_mul(factor).min(LOG_RETRY_MAX_DELAY_SECONDS))
+}
+
+/// Move `delay` to a point in its upper half picked by `random` (0.0 - 1.0)
+///
+/// Spreads out clients that lost the endpoint at the same moment so they don't
+/// all come back at once.
+fn with_jitter(delay: Duration, random: f32) -> Duration {
+    delay.mul_f32(0.5 + 0.5 * random.clamp(0.0, 1.0))
+}
+
+/// Consecutive failed uploads, when the next attempt may start and where the
+/// backlog is persisted once failures pile up
+#[derive(Debug, Default)]
+struct RetryState {
+    failures: u32,
+    retry_at: Option<SystemTime>,
+    /// `None` keeps the backlog in memory only
+    spool_path: Option<PathBuf>,
+}
+
+impl RetryState {
+    /// Whether uploads are still backing off at `now`
+    fn is_waiting(&self, now: SystemTime) -> bool {
+        self.retry_at.is_some_and(|retry_at| now < retry_at)
+    }
+
+    /// Count a failed upload and schedule the next attempt; returns the delay
+    fn record_failure(&mut self, now: SystemTime) -> Duration {
+        self.failures = self.failures.saturating_add(1);
+        let delay = with_jitter(retry_backoff(self.failures), rand::random());
+        self.retry_at = Some(now + delay);
+        delay
+    }
+
+    fn record_success(&mut self) {
+        self.failures = 0;
+        self.retry_at = None;
+    }
+}
+
+/// Write the newest `entries` that fit in `max_bytes` to `path`, one JSON object per line
+///
+/// Returns the number of entries written.
+fn write_spool<'a>(path: &Path, entries: impl DoubleEndedIterator<Item = &'a LogEntry>, max_bytes: usize) -> std::io::Result<usize> {
+    let mut lines = Vec::new();
+    let mut size = 0;
+    for entry in entries.rev() {
+        let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        if size + line.len() + 1 > max_bytes {
+            break;
+        }
+        size += line.len() + 1;
+        lines.push(line);
+    }
+    lines.reverse();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    std::fs::write(path, contents)?;
+    Ok(lines.len())
+}
+
+/// Read entries spooled by an earlier run; unreadable lines are skipped
+fn read_spool(path: &Path) -> Vec<LogEntry> {
+    std::fs::read_to_string(path)
+        .map(|contents| {
+            contents.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Location of the on-disk backlog (next to `config.toml`)
+pub fn default_spool_path() -> Option<PathBuf> {
+    crate::config::KwiteConfig::config_dir().ok().map(|dir| dir.join("pending_logs.jsonl"))
+}
+
 /// Remote logging buffer and transmission manager
 pub struct RemoteLogger {
     config: RemoteLoggingConfig,
@@ -175,6 +501,7 @@ pub struct RemoteLogger {
     system_info: SystemInfo,
     session_id: String,
     last_flush: Arc<Mutex<SystemTime>>,
+    retry: Arc<Mutex<RetryState>>,
     #[cfg(feature = "remote-logging")]
     client: Option<reqwest::Client>,
 }
@@ -182,14 +509,14 @@ pub struct RemoteLogger {
 impl RemoteLogger {
     /// Create a new remote logger with the given configuration
     pub fn new(config: RemoteLoggingConfig) -> Self {
-        let session_id = format!(
-            "kwite_{}_{}", 
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            rand::random::<u32>()
-        );
+        let config = config.validated();
+        if config.enabled {
+            if let Err(e) = config.transport() {
+                warn!("Remote logging will not transmit: {}", e);
+            }
+        }
+        // One ID per application run, also used by usage stats and crash reports
+        let session_id = crate::system_info::session_id().to_string();
 
         Self {
             config: config.clone(),
@@ -197,6 +524,7 @@ impl RemoteLogger {
             system_info: SystemInfo::collect(),
             session_id,
             last_flush: Arc::new(Mutex::new(SystemTime::now())),
+            retry: Arc::new(Mutex::new(RetryState::default())),
             #[cfg(feature = "remote-logging")]
             client: if config.enabled {
                 Some(reqwest::Client::new())
@@ -206,36 +534,88 @@ impl RemoteLogger {
         }
     }
 
-    /// Add a log entry to the buffer
+    /// Persist the backlog to `path` after repeated upload failures
+    ///
+    /// Entries a previous run left there are loaded ahead of new ones; the file
+    /// is removed once an upload succeeds.
+    pub fn with_spool_file(self, path: PathBuf) -> Self {
+        if self.config.enabled {
+            let pending = read_spool(&path);
+            if !pending.is_empty() {
+                debug!("Reloaded {} unsent log entries from {}", pending.len(), path.display());
+                if let Ok(mut buffer) = self.buffer.lock() {
+                    for entry in pending.into_iter().rev() {
+                        buffer.push_front(entry);
+                    }
+                }
+            }
+        }
+        if let Ok(mut retry) = self.retry.lock() {
+            retry.spool_path = Some(path);
+        }
+        self
+    }
+
+    /// Add a log entry to the buffer, flushing if a batch is full or the interval has passed
     pub fn log(&self, level: &str, message: &str, source: Option<&str>, fields: std::collections::HashMap<String, String>) {
-        if !self.config.enabled {
-            return;
+        if self.buffer_entry(level, message, source, fields) {
+            self.flush_if_due();
         }
+    }
 
-        let entry = LogEntry {
+    /// Add a log entry to the buffer without ever starting an upload
+    ///
+    /// Used where the caller may be a real-time thread; the flush timer picks
+    /// the entry up later. Returns whether the entry was kept.
+    fn buffer_entry(&self, level: &str, message: &str, source: Option<&str>, mut fields: std::collections::HashMap<String, String>) -> bool {
+        if !self.config.enabled || !self.config.should_sample(level) {
+            return false;
+        }
+        self.config.redact(&mut fields);
+
+        let mut entry = LogEntry {
             timestamp: chrono::Utc::now().to_rfc3339(),
             level: level.to_string(),
             message: message.to_string(),
             source: source.map(|s| s.to_string()),
             fields,
         };
-
-        if let Ok(mut buffer) = self.buffer.lock() {
-            buffer.push_back(entry);
-            
-            // Check if we need to flush based on buffer size
-            if buffer.len() >= self.config.batch_size {
-                drop(buffer); // Release lock before async operation
-                self.flush_async();
+        entry.truncate_message(MAX_LOG_MESSAGE_BYTES);
+
+        match self.buffer.lock() {
+            Ok(mut buffer) => {
+                buffer.push_back(entry);
+                // Bounded even if nothing flushes for a while
+                while buffer.len() > self.config.batch_size * 5 {
+                    buffer.pop_front();
+                }
+                true
             }
+            Err(_) => false,
+        }
+    }
+
+    /// Start an upload if a full batch is waiting or the flush interval has passed
+    pub fn flush_if_due(&self) {
+        let buffered = self.buffer_size();
+        if buffered == 0 {
+            return;
         }
 
-        // Check if we need to flush based on time
-        if let Ok(last_flush) = self.last_flush.lock() {
-            if last_flush.elapsed().unwrap_or_default().as_secs() >= self.config.flush_interval_seconds {
-                drop(last_flush); // Release lock before async operation
-                self.flush_async();
+        let interval_elapsed = match self.last_flush.lock() {
+            Ok(mut last_flush) => {
+                let elapsed = last_flush.elapsed().unwrap_or_default().as_secs() >= self.config.flush_interval_seconds;
+                if elapsed {
+                    // Don't start another upload on every tick while this one is in flight
+                    *last_flush = SystemTime::now();
+                }
+                elapsed
             }
+            Err(_) => false,
+        };
+
+        if buffered >= self.config.batch_size || interval_elapsed {
+            self.flush_async();
         }
     }
 
@@ -245,6 +625,18 @@ impl RemoteLogger {
             return;
         }
 
+        // Nothing to send to (or a refused scheme): keep a bounded backlog locally
+        if !matches!(self.config.transport(), Ok(Transport::Http | Transport::Https)) {
+            self.trim_backlog();
+            return;
+        }
+
+        // Still backing off after a failed upload
+        if self.retry.lock().map(|retry| retry.is_waiting(SystemTime::now())).unwrap_or(false) {
+            self.trim_backlog();
+            return;
+        }
+
         let buffer = self.buffer.clone();
         let config = self.config.clone();
         let system_info = if self.config.include_system_info {
@@ -254,6 +646,7 @@ impl RemoteLogger {
         };
         let session_id = self.session_id.clone();
         let last_flush = self.last_flush.clone();
+        let retry = self.retry.clone();
 
         #[cfg(feature = "remote-logging")]
         {
@@ -284,6 +677,7 @@ impl RemoteLogger {
                             system_info,
                             session_id,
                             last_flush,
+                            retry,
                         ).await;
                     });
                 });
@@ -305,6 +699,7 @@ impl RemoteLogger {
         system_info: Option<SystemInfo>,
         session_id: String,
         last_flush: Arc<Mutex<SystemTime>>,
+        retry: Arc<Mutex<RetryState>>,
     ) {
         // Extract logs from buffer
         let logs = {
@@ -332,7 +727,7 @@ impl RemoteLogger {
         };
 
         // Check size and trim if necessary to stay within 2MB limit
-        let final_batch = batch.trim_to_size_limit(MAX_PAYLOAD_SIZE_BYTES_LOCAL);
+        let final_batch = batch.trim_to_size_limit(MAX_PAYLOAD_SIZE_BYTES_LOCAL, config.compress);
 
         // Attempt to send the batch
         let mut request = client.post(&config.endpoint);
@@ -341,33 +736,81 @@ impl RemoteLogger {
             request = request.bearer_auth(auth_token);
         }
 
-        match request
-            .json(&final_batch)
-            .timeout(Duration::from_secs(30))
+        let compressed = if config.compress {
+            final_batch.to_gzip_json()
+                .map_err(|e| debug!("Failed to compress log batch, sending it uncompressed: {}", e))
+                .ok()
+        } else {
+            None
+        };
+        request = match compressed {
+            Some(body) => request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                .body(body),
+            None => request.json(&final_batch),
+        };
+
+        let failed = match request
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
             .send()
             .await
         {
+            Ok(response) if response.status().is_success() => {
+                debug!("Successfully sent log batch with {} entries", final_batch.logs.len());
+                false
+            }
+            // Client errors won't go away by resending the same batch
+            Ok(response) if response.status().is_client_error() => {
+                warn!("Remote logging endpoint returned status: {}", response.status());
+                false
+            }
             Ok(response) => {
-                if response.status().is_success() {
-                    debug!("Successfully sent log batch with {} entries", final_batch.logs.len());
-                } else {
-                    warn!("Remote logging endpoint returned status: {}", response.status());
-                }
+                warn!("Remote logging endpoint returned status: {}", response.status());
+                true
             }
             Err(e) => {
-                error!("Failed to send log batch to remote endpoint: {}", e);
-                
-                // Re-add logs to buffer for retry (optional)
-                if let Ok(mut buffer) = buffer.lock() {
-                    for log in final_batch.logs {
-                        buffer.push_front(log);
-                    }
-                    // Limit buffer size to prevent memory issues
-                    while buffer.len() > config.batch_size * 5 {
-                        buffer.pop_back();
+                error!("Failed to send log batch to remote endpoint ({}): {}", send_error_kind(&e), e);
+                true
+            }
+        };
+
+        if failed {
+            // Re-add logs to buffer for retry, oldest first
+            if let Ok(mut buffer) = buffer.lock() {
+                for log in final_batch.logs.into_iter().rev() {
+                    buffer.push_front(log);
+                }
+                // Limit buffer size to prevent memory issues, dropping the oldest
+                // entries like `trim_backlog`
+                while buffer.len() > config.batch_size * 5 {
+                    buffer.pop_front();
+                }
+            }
+
+            let (failures, spool_path) = match retry.lock() {
+                Ok(mut retry) => {
+                    let delay = retry.record_failure(SystemTime::now());
+                    debug!("Retrying remote logging in {:.1}s", delay.as_secs_f32());
+                    (retry.failures, retry.spool_path.clone())
+                }
+                Err(_) => (0, None),
+            };
+
+            // Keep the backlog across restarts once the endpoint looks down for good
+            if failures >= LOG_SPOOL_AFTER_FAILURES {
+                if let (Some(path), Ok(buffer)) = (&spool_path, buffer.lock()) {
+                    match write_spool(path, buffer.iter(), MAX_LOG_SPOOL_BYTES) {
+                        Ok(written) => debug!("Saved {} unsent log entries to {}", written, path.display()),
+                        Err(e) => debug!("Failed to save unsent log entries to {}: {}", path.display(), e),
                     }
                 }
             }
+        } else if let Ok(mut retry) = retry.lock() {
+            retry.record_success();
+            if let Some(path) = retry.spool_path.as_deref().filter(|path| path.exists()) {
+                let _ = std::fs::remove_file(path);
+            }
         }
 
         // Update last flush time
@@ -385,13 +828,26 @@ impl RemoteLogger {
         self.flush_async();
     }
 
+    /// Drop the oldest entries once the buffer holds more than five batches
+    fn trim_backlog(&self) {
+        if let Ok(mut buffer) = self.buffer.lock() {
+            while buffer.len() > self.config.batch_size * 5 {
+                buffer.pop_front();
+            }
+        }
+    }
+
     /// Get current buffer size
     pub fn buffer_size(&self) -> usize {
         self.buffer.lock().map(|b| b.len()).unwrap_or(0)
     }
 
     /// Update configuration
+    ///
+    /// Batching limits are validated before being applied. If the buffer already
+    /// holds a full batch under the new size it is flushed right away.
     pub fn update_config(&mut self, config: RemoteLoggingConfig) {
+        let config = config.validated();
         self.config = config.clone();
         
         #[cfg(feature = "remote-logging")]
@@ -402,19 +858,158 @@ impl RemoteLogger {
                 None
             };
         }
+
+        if self.buffer_size() >= self.config.batch_size {
+            self.flush_async();
+        }
     }
+
+    /// Get the active configuration
+    pub fn config(&self) -> &RemoteLoggingConfig {
+        &self.config
+    }
+}
+
+/// Classify a transport failure so TLS problems stand out from plain connectivity issues
+#[cfg(feature = "remote-logging")]
+fn send_error_kind(error: &reqwest::Error) -> &'static str {
+    if error.is_timeout() {
+        return "timed out";
+    }
+
+    // reqwest wraps TLS failures in connect errors; look for them in the source chain
+    let mut source: Option<&dyn std::error::Error> = Some(error);
+    while let Some(err) = source {
+        let text = err.to_string().to_ascii_lowercase();
+        if text.contains("certificate") || text.contains("tls") || text.contains("ssl") || text.contains("handshake") {
+            return "TLS error";
+        }
+        source = err.source();
+    }
+
+    if error.is_connect() {
+        "connection failed"
+    } else {
+        "request failed"
+    }
+}
+
+/// Check once whether the configured endpoint is reachable
+///
+/// Sends a `HEAD` request (retried as `GET` when the server doesn't allow
+/// `HEAD`) bounded by `health_check_timeout_seconds`. Blocks the calling thread;
+/// use [`spawn_endpoint_health_check`] from the GUI.
+#[cfg(feature = "remote-logging")]
+pub fn check_endpoint_health(config: &RemoteLoggingConfig) -> EndpointHealth {
+    let config = config.clone().validated();
+    match config.transport() {
+        Err(e) => return EndpointHealth::Unreachable(e),
+        Ok(Transport::None) => return EndpointHealth::Unreachable("no endpoint configured".to_string()),
+        Ok(Transport::Http | Transport::Https) => {}
+    }
+
+    let rt = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(rt) => rt,
+        Err(e) => return EndpointHealth::Unreachable(format!("failed to start health check: {}", e)),
+    };
+
+    rt.block_on(async {
+        let client = reqwest::Client::new();
+        let timeout = Duration::from_secs(config.health_check_timeout_seconds);
+        let probe = |method: reqwest::Method| {
+            let mut request = client.request(method, &config.endpoint).timeout(timeout);
+            if let Some(auth_token) = &config.auth_token {
+                request = request.bearer_auth(auth_token);
+            }
+            request.send()
+        };
+
+        let response = match probe(reqwest::Method::HEAD).await {
+            Ok(response) if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED => {
+                probe(reqwest::Method::GET).await
+            }
+            other => other,
+        };
+
+        match response {
+            Ok(response) => EndpointHealth::from_status(response.status().as_u16()),
+            Err(e) => EndpointHealth::Unreachable(format!("{}: {}", send_error_kind(&e), e)),
+        }
+    })
+}
+
+/// Run [`check_endpoint_health`] on a background thread
+///
+/// The result arrives on the returned channel; poll it with `try_recv`.
+#[cfg(feature = "remote-logging")]
+pub fn spawn_endpoint_health_check(config: RemoteLoggingConfig) -> std::sync::mpsc::Receiver<EndpointHealth> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let health = check_endpoint_health(&config);
+        match &health {
+            EndpointHealth::Reachable => debug!("Remote logging endpoint {} is reachable", config.endpoint),
+            EndpointHealth::Unreachable(reason) => warn!("Remote logging endpoint {} is unreachable: {}", config.endpoint, reason),
+        }
+        sender.send(health).ok();
+    });
+    receiver
 }
 
 /// Global remote logger instance
 static REMOTE_LOGGER: once_cell::sync::OnceCell<Arc<Mutex<RemoteLogger>>> = once_cell::sync::OnceCell::new();
 
+/// How often the flush timer checks the global logger for a due upload
+const FLUSH_TIMER_TICK: Duration = Duration::from_secs(1);
+
 /// Initialize the global remote logger
+///
+/// Also starts the flush timer, which is the only place buffered entries from
+/// [`RemoteLogLayer`] get uploaded.
 pub fn init_remote_logger(config: RemoteLoggingConfig) {
-    let logger = RemoteLogger::new(config);
-    REMOTE_LOGGER.set(Arc::new(Mutex::new(logger))).ok();
+    let mut logger = RemoteLogger::new(config);
+    if let Some(path) = default_spool_path() {
+        logger = logger.with_spool_file(path);
+    }
+    if REMOTE_LOGGER.set(Arc::new(Mutex::new(logger))).is_ok() {
+        spawn_flush_timer();
+    }
+}
+
+/// Periodically flush the global logger from its own thread
+fn spawn_flush_timer() {
+    let spawned = std::thread::Builder::new()
+        .name("remote-log-flush".to_string())
+        .spawn(|| loop {
+            std::thread::sleep(FLUSH_TIMER_TICK);
+            if let Some(logger) = REMOTE_LOGGER.get() {
+                if let Ok(logger) = logger.lock() {
+                    logger.flush_if_due();
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to start remote log flush timer: {}", e);
+    }
+}
+
+/// Apply a new configuration to the global remote logger
+///
+/// Initializes the logger if it hasn't been created yet (e.g. analytics were
+/// enabled from the settings dialog after startup).
+pub fn update_remote_logger_config(config: RemoteLoggingConfig) {
+    match REMOTE_LOGGER.get() {
+        Some(logger) => {
+            if let Ok(mut logger) = logger.lock() {
+                logger.update_config(config);
+            }
+        }
+        None => init_remote_logger(config),
+    }
 }
 
 /// Log a message to the remote logging system
+///
+/// Fields named in the config's `redact_fields` are redacted before buffering.
 pub fn log_remote(level: &str, message: &str, source: Option<&str>, fields: std::collections::HashMap<String, String>) {
     if let Some(logger) = REMOTE_LOGGER.get() {
         if let Ok(logger) = logger.lock() {
@@ -423,6 +1018,165 @@ pub fn log_remote(level: &str, message: &str, source: Option<&str>, fields: std:
     }
 }
 
+/// Buffer a log entry without waiting for the logger or starting an upload
+///
+/// For the panic hook: if the panicking thread holds the logger (e.g. the flush
+/// timer), waiting for it would deadlock. Returns whether the entry was buffered;
+/// the flush timer uploads it later.
+pub fn try_log_remote(level: &str, message: &str, source: Option<&str>, fields: std::collections::HashMap<String, String>) -> bool {
+    REMOTE_LOGGER
+        .get()
+        .and_then(|logger| logger.try_lock().ok())
+        .is_some_and(|logger| logger.buffer_entry(level, message, source, fields))
+}
+
+/// Span fields captured by [`RemoteLogLayer`], stored in the span's extensions
+#[cfg(feature = "remote-logging")]
+struct SpanFields(std::collections::HashMap<String, String>);
+
+/// Collects the message and structured fields of a tracing event or span
+#[cfg(feature = "remote-logging")]
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: std::collections::HashMap<String, String>,
+}
+
+#[cfg(feature = "remote-logging")]
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+#[cfg(feature = "remote-logging")]
+impl tracing::field::Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{:?}", value));
+    }
+}
+
+/// Targets whose `INFO` events are forwarded in addition to warnings and errors
+#[cfg(feature = "remote-logging")]
+pub const FORWARDED_INFO_TARGETS: &[&str] = &["kwite::auto_update", "kwite::crash_report"];
+
+/// Targets that are only logged locally because their events are buffered
+/// separately with structured fields (the panic hook's crash event)
+#[cfg(feature = "remote-logging")]
+pub const LOCAL_ONLY_TARGETS: &[&str] = &[crate::crash_report::PANIC_LOG_TARGET];
+
+/// Tracing layer that forwards application events into the remote log buffer
+///
+/// Warnings and errors are buffered together with the fields of every
+/// enclosing span, so existing `warn!`/`error!` calls reach the remote endpoint
+/// without a separate `log_remote` call. `INFO` events are only forwarded for
+/// [`FORWARDED_INFO_TARGETS`]. The privacy preset's sampling rate applies as
+/// for any other entry, and nothing is forwarded unless the remote logger has
+/// been initialized and is enabled.
+///
+/// Events are only buffered here; uploads are left to the flush timer so the
+/// emitting thread (possibly the audio thread) never starts one.
+#[cfg(feature = "remote-logging")]
+#[derive(Default)]
+pub struct RemoteLogLayer {
+    /// Explicit target logger; falls back to the global instance when `None`
+    logger: Option<Arc<Mutex<RemoteLogger>>>,
+}
+
+#[cfg(feature = "remote-logging")]
+impl RemoteLogLayer {
+    /// Create a layer that forwards into the global remote logger
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a layer that forwards into a specific logger instance
+    pub fn with_logger(logger: Arc<Mutex<RemoteLogger>>) -> Self {
+        Self { logger: Some(logger) }
+    }
+}
+
+#[cfg(feature = "remote-logging")]
+impl<S> Layer<S> for RemoteLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut visitor = FieldVisitor::default();
+            attrs.record(&mut visitor);
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+                let mut visitor = FieldVisitor::default();
+                values.record(&mut visitor);
+                fields.extend(visitor.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+
+        // Skip routine events and our own diagnostics (which would feed back
+        // into the buffer every time a send fails)
+        let level = *metadata.level();
+        let allowed = level <= Level::WARN
+            || (level == Level::INFO && FORWARDED_INFO_TARGETS.iter().any(|target| metadata.target().starts_with(target)));
+        if !allowed || metadata.target().starts_with(module_path!()) || LOCAL_ONLY_TARGETS.contains(&metadata.target()) {
+            return;
+        }
+
+        let logger = match self.logger.as_ref().or_else(|| REMOTE_LOGGER.get()) {
+            Some(logger) => logger,
+            None => return,
+        };
+
+        // Never block the emitting thread (which may be the audio thread)
+        let logger = match logger.try_lock() {
+            Ok(logger) => logger,
+            Err(_) => return,
+        };
+
+        if !logger.config.enabled {
+            return;
+        }
+
+        let mut fields = std::collections::HashMap::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(SpanFields(span_fields)) = span.extensions().get::<SpanFields>() {
+                    fields.extend(span_fields.clone());
+                }
+            }
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        fields.extend(visitor.fields);
+
+        logger.buffer_entry(
+            &level.as_str().to_lowercase(),
+            visitor.message.as_deref().unwrap_or_default(),
+            Some(metadata.target()),
+            fields,
+        );
+    }
+}
+
 /// Convenience macro for remote logging with automatic source detection
 #[macro_export]
 macro_rules! remote_log {
@@ -541,6 +1295,223 @@ mod tests {
         assert_eq!(logger.buffer_size(), 1);
     }
 
+    #[test]
+    fn test_remote_logging_config_validation() {
+        let config = RemoteLoggingConfig {
+            batch_size: 0,
+            flush_interval_seconds: 1,
+            ..RemoteLoggingConfig::default()
+        }
+        .validated();
+        assert_eq!(config.batch_size, MIN_LOG_BATCH_SIZE);
+        assert_eq!(config.flush_interval_seconds, MIN_LOG_FLUSH_INTERVAL_SECONDS);
+
+        let mut logger = RemoteLogger::new(RemoteLoggingConfig::default());
+        logger.update_config(RemoteLoggingConfig {
+            batch_size: usize::MAX,
+            flush_interval_seconds: u64::MAX,
+            ..RemoteLoggingConfig::default()
+        });
+        assert_eq!(logger.config().batch_size, MAX_LOG_BATCH_SIZE);
+        assert_eq!(logger.config().flush_interval_seconds, MAX_LOG_FLUSH_INTERVAL_SECONDS);
+    }
+
+    #[test]
+    fn test_endpoint_transport_validation() {
+        let config = |endpoint: &str, allow_insecure_http| RemoteLoggingConfig {
+            endpoint: endpoint.to_string(),
+            allow_insecure_http,
+            ..RemoteLoggingConfig::default()
+        };
+
+        assert_eq!(config("", false).transport(), Ok(Transport::None));
+        assert_eq!(config("https://logs.example.com/ingest", false).transport(), Ok(Transport::Https));
+        assert!(config("http://192.168.1.10:8080/logs", false).transport().is_err());
+        assert_eq!(config("http://192.168.1.10:8080/logs", true).transport(), Ok(Transport::Http));
+        assert!(config("ftp://example.com", true).transport().is_err());
+
+        let clamped = RemoteLoggingConfig { request_timeout_seconds: 0, ..RemoteLoggingConfig::default() }.validated();
+        assert_eq!(clamped.request_timeout_seconds, MIN_LOG_REQUEST_TIMEOUT_SECONDS);
+    }
+
+    #[test]
+    fn test_endpoint_health_from_status() {
+        assert_eq!(EndpointHealth::from_status(200), EndpointHealth::Reachable);
+        // The collector rejecting the probe still proves it is up
+        assert_eq!(EndpointHealth::from_status(401), EndpointHealth::Reachable);
+        assert_eq!(EndpointHealth::from_status(404), EndpointHealth::Reachable);
+        assert!(matches!(EndpointHealth::from_status(503), EndpointHealth::Unreachable(_)));
+    }
+
+    #[cfg(feature = "remote-logging")]
+    #[test]
+    fn test_health_check_refuses_unusable_endpoints() {
+        let empty = RemoteLoggingConfig { endpoint: String::new(), ..RemoteLoggingConfig::default() };
+        assert!(matches!(check_endpoint_health(&empty), EndpointHealth::Unreachable(_)));
+
+        let insecure = RemoteLoggingConfig { endpoint: "http://127.0.0.1:9/logs".to_string(), ..RemoteLoggingConfig::default() };
+        assert!(matches!(check_endpoint_health(&insecure), EndpointHealth::Unreachable(_)));
+    }
+
+    #[cfg(feature = "remote-logging")]
+    #[test]
+    fn test_tracing_layer_forwards_events_with_span_fields() {
+        use tracing_subscriber::prelude::*;
+
+        let logger = Arc::new(Mutex::new(RemoteLogger::new(RemoteLoggingConfig {
+            enabled: true,
+            batch_size: 100,
+            ..RemoteLoggingConfig::default()
+        })));
+        let subscriber = tracing_subscriber::registry().with(RemoteLogLayer::with_logger(logger.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("device_session", device = "mic_1");
+            let _guard = span.enter();
+            tracing::warn!(target: "kwite::audio", retries = 3, "Device reconnected");
+            tracing::info!(target: "kwite::audio", "Not forwarded");
+            tracing::debug!(target: "kwite::audio", "Not forwarded");
+            tracing::info!(target: "kwite::auto_update", "Update available");
+            // The panic hook buffers its crash event itself
+            tracing::error!(target: crate::crash_report::PANIC_LOG_TARGET, "Panic in thread 'main'");
+        });
+
+        let logger = logger.lock().unwrap();
+        let buffer = logger.buffer.lock().unwrap();
+        assert_eq!(buffer.len(), 2, "Only WARN and above plus allowlisted INFO targets should be forwarded");
+        assert_eq!(buffer[1].source.as_deref(), Some("kwite::auto_update"));
+
+        let entry = &buffer[0];
+        assert_eq!(entry.level, "warn");
+        assert_eq!(entry.message, "Device reconnected");
+        assert_eq!(entry.source.as_deref(), Some("kwite::audio"));
+        assert_eq!(entry.fields.get("device").map(String::as_str), Some("mic_1"));
+        assert_eq!(entry.fields.get("retries").map(String::as_str), Some("3"));
+    }
+
+    #[cfg(feature = "remote-logging")]
+    #[test]
+    fn test_tracing_layer_only_buffers() {
+        use tracing_subscriber::prelude::*;
+
+        let logger = Arc::new(Mutex::new(RemoteLogger::new(RemoteLoggingConfig {
+            enabled: true,
+            batch_size: 2,
+            ..RemoteLoggingConfig::default()
+        })));
+        let subscriber = tracing_subscriber::registry().with(RemoteLogLayer::with_logger(logger.clone()));
+
+        // Past a full batch, the layer still leaves the upload to the flush timer
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..4 {
+                tracing::error!(target: "kwite::audio", "Output stream failed");
+            }
+        });
+
+        assert_eq!(logger.lock().unwrap().buffer_size(), 4);
+    }
+
+    #[test]
+    fn test_redacted_fields_are_masked() {
+        let logger = RemoteLogger::new(RemoteLoggingConfig {
+            enabled: true,
+            batch_size: 100,
+            redact_fields: vec!["device_input".to_string(), "IP_ADDRESS".to_string()],
+            ..RemoteLoggingConfig::default()
+        });
+
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("device_input".to_string(), "Jane's AirPods".to_string());
+        fields.insert("ip_address".to_string(), "192.0.2.1".to_string());
+        fields.insert("action".to_string(), "start".to_string());
+        logger.log("error", "Noise cancellation started", None, fields);
+
+        let buffer = logger.buffer.lock().unwrap();
+        assert_eq!(buffer[0].fields["device_input"], REDACTED_VALUE);
+        assert_eq!(buffer[0].fields["ip_address"], REDACTED_VALUE);
+        assert_eq!(buffer[0].fields["action"], "start");
+    }
+
+    #[test]
+    fn test_sampling_keeps_errors() {
+        let config = RemoteLoggingConfig {
+            enabled: true,
+            batch_size: 100,
+            sampling_rate: 0.0,
+            ..RemoteLoggingConfig::default()
+        };
+        let logger = RemoteLogger::new(config);
+
+        for _ in 0..10 {
+            logger.log("info", "sampled out", None, std::collections::HashMap::new());
+        }
+        assert_eq!(logger.buffer_size(), 0, "Non-critical events should be dropped at 0% sampling");
+
+        logger.log("error", "always kept", None, std::collections::HashMap::new());
+        assert_eq!(logger.buffer_size(), 1, "Errors must never be sampled out");
+    }
+
+    #[test]
+    fn test_retry_backoff_schedule() {
+        assert_eq!(retry_backoff(0), Duration::ZERO);
+        assert_eq!(retry_backoff(1), Duration::from_secs(LOG_RETRY_BASE_DELAY_SECONDS));
+        assert_eq!(retry_backoff(2), Duration::from_secs(LOG_RETRY_BASE_DELAY_SECONDS * 2));
+        assert_eq!(retry_backoff(4), Duration::from_secs(LOG_RETRY_BASE_DELAY_SECONDS * 8));
+        // Capped, and no overflow however long the endpoint stays down
+        assert_eq!(retry_backoff(30), Duration::from_secs(LOG_RETRY_MAX_DELAY_SECONDS));
+        assert_eq!(retry_backoff(u32::MAX), Duration::from_secs(LOG_RETRY_MAX_DELAY_SECONDS));
+
+        // Jitter stays within the upper half of the delay
+        let delay = Duration::from_secs(8);
+        assert_eq!(with_jitter(delay, 0.0), Duration::from_secs(4));
+        assert_eq!(with_jitter(delay, 1.0), delay);
+        assert_eq!(with_jitter(delay, 7.0), delay);
+
+        let now = SystemTime::now();
+        let mut retry = RetryState::default();
+        assert!(!retry.is_waiting(now));
+        let delay = retry.record_failure(now);
+        assert!(delay >= retry_backoff(1) / 2 && delay <= retry_backoff(1));
+        assert!(retry.is_waiting(now));
+        assert!(!retry.is_waiting(now + retry_backoff(1)));
+        retry.record_success();
+        assert_eq!(retry.failures, 0);
+        assert!(!retry.is_waiting(now));
+    }
+
+    #[test]
+    fn test_spooled_logs_are_capped_and_reloaded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("pending_logs.jsonl");
+        let entries: Vec<LogEntry> = (0..20).map(|i| LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: "error".to_string(),
+            message: format!("crash {}", i),
+            source: None,
+            fields: std::collections::HashMap::new(),
+        }).collect();
+
+        // Only the newest entries that fit are kept
+        let line_len = serde_json::to_string(&entries[0]).unwrap().len() + 1;
+        let written = write_spool(&path, entries.iter(), line_len * 5 + line_len / 2).unwrap();
+        assert_eq!(written, 5);
+        let spooled = read_spool(&path);
+        assert_eq!(spooled.iter().map(|entry| entry.message.as_str()).collect::<Vec<_>>(),
+            vec!["crash 15", "crash 16", "crash 17", "crash 18", "crash 19"]);
+
+        // A new run picks them up ahead of anything logged later
+        let logger = RemoteLogger::new(RemoteLoggingConfig {
+            enabled: true,
+            batch_size: 100,
+            ..RemoteLoggingConfig::default()
+        }).with_spool_file(path);
+        logger.log("error", "after restart", None, std::collections::HashMap::new());
+        let buffer = logger.buffer.lock().unwrap();
+        assert_eq!(buffer.len(), 6);
+        assert_eq!(buffer[0].message, "crash 15");
+        assert_eq!(buffer[5].message, "after restart");
+    }
+
     #[test]
     fn test_app_info_default() {
         let app_info = AppInfo::default();
@@ -598,7 +1569,7 @@ mod tests {
         };
 
         // Trim to a very small size to force trimming
-        let trimmed = original_batch.trim_to_size_limit(10000); // 10KB limit
+        let trimmed = original_batch.trim_to_size_limit(10000, false); // 10KB limit
         
         assert!(trimmed.logs.len() < original_batch.logs.len(), "Trimmed batch should have fewer logs");
         assert!(trimmed.estimated_size() <= 10000, "Trimmed batch should be within size limit");
@@ -610,6 +1581,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_oversized_log_entry_is_truncated() {
+        let config = RemoteLoggingConfig {
+            enabled: true,
+            ..RemoteLoggingConfig::default()
+        };
+        let logger = RemoteLogger::new(config);
+
+        // A single message larger than the whole payload limit
+        let huge_message = "é".repeat(MAX_PAYLOAD_SIZE_BYTES_LOCAL);
+        logger.log("error", &huge_message, None, std::collections::HashMap::new());
+
+        let logs: Vec<LogEntry> = logger.buffer.lock().unwrap().iter().cloned().collect();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].message.len() <= MAX_LOG_MESSAGE_BYTES);
+        assert!(logs[0].message.ends_with(TRUNCATION_MARKER));
+
+        let batch = LogBatch {
+            app_info: AppInfo::default(),
+            system_info: None,
+            logs,
+            batch_timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: "test_session".to_string(),
+        };
+
+        // The truncated entry fits, so the batch is sent rather than emptied
+        let trimmed = batch.trim_to_size_limit(MAX_PAYLOAD_SIZE_BYTES_LOCAL, false);
+        assert_eq!(trimmed.logs.len(), 1, "Truncated entry should still be sent");
+    }
+
     #[test]
     fn test_log_batch_no_trimming_needed() {
         let logs = vec![LogEntry {
@@ -628,7 +1629,39 @@ mod tests {
             session_id: "test_session".to_string(),
         };
 
-        let trimmed = batch.trim_to_size_limit(MAX_PAYLOAD_SIZE_BYTES_LOCAL);
+        let trimmed = batch.trim_to_size_limit(MAX_PAYLOAD_SIZE_BYTES_LOCAL, false);
         assert_eq!(trimmed.logs.len(), batch.logs.len(), "No trimming should be needed for small batch");
     }
+
+    #[cfg(feature = "remote-logging")]
+    #[test]
+    fn test_compressed_batches_fit_more_logs() {
+        use std::io::Read;
+
+        let logs = (0..100).map(|i| LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: "info".to_string(),
+            message: format!("Repetitive message {}: {}", i, "x".repeat(1000)),
+            source: Some("test_module".to_string()),
+            fields: std::collections::HashMap::new(),
+        }).collect();
+        let batch = LogBatch {
+            app_info: AppInfo::default(),
+            system_info: None,
+            logs,
+            batch_timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: "test_session".to_string(),
+        };
+
+        let plain = batch.trim_to_size_limit(10000, false);
+        let compressed = batch.trim_to_size_limit(10000, true);
+        assert!(compressed.logs.len() > plain.logs.len(), "Compression should let more logs fit");
+        assert!(compressed.encoded_size(true) <= 10000);
+        assert_eq!(compressed.logs.last().unwrap().message, batch.logs.last().unwrap().message);
+
+        // The body decompresses back to the batch JSON
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&compressed.to_gzip_json().unwrap()[..]).read_to_string(&mut json).unwrap();
+        assert_eq!(json, serde_json::to_string(&compressed).unwrap());
+    }
 }
\ No newline at end of file
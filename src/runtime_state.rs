@@ -0,0 +1,128 @@
+//! # Runtime State Module
+//!
+//! This module persists a tiny "last known good" snapshot of the running session
+//! (enabled state, selected devices, sensitivity) so Kwite can offer to restore it
+//! after a crash. Unlike the configuration, this is ephemeral session state: it is
+//! rewritten on every relevant change and is never edited by the user.
+//!
+//! ## Crash Detection
+//!
+//! The snapshot carries a `clean_shutdown` marker. It is written as `false` while
+//! the application runs and flipped to `true` on a normal exit. Finding `false` at
+//! startup therefore means the previous session ended unexpectedly.
+//!
+//! ## Resilience
+//!
+//! The file is written to a temporary path and renamed into place, and any read or
+//! parse failure silently falls back to "no previous session". A corrupt state file
+//! must never prevent the application from starting.
+
+use crate::config::KwiteConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Snapshot of the running session used for crash recovery
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeState {
+    /// Whether noise cancellation was active
+    pub enabled: bool,
+    /// Selected input device identifier
+    pub input_device_id: String,
+    /// Selected output device identifier
+    pub output_device_id: String,
+    /// Active sensitivity value
+    pub sensitivity: f32,
+    /// Name of the active profile, if any
+    pub active_profile: Option<String>,
+    /// Set when the application exited normally
+    pub clean_shutdown: bool,
+}
+
+impl RuntimeState {
+    /// Load the runtime state from its default location
+    ///
+    /// Returns `None` if the file is missing, unreadable or corrupt.
+    pub fn load() -> Option<Self> {
+        Self::load_from(&Self::state_path().ok()?)
+    }
+
+    /// Load the runtime state from a specific path, falling back silently
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    /// Save the runtime state to its default location
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.save_to(&Self::state_path()?)
+    }
+
+    /// Save the runtime state to a specific path
+    ///
+    /// Writes to a temporary file first and renames it into place so a crash
+    /// mid-write can't leave a truncated state file behind.
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string(self)?;
+        let tmp_path = path.with_extension("toml.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Whether the session this state describes ended without a clean shutdown
+    pub fn was_unclean_shutdown(&self) -> bool {
+        !self.clean_shutdown
+    }
+
+    /// Location of the runtime state file (next to `config.toml`)
+    fn state_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(KwiteConfig::config_dir()?.join("runtime_state.toml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_state() -> RuntimeState {
+        RuntimeState {
+            enabled: true,
+            input_device_id: "input_1".to_string(),
+            output_device_id: "output_2".to_string(),
+            sensitivity: 0.2,
+            active_profile: None,
+            clean_shutdown: false,
+        }
+    }
+
+    #[test]
+    fn test_runtime_state_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("runtime_state.toml");
+
+        let state = sample_state();
+        state.save_to(&path).unwrap();
+
+        let loaded = RuntimeState::load_from(&path).unwrap();
+        assert_eq!(loaded, state);
+        assert!(loaded.was_unclean_shutdown());
+    }
+
+    #[test]
+    fn test_corrupt_runtime_state_falls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("runtime_state.toml");
+
+        fs::write(&path, "enabled = \"not a bool\"\n[[[").unwrap();
+        assert!(RuntimeState::load_from(&path).is_none());
+
+        let missing = temp_dir.path().join("missing.toml");
+        assert!(RuntimeState::load_from(&missing).is_none());
+    }
+}
@@ -19,10 +19,32 @@
 //! - Network interface MAC addresses (first available)
 //! - External WAN IP address (public IP for analytics)
 
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 #[cfg(any(target_os = "windows", target_os = "macos"))]
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Identifier of this application run, generated once at startup
+static SESSION_ID: Lazy<String> = Lazy::new(|| {
+    format!(
+        "kwite_{}_{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        rand::random::<u32>()
+    )
+});
+
+/// Unique ID of the current application session
+///
+/// Shared by remote logs, usage statistics and crash reports so support can
+/// correlate them; users can copy it from the settings window.
+pub fn session_id() -> &'static str {
+    &SESSION_ID
+}
 
 /// System information structure for logging and analytics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +12,11 @@
 //! - Error rates and recovery
 //! - System performance impact
 //!
+//! Sessions in which the microphone never carried a signal (muted the whole
+//! time) are counted separately and can be dropped from the aggregates.
+//!
+//! Statistics can be exported to a JSON file or reset from the settings dialog.
+//!
 //! ## Privacy
 //!
 //! All personally identifiable information is either hashed or excluded.
@@ -22,6 +27,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 use chrono::Utc;
 
@@ -30,6 +36,9 @@ use chrono::Utc;
 pub struct UsageStatistics {
     /// Total number of application sessions
     pub total_sessions: u64,
+    /// Sessions in which no meaningful input signal occurred
+    #[serde(default)]
+    pub silent_sessions: u64,
     /// Total cumulative usage time in seconds
     pub total_usage_seconds: u64,
     /// Average session duration in seconds
@@ -48,6 +57,18 @@ pub struct UsageStatistics {
     pub daily_usage: Vec<DailyUsage>,
     /// Last updated timestamp
     pub last_updated: String,
+    /// Session ID of the most recent session, matching the remote log session
+    #[serde(default)]
+    pub last_session_id: Option<String>,
+    /// Measured noise reduction of the most recent session that processed noise (%)
+    #[serde(default)]
+    pub last_session_noise_reduction_percent: Option<f32>,
+    /// Average measured noise reduction over those sessions (%)
+    #[serde(default)]
+    pub avg_noise_reduction_percent: f64,
+    /// Number of sessions included in `avg_noise_reduction_percent`
+    #[serde(default)]
+    pub noise_reduction_sessions: u64,
 }
 
 /// Performance-related metrics
@@ -106,6 +127,9 @@ pub struct SessionTracker {
     performance_samples: Vec<f64>,
     errors_this_session: u32,
     features_used: HashMap<String, u32>,
+    activations: u64,
+    had_signal: bool,
+    noise_reduction_percent: Option<f32>,
 }
 
 /// Usage statistics manager
@@ -113,12 +137,14 @@ pub struct UsageStatsManager {
     stats: UsageStatistics,
     current_session: Option<SessionTracker>,
     enabled: bool,
+    drop_silent_sessions: bool,
 }
 
 impl Default for UsageStatistics {
     fn default() -> Self {
         Self {
             total_sessions: 0,
+            silent_sessions: 0,
             total_usage_seconds: 0,
             avg_session_duration_seconds: 0.0,
             noise_cancellation_activations: 0,
@@ -128,6 +154,10 @@ impl Default for UsageStatistics {
             error_stats: ErrorStatistics::default(),
             daily_usage: Vec::new(),
             last_updated: Utc::now().to_rfc3339(),
+            last_session_id: None,
+            last_session_noise_reduction_percent: None,
+            avg_noise_reduction_percent: 0.0,
+            noise_reduction_sessions: 0,
         }
     }
 }
@@ -167,6 +197,9 @@ impl SessionTracker {
             performance_samples: Vec::new(),
             errors_this_session: 0,
             features_used: HashMap::new(),
+            activations: 0,
+            had_signal: false,
+            noise_reduction_percent: None,
         }
     }
 
@@ -208,6 +241,7 @@ impl UsageStatsManager {
             stats: UsageStatistics::default(),
             current_session: None,
             enabled,
+            drop_silent_sessions: false,
         }
     }
 
@@ -224,6 +258,7 @@ impl UsageStatsManager {
             stats,
             current_session: None,
             enabled,
+            drop_silent_sessions: false,
         })
     }
 
@@ -238,6 +273,27 @@ impl UsageStatsManager {
         Ok(())
     }
 
+    /// Write the statistics to `path` as pretty-printed JSON
+    pub fn export_json(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let content = serde_json::to_string_pretty(&self.stats)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Clear all accumulated statistics
+    ///
+    /// A running session keeps going and becomes the first session counted
+    /// after the reset.
+    pub fn reset(&mut self) {
+        let running = self.current_session.is_some();
+        self.stats = UsageStatistics {
+            total_sessions: running as u64,
+            noise_cancellation_activations: self.current_session.as_ref().map_or(0, |session| session.activations),
+            last_session_id: if running { self.stats.last_session_id.take() } else { None },
+            ..UsageStatistics::default()
+        };
+    }
+
     /// Start a new session
     pub fn start_session(&mut self) {
         if !self.enabled {
@@ -249,6 +305,7 @@ impl UsageStatsManager {
 
         self.current_session = Some(SessionTracker::new());
         self.stats.total_sessions += 1;
+        self.stats.last_session_id = Some(crate::system_info::session_id().to_string());
     }
 
     /// End the current session
@@ -262,6 +319,18 @@ impl UsageStatsManager {
             let mut session = session;
             session.stop_noise_cancellation();
 
+            // Sessions with a muted mic carry no meaningful data
+            if !session.had_signal {
+                if self.drop_silent_sessions {
+                    self.stats.total_sessions = self.stats.total_sessions.saturating_sub(1);
+                    self.stats.noise_cancellation_activations =
+                        self.stats.noise_cancellation_activations.saturating_sub(session.activations);
+                    self.stats.last_updated = Utc::now().to_rfc3339();
+                    return;
+                }
+                self.stats.silent_sessions += 1;
+            }
+
             // Update statistics
             let session_duration = session.session_duration();
             self.stats.total_usage_seconds += session_duration.as_secs();
@@ -283,6 +352,14 @@ impl UsageStatsManager {
                 self.update_performance_metrics(avg_latency, peak_latency);
             }
 
+            // Fold the session's measured noise reduction into the running average
+            if let Some(percent) = session.noise_reduction_percent {
+                self.stats.last_session_noise_reduction_percent = Some(percent);
+                self.stats.noise_reduction_sessions += 1;
+                self.stats.avg_noise_reduction_percent +=
+                    (percent as f64 - self.stats.avg_noise_reduction_percent) / self.stats.noise_reduction_sessions as f64;
+            }
+
             // Update feature usage
             for (feature, count) in session.features_used {
                 *self.stats.feature_usage.entry(feature).or_insert(0) += count as u64;
@@ -303,6 +380,7 @@ impl UsageStatsManager {
 
         if let Some(session) = &mut self.current_session {
             session.start_noise_cancellation();
+            session.activations += 1;
             self.stats.noise_cancellation_activations += 1;
         }
     }
@@ -423,6 +501,7 @@ impl UsageStatsManager {
             - **Total Usage Time**: {:.1} hours\n\
             - **Average Session**: {:.1} minutes\n\
             - **Noise Cancellation Usage**: {:.1} hours\n\
+            - **Average Noise Reduction**: {:.1}%\n\
             - **Average Latency**: {:.2} ms\n\
             - **Peak Performance**: {:.2} ms peak latency\n\
             - **Error Rate**: {:.2}%\n\
@@ -431,6 +510,7 @@ impl UsageStatsManager {
             self.stats.total_usage_seconds as f64 / 3600.0,
             self.stats.avg_session_duration_seconds / 60.0,
             self.stats.total_processing_time_seconds as f64 / 3600.0,
+            self.stats.avg_noise_reduction_percent,
             self.stats.performance_metrics.avg_latency_ms,
             self.stats.performance_metrics.peak_latency_ms,
             if self.stats.total_sessions > 0 {
@@ -451,6 +531,25 @@ impl UsageStatsManager {
             .join(", ")
     }
 
+    /// Mark the current session as having carried a real input signal
+    pub fn record_input_signal(&mut self) {
+        if let Some(session) = &mut self.current_session {
+            session.had_signal = true;
+        }
+    }
+
+    /// Update the current session's measured average noise reduction (%)
+    pub fn record_noise_reduction(&mut self, percent: f32) {
+        if let Some(session) = &mut self.current_session {
+            session.noise_reduction_percent = Some(percent);
+        }
+    }
+
+    /// Exclude sessions without any input signal from the statistics
+    pub fn set_drop_silent_sessions(&mut self, drop: bool) {
+        self.drop_silent_sessions = drop;
+    }
+
     /// Enable or disable statistics collection
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
@@ -461,6 +560,17 @@ impl UsageStatsManager {
     }
 }
 
+/// Timestamped file path for a statistics export
+///
+/// Uses the user's documents directory when available, falling back to home.
+pub fn default_export_path() -> PathBuf {
+    let dir = dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("kwite-usage-stats-{}.json", stamp))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,6 +594,31 @@ mod tests {
         assert!(stats.current_session.is_none());
     }
 
+    #[test]
+    fn test_silent_sessions() {
+        let mut stats = UsageStatsManager::new(true);
+
+        // Silent sessions are tagged by default
+        stats.start_session();
+        stats.end_session();
+        assert_eq!(stats.stats.total_sessions, 1);
+        assert_eq!(stats.stats.silent_sessions, 1);
+
+        // ...and excluded entirely when dropping is enabled
+        stats.set_drop_silent_sessions(true);
+        stats.start_session();
+        stats.start_noise_cancellation();
+        stats.end_session();
+        assert_eq!(stats.stats.total_sessions, 1);
+        assert_eq!(stats.stats.noise_cancellation_activations, 0);
+
+        stats.start_session();
+        stats.record_input_signal();
+        stats.end_session();
+        assert_eq!(stats.stats.total_sessions, 2);
+        assert_eq!(stats.stats.silent_sessions, 1);
+    }
+
     #[test]
     fn test_feature_usage_tracking() {
         let mut stats = UsageStatsManager::new(true);
@@ -519,4 +654,58 @@ mod tests {
         assert_eq!(stats.stats.performance_metrics.peak_latency_ms, 5.0);
         assert_eq!(stats.stats.performance_metrics.peak_cpu_usage_percent, 15.0);
     }
+
+    #[test]
+    fn test_reset_keeps_running_session() {
+        let mut stats = UsageStatsManager::new(true);
+        stats.start_session();
+        stats.record_input_signal();
+        stats.record_feature_usage("noise_cancellation");
+        stats.record_error("audio", true);
+        stats.end_session();
+        stats.start_session();
+        stats.start_noise_cancellation();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("stats.json");
+        stats.export_json(&path).unwrap();
+        let exported: UsageStatistics = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(exported.total_sessions, 2);
+        assert_eq!(exported.error_stats.total_errors, 1);
+
+        stats.reset();
+        assert_eq!(stats.stats.total_sessions, 1, "The running session should count after a reset");
+        assert!(stats.stats.feature_usage.is_empty());
+        assert_eq!(stats.stats.error_stats.total_errors, 0);
+        assert!(stats.stats.last_session_id.is_some());
+
+        // The session carries on and is recorded normally
+        stats.record_input_signal();
+        stats.end_session();
+        assert_eq!(stats.stats.total_sessions, 1);
+        assert_eq!(stats.stats.noise_cancellation_activations, 1);
+
+        stats.reset();
+        assert_eq!(stats.stats.total_sessions, 0);
+        assert!(stats.stats.last_session_id.is_none());
+    }
+
+    #[test]
+    fn test_noise_reduction_persisted_per_session() {
+        let mut stats = UsageStatsManager::new(true);
+        for percent in [80.0, 60.0] {
+            stats.start_session();
+            stats.record_input_signal();
+            stats.record_noise_reduction(percent);
+            stats.end_session();
+        }
+        // A session without noise frames leaves the average alone
+        stats.start_session();
+        stats.record_input_signal();
+        stats.end_session();
+
+        assert_eq!(stats.stats.last_session_noise_reduction_percent, Some(60.0));
+        assert_eq!(stats.stats.noise_reduction_sessions, 2);
+        assert!((stats.stats.avg_noise_reduction_percent - 70.0).abs() < 1e-9);
+    }
 }
\ No newline at end of file
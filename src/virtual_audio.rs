@@ -2,7 +2,10 @@
 /// 
 /// This module provides OS-specific guidance for installing and configuring
 /// virtual audio devices, making the setup process painless for users.
+/// On Linux it can also create the virtual device itself through `pactl`.
 
+use crate::audio::devices::AudioDeviceInfo;
+use crate::logger::log;
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -13,7 +16,7 @@ pub struct VirtualAudioInfo {
     pub setup_instructions: Vec<&'static str>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OperatingSystem {
     Windows,
     MacOS,
@@ -105,6 +108,191 @@ pub fn get_virtual_audio_info() -> VirtualAudioInfo {
     }
 }
 
+/// OS settings page where microphone access is granted, if the OS has one
+pub fn microphone_privacy_settings_url() -> Option<&'static str> {
+    match detect_os() {
+        OperatingSystem::Windows => Some("ms-settings:privacy-microphone"),
+        OperatingSystem::MacOS => Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"),
+        OperatingSystem::Linux | OperatingSystem::Unknown => None,
+    }
+}
+
+/// Open the OS microphone privacy settings page
+pub fn open_microphone_privacy_settings() -> Result<(), String> {
+    let url = microphone_privacy_settings_url()
+        .ok_or_else(|| "No microphone privacy settings page on this OS".to_string())?;
+
+    let result = match detect_os() {
+        OperatingSystem::Windows => std::process::Command::new("cmd").args(["/C", "start", "", url]).spawn(),
+        _ => std::process::Command::new("open").arg(url).spawn(),
+    };
+
+    result.map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Name of the null sink Kwite creates on Linux (the device to output to)
+pub const LINUX_VIRTUAL_SINK: &str = "Kwite";
+
+/// Name of the virtual microphone fed by that sink (the device chat apps record from)
+pub const LINUX_VIRTUAL_SOURCE: &str = "Kwite_Mic";
+
+/// Sound server running on a Linux desktop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundServer {
+    PulseAudio,
+    /// PipeWire with its PulseAudio compatibility layer (`pipewire-pulse`)
+    PipeWire,
+}
+
+impl fmt::Display for SoundServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoundServer::PulseAudio => write!(f, "PulseAudio"),
+            SoundServer::PipeWire => write!(f, "PipeWire"),
+        }
+    }
+}
+
+/// Identify the sound server from `pactl info` output
+fn parse_sound_server(info: &str) -> Option<SoundServer> {
+    let server_name = info.lines().find_map(|line| line.trim().strip_prefix("Server Name:"))?;
+    if server_name.to_lowercase().contains("pipewire") {
+        Some(SoundServer::PipeWire)
+    } else {
+        Some(SoundServer::PulseAudio)
+    }
+}
+
+/// Run `pactl` and return its standard output
+fn pactl(args: &[String]) -> Result<String, String> {
+    let output = std::process::Command::new("pactl")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Could not run pactl ({}) - install pulseaudio-utils or pipewire-pulse", e))?;
+    if !output.status.success() {
+        return Err(format!("pactl {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Detect whether PipeWire or PulseAudio is running; `None` if neither answers
+pub fn detect_sound_server() -> Option<SoundServer> {
+    pactl(&["info".to_string()]).ok().as_deref().and_then(parse_sound_server)
+}
+
+/// `pactl load-module` arguments creating the Kwite sink and its virtual microphone
+///
+/// Both servers take the same modules (PipeWire through `pipewire-pulse`), but
+/// PipeWire names the node class explicitly so the microphone is listed as a
+/// regular input device.
+fn virtual_device_modules(server: SoundServer) -> Vec<Vec<String>> {
+    let mut sink = vec![
+        "load-module".to_string(),
+        "module-null-sink".to_string(),
+        format!("sink_name={}", LINUX_VIRTUAL_SINK),
+        "sink_properties=device.description=Kwite".to_string(),
+    ];
+    let mut source = vec![
+        "load-module".to_string(),
+        "module-remap-source".to_string(),
+        format!("master={}.monitor", LINUX_VIRTUAL_SINK),
+        format!("source_name={}", LINUX_VIRTUAL_SOURCE),
+        "source_properties=device.description=Kwite-Microphone".to_string(),
+    ];
+    if server == SoundServer::PipeWire {
+        sink.push("media.class=Audio/Sink".to_string());
+        source.push("media.class=Audio/Source/Virtual".to_string());
+    }
+    vec![sink, source]
+}
+
+/// Whether `pactl list short sinks` output contains a sink called `name`
+fn sink_exists(short_sinks: &str, name: &str) -> bool {
+    short_sinks.lines().any(|line| line.split('\t').nth(1) == Some(name))
+}
+
+/// IDs of the modules providing the Kwite sink and microphone, from `pactl list short modules` output
+fn kwite_module_ids(short_modules: &str) -> Vec<u32> {
+    let sink_arg = format!("sink_name={}", LINUX_VIRTUAL_SINK);
+    let source_arg = format!("source_name={}", LINUX_VIRTUAL_SOURCE);
+    short_modules
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let id = fields.next()?.trim().parse::<u32>().ok()?;
+            let args = fields.nth(1)?;
+            args.split_whitespace()
+                .any(|arg| arg == sink_arg || arg == source_arg)
+                .then_some(id)
+        })
+        .collect()
+}
+
+/// Virtual sink and microphone created by Kwite on Linux
+///
+/// The modules are unloaded when this is dropped, so the devices only live as
+/// long as the Kwite session that created them. A device reused from an earlier
+/// session is left in place.
+#[derive(Debug)]
+pub struct LinuxVirtualDevice {
+    pub server: SoundServer,
+    module_ids: Vec<u32>,
+}
+
+impl LinuxVirtualDevice {
+    /// Create the `Kwite` sink and `Kwite_Mic` virtual microphone
+    ///
+    /// Fails if no sound server answers. If a `Kwite` sink already exists (for
+    /// example one left behind by a crashed session), it is reused instead; see
+    /// [`is_reused`](Self::is_reused) and [`remove_existing`](Self::remove_existing).
+    pub fn create() -> Result<Self, String> {
+        let server = detect_sound_server().ok_or("No PulseAudio or PipeWire server is running")?;
+        if sink_exists(&pactl(&["list".to_string(), "short".to_string(), "sinks".to_string()])?, LINUX_VIRTUAL_SINK) {
+            log::info!("🔌 Reusing existing {} virtual device \"{}\"", server, LINUX_VIRTUAL_SINK);
+            return Ok(Self { server, module_ids: Vec::new() });
+        }
+
+        // Modules loaded so far are unloaded by `Drop` if a later one fails
+        let mut device = Self { server, module_ids: Vec::new() };
+        for args in virtual_device_modules(server) {
+            let id = pactl(&args)?.trim().parse::<u32>()
+                .map_err(|e| format!("Unexpected pactl output: {}", e))?;
+            device.module_ids.push(id);
+        }
+        log::info!("🔌 Created {} virtual device \"{}\" with microphone \"{}\"", server, LINUX_VIRTUAL_SINK, LINUX_VIRTUAL_SOURCE);
+        Ok(device)
+    }
+
+    /// Whether this device already existed and is not owned by this session
+    pub fn is_reused(&self) -> bool {
+        self.module_ids.is_empty()
+    }
+
+    /// Unload every module providing a Kwite sink or microphone, including ones
+    /// loaded by other sessions, and return how many were removed
+    pub fn remove_existing() -> Result<usize, String> {
+        let modules = pactl(&["list".to_string(), "short".to_string(), "modules".to_string()])?;
+        let ids = kwite_module_ids(&modules);
+        // Reverse load order, so the microphone goes before the sink it reads from
+        for id in ids.iter().rev() {
+            pactl(&["unload-module".to_string(), id.to_string()])?;
+        }
+        log::info!("🔌 Removed {} virtual device module(s)", ids.len());
+        Ok(ids.len())
+    }
+}
+
+impl Drop for LinuxVirtualDevice {
+    fn drop(&mut self) {
+        // Unload in reverse so the microphone goes before the sink it reads from
+        for id in self.module_ids.drain(..).rev() {
+            if let Err(e) = pactl(&["unload-module".to_string(), id.to_string()]) {
+                log::warn!("Failed to remove virtual device module {}: {}", id, e);
+            }
+        }
+    }
+}
+
 /// Check if virtual audio devices are available on the current system
 pub fn has_virtual_devices(output_devices: &[crate::audio::devices::AudioDeviceInfo]) -> bool {
     output_devices.iter().any(|d| d.is_virtual)
@@ -152,9 +340,60 @@ pub fn detect_virtual_device_type(device_name: &str) -> Option<&'static str> {
     None
 }
 
+/// Virtual device types worth routing processed audio to on `os`, most preferred first
+///
+/// Names match `detect_virtual_device_type`. PulseAudio/PipeWire monitors are
+/// never listed: they are the capture side of a sink and look "virtual", but
+/// writing to one goes nowhere useful.
+pub fn routing_targets(os: OperatingSystem) -> &'static [&'static str] {
+    match os {
+        OperatingSystem::Windows => &["VB-Audio Cable", "Voicemeeter", "Virtual Audio Device"],
+        OperatingSystem::MacOS => &["BlackHole", "VB-Audio Cable", "Loopback", "Soundflower", "Virtual Audio Device"],
+        OperatingSystem::Linux => &["PulseAudio Null Sink", "Virtual Audio Device"],
+        OperatingSystem::Unknown => &[
+            "VB-Audio Cable", "BlackHole", "Voicemeeter", "Loopback", "Soundflower",
+            "PulseAudio Null Sink", "Virtual Audio Device",
+        ],
+    }
+}
+
+/// Pick the default output device for `os`
+///
+/// Routing to other apps wants the best-ranked virtual device from
+/// `routing_targets`; local playback wants the real speakers. Falls back to the
+/// system default, then to the first device.
+pub fn select_default_output(
+    devices: &[AudioDeviceInfo],
+    local_playback_only: bool,
+    os: OperatingSystem,
+) -> Option<&AudioDeviceInfo> {
+    let preferred = if local_playback_only {
+        devices.iter()
+            .find(|d| d.is_default && !d.is_virtual)
+            .or_else(|| devices.iter().find(|d| !d.is_virtual))
+    } else {
+        let targets = routing_targets(os);
+        devices.iter()
+            .filter_map(|d| {
+                let kind = detect_virtual_device_type(&d.name)?;
+                let rank = targets.iter().position(|&target| target == kind)?;
+                Some((rank, d))
+            })
+            .min_by_key(|&(rank, _)| rank)
+            .map(|(_, d)| d)
+            .or_else(|| devices.iter().find(|d| d.is_default))
+    };
+    preferred.or_else(|| devices.first())
+}
+
 /// Get user-friendly setup status message
-pub fn get_setup_status_message(has_virtual_devices: bool) -> (String, egui::Color32) {
-    if has_virtual_devices {
+///
+/// In local playback mode the user only wants cleaned audio on their own speakers,
+/// so a missing virtual device is not reported as a problem.
+pub fn get_setup_status_message(has_virtual_devices: bool, local_playback_only: bool) -> (String, egui::Color32) {
+    if local_playback_only {
+        ("🔈 Local playback only - processed audio goes to your speakers".to_string(), egui::Color32::GRAY)
+    } else if has_virtual_devices {
         ("✅ Virtual audio device detected and ready!".to_string(), egui::Color32::GREEN)
     } else {
         let os = detect_os();
@@ -189,14 +428,92 @@ mod tests {
         assert_eq!(detect_virtual_device_type("Regular Speakers"), None);
     }
 
+    fn devices(names: &[&str], default_name: &str) -> Vec<AudioDeviceInfo> {
+        names.iter()
+            .map(|&name| AudioDeviceInfo {
+                id: name.to_string(),
+                name: name.to_string(),
+                is_default: name == default_name,
+                is_virtual: detect_virtual_device_type(name).is_some(),
+            })
+            .collect()
+    }
+
+    fn selected(devices: &[AudioDeviceInfo], local_playback_only: bool, os: OperatingSystem) -> &str {
+        select_default_output(devices, local_playback_only, os).map(|d| d.name.as_str()).unwrap_or("")
+    }
+
+    #[test]
+    fn test_default_output_windows() {
+        let list = devices(&["Speakers (Realtek Audio)", "Voicemeeter Input", "CABLE Input (VB-Audio Virtual Cable)"], "Speakers (Realtek Audio)");
+        assert_eq!(selected(&list, false, OperatingSystem::Windows), "CABLE Input (VB-Audio Virtual Cable)");
+        assert_eq!(selected(&list, true, OperatingSystem::Windows), "Speakers (Realtek Audio)");
+    }
+
+    #[test]
+    fn test_default_output_macos() {
+        let list = devices(&["MacBook Pro Speakers", "VB-Cable", "BlackHole 2ch"], "MacBook Pro Speakers");
+        assert_eq!(selected(&list, false, OperatingSystem::MacOS), "BlackHole 2ch");
+        assert_eq!(selected(&list, true, OperatingSystem::MacOS), "MacBook Pro Speakers");
+    }
+
+    #[test]
+    fn test_default_output_linux_skips_monitors() {
+        // PipeWire exposes sink monitors that the generic heuristic mistook for a virtual cable
+        let list = devices(&["Monitor of Built-in Audio Analog Stereo", "pipewire", "default"], "default");
+        assert_eq!(selected(&list, false, OperatingSystem::Linux), "default");
+
+        let list = devices(&["Monitor of Built-in Audio Analog Stereo", "Null Output", "default"], "default");
+        assert_eq!(selected(&list, false, OperatingSystem::Linux), "Null Output");
+        assert_eq!(selected(&list, true, OperatingSystem::Linux), "default");
+    }
+
+    #[test]
+    fn test_default_output_fallbacks() {
+        assert!(select_default_output(&[], false, OperatingSystem::Linux).is_none());
+
+        // No virtual device and no default: first device
+        let list = devices(&["USB Headset", "HDMI Output"], "");
+        assert_eq!(selected(&list, false, OperatingSystem::Windows), "USB Headset");
+    }
+
+    #[test]
+    fn test_linux_virtual_device_commands() {
+        let pipewire_info = "Server String: /run/user/1000/pulse/native\nServer Name: PulseAudio (on PipeWire 1.0.5)\nServer Version: 15.0.0\n";
+        assert_eq!(parse_sound_server(pipewire_info), Some(SoundServer::PipeWire));
+        assert_eq!(parse_sound_server("Server Name: pulseaudio\nServer Version: 16.1\n"), Some(SoundServer::PulseAudio));
+        assert_eq!(parse_sound_server("Connection failure: Connection refused"), None);
+
+        let pulse = virtual_device_modules(SoundServer::PulseAudio);
+        assert_eq!(pulse[0][..3], ["load-module", "module-null-sink", "sink_name=Kwite"]);
+        assert!(pulse[1].contains(&"master=Kwite.monitor".to_string()));
+        assert!(pulse.iter().flatten().all(|arg| !arg.starts_with("media.class")));
+        let pipewire = virtual_device_modules(SoundServer::PipeWire);
+        assert!(pipewire[1].contains(&"media.class=Audio/Source/Virtual".to_string()));
+
+        let sinks = "47\talsa_output.pci-0000_00_1f.3.analog-stereo\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED\n52\tKwite\tPipeWire\tfloat32le 2ch 48000Hz\tIDLE\n";
+        assert!(sink_exists(sinks, "Kwite"));
+        assert!(!sink_exists(sinks, "Kwite_Mic"));
+
+        let modules = "536870912\tlibpipewire-module-protocol-pulse\t\t\n\
+            25\tmodule-null-sink\tsink_name=Kwite sink_properties=device.description=Kwite\t\n\
+            26\tmodule-remap-source\tmaster=Kwite.monitor source_name=Kwite_Mic source_properties=device.description=Kwite-Microphone\t\n\
+            27\tmodule-null-sink\tsink_name=Kwite_Test\t\n";
+        assert_eq!(kwite_module_ids(modules), vec![25, 26]);
+        assert!(kwite_module_ids("").is_empty());
+    }
+
     #[test]
     fn test_setup_status_message() {
-        let (message, color) = get_setup_status_message(true);
+        let (message, color) = get_setup_status_message(true, false);
         assert!(message.contains("ready"));
         assert_eq!(color, egui::Color32::GREEN);
 
-        let (message, color) = get_setup_status_message(false);
+        let (message, color) = get_setup_status_message(false, false);
         assert!(message.contains("⚠"));
         assert_eq!(color, egui::Color32::GRAY);
+
+        let (message, _) = get_setup_status_message(false, true);
+        assert!(!message.contains("⚠"), "Local playback must not nag about virtual devices");
     }
 }
\ No newline at end of file
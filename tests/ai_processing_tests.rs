@@ -349,3 +349,21 @@ fn test_available_models_include_rnnoise() {
     assert_eq!(available.len(), 2, "Auto and RNNoise should be available");
 }
 
+
+#[test]
+fn test_diagnostic_presets_set_toggles_together() {
+    use kwite::audio::{apply_diagnostic_preset, current_diagnostic_preset, DiagnosticPreset};
+    use kwite::audio::{is_max_test_mode_enabled, is_pipeline_verification_mode_enabled};
+    
+    apply_diagnostic_preset(DiagnosticPreset::FullDiagnostics);
+    assert!(is_max_test_mode_enabled());
+    assert!(is_pipeline_verification_mode_enabled());
+    assert_eq!(current_diagnostic_preset(), Some(DiagnosticPreset::FullDiagnostics));
+    
+    apply_diagnostic_preset(DiagnosticPreset::Minimal);
+    assert!(!is_max_test_mode_enabled());
+    assert!(is_pipeline_verification_mode_enabled());
+    
+    apply_diagnostic_preset(DiagnosticPreset::Off);
+    assert_eq!(current_diagnostic_preset(), Some(DiagnosticPreset::Off));
+}
@@ -131,6 +131,7 @@ fn test_unicode_device_names_cross_platform() {
             remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
             analytics: kwite::config::AnalyticsConfig::default(),
             auto_update: kwite::config::AutoUpdateConfig::default(),
+            ..KwiteConfig::default()
         };
         
         // Test that unicode survives serialization/deserialization
@@ -196,6 +197,7 @@ fn test_cross_platform_path_handling() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     // Serialization should preserve the strings as-is
@@ -253,6 +255,7 @@ fn test_platform_floating_point_precision() {
             remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
             analytics: kwite::config::AnalyticsConfig::default(),
             auto_update: kwite::config::AutoUpdateConfig::default(),
+            ..KwiteConfig::default()
         };
         
         let toml_content = toml::to_string_pretty(&config)
@@ -36,6 +36,7 @@ fn test_device_config_integration() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     // Verify device lookup works with config
@@ -65,6 +66,7 @@ fn test_config_persistence_integration() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     // Test serialization
@@ -191,6 +193,7 @@ fn test_error_recovery_integration() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     // Device lookup should fail gracefully
@@ -239,6 +242,7 @@ fn test_virtual_device_preference_workflow() {
             remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
             analytics: kwite::config::AnalyticsConfig::default(),
             auto_update: kwite::config::AutoUpdateConfig::default(),
+            ..KwiteConfig::default()
         };
         
         // Verify configuration is valid
@@ -268,6 +272,7 @@ fn test_sensitivity_configuration_integration() {
             remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
             analytics: kwite::config::AnalyticsConfig::default(),
             auto_update: kwite::config::AutoUpdateConfig::default(),
+            ..KwiteConfig::default()
         };
         
         // Test serialization preserves precision
@@ -308,7 +313,9 @@ fn test_save_config_saves_all_ui_settings() {
             check_interval_hours: 24,
             update_endpoint: "test_update_endpoint".to_string(),
             notify_before_download: true,
+            channel: kwite::config::UpdateChannel::Beta,
         },
+        ..KwiteConfig::default()
     };
     
     // Test that config can be serialized and saves all fields
@@ -331,4 +338,4 @@ fn test_save_config_saves_all_ui_settings() {
     assert_eq!(config.sensitivity, loaded_config.sensitivity);
     assert_eq!(config.input_device_id, loaded_config.input_device_id);
     assert_eq!(config.output_device_id, loaded_config.output_device_id);
-}
\ No newline at end of file
+}
@@ -80,6 +80,7 @@ fn test_config_roundtrip_save_load() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     // Mock the config_path function by testing the serialization directly
@@ -175,6 +176,7 @@ fn test_config_serialization_format() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     let toml_content = toml::to_string_pretty(&config)
@@ -202,6 +204,7 @@ fn test_config_edge_cases() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     let toml_content = toml::to_string_pretty(&config)
@@ -229,6 +232,7 @@ fn test_config_unicode_handling() {
         remote_logging: kwite::remote_logging::RemoteLoggingConfig::default(),
         analytics: kwite::config::AnalyticsConfig::default(),
         auto_update: kwite::config::AutoUpdateConfig::default(),
+        ..KwiteConfig::default()
     };
     
     let toml_content = toml::to_string_pretty(&config)
@@ -239,4 +243,123 @@ fn test_config_unicode_handling() {
     
     assert_eq!(config.input_device_id, parsed_config.input_device_id);
     assert_eq!(config.output_device_id, parsed_config.output_device_id);
-}
\ No newline at end of file
+}
+#[test]
+#[serial]
+fn test_config_missing_and_unknown_fields() {
+    // An older config missing most fields, plus keys this version doesn't know
+    let content = r#"
+input_device_id = "my_mic"
+sensitivity = 0.2
+future_setting = "value"
+
+[auto_update]
+enabled = false
+future_nested = 3
+"#;
+    
+    let (config, unknown_fields) = KwiteConfig::from_toml_str(content)
+        .expect("Partial config with unknown fields should still load");
+    let defaults = KwiteConfig::default();
+    
+    assert_eq!(config.input_device_id, "my_mic");
+    assert_eq!(config.sensitivity, 0.2);
+    assert!(!config.auto_update.enabled);
+    assert_eq!(config.output_device_id, defaults.output_device_id, "Missing fields use defaults");
+    assert_eq!(config.auto_update.check_interval_hours, defaults.auto_update.check_interval_hours);
+    assert_eq!(unknown_fields, vec!["future_setting".to_string(), "auto_update.future_nested".to_string()]);
+    
+    // Wrongly typed values are still rejected so load() can fall back to defaults
+    assert!(KwiteConfig::from_toml_str("sensitivity = \"loud\"").is_err());
+}
+
+#[test]
+fn test_privacy_presets() {
+    for preset in PrivacyPreset::ALL {
+        let mut config = KwiteConfig::default();
+        preset.apply(&mut config);
+        assert_eq!(PrivacyPreset::detect(&config), Some(preset));
+    }
+    
+    let mut config = KwiteConfig::default();
+    PrivacyPreset::Off.apply(&mut config);
+    assert!(!config.analytics.enabled && !config.remote_logging.enabled);
+    
+    PrivacyPreset::Minimal.apply(&mut config);
+    assert!(config.remote_logging.enabled);
+    assert!(!config.analytics.enabled, "Minimal must not send usage statistics");
+    assert!(!config.remote_logging.include_system_info, "Minimal must not send system info");
+    assert_eq!(config.remote_logging.sampling_rate, 0.0, "Minimal sends errors only");
+    assert!(config.remote_logging.redact_fields.iter().any(|field| field == "device_input"), "Minimal must not send device names");
+    
+    PrivacyPreset::Full.apply(&mut config);
+    assert!(config.remote_logging.redact_fields.is_empty());
+    
+    // Hand-tuned settings no longer match a preset
+    PrivacyPreset::Standard.apply(&mut config);
+    config.remote_logging.sampling_rate = 0.5;
+    assert_eq!(PrivacyPreset::detect(&config), None);
+}
+
+#[test]
+fn test_device_slots_roundtrip() {
+    let mut config = KwiteConfig::default();
+    assert!(config.device_slots.iter().all(DeviceSlot::is_empty));
+    assert_eq!(config.device_slots[1].label(1), "Slot 2");
+    
+    config.device_slots[0] = DeviceSlot {
+        name: "Headset".to_string(),
+        input_device_id: "input_0_headset".to_string(),
+        output_device_id: "output_0_headset".to_string(),
+        hotkey: Some("F9".to_string()),
+    };
+    
+    let toml_content = toml::to_string_pretty(&config)
+        .expect("Failed to serialize config with device slots");
+    let parsed: KwiteConfig = toml::from_str(&toml_content)
+        .expect("Failed to parse config with device slots");
+    
+    assert_eq!(parsed.device_slots, config.device_slots);
+    assert_eq!(parsed.device_slots[0].label(0), "Headset");
+    assert!(parsed.device_slots[1].is_empty());
+}
+
+#[test]
+fn test_output_profiles() {
+    let mut config = KwiteConfig::default();
+    config.output_device_id = "output_1_discord_cable".to_string();
+    config.sensitivity = 0.05;
+    config.save_profile("Discord");
+
+    config.output_device_id = "output_2_obs_cable".to_string();
+    config.sensitivity = 0.3;
+    config.noise_gain = 0.2;
+    config.save_profile("OBS");
+    assert_eq!(config.active_profile.as_deref(), Some("OBS"));
+
+    // Switching restores the profile's devices and parameters
+    assert!(config.activate_profile("Discord"));
+    assert_eq!(config.output_device_id, "output_1_discord_cable");
+    assert_eq!(config.sensitivity, 0.05);
+    assert_eq!(config.noise_gain, KwiteConfig::default().noise_gain);
+    assert!(!config.activate_profile("Zoom"));
+    assert_eq!(config.active_profile.as_deref(), Some("Discord"));
+
+    // Saving under an existing name overwrites it
+    config.sensitivity = 0.1;
+    config.save_profile("Discord");
+    assert_eq!(config.profiles.len(), 2);
+    assert_eq!(config.profile("Discord").unwrap().sensitivity, 0.1);
+
+    // The mapping survives a save/load round trip
+    let toml_content = toml::to_string_pretty(&config)
+        .expect("Failed to serialize config with profiles");
+    let parsed: KwiteConfig = toml::from_str(&toml_content)
+        .expect("Failed to parse config with profiles");
+    assert_eq!(parsed.profiles, config.profiles);
+    assert_eq!(parsed.active_profile.as_deref(), Some("Discord"));
+
+    config.remove_profile("Discord");
+    assert_eq!(config.active_profile, None);
+    assert_eq!(config.profiles.len(), 1);
+}